@@ -20,6 +20,9 @@ pub struct BlameParams {
 	pub file_path: String,
 	/// blame at a specific revision
 	pub commit_id: Option<CommitId>,
+	/// blame the revision of the file before `commit_id` instead,
+	/// under the name it had before if `commit_id` renamed it
+	pub previous: bool,
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -147,10 +150,31 @@ impl AsyncBlame {
 		arc_current: &Arc<Mutex<Request<u64, FileBlame>>>,
 		hash: u64,
 	) -> Result<bool> {
+		let revision = if params.previous {
+			params
+				.commit_id
+				.map(|id| {
+					sync::blame::previous_revision(
+						repo_path,
+						&params.file_path,
+						id,
+					)
+				})
+				.transpose()?
+				.flatten()
+				.map(|(path, id)| (path, Some(id)))
+		} else {
+			Some((params.file_path.clone(), params.commit_id))
+		};
+
+		let (file_path, commit_id) = match revision {
+			Some(revision) => revision,
+			// nothing earlier to blame, the result stays empty
+			None => return Ok(true),
+		};
+
 		let file_blame = sync::blame::blame_file(
-			repo_path,
-			&params.file_path,
-			params.commit_id,
+			repo_path, &file_path, commit_id,
 		)?;
 
 		let mut notify = false;
@@ -18,6 +18,7 @@ enum JobState {
 #[derive(Clone, Default)]
 pub struct AsyncBranchesJob {
 	state: Arc<Mutex<Option<JobState>>>,
+	local_branches: bool,
 }
 
 ///
@@ -29,9 +30,15 @@ impl AsyncBranchesJob {
 				repo,
 				local_branches,
 			}))),
+			local_branches,
 		}
 	}
 
+	/// whether this job lists local or remote branches
+	pub const fn is_local(&self) -> bool {
+		self.local_branches
+	}
+
 	///
 	pub fn result(&self) -> Option<Result<Vec<BranchInfo>>> {
 		if let Ok(mut state) = self.state.lock() {
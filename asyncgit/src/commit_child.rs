@@ -0,0 +1,75 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{child_in_head_history, CommitId, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<Option<CommitId>>),
+}
+
+/// looks up a child of a commit in the history of `HEAD` in the
+/// background
+#[derive(Clone)]
+pub struct AsyncCommitChildJob {
+	commit: CommitId,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCommitChildJob {
+	///
+	pub fn new(repo: RepoPath, commit: CommitId) -> Self {
+		Self {
+			commit,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	/// the commit whose child is looked up
+	pub const fn commit(&self) -> CommitId {
+		self.commit
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<Option<CommitId>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncCommitChildJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => JobState::Response(
+					child_in_head_history(&repo, self.commit),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::CommitChild)
+	}
+}
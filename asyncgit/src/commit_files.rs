@@ -19,11 +19,29 @@ pub struct CommitFilesParams {
 	pub id: CommitId,
 	///
 	pub other: Option<CommitId>,
+	/// parent of a merge commit to list the changes against, `0`
+	/// being the first one (ignored if comparing to `other`)
+	pub parent: usize,
+}
+
+impl CommitFilesParams {
+	/// files of merge commit `id` compared to its `parent`-th parent
+	pub const fn with_parent(id: CommitId, parent: usize) -> Self {
+		Self {
+			id,
+			other: None,
+			parent,
+		}
+	}
 }
 
 impl From<CommitId> for CommitFilesParams {
 	fn from(id: CommitId) -> Self {
-		Self { id, other: None }
+		Self {
+			id,
+			other: None,
+			parent: 0,
+		}
 	}
 }
 
@@ -32,6 +50,7 @@ impl From<(CommitId, CommitId)> for CommitFilesParams {
 		Self {
 			id,
 			other: Some(other),
+			parent: 0,
 		}
 	}
 }
@@ -119,11 +138,15 @@ impl AsyncCommitFiles {
 			Mutex<Option<Request<CommitFilesParams, ResultType>>>,
 		>,
 	) -> Result<()> {
-		let res = sync::get_commit_files(
-			repo_path,
-			params.id,
-			params.other,
-		)?;
+		let res = if params.other.is_none() && params.parent > 0 {
+			sync::get_commit_parent_files(
+				repo_path,
+				params.id,
+				params.parent,
+			)?
+		} else {
+			sync::get_commit_files(repo_path, params.id, params.other)?
+		};
 
 		log::trace!("get_commit_files: {:?} ({})", params, res.len());
 
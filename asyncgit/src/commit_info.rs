@@ -0,0 +1,74 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{get_commit_info, CommitId, CommitInfo, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<CommitInfo>),
+}
+
+/// looks up author, time and message of a commit in the background
+#[derive(Clone)]
+pub struct AsyncCommitInfoJob {
+	commit: CommitId,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCommitInfoJob {
+	///
+	pub fn new(repo: RepoPath, commit: CommitId) -> Self {
+		Self {
+			commit,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	/// the commit that is looked up
+	pub const fn commit(&self) -> CommitId {
+		self.commit
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<CommitInfo>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncCommitInfoJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => JobState::Response(
+					get_commit_info(&repo, &self.commit),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::CommitInfo)
+	}
+}
@@ -0,0 +1,131 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		commits_files_count, get_commits_with_notes, verify_commits,
+		CommitId, RepoPath, SignatureStatus,
+	},
+	AsyncGitNotification,
+};
+use std::{
+	collections::HashSet,
+	sync::{Arc, Mutex},
+};
+
+/// what an `AsyncCommitLookupJob` looks up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitLookup {
+	/// signature of each commit
+	Signatures,
+	/// number of files each commit changed
+	FileCounts,
+	/// commits with a note attached, the commits asked for are
+	/// ignored
+	Notes,
+}
+
+/// the answer of an `AsyncCommitLookupJob`, one per `CommitLookup`
+pub enum CommitLookupResult {
+	///
+	Signatures(Vec<(CommitId, SignatureStatus)>),
+	///
+	FileCounts(Vec<(CommitId, usize)>),
+	///
+	Notes(HashSet<CommitId>),
+}
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		commits: Vec<CommitId>,
+	},
+	Response(Result<CommitLookupResult>),
+}
+
+/// looks up something about a list of commits in the background
+#[derive(Clone)]
+pub struct AsyncCommitLookupJob {
+	lookup: CommitLookup,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCommitLookupJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		lookup: CommitLookup,
+		commits: Vec<CommitId>,
+	) -> Self {
+		Self {
+			lookup,
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				commits,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<CommitLookupResult>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	fn look_up(
+		lookup: CommitLookup,
+		repo: &RepoPath,
+		commits: &[CommitId],
+	) -> Result<CommitLookupResult> {
+		Ok(match lookup {
+			CommitLookup::Signatures => {
+				CommitLookupResult::Signatures(verify_commits(
+					repo, commits,
+				)?)
+			}
+			CommitLookup::FileCounts => {
+				CommitLookupResult::FileCounts(commits_files_count(
+					repo, commits,
+				)?)
+			}
+			CommitLookup::Notes => CommitLookupResult::Notes(
+				get_commits_with_notes(repo)?,
+			),
+		})
+	}
+}
+
+impl AsyncJob for AsyncCommitLookupJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, commits } => {
+					JobState::Response(Self::look_up(
+						self.lookup,
+						&repo,
+						&commits,
+					))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::CommitLookup)
+	}
+}
@@ -0,0 +1,193 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{repo, CommitId, RepoPath},
+	AsyncGitNotification, ProgressPercent,
+};
+use std::sync::{Arc, Mutex};
+
+/// gets called with `(author, message, hash)` of each candidate
+pub type CommitSearchPredicate =
+	Arc<dyn Fn(&str, &str, &str) -> bool + Send + Sync>;
+
+/// how many candidates to check between two progress updates
+const PROGRESS_STEP: usize = 500;
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		candidates: Vec<(usize, CommitId)>,
+		hash_only: bool,
+		predicate: CommitSearchPredicate,
+	},
+	Response(Result<Option<usize>>),
+}
+
+/// walks a list of commits in the background and finds the
+/// first one matching the predicate
+#[derive(Clone, Default)]
+pub struct AsyncCommitSearchJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCommitSearchJob {
+	/// `candidates` are `(log index, id)` pairs in search order,
+	/// `hash_only` skips looking up author and message
+	pub fn new(
+		repo: RepoPath,
+		candidates: Vec<(usize, CommitId)>,
+		hash_only: bool,
+		predicate: CommitSearchPredicate,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				candidates,
+				hash_only,
+				predicate,
+			}))),
+		}
+	}
+
+	/// log index of the first match, `Ok(None)` if nothing matched
+	pub fn result(&self) -> Option<Result<Option<usize>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	fn search(
+		repo_path: &RepoPath,
+		candidates: &[(usize, CommitId)],
+		hash_only: bool,
+		predicate: &CommitSearchPredicate,
+		params: &RunParams<AsyncGitNotification, ProgressPercent>,
+	) -> Result<Option<usize>> {
+		let repo = repo(repo_path)?;
+		let total = candidates.len();
+
+		for (checked, (idx, id)) in candidates.iter().enumerate() {
+			if checked % PROGRESS_STEP == 0 {
+				params.set_progress(ProgressPercent::new(
+					checked, total,
+				))?;
+				params.send(AsyncGitNotification::CommitSearch)?;
+			}
+
+			let hash = id.to_string();
+			let found = if hash_only {
+				predicate("", "", &hash)
+			} else {
+				let commit = repo.find_commit((*id).into())?;
+				let author = commit.author();
+				predicate(
+					author.name().unwrap_or_default(),
+					commit.message().unwrap_or_default(),
+					&hash,
+				)
+			};
+
+			if found {
+				return Ok(Some(*idx));
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+impl AsyncJob for AsyncCommitSearchJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ProgressPercent;
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					repo,
+					candidates,
+					hash_only,
+					predicate,
+				} => JobState::Response(Self::search(
+					&repo,
+					&candidates,
+					hash_only,
+					&predicate,
+					&params,
+				)),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		params.set_progress(ProgressPercent::full())?;
+
+		Ok(AsyncGitNotification::CommitSearch)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::asyncjob::AsyncSingleJob;
+	use crate::sync::{
+		commit, stage_add_file, tests::repo_init_empty, RepoPath,
+	};
+	use crossbeam_channel::unbounded;
+	use std::{fs::File, io::Write, path::Path, time::Duration};
+
+	#[test]
+	fn test_search_finds_first_match() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let mut ids = Vec::new();
+		for msg in ["first", "needle", "last"] {
+			File::create(root.join(file_path))?
+				.write_all(msg.as_bytes())?;
+			stage_add_file(repo_path, file_path)?;
+			ids.push(commit(repo_path, msg)?);
+		}
+
+		let candidates: Vec<(usize, CommitId)> =
+			ids.into_iter().rev().enumerate().collect();
+
+		let (tx, rx) = unbounded();
+		let mut job = AsyncSingleJob::new(tx);
+		job.spawn(AsyncCommitSearchJob::new(
+			repo_path.clone(),
+			candidates,
+			false,
+			Arc::new(|_author: &str, msg: &str, _hash: &str| {
+				msg.contains("needle")
+			}),
+		));
+
+		// `last` is set before the final notification is sent
+		let finished = loop {
+			if let Some(job) = job.take_last() {
+				break job;
+			}
+			rx.recv_timeout(Duration::from_secs(5)).unwrap();
+		};
+
+		assert_eq!(finished.result().unwrap()?, Some(1));
+
+		Ok(())
+	}
+}
@@ -20,6 +20,8 @@ pub enum DiffType {
 	Commits((CommitId, CommitId)),
 	/// diff in a given commit
 	Commit(CommitId),
+	/// diff of a (merge) commit to its n-th parent
+	CommitParent(CommitId, usize),
 	/// diff against staged file
 	Stage,
 	/// diff against file in workdir
@@ -177,6 +179,15 @@ impl AsyncDiff {
 				params.path.clone(),
 				Some(params.options),
 			)?,
+			DiffType::CommitParent(id, parent) => {
+				sync::diff::get_diff_commit_parent(
+					repo_path,
+					id,
+					parent,
+					&params.path,
+					Some(params.options),
+				)?
+			}
 			DiffType::Commits(ids) => sync::diff::get_diff_commits(
 				repo_path,
 				ids,
@@ -0,0 +1,80 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{tree_file_head, RepoPath, TreeFile},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<String>),
+}
+
+/// reads the first lines of a file in the background
+#[derive(Clone)]
+pub struct AsyncFileHeadJob {
+	file: TreeFile,
+	max_lines: usize,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncFileHeadJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		file: TreeFile,
+		max_lines: usize,
+	) -> Self {
+		Self {
+			file,
+			max_lines,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	/// the file whose lines are read
+	pub const fn file(&self) -> &TreeFile {
+		&self.file
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<String>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncFileHeadJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => JobState::Response(
+					tree_file_head(&repo, &self.file, self.max_lines),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::FileHead)
+	}
+}
@@ -28,10 +28,15 @@ pub mod asyncjob;
 mod blame;
 mod branches;
 pub mod cached;
+mod commit_child;
 mod commit_files;
+mod commit_info;
+mod commit_lookup;
+mod commit_search;
 mod diff;
 mod error;
 mod fetch_job;
+mod file_head;
 mod progress;
 mod pull;
 mod push;
@@ -43,14 +48,22 @@ mod status;
 pub mod sync;
 mod tags;
 mod treefiles;
+mod upstream;
 
 pub use crate::{
 	blame::{AsyncBlame, BlameParams},
 	branches::AsyncBranchesJob,
+	commit_child::AsyncCommitChildJob,
 	commit_files::{AsyncCommitFiles, CommitFilesParams},
+	commit_info::AsyncCommitInfoJob,
+	commit_lookup::{
+		AsyncCommitLookupJob, CommitLookup, CommitLookupResult,
+	},
+	commit_search::{AsyncCommitSearchJob, CommitSearchPredicate},
 	diff::{AsyncDiff, DiffParams, DiffType},
 	error::{Error, Result},
 	fetch_job::{AsyncFetchAsExternCmdJob, AsyncFetchJob},
+	file_head::AsyncFileHeadJob,
 	progress::ProgressPercent,
 	pull::{AsyncPull, FetchRequest},
 	push::{AsyncPush, PushRequest},
@@ -65,6 +78,7 @@ pub use crate::{
 	},
 	tags::AsyncTags,
 	treefiles::AsyncTreeFilesJob,
+	upstream::AsyncUpstreamJob,
 };
 pub use git2::message_prettify;
 use std::{
@@ -105,6 +119,18 @@ pub enum AsyncGitNotification {
 	Branches,
 	///
 	TreeFiles,
+	///
+	CommitSearch,
+	///
+	CommitLookup,
+	///
+	CommitChild,
+	///
+	CommitInfo,
+	///
+	Upstream,
+	///
+	FileHead,
 }
 
 /// helper function to calculate the hash of an arbitrary type that implements the `Hash` trait
@@ -2,7 +2,9 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::{fetch, push::ProgressNotification},
+		remotes::{
+			fetch, fetch_pull_request, push::ProgressNotification,
+		},
 		utils, RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
@@ -22,6 +24,9 @@ pub struct FetchRequest {
 	pub branch: String,
 	///
 	pub basic_credential: Option<BasicAuthCredential>,
+	/// remote ref of a pull request to fetch into the local
+	/// `branch` instead of fetching the upstream of `branch`
+	pub pull_request: Option<String>,
 }
 
 //TODO: since this is empty we can go with a simple AtomicBool to mark that we are fetching or not
@@ -36,6 +41,7 @@ pub struct AsyncPull {
 	sender: Sender<AsyncGitNotification>,
 	repo: RepoPath,
 	external_fetch: Option<String>,
+	external_pull: Option<String>,
 }
 
 impl AsyncPull {
@@ -51,6 +57,7 @@ impl AsyncPull {
 			progress: Arc::new(Mutex::new(None)),
 			sender: sender.clone(),
 			external_fetch: None,
+			external_pull: None,
 		}
 	}
 
@@ -77,6 +84,12 @@ impl AsyncPull {
 		self.external_fetch = o;
 	}
 
+	/// external pull command, takes precedence over
+	/// the external fetch and does the merge on its own
+	pub fn set_git_pull_external(&mut self, o: Option<String>) {
+		self.external_pull = o;
+	}
+
 	///
 	pub fn request(&mut self, params: FetchRequest) -> Result<()> {
 		log::trace!("request");
@@ -93,7 +106,10 @@ impl AsyncPull {
 		let arc_progress = Arc::clone(&self.progress);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
-		let ext_fetch = self.external_fetch.clone();
+		let ext_cmd = self
+			.external_pull
+			.clone()
+			.or_else(|| self.external_fetch.clone());
 
 		thread::spawn(move || {
 			let (progress_sender, receiver) = unbounded();
@@ -105,9 +121,17 @@ impl AsyncPull {
 				arc_progress,
 			);
 
-			let res = if let Some(base_fetch) = ext_fetch {
-				utils::exec_git_external_command(&base_fetch)
-					.map(|_| 0)
+			let res = if let Some(src_ref) = params.pull_request {
+				fetch_pull_request(
+					&repo,
+					&params.remote,
+					&src_ref,
+					&params.branch,
+					params.basic_credential,
+					Some(progress_sender.clone()),
+				)
+			} else if let Some(base_cmd) = ext_cmd {
+				utils::exec_git_external_command(&base_cmd).map(|_| 0)
 			} else {
 				fetch(
 					&repo,
@@ -3,12 +3,12 @@ use crate::{
 	sync::{repo, CommitId, LogWalker, LogWalkerFilter, RepoPath},
 	AsyncGitNotification,
 };
-use crossbeam_channel::Sender;
+use crossbeam_channel::{unbounded, Sender};
 use scopetime::scope_time;
 use std::{
 	sync::{
 		atomic::{AtomicBool, Ordering},
-		Arc, Mutex,
+		Arc, Condvar, Mutex,
 	},
 	thread,
 	time::Duration,
@@ -25,6 +25,80 @@ pub enum FetchStatus {
 	Started,
 }
 
+#[derive(Default)]
+struct WalkState {
+	/// `0` loads everything
+	limit: usize,
+	paused: bool,
+	abort: bool,
+}
+
+/// pauses a walk once `limit` commits are loaded until it is raised,
+/// `abort` ends it for good
+#[derive(Default)]
+struct WalkLimit {
+	state: Mutex<WalkState>,
+	resume: Condvar,
+}
+
+impl WalkLimit {
+	fn new(limit: usize) -> Self {
+		Self {
+			state: Mutex::new(WalkState {
+				limit,
+				..WalkState::default()
+			}),
+			resume: Condvar::new(),
+		}
+	}
+
+	fn limit(&self) -> Result<usize> {
+		Ok(self.state.lock()?.limit)
+	}
+
+	fn is_paused(&self) -> bool {
+		self.state.lock().map_or(false, |state| state.paused)
+	}
+
+	/// blocks while `loaded` reached the limit, `false` once aborted
+	fn wait_for_room(&self, loaded: usize) -> Result<bool> {
+		let mut state = self.state.lock()?;
+		if state.limit > 0 && loaded >= state.limit {
+			state.paused = true;
+		}
+		let state = self.resume.wait_while(state, |state| {
+			state.paused && !state.abort
+		})?;
+		Ok(!state.abort)
+	}
+
+	fn raise(&self, amount: usize) {
+		if let Ok(mut state) = self.state.lock() {
+			// no second batch before the walk paused again
+			if state.paused {
+				state.paused = false;
+				state.limit += amount;
+				self.resume.notify_all();
+			}
+		}
+	}
+
+	fn abort(&self) {
+		if let Ok(mut state) = self.state.lock() {
+			state.abort = true;
+			self.resume.notify_all();
+		}
+	}
+
+	fn finish(&self) {
+		if let Ok(mut state) = self.state.lock() {
+			state.paused = false;
+		}
+	}
+}
+
+type WalkJob = Box<dyn FnOnce() + Send>;
+
 ///
 pub struct AsyncLog {
 	current: Arc<Mutex<Vec<CommitId>>>,
@@ -33,9 +107,14 @@ pub struct AsyncLog {
 	pending: Arc<AtomicBool>,
 	background: Arc<AtomicBool>,
 	filter: Option<LogWalkerFilter>,
+	hidden: Option<CommitId>,
 	repo: RepoPath,
 	filter_updated: bool,
 	start_commit: Arc<Mutex<Option<CommitId>>>,
+	walk_limit: Arc<WalkLimit>,
+	batch_size: usize,
+	/// runs batched walks, see `walk_thread`
+	walk_jobs: Option<Sender<WalkJob>>,
 }
 
 static LIMIT_COUNT: usize = 3000;
@@ -57,11 +136,32 @@ impl AsyncLog {
 			pending: Arc::new(AtomicBool::new(false)),
 			background: Arc::new(AtomicBool::new(false)),
 			filter,
+			hidden: None,
 			filter_updated: false,
 			start_commit: Arc::new(Mutex::new(None)),
+			walk_limit: Arc::new(WalkLimit::default()),
+			batch_size: 0,
+			walk_jobs: None,
 		}
 	}
 
+	/// loads `batch_size` commits per walk and waits for
+	/// `load_more` before reading on, `0` loads everything at once.
+	/// applies to the next walk
+	pub fn set_batch_size(&mut self, batch_size: usize) {
+		self.batch_size = batch_size;
+	}
+
+	/// the walk stopped at the batch size and has more to read
+	pub fn has_more(&self) -> bool {
+		self.walk_limit.is_paused()
+	}
+
+	/// lets a paused walk read another batch
+	pub fn load_more(&self) {
+		self.walk_limit.raise(self.batch_size);
+	}
+
 	///
 	pub fn count(&self) -> Result<usize> {
 		Ok(self.current.lock()?.len())
@@ -96,47 +196,6 @@ impl AsyncLog {
 		}
 	}
 
-	///
-	pub fn search_commit_forward<F>(
-		&self,
-		start_index: usize,
-		predicate: F,
-	) -> Option<usize>
-	where
-		F: Fn(&CommitId) -> bool,
-	{
-		if let Ok(list) = self.current.lock() {
-			list.iter()
-				.enumerate()
-				.skip(start_index)
-				.find(|i| predicate(i.1))
-				.map(|i| i.0)
-		} else {
-			None
-		}
-	}
-
-	///
-	pub fn search_commit_backward<F>(
-		&self,
-		start_index: usize,
-		predicate: F,
-	) -> Option<usize>
-	where
-		F: Fn(&CommitId) -> bool,
-	{
-		if let Ok(list) = self.current.lock() {
-			list.iter()
-				.take(start_index)
-				.enumerate()
-				.rev()
-				.find(|i| predicate(i.1))
-				.map(|i| i.0)
-		} else {
-			None
-		}
-	}
-
 	///
 	pub fn set_start_commit(
 		&mut self,
@@ -154,9 +213,9 @@ impl AsyncLog {
 		Ok(position)
 	}
 
-	///
+	/// a walk waiting for `load_more` is not pending
 	pub fn is_pending(&self) -> bool {
-		self.pending.load(Ordering::Relaxed)
+		self.pending.load(Ordering::Relaxed) && !self.has_more()
 	}
 
 	///
@@ -171,7 +230,7 @@ impl AsyncLog {
 
 	///
 	fn head_changed(&self) -> Result<bool> {
-		if let Some(c) = self.start_commit.lock()?.clone() {
+		if let Some(c) = *self.start_commit.lock()? {
 			return Ok(Some(c) != self.current_head()?);
 		} else if let Ok(head) = repo(&self.repo)?.head() {
 			return Ok(
@@ -187,11 +246,23 @@ impl AsyncLog {
 		self.filter_updated = true;
 	}
 
+	/// leaves out `hidden` and its ancestry, like `git log ^hidden`
+	pub fn update_hidden(&mut self, hidden: Option<CommitId>) {
+		self.hidden = hidden;
+		self.filter_updated = true;
+	}
+
 	///
 	pub fn fetch(&mut self) -> Result<FetchStatus> {
 		self.background.store(false, Ordering::Relaxed);
 
-		if self.is_pending() {
+		if self.pending.load(Ordering::Relaxed) {
+			// a paused walk would wait forever for an outdated log
+			if self.has_more()
+				&& (self.head_changed()? || self.filter_updated)
+			{
+				self.walk_limit.abort();
+			}
 			return Ok(FetchStatus::Pending);
 		}
 
@@ -207,19 +278,23 @@ impl AsyncLog {
 		let arc_pending = Arc::clone(&self.pending);
 		let arc_background = Arc::clone(&self.background);
 		let filter = self.filter.clone();
+		let hidden = self.hidden;
 		let repo_path = self.repo.clone();
-		let start_commit = self.start_commit.clone();
+		let start_commit = Arc::clone(&self.start_commit);
+
+		self.walk_limit = Arc::new(WalkLimit::new(self.batch_size));
+		let walk_limit = Arc::clone(&self.walk_limit);
 
 		self.pending.store(true, Ordering::Relaxed);
 
-		if let Some(c) = self.start_commit.lock()?.clone() {
+		if let Some(c) = *self.start_commit.lock()? {
 			*self.current_head.lock()? = Some(c);
 		} else if let Ok(head) = repo(&self.repo)?.head() {
 			*self.current_head.lock()? =
 				head.target().map(CommitId::new);
 		}
 
-		rayon_core::spawn(move || {
+		let job = move || {
 			scope_time!("async::revlog");
 
 			Self::fetch_helper(
@@ -228,38 +303,77 @@ impl AsyncLog {
 				&arc_background,
 				&sender,
 				filter,
+				hidden,
 				start_commit,
+				&walk_limit,
 			)
 			.expect("failed to fetch");
 
+			walk_limit.finish();
 			arc_pending.store(false, Ordering::Relaxed);
 
 			Self::notify(&sender);
-		});
+		};
+
+		if self.batch_size > 0 {
+			self.walk_thread()
+				.send(Box::new(job))
+				.expect("walk thread gone");
+		} else {
+			rayon_core::spawn(job);
+		}
 
 		Ok(FetchStatus::Started)
 	}
 
+	/// a paused walk waits for `load_more`, which must not tie up a
+	/// thread of the pool all other async jobs share. walks never
+	/// overlap so one thread serves them all, it ends with us
+	fn walk_thread(&mut self) -> &Sender<WalkJob> {
+		self.walk_jobs.get_or_insert_with(|| {
+			let (tx, rx) = unbounded::<WalkJob>();
+			thread::spawn(move || {
+				for job in rx {
+					job();
+				}
+			});
+			tx
+		})
+	}
+
+	#[allow(clippy::too_many_arguments)]
 	fn fetch_helper(
 		repo_path: &RepoPath,
 		arc_current: &Arc<Mutex<Vec<CommitId>>>,
 		arc_background: &Arc<AtomicBool>,
 		sender: &Sender<AsyncGitNotification>,
 		filter: Option<LogWalkerFilter>,
+		hidden: Option<CommitId>,
 		arc_start: Arc<Mutex<Option<CommitId>>>,
+		walk_limit: &WalkLimit,
 	) -> Result<()> {
 		let mut entries = Vec::with_capacity(LIMIT_COUNT);
 		let r = repo(repo_path)?;
-		let has_filter = filter.is_some();
-		let start = arc_start.lock()?;
-		let start_commit = start.clone();
+		let has_filter = filter.is_some() || hidden.is_some();
+		// not held during the walk, `fetch` locks it while paused
+		let start_commit = *arc_start.lock()?;
+		let read_count = match walk_limit.limit()? {
+			0 => LIMIT_COUNT,
+			batch_size => batch_size.min(LIMIT_COUNT),
+		};
 		let mut walker = LogWalker::new_with_start(
 			&r,
 			start_commit.as_ref(),
-			LIMIT_COUNT,
+			read_count,
 		)?
-		.filter(filter);
+		.filter(filter)
+		.hide(hidden.as_ref())?;
 		loop {
+			let loaded = arc_current.lock()?.len();
+			if !walk_limit.wait_for_room(loaded)? {
+				break;
+			}
+
 			entries.clear();
 			let res_is_err = walker.read_eof(&mut entries).is_err();
 
@@ -297,3 +411,96 @@ impl AsyncLog {
 			.expect("error sending");
 	}
 }
+
+impl Drop for AsyncLog {
+	fn drop(&mut self) {
+		// a paused walk would otherwise outlive us
+		self.walk_limit.abort();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init_empty, write_commit_file};
+	use crossbeam_channel::unbounded;
+
+	/// `false` if the walk did not get there in time
+	fn wait_for(
+		log: &AsyncLog,
+		done: impl Fn(&AsyncLog) -> bool,
+	) -> bool {
+		for _ in 0..500 {
+			if done(log) {
+				return true;
+			}
+			thread::sleep(Duration::from_millis(10));
+		}
+		false
+	}
+
+	#[test]
+	fn test_batches_wait_for_load_more() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		for idx in 0..5 {
+			write_commit_file(&repo, "f.txt", &idx.to_string(), "c");
+		}
+
+		let (tx, _rx) = unbounded();
+		let mut log = AsyncLog::new(repo_path, &tx, None);
+		log.set_batch_size(2);
+		assert!(log.fetch()? == FetchStatus::Started);
+
+		assert!(wait_for(&log, AsyncLog::has_more));
+		assert_eq!(log.count()?, 2);
+		assert!(!log.is_pending());
+
+		log.load_more();
+		assert!(wait_for(&log, |log| {
+			log.has_more() && log.count().unwrap() == 4
+		}));
+
+		log.load_more();
+		assert!(wait_for(&log, |log| !log
+			.pending
+			.load(Ordering::Relaxed)));
+		assert_eq!(log.count()?, 5);
+		assert!(!log.has_more());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_paused_walk_aborts_on_new_head() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		for idx in 0..3 {
+			write_commit_file(&repo, "f.txt", &idx.to_string(), "c");
+		}
+
+		let (tx, _rx) = unbounded();
+		let mut log = AsyncLog::new(repo_path, &tx, None);
+		log.set_batch_size(2);
+		assert!(log.fetch()? == FetchStatus::Started);
+		assert!(wait_for(&log, AsyncLog::has_more));
+
+		write_commit_file(&repo, "f.txt", "new", "c");
+		assert!(log.fetch()? == FetchStatus::Pending);
+		assert!(wait_for(&log, |log| !log
+			.pending
+			.load(Ordering::Relaxed)));
+
+		assert!(log.fetch()? == FetchStatus::Started);
+		assert!(wait_for(&log, AsyncLog::has_more));
+		assert_eq!(log.count()?, 2);
+
+		Ok(())
+	}
+}
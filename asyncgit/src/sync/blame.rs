@@ -3,7 +3,10 @@
 use super::{utils, CommitId, RepoPath};
 use crate::{
 	error::{Error, Result},
-	sync::{get_commits_info, repository::repo},
+	sync::{
+		commit_renamed_from, filter_by_path, get_commits_info,
+		repository::repo, LogWalker,
+	},
 };
 use git2::BlameOptions;
 use scopetime::scope_time;
@@ -19,6 +22,8 @@ pub struct BlameHunk {
 	///
 	pub author: String,
 	///
+	pub email: String,
+	///
 	pub time: i64,
 	/// `git2::BlameHunk::final_start_line` returns 1-based indices, but
 	/// `start_line` is 0-based because the `Vec` storing the lines starts at
@@ -124,6 +129,7 @@ pub fn blame_file(
 					let hunk = BlameHunk {
 						commit_id,
 						author: commit_info.author.clone(),
+						email: commit_info.email.clone(),
 						time: commit_info.time,
 						start_line,
 						end_line,
@@ -149,12 +155,42 @@ pub fn blame_file(
 	Ok(file_blame)
 }
 
+/// the revision of `file_path` before `commit_id`: the last earlier
+/// commit touching it and its path there, which differs if
+/// `commit_id` renamed the file
+pub fn previous_revision(
+	repo_path: &RepoPath,
+	file_path: &str,
+	commit_id: CommitId,
+) -> Result<Option<(String, CommitId)>> {
+	scope_time!("previous_revision");
+
+	let path = commit_renamed_from(repo_path, commit_id, file_path)?
+		.unwrap_or_else(|| file_path.to_string());
+
+	let commits = LogWalker::new_with_start_by_path(
+		repo_path,
+		Some(&commit_id),
+		|walker| {
+			walker.filter_with_limit(
+				Some(filter_by_path(path.clone(), true)),
+				2,
+			)
+		},
+	)?;
+
+	Ok(commits.get(1).map(|id| (path, *id)))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::{
 		error::Result,
-		sync::{commit, stage_add_file, tests::repo_init_empty},
+		sync::{
+			commit, stage_add_all, stage_add_file,
+			tests::repo_init_empty,
+		},
 	};
 	use std::{
 		fs::{File, OpenOptions},
@@ -184,12 +220,13 @@ mod tests {
 			[(
 				Some(BlameHunk {
 					author,
+					email,
 					start_line: 0,
 					end_line: 1,
 					..
 				}),
 				line
-			)] if author == "name" && line == "line 1"
+			)] if author == "name" && email == "email" && line == "line 1"
 		));
 
 		let mut file = OpenOptions::new()
@@ -262,4 +299,32 @@ mod tests {
 
 		assert!(blame_file(repo_path, "bar\\foo", None).is_ok());
 	}
+
+	#[test]
+	fn test_previous_revision_follows_rename() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("old.txt"))?
+			.write_all(b"some content\nthat stays the same\n")?;
+		stage_add_file(repo_path, Path::new("old.txt"))?;
+		let added = commit(repo_path, "add")?;
+
+		std::fs::rename(root.join("old.txt"), root.join("new.txt"))?;
+		stage_add_all(repo_path, "*", None)?;
+		let renamed = commit(repo_path, "rename")?;
+
+		assert_eq!(
+			previous_revision(repo_path, "new.txt", renamed)?,
+			Some((String::from("old.txt"), added))
+		);
+		assert_eq!(
+			previous_revision(repo_path, "old.txt", added)?,
+			None
+		);
+
+		Ok(())
+	}
 }
@@ -14,7 +14,7 @@ use crate::{
 	error::{Error, Result},
 	sync::{repository::repo, utils::get_head_repo, CommitId},
 };
-use git2::{Branch, BranchType, Repository};
+use git2::{Branch, BranchType, ErrorCode, Repository};
 use scopetime::scope_time;
 use std::collections::HashSet;
 
@@ -95,6 +95,10 @@ pub struct BranchInfo {
 	pub top_commit_message: String,
 	///
 	pub top_commit: CommitId,
+	/// author name of `top_commit`
+	pub top_commit_author: String,
+	/// author time of `top_commit` (seconds since epoch)
+	pub top_commit_time: i64,
 	///
 	pub details: BranchDetails,
 }
@@ -152,6 +156,7 @@ pub fn get_branches_info(
 		.map(|b| {
 			let branch = b?.0;
 			let top_commit = branch.get().peel_to_commit()?;
+			let author = top_commit.author();
 			let reference = bytes2string(branch.get().name_bytes())?;
 			let upstream = branch.upstream();
 
@@ -192,6 +197,8 @@ pub fn get_branches_info(
 					top_commit.summary_bytes().unwrap_or_default(),
 				)?,
 				top_commit: top_commit.id().into(),
+				top_commit_author: bytes2string(author.name_bytes())?,
+				top_commit_time: author.when().seconds(),
 				details,
 			})
 		})
@@ -247,6 +254,24 @@ pub fn get_branch_remote(
 	}
 }
 
+/// short name of the upstream tracking branch of `branch`,
+/// like `origin/master`
+pub fn get_branch_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	let branch = repo.find_branch(branch, BranchType::Local)?;
+
+	let upstream = match branch.upstream() {
+		Ok(upstream) => upstream,
+		Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(bytes2string(upstream.name_bytes()?)?))
+}
+
 /// returns whether the pull merge strategy is set to rebase
 pub fn config_is_pull_rebase(repo_path: &RepoPath) -> Result<bool> {
 	let repo = repo(repo_path)?;
@@ -439,6 +464,24 @@ pub fn create_branch(
 	Ok(branch_ref_name)
 }
 
+/// creates a new branch pointing to `commit` without checking it out
+pub fn create_branch_at_commit(
+	repo_path: &RepoPath,
+	name: &str,
+	commit: CommitId,
+) -> Result<String> {
+	scope_time!("create_branch_at_commit");
+
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+
+	let branch = repo.branch(name, &commit, false)?;
+	let branch_ref = branch.into_reference();
+
+	bytes2string(branch_ref.name_bytes())
+}
+
 #[cfg(test)]
 mod tests_branch_name {
 	use super::*;
@@ -490,6 +533,27 @@ mod tests_create_branch {
 			"branch1"
 		);
 	}
+
+	#[test]
+	fn test_at_commit_keeps_head() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = get_head_repo(&repo).unwrap();
+		let head_name = get_branch_name(repo_path).unwrap();
+
+		assert_eq!(
+			create_branch_at_commit(repo_path, "other", head)
+				.unwrap()
+				.as_str(),
+			"refs/heads/other"
+		);
+
+		assert_eq!(get_branch_name(repo_path).unwrap(), head_name);
+		assert!(repo.find_branch("other", BranchType::Local).is_ok());
+	}
 }
 
 #[cfg(test)]
@@ -541,6 +605,23 @@ mod tests_branches {
 		);
 	}
 
+	#[test]
+	fn test_top_commit_author() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		let branches = get_branches_info(repo_path, true).unwrap();
+
+		assert_eq!(branches[0].top_commit_author, "name");
+		assert_eq!(
+			branches[0].top_commit_time,
+			head.author().when().seconds()
+		);
+	}
+
 	#[test]
 	fn test_multiple() {
 		let (_td, repo) = repo_init().unwrap();
@@ -660,6 +741,13 @@ mod tests_branches {
 				.unwrap(),
 			String::from("r2")
 		);
+
+		assert_eq!(
+			get_branch_upstream(repo_path, "r1branch")
+				.unwrap()
+				.unwrap(),
+			String::from("r1/r1branch")
+		);
 	}
 
 	#[test]
@@ -673,6 +761,10 @@ mod tests_branches {
 			get_branch_remote(repo_path, "master").unwrap(),
 			None
 		);
+		assert_eq!(
+			get_branch_upstream(repo_path, "master").unwrap(),
+			None
+		);
 	}
 
 	#[test]
@@ -1,10 +1,14 @@
 use super::{CommitId, RepoPath};
 use crate::{
-	error::Result,
+	error::{Error, Result},
 	sync::{repository::repo, utils::get_head_repo},
 };
-use git2::{ErrorCode, ObjectType, Repository, Signature};
+use git2::{
+	DiffFindOptions, ErrorCode, Index, ObjectType, Repository,
+	Signature, Sort,
+};
 use scopetime::scope_time;
+use std::path::Path;
 
 struct CleanupState<'a> {
 	pub repo: &'a Repository,
@@ -25,14 +29,66 @@ pub fn parent_ids(
 ) -> Result<Vec<CommitId>> {
 	let repo = repo(repo_path)?;
 	let res = repo
-		.find_commit(c.into())
-		.unwrap()
+		.find_commit(c.into())?
 		.parent_ids()
 		.map(CommitId::from)
 		.collect();
 	Ok(res)
 }
 
+/// follows the first parent `depth` times,
+/// fails with `Error::NoParent` if the history ends before that
+pub fn first_parent_ancestor(
+	repo_path: &RepoPath,
+	c: CommitId,
+	depth: usize,
+) -> Result<CommitId> {
+	let repo = repo(repo_path)?;
+	let mut commit = repo.find_commit(c.into())?;
+	for _ in 0..depth {
+		if commit.parent_count() == 0 {
+			return Err(Error::NoParent);
+		}
+		commit = commit.parent(0)?;
+	}
+	Ok(commit.id().into())
+}
+
+/// whether `c` is a proper ancestor of `HEAD`, i.e. there are
+/// commits between it and `HEAD` that could be rebased onto it
+pub fn is_ancestor_of_head(
+	repo_path: &RepoPath,
+	c: CommitId,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+	let head = repo.head()?.peel_to_commit()?.id();
+	Ok(repo.graph_descendant_of(head, c.into())?)
+}
+
+/// a commit reachable from `HEAD` that has `c` as one of its
+/// parents, the oldest one if there are several
+pub fn child_in_head_history(
+	repo_path: &RepoPath,
+	c: CommitId,
+) -> Result<Option<CommitId>> {
+	scope_time!("child_in_head_history");
+
+	let repo = repo(repo_path)?;
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+	walk.push_head()?;
+	walk.hide(c.into())?;
+
+	for id in walk {
+		let commit = repo.find_commit(id?)?;
+		if commit.parent_ids().any(|parent| parent == c.into()) {
+			return Ok(Some(commit.id().into()));
+		}
+	}
+
+	Ok(None)
+}
+
 ///
 pub fn cherrypick(
 	repo_path: &RepoPath,
@@ -168,6 +224,109 @@ pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
 		.into())
 }
 
+/// message committing the staged changes as a fixup of `target`:
+/// `fixup! <summary of target>`, so a later autosquash rebase folds
+/// them into `target`
+pub fn fixup_message(
+	repo_path: &RepoPath,
+	target: CommitId,
+) -> Result<String> {
+	scope_time!("fixup_message");
+
+	let repo = repo(repo_path)?;
+	let target = repo.find_commit(target.into())?;
+
+	let head_tree = repo.head()?.peel_to_tree()?.id();
+	if repo.index()?.write_tree()? == head_tree {
+		return Err(Error::Generic(String::from(
+			"nothing staged to fixup with",
+		)));
+	}
+
+	Ok(format!("fixup! {}", target.summary().unwrap_or_default()))
+}
+
+/// commits the staged changes of `paths` only, everything else
+/// staged stays staged (and uncommitted)
+///
+/// a staged rename is committed as a whole if either its old or
+/// new path is part of `paths`
+pub fn commit_paths(
+	repo_path: &RepoPath,
+	msg: &str,
+	paths: &[String],
+) -> Result<CommitId> {
+	scope_time!("commit_paths");
+
+	let repo = repo(repo_path)?;
+
+	let signature = signature_allow_undefined_name(&repo)?;
+	let index = repo.index()?;
+
+	let parent = get_head_repo(&repo)
+		.ok()
+		.map(|id| repo.find_commit(id.into()))
+		.transpose()?;
+	let head_tree =
+		parent.as_ref().map(git2::Commit::tree).transpose()?;
+
+	let mut partial = Index::new()?;
+	if let Some(tree) = &head_tree {
+		partial.read_tree(tree)?;
+	}
+
+	let mut diff = repo.diff_tree_to_index(
+		head_tree.as_ref(),
+		Some(&index),
+		None,
+	)?;
+	diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+	let is_selected = |path: Option<&Path>| {
+		path.map_or(false, |path| {
+			paths.iter().any(|p| Path::new(p) == path)
+		})
+	};
+
+	for delta in diff.deltas() {
+		let old = delta.old_file().path();
+		let new = delta.new_file().path();
+		if !is_selected(old) && !is_selected(new) {
+			continue;
+		}
+
+		for path in [old, new].into_iter().flatten() {
+			if let Some(entry) = index.get_path(path, 0) {
+				partial.add(&entry)?;
+			} else {
+				partial.remove_path(path)?;
+			}
+		}
+	}
+
+	let tree_id = partial.write_tree_to(&repo)?;
+	let tree = repo.find_tree(tree_id)?;
+
+	if head_tree.as_ref().map(git2::Tree::id) == Some(tree_id) {
+		return Err(Error::Generic(
+			"none of the selected files has staged changes".into(),
+		));
+	}
+
+	let parents = parent.iter().collect::<Vec<_>>();
+
+	Ok(repo
+		.commit(
+			Some("HEAD"),
+			&signature,
+			&signature,
+			msg,
+			&tree,
+			parents.as_slice(),
+		)?
+		.into())
+}
+
 /// Tag a commit.
 ///
 /// This function will return an `Err(…)` variant if the tag’s name is refused
@@ -199,11 +358,13 @@ pub fn tag_commit(
 #[cfg(test)]
 mod tests {
 
-	use crate::error::Result;
+	use crate::error::{Error, Result};
 	use crate::sync::tags::Tag;
 	use crate::sync::RepoPath;
 	use crate::sync::{
-		commit, get_commit_details, get_commit_files, stage_add_file,
+		child_in_head_history, commit, commit_paths,
+		first_parent_ancestor, fixup_message, get_commit_details,
+		get_commit_files, is_ancestor_of_head, stage_add_file,
 		tags::get_tags,
 		tests::{get_statuses, repo_init, repo_init_empty},
 		utils::get_head,
@@ -244,6 +405,64 @@ mod tests {
 		assert_eq!(get_statuses(repo_path), (0, 0));
 	}
 
+	#[test]
+	fn test_commit_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		for name in ["a", "b", "c"] {
+			File::create(root.join(name))
+				.unwrap()
+				.write_all(name.as_bytes())
+				.unwrap();
+			stage_add_file(repo_path, Path::new(name)).unwrap();
+		}
+		commit(repo_path, "add").unwrap();
+
+		// rename `a`, change `b` and `c`
+		std::fs::rename(root.join("a"), root.join("d")).unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("a")).unwrap();
+		index.add_path(Path::new("d")).unwrap();
+		index.write().unwrap();
+		for name in ["b", "c"] {
+			File::create(root.join(name))
+				.unwrap()
+				.write_all(b"changed")
+				.unwrap();
+			stage_add_file(repo_path, Path::new(name)).unwrap();
+		}
+		assert_eq!(get_statuses(repo_path), (0, 3));
+
+		let id = commit_paths(
+			repo_path,
+			"partial",
+			&[String::from("d"), String::from("b")],
+		)
+		.unwrap();
+
+		let mut files = get_commit_files(repo_path, id, None)
+			.unwrap()
+			.into_iter()
+			.map(|f| f.path)
+			.collect::<Vec<_>>();
+		files.sort();
+		// the whole rename: `a` deleted, `d` added
+		assert_eq!(files, vec!["a", "b", "d"]);
+
+		// `c` stays staged
+		assert_eq!(get_statuses(repo_path), (0, 1));
+
+		assert!(commit_paths(
+			repo_path,
+			"none",
+			&[String::from("b")]
+		)
+		.is_err());
+	}
+
 	#[test]
 	fn test_commit_in_empty_repo() {
 		let file_path = Path::new("foo");
@@ -461,4 +680,123 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_first_parent_ancestor_of_root() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path)?;
+		let first = commit(repo_path, "first")?;
+
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path)?;
+		let second = commit(repo_path, "second")?;
+
+		assert_eq!(
+			first_parent_ancestor(repo_path, second, 0)?,
+			second
+		);
+		assert_eq!(
+			first_parent_ancestor(repo_path, second, 1)?,
+			first
+		);
+		assert!(matches!(
+			first_parent_ancestor(repo_path, second, 2),
+			Err(Error::NoParent)
+		));
+		assert!(matches!(
+			first_parent_ancestor(repo_path, first, 1),
+			Err(Error::NoParent)
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_is_ancestor_of_head() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path)?;
+		let first = commit(repo_path, "first")?;
+
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path)?;
+		let second = commit(repo_path, "second")?;
+
+		assert!(is_ancestor_of_head(repo_path, first)?);
+		assert!(!is_ancestor_of_head(repo_path, second)?);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_child_in_head_history() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path)?;
+		let first = commit(repo_path, "first")?;
+
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path)?;
+		let second = commit(repo_path, "second")?;
+
+		File::create(root.join(file_path))?.write_all(b"c")?;
+		stage_add_file(repo_path, file_path)?;
+		let third = commit(repo_path, "third")?;
+
+		assert_eq!(
+			child_in_head_history(repo_path, first)?,
+			Some(second)
+		);
+		assert_eq!(
+			child_in_head_history(repo_path, second)?,
+			Some(third)
+		);
+		assert_eq!(child_in_head_history(repo_path, third)?, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_fixup_message() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path)?;
+		let first = commit(repo_path, "first\n\nbody")?;
+
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path)?;
+		commit(repo_path, "second")?;
+
+		assert!(matches!(
+			fixup_message(repo_path, first),
+			Err(Error::Generic(_))
+		));
+
+		File::create(root.join(file_path))?.write_all(b"c")?;
+		stage_add_file(repo_path, file_path)?;
+		assert_eq!(fixup_message(repo_path, first)?, "fixup! first");
+
+		Ok(())
+	}
 }
@@ -6,9 +6,9 @@ use super::{
 use crate::{
 	error::Result, sync::repository::repo, StatusItem, StatusItemType,
 };
-use git2::{Commit, Diff, Repository};
+use git2::{Commit, Delta, Diff, DiffFindOptions, Repository};
 use scopetime::scope_time;
-use std::cmp::Ordering;
+use std::{cmp::Ordering, path::Path};
 
 /// get all files that are part of a commit
 pub fn get_commit_files(
@@ -26,8 +26,27 @@ pub fn get_commit_files(
 		get_commit_diff(repo_path, &repo, id, None, None)?
 	};
 
-	let res = diff
-		.deltas()
+	Ok(diff_files(&diff))
+}
+
+/// files a merge commit changed compared to its `parent`-th parent
+/// (`0` being the first one)
+pub fn get_commit_parent_files(
+	repo_path: &RepoPath,
+	id: CommitId,
+	parent: usize,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("get_commit_parent_files");
+
+	let repo = repo(repo_path)?;
+
+	let diff = get_commit_parent_diff(&repo, id, parent, None, None)?;
+
+	Ok(diff_files(&diff))
+}
+
+fn diff_files(diff: &Diff) -> Vec<StatusItem> {
+	diff.deltas()
 		.map(|delta| {
 			let status = StatusItemType::from(delta.status());
 
@@ -40,9 +59,106 @@ pub fn get_commit_files(
 				status,
 			}
 		})
-		.collect::<Vec<_>>();
+		.collect::<Vec<_>>()
+}
+
+/// number of files changed by each of the `commits` (compared to
+/// their first parent)
+pub fn commits_files_count(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+) -> Result<Vec<(CommitId, usize)>> {
+	scope_time!("commits_files_count");
+
+	let repo = repo(repo_path)?;
+
+	commits
+		.iter()
+		.map(|id| {
+			let diff =
+				get_commit_diff(repo_path, &repo, *id, None, None)?;
+			Ok((*id, diff.deltas().len()))
+		})
+		.collect()
+}
+
+/// the path `path` had before commit `id` renamed it to `path`,
+/// `None` if the commit did not rename it
+pub fn commit_renamed_from(
+	repo_path: &RepoPath,
+	id: CommitId,
+	path: &str,
+) -> Result<Option<String>> {
+	scope_time!("commit_renamed_from");
+
+	let repo = repo(repo_path)?;
+
+	// no pathspec, the old name has to be part of the diff to be
+	// paired with the new one
+	let mut diff = get_commit_diff(repo_path, &repo, id, None, None)?;
+	diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+	let renamed_from = diff
+		.deltas()
+		.filter(|delta| delta.status() == Delta::Renamed)
+		.find(|delta| {
+			delta.new_file().path() == Some(Path::new(path))
+		})
+		.and_then(|delta| {
+			delta
+				.old_file()
+				.path()
+				.map(|p| p.to_string_lossy().to_string())
+		});
+
+	Ok(renamed_from)
+}
+
+/// whether commit `id` changed anything at `path`
+pub fn commit_touches_path(
+	repo_path: &RepoPath,
+	id: CommitId,
+	path: &str,
+) -> Result<bool> {
+	scope_time!("commit_touches_path");
+
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(id.into())?;
+	let diff = get_commit_diff_by_path(&repo, &commit, path)?;
+
+	Ok(diff.deltas().len() > 0)
+}
+
+/// the old and new side of comparing `ids`, the older commit is
+/// the old side
+pub fn compare_commits_sides(
+	repo_path: &RepoPath,
+	ids: (CommitId, CommitId),
+) -> Result<(CommitId, CommitId)> {
+	let repo = repo(repo_path)?;
+	let commits = compare_commits_ordered(&repo, ids)?;
+
+	Ok((commits.0.id().into(), commits.1.id().into()))
+}
 
-	Ok(res)
+fn compare_commits_ordered(
+	repo: &Repository,
+	ids: (CommitId, CommitId),
+) -> Result<(Commit<'_>, Commit<'_>)> {
+	let commits = (
+		repo.find_commit(ids.0.into())?,
+		repo.find_commit(ids.1.into())?,
+	);
+
+	Ok(
+		if commits.0.time().cmp(&commits.1.time())
+			== Ordering::Greater
+		{
+			(commits.1, commits.0)
+		} else {
+			commits
+		},
+	)
 }
 
 /// get diff of two arbitrary commits
@@ -55,18 +171,7 @@ pub fn get_compare_commits_diff(
 ) -> Result<Diff<'_>> {
 	// scope_time!("get_compare_commits_diff");
 
-	let commits = (
-		repo.find_commit(ids.0.into())?,
-		repo.find_commit(ids.1.into())?,
-	);
-
-	let commits = if commits.0.time().cmp(&commits.1.time())
-		== Ordering::Greater
-	{
-		(commits.1, commits.0)
-	} else {
-		commits
-	};
+	let commits = compare_commits_ordered(repo, ids)?;
 
 	let trees = (commits.0.tree()?, commits.1.tree()?);
 
@@ -74,6 +179,7 @@ pub fn get_compare_commits_diff(
 	if let Some(options) = options {
 		opts.context_lines(options.context);
 		opts.ignore_whitespace(options.ignore_whitespace);
+		opts.ignore_blank_lines(options.ignore_blank_lines);
 		opts.interhunk_lines(options.interhunk_lines);
 	}
 	if let Some(p) = &pathspec {
@@ -128,63 +234,88 @@ pub fn get_commit_diff<'a>(
 ) -> Result<Diff<'a>> {
 	// scope_time!("get_commit_diff");
 
+	let mut diff = get_commit_parent_diff(
+		repo,
+		id,
+		0,
+		pathspec.as_deref(),
+		options,
+	)?;
+
+	if is_stash_commit(repo_path, &id)? {
+		let commit = repo.find_commit(id.into())?;
+		if let Ok(untracked_commit) = commit.parent_id(2) {
+			let untracked_diff = get_commit_diff(
+				repo_path,
+				repo,
+				CommitId::new(untracked_commit),
+				pathspec,
+				options,
+			)?;
+
+			diff.merge(&untracked_diff)?;
+		}
+	}
+
+	Ok(diff)
+}
+
+/// get diff of a commit to its `parent`-th parent, a root commit is
+/// diffed against the empty tree. fails if there is no such parent
+pub fn get_commit_parent_diff<'a>(
+	repo: &'a Repository,
+	id: CommitId,
+	parent: usize,
+	pathspec: Option<&str>,
+	options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
 	let commit = repo.find_commit(id.into())?;
 	let commit_tree = commit.tree()?;
 
-	let parent = if commit.parent_count() > 0 {
-		repo.find_commit(commit.parent_id(0)?)
-			.ok()
-			.and_then(|c| c.tree().ok())
-	} else {
+	let parent = if commit.parent_count() == 0 && parent == 0 {
 		None
+	} else {
+		Some(repo.find_commit(commit.parent_id(parent)?)?.tree()?)
 	};
 
 	let mut opts = git2::DiffOptions::new();
 	if let Some(options) = options {
 		opts.context_lines(options.context);
 		opts.ignore_whitespace(options.ignore_whitespace);
+		opts.ignore_blank_lines(options.ignore_blank_lines);
 		opts.interhunk_lines(options.interhunk_lines);
 	}
-	if let Some(p) = &pathspec {
-		opts.pathspec(p.clone());
+	if let Some(p) = pathspec {
+		opts.pathspec(p);
 	}
 	opts.show_binary(true);
 
-	let mut diff = repo.diff_tree_to_tree(
+	let diff = repo.diff_tree_to_tree(
 		parent.as_ref(),
 		Some(&commit_tree),
 		Some(&mut opts),
 	)?;
 
-	if is_stash_commit(repo_path, &id)? {
-		if let Ok(untracked_commit) = commit.parent_id(2) {
-			let untracked_diff = get_commit_diff(
-				repo_path,
-				repo,
-				CommitId::new(untracked_commit),
-				pathspec,
-				options,
-			)?;
-
-			diff.merge(&untracked_diff)?;
-		}
-	}
-
 	Ok(diff)
 }
 
 #[cfg(test)]
 mod tests {
-	use super::get_commit_files;
+	use super::{
+		commit_renamed_from, commits_files_count, get_commit_files,
+		get_commit_parent_files,
+	};
 	use crate::{
 		error::Result,
 		sync::{
-			commit, stage_add_file, stash_save,
-			tests::{get_statuses, repo_init},
+			checkout_branch, commit, create_branch, merge_branch,
+			merge_commit, stage_add_all, stage_add_file, stash_save,
+			tests::{get_statuses, repo_init, write_commit_file},
 			RepoPath,
 		},
 		StatusItemType,
 	};
+	use git2::BranchType;
 	use std::{fs::File, io::Write, path::Path};
 
 	#[test]
@@ -210,6 +341,104 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_merge_parent_files() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "first");
+		create_branch(repo_path, "foo")?;
+		let theirs = write_commit_file(&repo, "b.txt", "b", "theirs");
+		checkout_branch(repo_path, "refs/heads/master")?;
+		write_commit_file(&repo, "c.txt", "c", "ours");
+
+		merge_branch(repo_path, "foo", BranchType::Local)?;
+		let merge = merge_commit(repo_path, "merge", &[theirs])?;
+
+		let paths = |files: Vec<crate::StatusItem>| -> Vec<String> {
+			files.into_iter().map(|f| f.path).collect()
+		};
+
+		// the first parent is the default, so the side merged in
+		assert_eq!(
+			paths(get_commit_files(repo_path, merge, None)?),
+			vec!["b.txt"]
+		);
+		assert_eq!(
+			paths(get_commit_parent_files(repo_path, merge, 0)?),
+			vec!["b.txt"]
+		);
+		assert_eq!(
+			paths(get_commit_parent_files(repo_path, merge, 1)?),
+			vec!["c.txt"]
+		);
+		assert!(get_commit_parent_files(repo_path, merge, 2).is_err());
+		assert!(
+			get_commit_parent_files(repo_path, theirs, 1).is_err()
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_files_count() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("a.txt"))?.write_all(b"a")?;
+		stage_add_file(repo_path, Path::new("a.txt"))?;
+		let first = commit(repo_path, "first")?;
+
+		File::create(root.join("b.txt"))?.write_all(b"b")?;
+		File::create(root.join("c.txt"))?.write_all(b"c")?;
+		stage_add_file(repo_path, Path::new("b.txt"))?;
+		stage_add_file(repo_path, Path::new("c.txt"))?;
+		let second = commit(repo_path, "second")?;
+
+		let counts =
+			commits_files_count(repo_path, &[second, first])?;
+
+		assert_eq!(counts, vec![(second, 2), (first, 1)]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_renamed_from() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("old.txt"))?
+			.write_all(b"some content\nthat stays the same\n")?;
+		stage_add_file(repo_path, Path::new("old.txt"))?;
+		let added = commit(repo_path, "add")?;
+
+		std::fs::rename(root.join("old.txt"), root.join("new.txt"))?;
+		stage_add_all(repo_path, "*", None)?;
+		let renamed = commit(repo_path, "rename")?;
+
+		assert_eq!(
+			commit_renamed_from(repo_path, renamed, "new.txt")?,
+			Some(String::from("old.txt"))
+		);
+		assert_eq!(
+			commit_renamed_from(repo_path, renamed, "old.txt")?,
+			None
+		);
+		assert_eq!(
+			commit_renamed_from(repo_path, added, "old.txt")?,
+			None
+		);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_stashed_untracked() -> Result<()> {
 		let file_path = Path::new("file1.txt");
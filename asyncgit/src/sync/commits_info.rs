@@ -3,6 +3,7 @@ use crate::{error::Result, sync::repository::repo};
 use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use git2::{Commit, Error, Oid};
 use scopetime::scope_time;
+use std::str::FromStr;
 use unicode_truncate::UnicodeTruncateStr;
 
 /// identifies a single commit
@@ -40,6 +41,15 @@ impl ToString for CommitId {
 	}
 }
 
+impl FromStr for CommitId {
+	type Err = Error;
+
+	/// parses a full hex hash
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		Oid::from_str(s).map(Self::new)
+	}
+}
+
 impl From<CommitId> for Oid {
 	fn from(id: CommitId) -> Self {
 		id.0
@@ -65,6 +75,8 @@ pub struct CommitInfo {
 	pub id: CommitId,
 	///
 	pub email: String,
+	/// committer name when it differs from `author`
+	pub committer: Option<String>,
 }
 
 impl CommitInfo {
@@ -93,11 +105,17 @@ impl CommitInfo {
 			date.unwrap_or_default(),
 			Utc,
 		));
+		let committer = self
+			.committer
+			.as_ref()
+			.map(|c| format!("Committer: {c}\n"))
+			.unwrap_or_default();
 		let summary = format!(
-			"SHA: {}\nAuthor: {} <{}>\nDate: {}\n\n{}",
+			"SHA: {}\nAuthor: {} <{}>\n{}Date: {}\n\n{}",
 			self.id.to_string(),
 			self.author,
 			self.email,
+			committer,
 			dt,
 			self.message
 		);
@@ -128,12 +146,14 @@ pub fn get_commits_info(
 				c.author().name().unwrap_or("<unkown").to_owned();
 			let email =
 				c.author().email().unwrap_or("<no email>").to_owned();
+			let committer = get_committer(&c, &author);
 			CommitInfo {
 				message,
 				author,
 				time: c.time().seconds(),
 				id: CommitId(c.id()),
 				email,
+				committer,
 			}
 		})
 		.collect::<Vec<_>>();
@@ -152,16 +172,25 @@ pub fn get_commit_info(
 
 	let commit = repo.find_commit((*commit_id).into())?;
 	let author = commit.author();
+	let author_name = author.name().unwrap_or("<unknown>");
 
 	Ok(CommitInfo {
 		message: commit.message().unwrap_or("").into(),
-		author: author.name().unwrap_or("<unknown>").into(),
+		author: author_name.into(),
 		time: commit.time().seconds(),
 		id: CommitId(commit.id()),
 		email: author.email().unwrap_or("<no email>").into(),
+		committer: get_committer(&commit, author_name),
 	})
 }
 
+/// committer name, `None` if it matches the author
+fn get_committer(c: &Commit, author: &str) -> Option<String> {
+	let committer = c.committer();
+	let name = committer.name().unwrap_or("<unknown>");
+	(name != author).then(|| name.to_owned())
+}
+
 /// if `message_limit` is set the message will be
 /// limited to the first line and truncated to fit
 pub fn get_message(
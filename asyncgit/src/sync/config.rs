@@ -5,11 +5,12 @@ use crate::error::Result;
 use git2::Repository;
 use scopetime::scope_time;
 use serde::{Deserialize, Serialize};
+use std::{fs::read_to_string, path::Path};
 
 use super::{repository::repo, RepoPath};
 
 ///
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GitExternCommands {
 	///base push command (git push)
 	pub push_base: Option<String>,
@@ -17,6 +18,9 @@ pub struct GitExternCommands {
 	pub fetch_base: Option<String>,
 	///base checkout command (git checkout)
 	pub checkout_base: Option<String>,
+	///base pull command (git pull), replaces fetch + merge
+	#[serde(default)]
+	pub pull_base: Option<String>,
 }
 
 impl Default for GitExternCommands {
@@ -25,6 +29,7 @@ impl Default for GitExternCommands {
 			push_base: None,
 			fetch_base: None,
 			checkout_base: None,
+			pull_base: None,
 		}
 	}
 }
@@ -124,6 +129,28 @@ pub fn get_config_string_repo(
 	}
 }
 
+/// content of the commit message template: the file set in
+/// `commit.template` (relative paths are taken from the worktree
+/// root, `~` is expanded) or else `.gitmessage` in the worktree root
+pub fn commit_template(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	scope_time!("commit_template");
+
+	let repo = repo(repo_path)?;
+	let workdir = repo.workdir().map(Path::to_path_buf);
+
+	let path = match repo.config()?.get_path("commit.template") {
+		Ok(path) if path.is_relative() => {
+			workdir.map(|workdir| workdir.join(path))
+		}
+		Ok(path) => Some(path),
+		Err(_) => workdir.map(|workdir| workdir.join(".gitmessage")),
+	};
+
+	Ok(path.and_then(|path| read_to_string(path).ok()))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -149,4 +176,38 @@ mod tests {
 		assert!(good_cfg.is_ok());
 		assert!(good_cfg.unwrap().is_some());
 	}
+
+	#[test]
+	fn test_commit_template() {
+		let (td, repo) = repo_init().unwrap();
+		let root = td.path();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(commit_template(repo_path).unwrap(), None);
+
+		std::fs::write(root.join(".gitmessage"), "default\n")
+			.unwrap();
+		assert_eq!(
+			commit_template(repo_path).unwrap().as_deref(),
+			Some("default\n")
+		);
+
+		std::fs::write(root.join("template.txt"), "configured\n")
+			.unwrap();
+		repo.config()
+			.unwrap()
+			.set_str("commit.template", "template.txt")
+			.unwrap();
+		assert_eq!(
+			commit_template(repo_path).unwrap().as_deref(),
+			Some("configured\n")
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str("commit.template", "missing.txt")
+			.unwrap();
+		assert_eq!(commit_template(repo_path).unwrap(), None);
+	}
 }
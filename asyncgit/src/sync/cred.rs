@@ -6,6 +6,21 @@ use super::{
 use crate::error::{Error, Result};
 use git2::CredentialHelper;
 
+pub(crate) const MSG_BAD_CREDENTIALS: &str = "Bad credentials.";
+pub(crate) const MSG_NO_CREDENTIALS: &str =
+	"Couldn't find credentials";
+
+/// messages of failed authentication attempts, from our own
+/// credentials callback, libgit2 and the git cli
+const AUTH_ERROR_PATTERNS: &[&str] = &[
+	MSG_BAD_CREDENTIALS,
+	MSG_NO_CREDENTIALS,
+	"authentication replays",
+	"authentication required",
+	"Authentication failed",
+	"could not read Username",
+];
+
 /// basic Authentication Credentials
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct BasicAuthCredential {
@@ -29,6 +44,15 @@ impl BasicAuthCredential {
 	}
 }
 
+/// true if a failed push/fetch (its error message) was rejected
+/// because of missing or wrong credentials, a retry with other
+/// credentials might succeed then
+pub fn is_auth_error(msg: &str) -> bool {
+	AUTH_ERROR_PATTERNS
+		.iter()
+		.any(|pattern| msg.contains(pattern))
+}
+
 /// know if username and password are needed for this url
 pub fn need_username_password(repo_path: &RepoPath) -> Result<bool> {
 	let repo = repo(repo_path)?;
@@ -93,7 +117,8 @@ mod tests {
 	use crate::sync::{
 		cred::{
 			extract_cred_from_url, extract_username_password,
-			need_username_password, BasicAuthCredential,
+			is_auth_error, need_username_password,
+			BasicAuthCredential,
 		},
 		remotes::DEFAULT_REMOTE_NAME,
 		tests::repo_init,
@@ -113,6 +138,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_is_auth_error() {
+		assert!(is_auth_error(
+			"git error:Bad credentials.; class=Http (34)"
+		));
+		assert!(is_auth_error(
+			"fatal: Authentication failed for 'https://host/repo'"
+		));
+		assert!(!is_auth_error(
+			"git error:failed to resolve address"
+		));
+	}
+
 	#[test]
 	fn test_credential_not_complete() {
 		assert_eq!(
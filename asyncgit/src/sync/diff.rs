@@ -1,12 +1,15 @@
 //! sync git api for fetching a diff
 
 use super::{
-	commit_files::{get_commit_diff, get_compare_commits_diff},
+	commit_files::{
+		get_commit_diff, get_commit_parent_diff, get_compare_commits_diff,
+	},
 	utils::{get_head_repo, work_dir},
 	CommitId, RepoPath,
 };
 use crate::{
 	error::Error, error::Result, hash, sync::repository::repo,
+	DiffParams, DiffType,
 };
 use easy_cast::Conv;
 use git2::{
@@ -134,6 +137,10 @@ pub struct FileDiff {
 pub struct DiffOptions {
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub ignore_whitespace: bool,
+	/// hide changes that only add or remove blank lines
+	/// (`--ignore-blank-lines`)
+	#[serde(default)]
+	pub ignore_blank_lines: bool,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub context: u32,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
@@ -144,6 +151,7 @@ impl Default for DiffOptions {
 	fn default() -> Self {
 		Self {
 			ignore_whitespace: false,
+			ignore_blank_lines: false,
 			context: 3,
 			interhunk_lines: 0,
 		}
@@ -159,14 +167,7 @@ pub(crate) fn get_diff_raw<'a>(
 ) -> Result<Diff<'a>> {
 	// scope_time!("get_diff_raw");
 
-	let mut opt = git2::DiffOptions::new();
-	if let Some(options) = options {
-		opt.context_lines(options.context);
-		opt.ignore_whitespace(options.ignore_whitespace);
-		opt.interhunk_lines(options.interhunk_lines);
-	}
-	opt.pathspec(p);
-	opt.reverse(reverse);
+	let mut opt = raw_diff_options(p, reverse, options);
 
 	let diff = if stage {
 		// diff against head
@@ -195,6 +196,23 @@ pub(crate) fn get_diff_raw<'a>(
 	Ok(diff)
 }
 
+fn raw_diff_options(
+	p: &str,
+	reverse: bool,
+	options: Option<DiffOptions>,
+) -> git2::DiffOptions {
+	let mut opt = git2::DiffOptions::new();
+	if let Some(options) = options {
+		opt.context_lines(options.context);
+		opt.ignore_whitespace(options.ignore_whitespace);
+		opt.ignore_blank_lines(options.ignore_blank_lines);
+		opt.interhunk_lines(options.interhunk_lines);
+	}
+	opt.pathspec(p);
+	opt.reverse(reverse);
+	opt
+}
+
 /// returns diff of a specific file either in `stage` or workdir
 pub fn get_diff(
 	repo_path: &RepoPath,
@@ -229,6 +247,25 @@ pub fn get_diff_commit(
 	raw_diff_to_file_diff(&diff, work_dir)
 }
 
+/// get file changes of a diff of commit `id` to its `parent`-th
+/// parent, to see what a merge brought in from either side
+pub fn get_diff_commit_parent(
+	repo_path: &RepoPath,
+	id: CommitId,
+	parent: usize,
+	p: &str,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_commit_parent");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let diff =
+		get_commit_parent_diff(&repo, id, parent, Some(p), options)?;
+
+	raw_diff_to_file_diff(&diff, work_dir)
+}
+
 /// get file changes of a diff between two commits
 pub fn get_diff_commits(
 	repo_path: &RepoPath,
@@ -250,6 +287,61 @@ pub fn get_diff_commits(
 	raw_diff_to_file_diff(&diff, work_dir)
 }
 
+/// the diff `params` ask for as a patch that `git apply` takes,
+/// with the file's line endings, encoding and modes kept
+pub fn get_diff_patch(
+	repo_path: &RepoPath,
+	params: &DiffParams,
+) -> Result<Vec<u8>> {
+	scope_time!("get_diff_patch");
+
+	let repo = repo(repo_path)?;
+	let pathspec = Some(params.path.clone());
+	let options = Some(params.options);
+
+	let diff = match params.diff_type {
+		DiffType::Stage => {
+			get_diff_raw(&repo, &params.path, true, false, options)?
+		}
+		DiffType::WorkDir => {
+			let mut opt =
+				raw_diff_options(&params.path, false, options);
+			opt.include_untracked(true);
+			opt.recurse_untracked_dirs(true);
+			opt.show_untracked_content(true);
+			repo.diff_index_to_workdir(None, Some(&mut opt))?
+		}
+		DiffType::Commit(id) => {
+			get_commit_diff(repo_path, &repo, id, pathspec, options)?
+		}
+		DiffType::CommitParent(id, parent) => get_commit_parent_diff(
+			&repo,
+			id,
+			parent,
+			pathspec.as_deref(),
+			options,
+		)?,
+		DiffType::Commits(ids) => {
+			get_compare_commits_diff(&repo, ids, pathspec, options)?
+		}
+	};
+
+	let mut patch = Vec::new();
+	diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+		let prefix: &[u8] = match line.origin() {
+			'+' => b"+",
+			'-' => b"-",
+			' ' => b" ",
+			_ => b"",
+		};
+		patch.extend_from_slice(prefix);
+		patch.extend_from_slice(line.content());
+		true
+	})?;
+
+	Ok(patch)
+}
+
 ///
 //TODO: refactor into helper type with the inline closures as dedicated functions
 #[allow(clippy::too_many_lines)]
@@ -414,7 +506,9 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-	use super::{get_diff, get_diff_commit};
+	use super::{
+		get_diff, get_diff_commit, get_diff_patch, DiffOptions,
+	};
 	use crate::{
 		error::Result,
 		sync::{
@@ -423,6 +517,7 @@ mod tests {
 			tests::{get_statuses, repo_init, repo_init_empty},
 			RepoPath,
 		},
+		DiffParams, DiffType,
 	};
 	use std::{
 		fs::{self, File},
@@ -554,6 +649,32 @@ mod tests {
 		assert_eq!(res.hunks.len(), 2)
 	}
 
+	#[test]
+	fn test_ignore_blank_lines() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let file_path = root.join("bar.txt");
+		fs::write(&file_path, "a\nb\nc\n").unwrap();
+		stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+		fs::write(&file_path, "a\n\nb\nc\n\n").unwrap();
+
+		let res =
+			get_diff(repo_path, "bar.txt", false, None).unwrap();
+		assert_eq!(res.hunks.len(), 1);
+
+		let options = DiffOptions {
+			ignore_blank_lines: true,
+			..DiffOptions::default()
+		};
+		let res =
+			get_diff(repo_path, "bar.txt", false, Some(options))
+				.unwrap();
+		assert!(res.hunks.is_empty());
+	}
+
 	#[test]
 	fn test_diff_newfile_in_sub_dir_current_dir() {
 		let file_path = Path::new("foo/foo.txt");
@@ -665,4 +786,45 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_diff_patch_keeps_raw_lines() -> Result<()> {
+		let file_path = Path::new("foo.tex");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		let workdir_patch = |path: &str| -> Result<String> {
+			let patch = get_diff_patch(
+				repo_path,
+				&DiffParams {
+					path: path.into(),
+					diff_type: DiffType::WorkDir,
+					options: DiffOptions::default(),
+				},
+			)?;
+			Ok(String::from_utf8(patch).unwrap())
+		};
+
+		File::create(root.join(file_path))?
+			.write_all(b"\\begin\r\nold\r\n")?;
+		stage_add_file(repo_path, file_path)?;
+		commit(repo_path, "")?;
+		File::create(root.join(file_path))?
+			.write_all(b"\\begin\r\nnew\r\n")?;
+
+		let modified = workdir_patch("foo.tex")?;
+		assert!(
+			modified.starts_with("diff --git a/foo.tex b/foo.tex\n")
+		);
+		assert!(modified.ends_with(" \\begin\r\n-old\r\n+new\r\n"));
+
+		File::create(root.join("bar"))?.write_all(b"bar\n")?;
+
+		let untracked = workdir_patch("bar")?;
+		assert!(untracked.contains("new file mode 100644\n"));
+		assert!(untracked.ends_with("@@ -0,0 +1 @@\n+bar\n"));
+
+		Ok(())
+	}
 }
@@ -2,9 +2,10 @@
 
 use anyhow::{anyhow, Result};
 use std::{
-	collections::HashSet,
+	collections::{HashMap, HashSet},
+	hash::BuildHasher,
 	path::PathBuf,
-	process::{Command, Stdio},
+	process::{Child, Command, Stdio},
 	time::Duration,
 };
 
@@ -13,13 +14,13 @@ use shared_memory::*;
 
 use super::CommitId;
 
-struct TempEditor<'a> {
+struct TempEditor {
 	cache_path: PathBuf,
-	event_id: &'a str,
+	event_id: String,
 }
 
-impl<'a> TempEditor<'a> {
-	pub fn new(e: &'a str) -> Self {
+impl TempEditor {
+	pub fn new(e: String) -> Self {
 		Self {
 			cache_path: PathBuf::new(),
 			event_id: e,
@@ -46,9 +47,7 @@ impl<'a> TempEditor<'a> {
 			.ok_or_else(|| anyhow!("failed to find os cache dir."))?;
 
 		self.cache_path.push("gitui");
-		if let Err(_) = std::fs::create_dir(self.cache_path.as_path())
-		{
-		}
+		std::fs::create_dir_all(self.cache_path.as_path())?;
 		Ok(())
 	}
 
@@ -94,7 +93,7 @@ impl<'a> TempEditor<'a> {
 	}
 }
 
-impl<'a> Drop for TempEditor<'a> {
+impl Drop for TempEditor {
 	fn drop(&mut self) {
 		if let Err(_) =
 			std::fs::remove_file(self.cache_path.as_path())
@@ -312,7 +311,107 @@ impl Drop for TerminalState {
 	}
 }
 
+/// a running `git rebase -i` that waits for its todo list to be
+/// edited, git goes on once the session is finished
 ///
+/// dropping an unfinished session empties the todo, which makes git
+/// abort the rebase
+pub struct RebaseTodoSession {
+	child: Child,
+	todo_file: String,
+	finished: bool,
+	// dropped in this order: let git go on, then clean up
+	events: IPCEvents,
+	_sequence_editor: TempEditor,
+	_terminal_state: TerminalState,
+}
+
+impl RebaseTodoSession {
+	/// starts `git rebase -i <base>` and waits for the todo list,
+	/// with an `editor` git gets the terminal (for reword and squash
+	/// messages), otherwise its output is muted. its progress and
+	/// error messages are muted either way, they would end up in the
+	/// middle of the UI
+	pub fn start(
+		repo: &str,
+		base: &str,
+		editor: Option<&str>,
+	) -> Result<Self> {
+		let event_id = format!("{}", std::process::id());
+		let mut sequence_editor = TempEditor::new(event_id.clone());
+		sequence_editor.create()?;
+		let sequence_editor_path =
+			sequence_editor.to_str().ok_or_else(|| {
+				anyhow!("sequence editor path is not valid utf8")
+			})?;
+
+		let mut cmd = Command::new("git");
+		cmd.current_dir(repo)
+			.arg("-c")
+			.arg(format!("sequence.editor='{sequence_editor_path}'"))
+			.arg("-c")
+			.arg("rebase.instructionFormat=\"%H\"")
+			.arg("rebase")
+			.arg("-i")
+			.arg(base);
+
+		cmd.stderr(Stdio::null());
+		if let Some(editor) = editor {
+			cmd.env("GIT_EDITOR", editor);
+		} else {
+			cmd.stdout(Stdio::null());
+		}
+
+		let terminal_state = TerminalState::capture();
+		let events = IPCEvents::main(&event_id)?;
+		let child = cmd.spawn()?;
+		events.wait_connected_ready()?;
+		let todo_file = events.get_str();
+
+		Ok(Self {
+			child,
+			todo_file,
+			finished: false,
+			events,
+			_sequence_editor: sequence_editor,
+			_terminal_state: terminal_state,
+		})
+	}
+
+	/// path of the todo list git is waiting on
+	pub fn todo_file(&self) -> &str {
+		&self.todo_file
+	}
+
+	/// hands the todo list back to git and waits for the rebase
+	/// to finish or stop
+	pub fn finish(mut self) -> Result<()> {
+		self.finished = true;
+		self.events.signal_connected_shutdown()?;
+		self.child.wait()?;
+		Ok(())
+	}
+
+	/// lets git abort the rebase, nothing gets rewritten
+	pub fn abort(self) -> Result<()> {
+		write_rebase_todo(&self.todo_file, Vec::new())?;
+		self.finish()
+	}
+}
+
+impl Drop for RebaseTodoSession {
+	fn drop(&mut self) {
+		if !self.finished {
+			// an empty todo makes git abort the rebase
+			let _ = write_rebase_todo(&self.todo_file, Vec::new());
+			let _ = self.events.signal_connected_shutdown();
+			let _ = self.child.wait();
+		}
+	}
+}
+
+/// runs `git rebase -i <base>` with the todo list edited by `f`,
+/// the rebase is aborted if `f` fails
 pub fn rebase_interactive<F>(
 	repo: &str,
 	base: &str,
@@ -321,35 +420,9 @@ pub fn rebase_interactive<F>(
 where
 	F: Fn(&str) -> Result<()>,
 {
-	let event_id = format!("{}", std::process::id());
-	let mut sequence_editor = TempEditor::new(event_id.as_str());
-	sequence_editor.create()?;
-
-	let mut cmd = Command::new("git");
-	cmd.current_dir(repo)
-		.arg("-c")
-		.arg(format!(
-			"sequence.editor='{}'",
-			sequence_editor.to_str().unwrap(),
-		))
-		.arg("-c")
-		.arg("rebase.instructionFormat=\"%H\"")
-		.arg("rebase")
-		.arg("-i")
-		.arg(base)
-		.stdout(Stdio::null()) //muting output. TODO: redirect?
-		.stderr(Stdio::null());
-
-	let terminal_state = TerminalState::capture();
-	let events = IPCEvents::main(&event_id)?;
-	let mut child = cmd.spawn()?;
-	events.wait_connected_ready()?;
-	let todo_file = events.get_str();
-	f(&todo_file)?;
-	events.signal_connected_shutdown()?;
-	child.wait()?;
-	drop(terminal_state);
-	Ok(())
+	let session = RebaseTodoSession::start(repo, base, None)?;
+	f(session.todo_file())?;
+	session.finish()
 }
 
 ///
@@ -416,7 +489,389 @@ pub fn rebase_fixup_commits(
 	Ok(())
 }
 
+/// commit summary prefixes `git rebase --autosquash` recognizes
+const AUTOSQUASH_PREFIXES: [(&str, InteractiveOperation); 2] = [
+	("fixup! ", InteractiveOperation::Fixup),
+	("squash! ", InteractiveOperation::Squash),
+];
+
+/// how far back to look for the target of a `fixup!`/`squash!` commit
+const AUTOSQUASH_SEARCH_DEPTH: usize = 1000;
+
+/// operation and target subject of a `fixup!`/`squash!` summary,
+/// stacked prefixes like `fixup! fixup! subject` are skipped
+fn autosquash_target(
+	summary: &str,
+) -> Option<(InteractiveOperation, &str)> {
+	let op = AUTOSQUASH_PREFIXES
+		.iter()
+		.find(|(prefix, _)| summary.starts_with(prefix))
+		.map(|(_, op)| *op)?;
+
+	let mut target = summary;
+	while let Some(rest) = AUTOSQUASH_PREFIXES
+		.iter()
+		.find_map(|(prefix, _)| target.strip_prefix(prefix))
+	{
+		target = rest;
+	}
+
+	Some((op, target))
+}
+
+/// same order of preference as git: exact summary, hash prefix,
+/// then summary prefix
+fn autosquash_matches<'a, I>(
+	candidates: &I,
+	target: &str,
+) -> Option<usize>
+where
+	I: Iterator<Item = (&'a str, &'a str)> + Clone,
+{
+	let is_hash = target.len() >= 4
+		&& target.chars().all(|c| c.is_ascii_hexdigit());
+
+	candidates
+		.clone()
+		.position(|(_, summary)| summary == target)
+		.or_else(|| {
+			is_hash
+				.then(|| {
+					candidates.clone().position(|(hash, _)| {
+						hash.starts_with(target)
+					})
+				})
+				.flatten()
+		})
+		.or_else(|| {
+			candidates
+				.clone()
+				.position(|(_, summary)| summary.starts_with(target))
+		})
+}
+
+/// moves every `fixup!`/`squash!` commit of `autosquash` right behind
+/// its target and changes its operation accordingly,
+/// `summary` looks up the summary of a full hash
+pub fn autosquash_todo<F, S>(
+	commits: Vec<RebaseCommit>,
+	autosquash: &HashSet<String, S>,
+	summary: F,
+) -> Vec<RebaseCommit>
+where
+	F: Fn(&str) -> Option<String>,
+	S: BuildHasher,
+{
+	// every group is a picked commit followed by the ones squashed into it
+	let mut groups: Vec<(String, Vec<RebaseCommit>)> = Vec::new();
+
+	for commit in commits {
+		let commit_summary =
+			summary(&commit.full_hash).unwrap_or_default();
+
+		let target = if autosquash.contains(&commit.full_hash) {
+			autosquash_target(&commit_summary).and_then(
+				|(op, target)| {
+					autosquash_matches(
+						&groups.iter().map(|(summary, group)| {
+							(
+								group[0].full_hash.as_str(),
+								summary.as_str(),
+							)
+						}),
+						target,
+					)
+					.map(|idx| (idx, op))
+				},
+			)
+		} else {
+			None
+		};
+
+		if let Some((idx, op)) = target {
+			groups[idx].1.push(commit.change_op(op));
+		} else {
+			groups.push((commit_summary, vec![commit]));
+		}
+	}
+
+	groups.into_iter().flat_map(|(_, group)| group).collect()
+}
+
+/// looks up the summary of `hash`
+fn commit_summary(
+	repo: &git2::Repository,
+	hash: &str,
+) -> Option<String> {
+	let oid = git2::Oid::from_str(hash).ok()?;
+	let commit = repo.find_commit(oid).ok()?;
+	commit.summary().map(String::from)
+}
+
+/// oldest commit any of the `fixup!`/`squash!` commits in `commits`
+/// will be squashed into, searched along first parents
+fn autosquash_oldest_target(
+	repo: &git2::Repository,
+	commits: &[&CommitId],
+) -> Result<Option<git2::Oid>> {
+	let mut oldest: Option<git2::Oid> = None;
+
+	for id in commits {
+		let commit = repo.find_commit((**id).into())?;
+		let summary =
+			commit.summary().unwrap_or_default().to_string();
+		let target = match autosquash_target(&summary) {
+			Some((_, target)) => target,
+			None => continue,
+		};
+
+		let mut ancestors = Vec::new();
+		let mut current = commit;
+		while ancestors.len() < AUTOSQUASH_SEARCH_DEPTH
+			&& current.parent_count() > 0
+		{
+			current = current.parent(0)?;
+			ancestors.push((
+				current.id().to_string(),
+				current.summary().unwrap_or_default().to_string(),
+			));
+		}
+
+		// the todo list searches oldest first
+		ancestors.reverse();
+		let found = autosquash_matches(
+			&ancestors.iter().map(|(hash, summary)| {
+				(hash.as_str(), summary.as_str())
+			}),
+			target,
+		)
+		.map(|idx| git2::Oid::from_str(&ancestors[idx].0))
+		.transpose()?;
+
+		if let Some(found) = found {
+			oldest = match oldest {
+				Some(oldest)
+					if !repo
+						.graph_descendant_of(oldest, found)? =>
+				{
+					Some(oldest)
+				}
+				_ => Some(found),
+			};
+		}
+	}
+
+	Ok(oldest)
+}
+
+/// moves the `fixup!`/`squash!` commits among `commits` behind their
+/// targets like `git rebase -i --autosquash` does,
+/// returns how many commits were squashed
+pub fn rebase_autosquash_commits(
+	repo: &str,
+	commits: &[&CommitId],
+) -> Result<usize> {
+	let git_repo = git2::Repository::open(repo)?;
+
+	let oldest_target =
+		autosquash_oldest_target(&git_repo, commits)?.ok_or_else(
+			|| anyhow!("no target found for the selected commits"),
+		)?;
+	let base = git_repo.find_commit(oldest_target)?;
+	if base.parent_count() == 0 {
+		return Err(anyhow!("cannot rebase past the initial commit"));
+	}
+	let base = base.parent_id(0)?;
+
+	let hashed_commits = commits
+		.iter()
+		.map(|i| i.to_string())
+		.collect::<HashSet<String>>();
+	let squashed = std::cell::Cell::new(0);
+	rebase_interactive(
+		repo,
+		base.to_string().as_str(),
+		|todo_file| {
+			let rebase_commits = autosquash_todo(
+				parse_rebase_todo(todo_file)?,
+				&hashed_commits,
+				|hash| commit_summary(&git_repo, hash),
+			);
+			squashed.set(
+				rebase_commits
+					.iter()
+					.filter(|i| {
+						matches!(
+							i.op,
+							InteractiveOperation::Fixup
+								| InteractiveOperation::Squash
+						)
+					})
+					.count(),
+			);
+			write_rebase_todo(todo_file, rebase_commits)?;
+			Ok(())
+		},
+	)?;
+	Ok(squashed.get())
+}
+
+/// what `rebase_transform_commits` does with a commit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RebaseTransform {
+	///
+	Drop,
+	/// squash into the commit before, keeping its message
+	Fixup,
+	/// replace the commit message
+	Reword(String),
+}
+
+/// applies `transforms` (keyed by full hash) to a todo list,
+/// rewording is done by stopping at the commit, so the messages
+/// are returned in the order the rebase will stop at them
+fn transform_todo(
+	commits: Vec<RebaseCommit>,
+	transforms: &HashMap<String, RebaseTransform>,
+) -> Result<(Vec<RebaseCommit>, Vec<String>)> {
+	let mut messages = Vec::new();
+	let commits: Vec<_> = commits
+		.into_iter()
+		.map(|i| match transforms.get(&i.full_hash) {
+			Some(RebaseTransform::Drop) => {
+				i.change_op(InteractiveOperation::Drop)
+			}
+			Some(RebaseTransform::Fixup) => {
+				i.change_op(InteractiveOperation::Fixup)
+			}
+			Some(RebaseTransform::Reword(msg)) => {
+				messages.push(msg.clone());
+				i.change_op(InteractiveOperation::Edit)
+			}
+			None => i,
+		})
+		.collect();
+
+	if let Some(first) = commits
+		.iter()
+		.find(|i| !matches!(i.op, InteractiveOperation::Drop))
+	{
+		if matches!(first.op, InteractiveOperation::Fixup) {
+			return Err(anyhow!(
+				"cannot fixup {}, there is no commit before it to squash into",
+				first.hash
+			));
+		}
+	}
+
+	Ok((commits, messages))
+}
+
+/// runs `git rebase -i <base>` without any user interaction
+///
+/// the commits (any revision git understands) in `transforms` get
+/// dropped, squashed or reworded, everything else is picked.
+/// fails if the rebase stops on its own, e.g. on a conflict
+pub fn rebase_transform_commits(
+	repo: &str,
+	base: &str,
+	transforms: &[(String, RebaseTransform)],
+) -> Result<()> {
+	let git_repo = git2::Repository::open(repo)?;
+	let base = git_repo.revparse_single(base)?.peel_to_commit()?.id();
+	let head = git_repo.head()?.peel_to_commit()?.id();
+
+	let mut by_hash = HashMap::new();
+	for (rev, transform) in transforms {
+		let id =
+			git_repo.revparse_single(rev)?.peel_to_commit()?.id();
+		if id == base
+			|| !git_repo.graph_descendant_of(id, base)?
+			|| !(id == head
+				|| git_repo.graph_descendant_of(head, id)?)
+		{
+			return Err(anyhow!(
+				"{rev} is not a commit between {base} and HEAD"
+			));
+		}
+		by_hash.insert(id.to_string(), transform.clone());
+	}
+
+	let messages = std::cell::RefCell::new(Vec::new());
+	rebase_interactive(
+		repo,
+		base.to_string().as_str(),
+		|todo_file| {
+			match transform_todo(
+				parse_rebase_todo(todo_file)?,
+				&by_hash,
+			) {
+				Ok((rebase_commits, reword_messages)) => {
+					messages.replace(reword_messages);
+					write_rebase_todo(todo_file, rebase_commits)
+				}
+				Err(e) => {
+					// an empty todo makes git abort the rebase
+					write_rebase_todo(todo_file, Vec::new())?;
+					Err(e)
+				}
+			}
+		},
+	)?;
+
+	// every reworded commit stops the rebase, in todo order
+	for msg in messages.into_inner() {
+		let git_repo = git2::Repository::open(repo)?;
+		if git_repo.state()
+			!= git2::RepositoryState::RebaseInteractive
+			|| git_repo.index()?.has_conflicts()
+		{
+			break;
+		}
+
+		git_repo.head()?.peel_to_commit()?.amend(
+			Some("HEAD"),
+			None,
+			None,
+			None,
+			Some(&msg),
+			None,
+		)?;
+		rebase_continue(repo)?;
+	}
+
+	if git2::Repository::open(repo)?.state()
+		!= git2::RepositoryState::Clean
+	{
+		return Err(anyhow!(
+			"rebase stopped, resolve and `git rebase --continue` or `git rebase --abort`"
+		));
+	}
+
+	Ok(())
+}
+
+fn rebase_continue(repo: &str) -> Result<()> {
+	let output = Command::new("git")
+		.current_dir(repo)
+		.arg("rebase")
+		.arg("--continue")
+		.stdin(Stdio::null())
+		.output()?;
+
+	if !output.status.success() {
+		return Err(anyhow!(
+			"`git rebase --continue` failed ({}): {}",
+			output.status,
+			String::from_utf8_lossy(&output.stderr).trim()
+		));
+	}
+
+	Ok(())
+}
+
 ///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InteractiveOperation {
 	///
 	Pick,
@@ -502,6 +957,23 @@ impl RebaseCommit {
 		}
 	}
 
+	///
+	pub fn set_op(&mut self, op: InteractiveOperation) {
+		self.op = op;
+	}
+
+	///
+	pub const fn op(&self) -> InteractiveOperation {
+		self.op
+	}
+
+	/// `None` if the todo line does not carry a valid hash
+	pub fn id(&self) -> Option<CommitId> {
+		git2::Oid::from_str(&self.full_hash)
+			.ok()
+			.map(CommitId::from)
+	}
+
 	///
 	pub fn try_parse(l: &str) -> Result<RebaseCommit> {
 		let mut i = l.split_ascii_whitespace();
@@ -563,3 +1035,143 @@ pub fn write_rebase_todo(
 	)?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn todo(lines: &[&str]) -> Vec<RebaseCommit> {
+		lines
+			.iter()
+			.map(|hash| {
+				RebaseCommit::try_parse(&format!(
+					"pick {hash} \"{hash}\""
+				))
+				.unwrap()
+			})
+			.collect()
+	}
+
+	fn summary(hash: &str) -> Option<String> {
+		match hash {
+			"aaaa1" => Some("add feature".into()),
+			"bbbb2" => Some("unrelated".into()),
+			"cccc3" => Some("fixup! add feature".into()),
+			"dddd4" => Some("squash! fixup! add feature".into()),
+			"eeee5" => Some("fixup! bbbb2".into()),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn test_autosquash_moves_behind_target() {
+		let marked = ["cccc3", "dddd4", "eeee5"]
+			.into_iter()
+			.map(String::from)
+			.collect::<HashSet<_>>();
+		let result = autosquash_todo(
+			todo(&["aaaa1", "bbbb2", "cccc3", "dddd4", "eeee5"]),
+			&marked,
+			summary,
+		);
+
+		assert_eq!(
+			result
+				.iter()
+				.map(RebaseCommit::to_string)
+				.collect::<Vec<_>>(),
+			vec![
+				"pick aaaa1 \"aaaa1\"",
+				"fixup cccc3 \"cccc3\"",
+				"squash dddd4 \"dddd4\"",
+				"pick bbbb2 \"bbbb2\"",
+				"fixup eeee5 \"eeee5\"",
+			]
+		);
+	}
+
+	#[test]
+	fn test_autosquash_only_marked() {
+		let result = autosquash_todo(
+			todo(&["aaaa1", "bbbb2", "cccc3"]),
+			&HashSet::new(),
+			summary,
+		);
+
+		assert_eq!(
+			result
+				.iter()
+				.map(RebaseCommit::to_string)
+				.collect::<Vec<_>>(),
+			vec![
+				"pick aaaa1 \"aaaa1\"",
+				"pick bbbb2 \"bbbb2\"",
+				"pick cccc3 \"cccc3\"",
+			]
+		);
+	}
+
+	#[test]
+	fn test_transform_todo() {
+		let transforms = HashMap::from([
+			("bbbb2".to_string(), RebaseTransform::Drop),
+			("cccc3".to_string(), RebaseTransform::Fixup),
+			(
+				"dddd4".to_string(),
+				RebaseTransform::Reword("new message".into()),
+			),
+		]);
+		let (result, messages) = transform_todo(
+			todo(&["aaaa1", "bbbb2", "cccc3", "dddd4"]),
+			&transforms,
+		)
+		.unwrap();
+
+		assert_eq!(
+			result
+				.iter()
+				.map(RebaseCommit::to_string)
+				.collect::<Vec<_>>(),
+			vec![
+				"pick aaaa1 \"aaaa1\"",
+				"drop bbbb2 \"bbbb2\"",
+				"fixup cccc3 \"cccc3\"",
+				"edit dddd4 \"dddd4\"",
+			]
+		);
+		assert_eq!(messages, vec!["new message".to_string()]);
+	}
+
+	#[test]
+	fn test_transform_todo_fixup_first() {
+		let transforms = HashMap::from([
+			("aaaa1".to_string(), RebaseTransform::Drop),
+			("bbbb2".to_string(), RebaseTransform::Fixup),
+		]);
+
+		assert!(transform_todo(
+			todo(&["aaaa1", "bbbb2", "cccc3"]),
+			&transforms,
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn test_rebase_commit_set_op() {
+		let hash = "0123456789abcdef0123456789abcdef01234567";
+		let mut commit = RebaseCommit::try_parse(&format!(
+			"pick 0123456 \"{hash}\""
+		))
+		.unwrap();
+		assert_eq!(commit.id().unwrap().to_string(), hash);
+
+		commit.set_op(InteractiveOperation::Squash);
+		assert_eq!(commit.op(), InteractiveOperation::Squash);
+		assert_eq!(
+			commit.to_string(),
+			format!("squash 0123456 \"{hash}\"")
+		);
+
+		assert!(todo(&["not-a-hash"])[0].id().is_none());
+	}
+}
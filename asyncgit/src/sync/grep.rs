@@ -0,0 +1,229 @@
+//! search the file contents of a commit or the worktree
+
+use super::{utils::work_dir, CommitId, RepoPath};
+use crate::{error::Result, sync::repository::repo};
+use git2::{ObjectType, Oid, TreeWalkMode, TreeWalkResult};
+use scopetime::scope_time;
+use std::path::PathBuf;
+
+/// `grep` stops looking for more once it found this many
+pub const GREP_MATCHES_MAX: usize = 10_000;
+
+/// bytes looked at to tell binary files apart, same as git
+const BINARY_CHECK_BYTES: usize = 8000;
+
+/// a line containing the searched text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+	/// path relative to the repo root
+	pub path: PathBuf,
+	/// 1-based number of the line
+	pub line: usize,
+	/// content of the line
+	pub content: String,
+}
+
+/// where a file's content comes from
+enum GrepSource {
+	Blob(Oid),
+	Worktree(PathBuf),
+}
+
+/// lines `is_match` accepts in the files of `commit`
+///
+/// searches the tracked files in the worktree if `commit` is `None`.
+/// binary files are skipped.
+/// `progress` gets `(files searched, files total)` before each file
+/// and stops the search early by returning `false`
+pub fn grep(
+	repo_path: &RepoPath,
+	commit: Option<CommitId>,
+	is_match: impl Fn(&str) -> bool,
+	mut progress: impl FnMut(usize, usize) -> bool,
+) -> Result<Vec<GrepMatch>> {
+	scope_time!("grep");
+
+	let repo = repo(repo_path)?;
+
+	let files = if let Some(commit) = commit {
+		let tree = repo.find_commit(commit.into())?.tree()?;
+
+		let mut files = Vec::new();
+		tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+			if entry.kind() == Some(ObjectType::Blob) {
+				let name =
+					String::from_utf8_lossy(entry.name_bytes());
+				files.push((
+					PathBuf::from(format!("{root}{name}")),
+					GrepSource::Blob(entry.id()),
+				));
+			}
+			TreeWalkResult::Ok
+		})?;
+		files
+	} else {
+		let work_dir = work_dir(&repo)?;
+
+		let mut files = repo
+			.index()?
+			.iter()
+			.map(|entry| {
+				let path = PathBuf::from(
+					String::from_utf8_lossy(&entry.path).to_string(),
+				);
+				let full_path = work_dir.join(&path);
+				(path, GrepSource::Worktree(full_path))
+			})
+			.collect::<Vec<_>>();
+		// a conflicted file has an entry per stage, sorted together
+		files.dedup_by(|a, b| a.0 == b.0);
+		files
+	};
+
+	let total = files.len();
+	let mut res = Vec::new();
+
+	for (idx, (path, source)) in files.into_iter().enumerate() {
+		if !progress(idx, total) {
+			break;
+		}
+
+		let content = match source {
+			GrepSource::Blob(id) => {
+				repo.find_blob(id)?.content().to_vec()
+			}
+			// deleted or unreadable files simply have no matches
+			GrepSource::Worktree(path) => {
+				if let Ok(content) = std::fs::read(path) {
+					content
+				} else {
+					continue;
+				}
+			}
+		};
+
+		if content[..content.len().min(BINARY_CHECK_BYTES)]
+			.contains(&0)
+		{
+			continue;
+		}
+
+		for (line_idx, line) in
+			String::from_utf8_lossy(&content).lines().enumerate()
+		{
+			if is_match(line) {
+				res.push(GrepMatch {
+					path: path.clone(),
+					line: line_idx + 1,
+					content: line.to_string(),
+				});
+
+				if res.len() >= GREP_MATCHES_MAX {
+					return Ok(res);
+				}
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+	use std::{fs::File, io::Write, path::Path};
+
+	#[test]
+	fn test_grep_commit_and_worktree() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "foo\nbar\nFoo bar", "c1");
+		std::fs::create_dir(root.join("dir"))?;
+		let c2 =
+			write_commit_file(&repo, "dir/b.txt", "no\nfoo", "c2");
+
+		File::create(root.join("a.txt"))?
+			.write_all(b"changed foo")?;
+		File::create(root.join("bin"))?.write_all(b"foo\0")?;
+		stage_add_file(repo_path, Path::new("bin"))?;
+
+		let hits = |commit, pattern| -> Vec<(String, usize)> {
+			grep(
+				repo_path,
+				commit,
+				|line: &str| line.contains(pattern),
+				|_, _| true,
+			)
+			.unwrap()
+			.into_iter()
+			.map(|m| (m.path.to_string_lossy().to_string(), m.line))
+			.collect()
+		};
+
+		assert_eq!(
+			hits(Some(c2), "foo"),
+			vec![
+				(String::from("a.txt"), 1),
+				(String::from("dir/b.txt"), 2),
+			]
+		);
+		assert_eq!(
+			hits(Some(c2), "Foo"),
+			vec![(String::from("a.txt"), 3)]
+		);
+
+		// worktree content, binary files skipped
+		assert_eq!(
+			hits(None, "foo"),
+			vec![
+				(String::from("a.txt"), 1),
+				(String::from("dir/b.txt"), 2),
+			]
+		);
+
+		assert!(grep(repo_path, Some(c2), |_| true, |_, _| false)?
+			.is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_grep_conflicted_file_once() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "foo", "c1");
+
+		let mut index = repo.index()?;
+		index.read(true)?;
+		let mut entry =
+			index.get_path(Path::new("a.txt"), 0).unwrap();
+		index.remove_path(Path::new("a.txt"))?;
+		for stage in 1..=3 {
+			entry.flags = stage << 12;
+			index.add(&entry)?;
+		}
+		index.write()?;
+		assert_eq!(repo.index()?.len(), 3);
+
+		let hits = grep(
+			repo_path,
+			None,
+			|line: &str| line.contains("foo"),
+			|_, _| true,
+		)?;
+		assert_eq!(hits.len(), 1);
+
+		Ok(())
+	}
+}
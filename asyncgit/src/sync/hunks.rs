@@ -150,7 +150,10 @@ mod tests {
 	use super::*;
 	use crate::{
 		error::Result,
-		sync::{diff::get_diff, tests::repo_init_empty},
+		sync::{
+			diff::get_diff,
+			tests::{repo_init, repo_init_empty, write_commit_file},
+		},
 	};
 	use std::{
 		fs::{self, File},
@@ -187,4 +190,46 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn stage_and_unstage_all_hunks_last_first() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let lines: Vec<String> =
+			(0..20).map(|i| format!("line {i}\n")).collect();
+		write_commit_file(&repo, "foo.txt", &lines.concat(), "c1");
+
+		// the first hunk adds a line, shifting the ones after it
+		let mut changed = lines;
+		changed.insert(1, String::from("added\n"));
+		changed[16] = String::from("changed\n");
+		fs::write(root.join("foo.txt"), changed.concat())?;
+
+		let diff = get_diff(repo_path, "foo.txt", false, None)?;
+		assert_eq!(diff.hunks.len(), 2);
+		for hunk in diff.hunks.iter().rev() {
+			stage_hunk(repo_path, "foo.txt", hunk.header_hash)?;
+		}
+		assert!(get_diff(repo_path, "foo.txt", false, None)?
+			.hunks
+			.is_empty());
+
+		let diff = get_diff(repo_path, "foo.txt", true, None)?;
+		assert_eq!(diff.hunks.len(), 2);
+		for hunk in diff.hunks.iter().rev() {
+			assert!(unstage_hunk(
+				repo_path,
+				"foo.txt",
+				hunk.header_hash
+			)?);
+		}
+		assert!(get_diff(repo_path, "foo.txt", true, None)?
+			.hunks
+			.is_empty());
+
+		Ok(())
+	}
 }
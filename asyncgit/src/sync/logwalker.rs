@@ -124,6 +124,7 @@ pub struct LogWalker<'a> {
 	filter: Option<LogWalkerFilter>,
 	stopper: Option<LogWalkerFilter>,
 	filtered_limit: usize,
+	hidden: HashSet<Oid>,
 }
 
 impl<'a> LogWalker<'a> {
@@ -151,7 +152,8 @@ impl<'a> LogWalker<'a> {
 			repo: &r,
 			filter: None,
 			stopper: None,
-			filtered_limit: 0
+			filtered_limit: 0,
+			hidden: HashSet::new(),
 		};
 		walker = config(walker);
 		let mut res = Vec::new();
@@ -179,7 +181,8 @@ impl<'a> LogWalker<'a> {
 			repo,
 			filter: None,
 			stopper: None,
-			filtered_limit: 0
+			filtered_limit: 0,
+			hidden: HashSet::new(),
 		})
 	}
 
@@ -206,6 +209,16 @@ impl<'a> LogWalker<'a> {
 		Self { stopper, ..self }
 	}
 
+	/// hides `commit` and its ancestry, like `git log ^commit`
+	pub fn hide(mut self, commit: Option<&CommitId>) -> Result<Self> {
+		if let Some(commit) = commit {
+			let c = self.repo.find_commit(commit.get_oid())?;
+			self.hidden.insert(c.id());
+			self.visit(c);
+		}
+		Ok(self)
+	}
+
 	///
 	pub fn read_eof(
 		&mut self,
@@ -226,10 +239,27 @@ impl<'a> LogWalker<'a> {
 		let mut filtered_count = 0_usize;
 
 		while let Some(c) = self.commits.pop() {
+			let is_hidden = self.hidden.contains(&c.0.id())
+				|| self.reachable_from_hidden(&c.0)?;
 			for p in c.0.parents() {
+				if is_hidden {
+					self.hidden.insert(p.id());
+				}
 				self.visit(p);
 			}
 
+			if is_hidden {
+				// only hidden commits left, nothing else to list
+				if self
+					.commits
+					.iter()
+					.all(|c| self.hidden.contains(&c.0.id()))
+				{
+					self.commits.clear();
+				}
+				continue;
+			}
+
 			let id: CommitId = c.0.id().into();
 			let commit_should_be_included =
 				if let Some(ref filter) = self.filter {
@@ -261,6 +291,23 @@ impl<'a> LogWalker<'a> {
 		Ok(count)
 	}
 
+	// with skewed commit dates a hidden commit may still be queued
+	// behind one of its own ancestors, so the time order alone does
+	// not tell whether `c` is part of the hidden history
+	fn reachable_from_hidden(&mut self, c: &Commit) -> Result<bool> {
+		for pending in &self.commits {
+			let pending = pending.0.id();
+			if self.hidden.contains(&pending)
+				&& self.repo.graph_descendant_of(pending, c.id())?
+			{
+				self.hidden.insert(c.id());
+				return Ok(true);
+			}
+		}
+
+		Ok(false)
+	}
+
 	//
 	fn visit(&mut self, c: Commit<'a>) {
 		if !self.visited.contains(&c.id()) {
@@ -393,4 +440,81 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_logwalker_hide() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let tree_id = repo.treebuilder(None)?.write()?;
+		let tree = repo.find_tree(tree_id)?;
+		let commit_at = |time: i64, parents: &[&Commit]| {
+			let sig = git2::Signature::new(
+				"name",
+				"email",
+				&git2::Time::new(time, 0),
+			)?;
+			let id =
+				repo.commit(None, &sig, &sig, "msg", &tree, parents)?;
+			repo.find_commit(id)
+		};
+
+		let root = commit_at(10, &[])?;
+		let base = commit_at(20, &[&root])?;
+		let side = commit_at(30, &[&root])?;
+		// unrelated history older than the common ancestor
+		let old = commit_at(1, &[])?;
+		let tip = commit_at(40, &[&side, &old])?;
+
+		let mut items = Vec::new();
+		let mut walker = LogWalker::new_with_start(
+			&repo,
+			Some(&tip.id().into()),
+			100,
+		)?
+		.hide(Some(&base.id().into()))?;
+		walker.read_eof(&mut items).unwrap();
+
+		assert_eq!(
+			items,
+			vec![tip.id().into(), side.id().into(), old.id().into()]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_logwalker_hide_skewed_dates() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let tree_id = repo.treebuilder(None)?.write()?;
+		let tree = repo.find_tree(tree_id)?;
+		let commit_at = |time: i64, parents: &[&Commit]| {
+			let sig = git2::Signature::new(
+				"name",
+				"email",
+				&git2::Time::new(time, 0),
+			)?;
+			let id =
+				repo.commit(None, &sig, &sig, "msg", &tree, parents)?;
+			repo.find_commit(id)
+		};
+
+		let root = commit_at(10, &[])?;
+		// dated ahead of everything built on top of it
+		let skewed = commit_at(50, &[&root])?;
+		let base = commit_at(20, &[&skewed])?;
+		let side = commit_at(30, &[&skewed])?;
+		let tip = commit_at(40, &[&side])?;
+
+		let mut items = Vec::new();
+		let mut walker = LogWalker::new_with_start(
+			&repo,
+			Some(&tip.id().into()),
+			100,
+		)?
+		.hide(Some(&base.id().into()))?;
+		walker.read_eof(&mut items).unwrap();
+
+		assert_eq!(items, vec![tip.id().into(), side.id().into()]);
+
+		Ok(())
+	}
 }
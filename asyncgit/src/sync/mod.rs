@@ -14,13 +14,16 @@ mod config;
 pub mod cred;
 pub mod diff;
 pub mod extern_git;
+mod grep;
 mod hooks;
 mod hunks;
 mod ignore;
 mod logwalker;
 mod merge;
+mod notes;
 mod patches;
 mod rebase;
+mod reflog;
 pub mod remotes;
 mod repository;
 mod reset;
@@ -33,34 +36,45 @@ mod submodules;
 mod tags;
 mod tree;
 pub mod utils;
+mod verify;
+mod worktrees;
 
 pub use blame::{blame_file, BlameHunk, FileBlame};
 pub use branch::{
 	branch_compare_upstream, checkout_branch, checkout_commit,
-	config_is_pull_rebase, create_branch, delete_branch,
-	get_branch_remote, get_branches_info,
+	config_is_pull_rebase, create_branch, create_branch_at_commit,
+	delete_branch,
+	get_branch_remote, get_branch_upstream, get_branches_info,
 	merge_commit::merge_upstream_commit,
 	merge_ff::branch_merge_upstream_fastforward,
 	merge_rebase::merge_upstream_rebase, rename::rename_branch,
 	validate_branch_name, BranchCompare, BranchDetails, BranchInfo,
 };
-pub use commit::{amend, cherrypick, commit, parent_ids, tag_commit};
+pub use commit::{
+	amend, cherrypick, child_in_head_history, commit, commit_paths,
+	first_parent_ancestor, fixup_message, is_ancestor_of_head,
+	parent_ids, tag_commit,
+};
 pub use commit_details::{
 	get_commit_details, CommitDetails, CommitMessage, CommitSignature,
 };
-pub use commit_files::get_commit_files;
+pub use commit_files::{
+	commit_renamed_from, commit_touches_path, commits_files_count,
+	compare_commits_sides, get_commit_files, get_commit_parent_files,
+};
 pub use commit_revert::{commit_revert, revert_commit, revert_head};
 pub use commits_info::{
 	get_commit_info, get_commits_info, CommitId, CommitInfo,
 };
 pub use config::{
-	get_config_string, untracked_files_config, GitExternCommands,
-	ShowUntrackedFilesConfig,
+	commit_template, get_config_string, untracked_files_config,
+	GitExternCommands, ShowUntrackedFilesConfig,
 };
 pub use diff::get_diff_commit;
 pub use git2::BranchType;
 pub use git2::Commit;
 pub use git2::Repository;
+pub use grep::{grep, GrepMatch, GREP_MATCHES_MAX};
 pub use hooks::{
 	hooks_commit_msg, hooks_post_commit, hooks_pre_commit, HookResult,
 };
@@ -74,39 +88,55 @@ pub use merge::{
 	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
 	mergehead_ids, rebase_progress,
 };
+pub use notes::{get_commits_with_notes, get_note, set_note};
 pub use rebase::rebase_branch;
+pub use reflog::{get_reflog, ReflogEntry};
 pub use remotes::{
-	get_default_remote, get_remotes, push::AsyncProgress,
-	tags::PushTagsProgress,
+	add_remote, delete_remote, get_default_remote,
+	get_default_remote_web_url, get_remotes, get_remotes_info,
+	push::AsyncProgress, remote_web_url, rename_remote,
+	set_remote_url, tags::PushTagsProgress, validate_remote_name,
+	validate_remote_url, RemoteInfo,
 };
 pub(crate) use repository::repo;
 pub use repository::{RepoPath, RepoPathRef};
-pub use reset::{reset_repo, reset_stage, reset_workdir};
+pub use reset::{
+	delete_untracked, reset_repo, reset_stage, reset_workdir,
+	restore_to_head,
+};
 pub use reword::reword;
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
 	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	stash_save_auto,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
 pub use submodules::{
-	get_submodules, submodule_parent_info, update_submodule,
-	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
+	get_submodules, init_submodule_cmd, submodule_parent_info,
+	update_submodule, SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
 };
 pub use tags::{
 	delete_tag, get_tags, get_tags_with_metadata, CommitTags, Tag,
 	TagWithMetadata, Tags,
 };
-pub use tree::{repo_files, tree_file_content, tree_files, TreeFile};
+pub use tree::{
+	repo_files, tree_file_content, tree_file_head, tree_files,
+	TreeFile,
+};
 pub use utils::{
-	get_head, get_head_tuple, get_head_tuple_branch, is_repo,
-	repo_dir, stage_add_all, stage_add_file, stage_addremoved, Head,
+	get_head, get_head_tuple, get_head_tuple_branch, is_head_detached,
+	is_repo, repo_dir, stage_add_all, stage_add_file,
+	stage_addremoved, Head,
 };
 
+pub use verify::{verify_commits, SignatureStatus};
+pub use worktrees::{get_worktrees, is_linked_worktree, WorktreeInfo};
+
 pub use git2::ResetType;
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use super::{
 		commit,
 		repository::repo,
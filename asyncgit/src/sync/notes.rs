@@ -0,0 +1,131 @@
+//! git notes attached to commits (in `refs/notes/commits`)
+
+use super::{
+	commit::signature_allow_undefined_name, CommitId, RepoPath,
+};
+use crate::{error::Result, sync::repository::repo};
+use git2::ErrorCode;
+use scopetime::scope_time;
+use std::collections::HashSet;
+
+/// the note attached to `commit`, `None` if there is none
+pub fn get_note(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<Option<String>> {
+	scope_time!("get_note");
+
+	let repo = repo(repo_path)?;
+
+	let note = match repo.find_note(None, commit.into()) {
+		Ok(note) => note,
+		Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(String::from_utf8_lossy(note.message_bytes()).into()))
+}
+
+/// attaches `note` to `commit` replacing any existing one, an empty
+/// `note` removes it
+pub fn set_note(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	note: &str,
+) -> Result<()> {
+	scope_time!("set_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	if note.is_empty() {
+		match repo.note_delete(
+			commit.into(),
+			None,
+			&signature,
+			&signature,
+		) {
+			Err(e) if e.code() != ErrorCode::NotFound => {
+				return Err(e.into())
+			}
+			_ => (),
+		}
+	} else {
+		repo.note(
+			&signature,
+			&signature,
+			None,
+			commit.into(),
+			note,
+			true,
+		)?;
+	}
+
+	Ok(())
+}
+
+/// all commits that have a note attached
+pub fn get_commits_with_notes(
+	repo_path: &RepoPath,
+) -> Result<HashSet<CommitId>> {
+	scope_time!("get_commits_with_notes");
+
+	let repo = repo(repo_path)?;
+
+	let notes = match repo.notes(None) {
+		Ok(notes) => notes,
+		Err(e) if e.code() == ErrorCode::NotFound => {
+			return Ok(HashSet::new())
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	let mut res = HashSet::new();
+	for note in notes {
+		let (_, annotated) = note?;
+		res.insert(CommitId::new(annotated));
+	}
+
+	Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_notes() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		assert_eq!(get_note(repo_path, c1)?, None);
+		assert!(get_commits_with_notes(repo_path)?.is_empty());
+
+		set_note(repo_path, c1, "reviewed")?;
+		set_note(repo_path, c1, "reviewed twice")?;
+		set_note(repo_path, c2, "")?;
+
+		assert_eq!(
+			get_note(repo_path, c1)?.as_deref(),
+			Some("reviewed twice")
+		);
+		assert_eq!(get_note(repo_path, c2)?, None);
+		assert_eq!(
+			get_commits_with_notes(repo_path)?,
+			HashSet::from([c1])
+		);
+
+		set_note(repo_path, c1, "")?;
+		assert_eq!(get_note(repo_path, c1)?, None);
+		assert!(get_commits_with_notes(repo_path)?.is_empty());
+
+		Ok(())
+	}
+}
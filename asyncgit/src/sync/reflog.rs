@@ -0,0 +1,103 @@
+//! reflog of `HEAD` and branches
+
+use super::{CommitId, RepoPath};
+use crate::{error::Result, sync::repository::repo};
+use scopetime::scope_time;
+
+/// one change of a reference, as recorded in its reflog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+	/// `HEAD@{n}` style name of the entry
+	pub selector: String,
+	/// commit the reference pointed to after the change
+	pub id: CommitId,
+	/// commit the reference pointed to before the change
+	pub old_id: CommitId,
+	/// kind of change, e.g. `commit` or `rebase (finish)`
+	pub action: String,
+	/// rest of the reflog message
+	pub message: String,
+	/// unix time of the change
+	pub time: i64,
+}
+
+/// reflog of `reference` (`HEAD` or a full ref like
+/// `refs/heads/main`), newest entry first
+pub fn get_reflog(
+	repo_path: &RepoPath,
+	reference: &str,
+) -> Result<Vec<ReflogEntry>> {
+	scope_time!("get_reflog");
+
+	let repo = repo(repo_path)?;
+	let reflog = repo.reflog(reference)?;
+	let name =
+		reference.strip_prefix("refs/heads/").unwrap_or(reference);
+
+	let entries = reflog
+		.iter()
+		.enumerate()
+		.map(|(idx, entry)| {
+			let (action, message) =
+				split_message(entry.message().unwrap_or_default());
+
+			ReflogEntry {
+				selector: format!("{name}@{{{idx}}}"),
+				id: entry.id_new().into(),
+				old_id: entry.id_old().into(),
+				action,
+				message,
+				time: entry.committer().when().seconds(),
+			}
+		})
+		.collect();
+
+	Ok(entries)
+}
+
+/// reflog messages look like `checkout: moving from a to b`
+fn split_message(msg: &str) -> (String, String) {
+	msg.split_once(": ").map_or_else(
+		|| (String::new(), msg.to_string()),
+		|(action, message)| (action.to_string(), message.to_string()),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_reflog() {
+		let (td, repo) = repo_init().unwrap();
+		let root = td.path();
+		let repo_path: &RepoPath = &root.to_str().unwrap().into();
+
+		let first = write_commit_file(&repo, "a.txt", "a", "first");
+		create_branch(repo_path, "other").unwrap();
+		checkout_branch(repo_path, "refs/heads/other").unwrap();
+		let second = write_commit_file(&repo, "a.txt", "b", "second");
+
+		let reflog = get_reflog(repo_path, "HEAD").unwrap();
+
+		assert_eq!(reflog[0].selector, "HEAD@{0}");
+		assert_eq!(reflog[0].id, second);
+		assert_eq!(reflog[0].old_id, first);
+		assert_eq!(reflog[0].action, "commit");
+		assert_eq!(reflog[0].message, "second");
+		assert_eq!(reflog[1].action, "checkout");
+		assert_eq!(reflog[1].message, "moving from master to other");
+		assert_eq!(reflog[2].id, first);
+
+		let reflog =
+			get_reflog(repo_path, "refs/heads/other").unwrap();
+
+		assert_eq!(reflog.len(), 2);
+		assert_eq!(reflog[0].selector, "other@{0}");
+		assert_eq!(reflog[1].id, first);
+	}
+}
@@ -1,5 +1,10 @@
 use super::push::ProgressNotification;
-use crate::{error::Result, sync::cred::BasicAuthCredential};
+use crate::{
+	error::Result,
+	sync::cred::{
+		BasicAuthCredential, MSG_BAD_CREDENTIALS, MSG_NO_CREDENTIALS,
+	},
+};
 use crossbeam_channel::Sender;
 use git2::{Cred, Error as GitError, RemoteCallbacks};
 use std::sync::{
@@ -257,7 +262,7 @@ impl Callbacks {
 			.count_calls_to_credentials
 			.fetch_add(1, Ordering::Relaxed);
 		if prev_call_count >= 2 {
-			return Err(GitError::from_str("Bad credentials."));
+			return Err(GitError::from_str(MSG_BAD_CREDENTIALS));
 		}
 
 		match &self.basic_credential {
@@ -291,7 +296,7 @@ impl Callbacks {
 				password: _,
 			}) if allowed_types.is_username() => Cred::username(user),
 			_ if allowed_types.is_default() => Cred::default(),
-			_ => Err(GitError::from_str("Couldn't find credentials")),
+			_ => Err(GitError::from_str(MSG_NO_CREDENTIALS)),
 		}
 	}
 }
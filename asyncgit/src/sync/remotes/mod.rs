@@ -44,6 +44,140 @@ pub fn get_remotes(repo_path: &RepoPath) -> Result<Vec<String>> {
 	Ok(remotes)
 }
 
+/// name and (fetch) url of a remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteInfo {
+	///
+	pub name: String,
+	/// `None` if the url is not valid utf8
+	pub url: Option<String>,
+}
+
+///
+pub fn get_remotes_info(
+	repo_path: &RepoPath,
+) -> Result<Vec<RemoteInfo>> {
+	scope_time!("get_remotes_info");
+
+	let repo = repo(repo_path)?;
+	let remotes = repo.remotes()?;
+
+	remotes
+		.iter()
+		.flatten()
+		.map(|name| {
+			let remote = repo.find_remote(name)?;
+			Ok(RemoteInfo {
+				name: name.to_string(),
+				url: remote.url().map(String::from),
+			})
+		})
+		.collect()
+}
+
+/// fails unless `name` can be used as a remote name
+pub fn validate_remote_name(name: &str) -> Result<()> {
+	if git2::Remote::is_valid_name(name) {
+		Ok(())
+	} else {
+		Err(Error::Generic(format!("invalid remote name: '{name}'")))
+	}
+}
+
+/// catches obvious typos: urls need to be non empty without
+/// surrounding whitespace or control characters and either
+/// `scheme://something`, scp like `[user@]host:path` or a local
+/// path (which may contain spaces)
+pub fn validate_remote_url(url: &str) -> Result<()> {
+	let valid = !url.is_empty()
+		&& url.trim() == url
+		&& !url.chars().any(char::is_control)
+		&& url.split_once("://").map_or_else(
+			|| {
+				url.split_once(':').map_or(true, |(host, path)| {
+					!host.is_empty() && !path.is_empty()
+				})
+			},
+			|(scheme, rest)| {
+				!scheme.is_empty()
+					&& scheme.chars().all(|c| {
+						c.is_ascii_alphanumeric() || "+-.".contains(c)
+					}) && !rest.is_empty()
+			},
+		);
+
+	if valid {
+		Ok(())
+	} else {
+		Err(Error::Generic(format!("invalid remote url: '{url}'")))
+	}
+}
+
+///
+pub fn add_remote(
+	repo_path: &RepoPath,
+	name: &str,
+	url: &str,
+) -> Result<()> {
+	scope_time!("add_remote");
+
+	validate_remote_name(name)?;
+	validate_remote_url(url)?;
+
+	let repo = repo(repo_path)?;
+	repo.remote(name, url)?;
+
+	Ok(())
+}
+
+/// renames the remote including its remote tracking branches
+/// and the upstream config of local branches
+pub fn rename_remote(
+	repo_path: &RepoPath,
+	name: &str,
+	new_name: &str,
+) -> Result<()> {
+	scope_time!("rename_remote");
+
+	validate_remote_name(new_name)?;
+
+	let repo = repo(repo_path)?;
+	let problems = repo.remote_rename(name, new_name)?;
+
+	for refspec in problems.iter().flatten() {
+		log::warn!("rename remote: refspec not renamed: {refspec}");
+	}
+
+	Ok(())
+}
+
+///
+pub fn set_remote_url(
+	repo_path: &RepoPath,
+	name: &str,
+	url: &str,
+) -> Result<()> {
+	scope_time!("set_remote_url");
+
+	validate_remote_url(url)?;
+
+	let repo = repo(repo_path)?;
+	repo.find_remote(name)?;
+	repo.remote_set_url(name, url)?;
+
+	Ok(())
+}
+
+/// removes the remote and its remote tracking branches
+pub fn delete_remote(repo_path: &RepoPath, name: &str) -> Result<()> {
+	scope_time!("delete_remote");
+
+	let repo = repo(repo_path)?;
+	repo.remote_delete(name)?;
+
+	Ok(())
+}
+
 /// tries to find origin or the only remote that is defined if any
 /// in case of multiple remotes and none named *origin* we fail
 pub fn get_default_remote(repo_path: &RepoPath) -> Result<String> {
@@ -85,6 +219,67 @@ pub(crate) fn get_default_remote_in_repo(
 	Err(Error::NoDefaultRemoteFound)
 }
 
+/// web url of the default remote, `None` if there is none or its
+/// url cannot be turned into one (see `remote_web_url`)
+pub fn get_default_remote_web_url(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	scope_time!("get_default_remote_web_url");
+
+	let repo = repo(repo_path)?;
+
+	let remote = match get_default_remote_in_repo(&repo) {
+		Ok(remote) => remote,
+		Err(Error::NoDefaultRemoteFound) => return Ok(None),
+		Err(e) => return Err(e),
+	};
+
+	let remote = repo.find_remote(&remote)?;
+
+	Ok(remote.url().and_then(remote_web_url))
+}
+
+/// https address of the web page of a repository hosted on a forge
+/// (`scheme://[user@]host[:port]/path` or `[user@]host:path`),
+/// `None` for local paths
+pub fn remote_web_url(url: &str) -> Option<String> {
+	let url = url.trim().trim_end_matches('/');
+	let url = url.strip_suffix(".git").unwrap_or(url);
+
+	let (scheme, host, path) =
+		if let Some((scheme, rest)) = url.split_once("://") {
+			let scheme = match scheme {
+				"http" => "http",
+				"https" | "ssh" | "git" | "git+ssh" | "ssh+git" => {
+					"https"
+				}
+				_ => return None,
+			};
+			let (authority, path) = rest.split_once('/')?;
+			let host = authority.rsplit('@').next()?;
+			// the web page does not live on the ssh port
+			let host = host.split(':').next()?;
+			(scheme, host, path)
+		} else {
+			let (authority, path) = url.split_once(':')?;
+			// a drive letter or a relative path with a colon
+			if authority.len() < 2
+				|| authority.contains('/')
+				|| path.contains('\\')
+			{
+				return None;
+			}
+			let host = authority.rsplit('@').next()?;
+			("https", host, path.trim_start_matches('/'))
+		};
+
+	if host.is_empty() || path.is_empty() {
+		return None;
+	}
+
+	Some(format!("{scheme}://{host}/{path}"))
+}
+
 ///
 fn fetch_from_remote(
 	repo_path: &RepoPath,
@@ -176,6 +371,45 @@ pub(crate) fn fetch(
 	Ok(remote.stats().received_bytes())
 }
 
+/// fetches `src_ref` (e.g. `refs/pull/42/head`) from `remote` into
+/// the local `branch`, which must not be the checked out one
+pub(crate) fn fetch_pull_request(
+	repo_path: &RepoPath,
+	remote: &str,
+	src_ref: &str,
+	branch: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<usize> {
+	scope_time!("fetch_pull_request");
+
+	let repo = repo(repo_path)?;
+	let dst_ref = format!("refs/heads/{branch}");
+
+	if repo.head().ok().and_then(|head| head.name().map(String::from))
+		== Some(dst_ref.clone())
+	{
+		return Err(Error::Generic(format!(
+			"cannot fetch into the checked out branch '{branch}'"
+		)));
+	}
+
+	let mut remote = repo.find_remote(remote)?;
+
+	let mut options = FetchOptions::new();
+	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(proxy_auto());
+
+	remote.fetch(
+		&[format!("+{src_ref}:{dst_ref}")],
+		Some(&mut options),
+		None,
+	)?;
+
+	Ok(remote.stats().received_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -202,6 +436,129 @@ mod tests {
 		fetch(repo_path, "master", None, None).unwrap();
 	}
 
+	#[test]
+	fn test_manage_remotes() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, _repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().as_os_str().to_str().unwrap().into();
+
+		add_remote(repo_path, "second", remote_path).unwrap();
+		assert!(add_remote(repo_path, "second", remote_path).is_err());
+		assert!(
+			add_remote(repo_path, "in valid", remote_path).is_err()
+		);
+		assert!(add_remote(repo_path, "third", "").is_err());
+
+		rename_remote(repo_path, "second", "upstream").unwrap();
+		set_remote_url(
+			repo_path,
+			"upstream",
+			"https://host/repo.git",
+		)
+		.unwrap();
+		assert!(set_remote_url(repo_path, "missing", remote_path)
+			.is_err());
+
+		assert_eq!(
+			get_remotes_info(repo_path).unwrap(),
+			vec![
+				RemoteInfo {
+					name: String::from("origin"),
+					url: Some(remote_path.to_string()),
+				},
+				RemoteInfo {
+					name: String::from("upstream"),
+					url: Some(String::from("https://host/repo.git")),
+				},
+			]
+		);
+
+		delete_remote(repo_path, "upstream").unwrap();
+		assert_eq!(get_remotes(repo_path).unwrap(), vec!["origin"]);
+	}
+
+	#[test]
+	fn test_remote_web_url() {
+		let web = |url| remote_web_url(url);
+
+		assert_eq!(
+			web("git@github.com:owner/repo.git").as_deref(),
+			Some("https://github.com/owner/repo")
+		);
+		assert_eq!(
+			web("https://user@gitlab.com/group/sub/repo.git/")
+				.as_deref(),
+			Some("https://gitlab.com/group/sub/repo")
+		);
+		assert_eq!(
+			web("ssh://git@git.example.com:2222/owner/repo")
+				.as_deref(),
+			Some("https://git.example.com/owner/repo")
+		);
+		assert_eq!(
+			web("http://host/repo").as_deref(),
+			Some("http://host/repo")
+		);
+
+		assert_eq!(web("/srv/git/repo.git"), None);
+		assert_eq!(web("file:///srv/git/repo.git"), None);
+		assert_eq!(web("C:\\git\\repo"), None);
+		assert_eq!(web("https://host"), None);
+	}
+
+	#[test]
+	fn test_get_default_remote_web_url() {
+		let (_td, repo) = repo_init().unwrap();
+		let repo_path: &RepoPath =
+			&repo.workdir().unwrap().to_str().unwrap().into();
+
+		assert_eq!(
+			get_default_remote_web_url(repo_path).unwrap(),
+			None
+		);
+
+		add_remote(
+			repo_path,
+			"origin",
+			"git@host.org:owner/repo.git",
+		)
+		.unwrap();
+		assert_eq!(
+			get_default_remote_web_url(repo_path).unwrap().as_deref(),
+			Some("https://host.org/owner/repo")
+		);
+	}
+
+	#[test]
+	fn test_validate_remote_url() {
+		for url in [
+			"https://github.com/gitui-org/gitui.git",
+			"ssh://git@host:22/repo",
+			"file:///srv/repo.git",
+			"git@github.com:gitui-org/gitui.git",
+			"/srv/repo.git",
+			"../repo",
+			"/home/me/my repo",
+		] {
+			assert!(validate_remote_url(url).is_ok(), "{url}");
+		}
+
+		for url in [
+			"",
+			"https://",
+			"://host/repo",
+			"host:",
+			" /srv/repo.git",
+			"/srv/repo.git\n",
+			"/srv/re\tpo.git",
+			":path",
+		] {
+			assert!(validate_remote_url(url).is_err(), "{url}");
+		}
+	}
+
 	#[test]
 	fn test_default_remote() {
 		let (remote_dir, _remote) = repo_init().unwrap();
@@ -304,4 +661,42 @@ mod tests {
 		assert_eq!(res.is_err(), true);
 		assert!(matches!(res, Err(Error::NoDefaultRemoteFound)));
 	}
+
+	#[test]
+	fn test_fetch_pull_request() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let head = remote.head().unwrap().target().unwrap();
+		remote
+			.reference("refs/pull/7/head", head, false, "")
+			.unwrap();
+
+		let (repo_dir, repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().as_os_str().to_str().unwrap().into();
+
+		fetch_pull_request(
+			repo_path,
+			"origin",
+			"refs/pull/7/head",
+			"pr/7",
+			None,
+			None,
+		)
+		.unwrap();
+
+		let branch =
+			repo.find_branch("pr/7", BranchType::Local).unwrap();
+		assert_eq!(branch.get().target(), Some(head));
+
+		assert!(fetch_pull_request(
+			repo_path,
+			"origin",
+			"refs/pull/7/head",
+			"master",
+			None,
+			None,
+		)
+		.is_err());
+	}
 }
@@ -1,14 +1,27 @@
-use super::{utils::get_head_repo, CommitId, RepoPath};
+use super::{
+	utils::{get_head_repo, repo_work_dir},
+	CommitId, RepoPath,
+};
 use crate::{error::Result, sync::repository::repo};
-use git2::{build::CheckoutBuilder, ObjectType, ResetType};
+use git2::{
+	build::CheckoutBuilder, DiffOptions, ObjectType, Repository,
+	ResetType,
+};
 use scopetime::scope_time;
+use std::{fs, path::Path};
 
-///
-pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
+/// unstages all files matching the `path` pathspec,
+/// returns the number of files unstaged
+pub fn reset_stage(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<usize> {
 	scope_time!("reset_stage");
 
 	let repo = repo(repo_path)?;
 
+	let staged = staged_count(&repo, path)?;
+
 	if let Ok(id) = get_head_repo(&repo) {
 		let obj =
 			repo.find_object(id.into(), Some(ObjectType::Commit))?;
@@ -18,7 +31,25 @@ pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
 		repo.reset_default(None, [path])?;
 	}
 
-	Ok(())
+	Ok(staged)
+}
+
+fn staged_count(repo: &Repository, pathspec: &str) -> Result<usize> {
+	let head_tree = match get_head_repo(repo) {
+		Ok(id) => Some(repo.find_commit(id.into())?.tree()?),
+		Err(_) => None,
+	};
+
+	let mut opts = DiffOptions::new();
+	opts.pathspec(pathspec);
+
+	let diff = repo.diff_tree_to_index(
+		head_tree.as_ref(),
+		None,
+		Some(&mut opts),
+	)?;
+
+	Ok(diff.deltas().len())
 }
 
 ///
@@ -38,6 +69,48 @@ pub fn reset_workdir(repo_path: &RepoPath, path: &str) -> Result<()> {
 	Ok(())
 }
 
+/// discards staged and unstaged changes of the files at `paths`,
+/// restoring them to their state in `HEAD`.
+/// files that are not part of `HEAD` are unstaged but kept
+pub fn restore_to_head(
+	repo_path: &RepoPath,
+	paths: &[String],
+) -> Result<()> {
+	scope_time!("restore_to_head");
+
+	let repo = repo(repo_path)?;
+
+	let head = get_head_repo(&repo)?;
+	let obj =
+		repo.find_object(head.into(), Some(ObjectType::Commit))?;
+	repo.reset_default(Some(&obj), paths)?;
+
+	let mut checkout_opts = CheckoutBuilder::new();
+	checkout_opts.update_index(true).force();
+	for path in paths {
+		checkout_opts.path(path);
+	}
+
+	repo.checkout_index(None, Some(&mut checkout_opts))?;
+	Ok(())
+}
+
+/// deletes the untracked files at `paths` from the working dir
+pub fn delete_untracked(
+	repo_path: &RepoPath,
+	paths: &[String],
+) -> Result<()> {
+	scope_time!("delete_untracked");
+
+	let work_dir = repo_work_dir(repo_path)?;
+
+	for path in paths {
+		fs::remove_file(Path::new(&work_dir).join(path))?;
+	}
+
+	Ok(())
+}
+
 ///
 pub fn reset_repo(
 	repo_path: &RepoPath,
@@ -57,7 +130,9 @@ pub fn reset_repo(
 
 #[cfg(test)]
 mod tests {
-	use super::{reset_stage, reset_workdir};
+	use super::{
+		delete_untracked, reset_stage, reset_workdir, restore_to_head,
+	};
 	use crate::error::Result;
 	use crate::sync::{
 		commit,
@@ -145,6 +220,75 @@ mod tests {
 		assert_eq!(get_statuses(repo_path), (0, 1));
 	}
 
+	#[test]
+	fn test_restore_to_head() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let file_path = root.join("bar.txt");
+		File::create(&file_path)
+			.unwrap()
+			.write_all(HUNK_A.as_bytes())
+			.unwrap();
+		stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+		commit(repo_path, "add bar").unwrap();
+
+		File::create(&file_path)
+			.unwrap()
+			.write_all(HUNK_B.as_bytes())
+			.unwrap();
+		stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+		File::create(&file_path)
+			.unwrap()
+			.write_all(b"third")
+			.unwrap();
+		File::create(root.join("new.txt"))
+			.unwrap()
+			.write_all(b"new")
+			.unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+
+		assert_eq!(get_statuses(repo_path), (1, 2));
+
+		restore_to_head(
+			repo_path,
+			&[String::from("bar.txt"), String::from("new.txt")],
+		)
+		.unwrap();
+
+		// `new.txt` is not in `HEAD` and stays around untracked
+		assert_eq!(get_statuses(repo_path), (1, 0));
+		assert_eq!(fs::read_to_string(&file_path).unwrap(), HUNK_A);
+	}
+
+	#[test]
+	fn test_delete_untracked() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::create_dir(root.join("foo")).unwrap();
+		File::create(root.join("foo/bar.txt"))
+			.unwrap()
+			.write_all(b"test")
+			.unwrap();
+		File::create(root.join("baz.txt"))
+			.unwrap()
+			.write_all(b"test")
+			.unwrap();
+
+		assert_eq!(get_statuses(repo_path), (2, 0));
+
+		delete_untracked(repo_path, &[String::from("foo/bar.txt")])
+			.unwrap();
+
+		assert_eq!(get_statuses(repo_path), (1, 0));
+		assert!(!root.join("foo/bar.txt").exists());
+	}
+
 	#[test]
 	fn test_reset_untracked_in_subdir() {
 		let (_td, repo) = repo_init().unwrap();
@@ -336,4 +480,22 @@ mod tests {
 
 		assert_eq!(get_statuses(repo_path), (0, 0));
 	}
+
+	#[test]
+	fn test_reset_stage_glob_count() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("a.rs"))?.write_all(b"a")?;
+		File::create(root.join("b.rs"))?.write_all(b"b")?;
+		File::create(root.join("c.txt"))?.write_all(b"c")?;
+		stage_add_all(repo_path, "*", None)?;
+
+		assert_eq!(reset_stage(repo_path, "*.rs")?, 2);
+		assert_eq!(get_statuses(repo_path), (2, 1));
+
+		Ok(())
+	}
 }
@@ -343,4 +343,69 @@ end
 
 		assert_eq!(result_file.as_str(), FILE_3);
 	}
+
+	#[test]
+	fn test_discard_across_hunks() {
+		static FILE_1: &str = r"a
+b
+c
+d
+e
+f
+g
+h
+";
+
+		static FILE_2: &str = r"a
+B
+c
+d
+e
+f
+G
+G2
+h
+";
+
+		static FILE_3: &str = r"a
+b
+c
+d
+e
+f
+G
+h
+";
+
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", FILE_1, "c1");
+
+		repo_write_file(&repo, "test.txt", FILE_2).unwrap();
+
+		discard_lines(
+			path,
+			"test.txt",
+			&[
+				DiffLinePosition {
+					old_lineno: Some(2),
+					new_lineno: None,
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(2),
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(8),
+				},
+			],
+		)
+		.unwrap();
+
+		let result_file = load_file(&repo, "test.txt").unwrap();
+
+		assert_eq!(result_file.as_str(), FILE_3);
+	}
 }
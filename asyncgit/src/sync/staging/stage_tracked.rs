@@ -189,4 +189,130 @@ c = 4";
 
 		assert_eq!(diff.lines, 4);
 	}
+
+	static ACROSS_HUNKS_OLD: &str = r"a
+b
+c
+d
+e
+f
+g
+h
+";
+
+	static ACROSS_HUNKS_NEW: &str = r"a
+B
+c
+d
+e
+f
+G
+G2
+h
+";
+
+	fn indexed_content(repo: &git2::Repository) -> String {
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		let entry = index.get_path(Path::new("test.txt"), 0).unwrap();
+		String::from_utf8(
+			repo.find_blob(entry.id).unwrap().content().into(),
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn test_stage_across_hunks() {
+		static FILE_3: &str = r"a
+B
+c
+d
+e
+f
+G
+h
+";
+
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", ACROSS_HUNKS_OLD, "c1");
+
+		repo_write_file(&repo, "test.txt", ACROSS_HUNKS_NEW).unwrap();
+
+		// selection from the first hunk into the second one,
+		// leaving out the last addition
+		stage_lines(
+			path,
+			"test.txt",
+			false,
+			&[
+				DiffLinePosition {
+					old_lineno: Some(2),
+					new_lineno: None,
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(2),
+				},
+				DiffLinePosition {
+					old_lineno: Some(7),
+					new_lineno: None,
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(7),
+				},
+			],
+		)
+		.unwrap();
+
+		assert_eq!(indexed_content(&repo), FILE_3);
+		assert_eq!(get_statuses(path), (1, 1));
+	}
+
+	#[test]
+	fn test_unstage_across_hunks() {
+		static FILE_3: &str = r"a
+b
+c
+d
+e
+f
+G
+h
+";
+
+		let (path, repo) = repo_init().unwrap();
+		let path: &RepoPath = &path.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", ACROSS_HUNKS_OLD, "c1");
+
+		repo_write_file(&repo, "test.txt", ACROSS_HUNKS_NEW).unwrap();
+
+		stage_add_file(path, Path::new("test.txt")).unwrap();
+
+		stage_lines(
+			path,
+			"test.txt",
+			true,
+			&[
+				DiffLinePosition {
+					old_lineno: Some(2),
+					new_lineno: None,
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(2),
+				},
+				DiffLinePosition {
+					old_lineno: None,
+					new_lineno: Some(8),
+				},
+			],
+		)
+		.unwrap();
+
+		assert_eq!(indexed_content(&repo), FILE_3);
+	}
 }
@@ -4,8 +4,8 @@ use crate::{
 	sync::repository::repo,
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, ErrorCode, Oid, Repository,
+	StashApplyOptions, StashFlags,
 };
 use scopetime::scope_time;
 
@@ -135,6 +135,23 @@ pub fn stash_save(
 	Ok(CommitId::new(id))
 }
 
+/// stashes tracked changes the way `git rebase --autostash` does,
+/// untracked files stay, `None` if there was nothing to stash
+pub fn stash_save_auto(
+	repo_path: &RepoPath,
+) -> Result<Option<CommitId>> {
+	scope_time!("stash_save_auto");
+
+	match stash_save(repo_path, Some("gitui autostash"), false, false)
+	{
+		Ok(id) => Ok(Some(id)),
+		Err(Error::Git(e)) if e.code() == ErrorCode::NotFound => {
+			Ok(None)
+		}
+		Err(e) => Err(e),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -387,4 +404,35 @@ mod tests {
 			"test3"
 		);
 	}
+
+	#[test]
+	fn test_stash_save_auto() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test", "c1");
+		repo_write_file(&repo, "untracked.txt", "new").unwrap();
+
+		assert_eq!(stash_save_auto(repo_path).unwrap(), None);
+
+		repo_write_file(&repo, "test.txt", "test2").unwrap();
+
+		let id = stash_save_auto(repo_path).unwrap().unwrap();
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test"
+		);
+		assert_eq!(
+			repo_read_file(&repo, "untracked.txt").unwrap(),
+			"new"
+		);
+
+		stash_pop(repo_path, id).unwrap();
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			"test2"
+		);
+	}
 }
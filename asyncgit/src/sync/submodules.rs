@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+	path::{Path, PathBuf},
+	process::Command,
+};
 
 use git2::{
 	Repository, RepositoryOpenFlags, Submodule,
@@ -106,6 +109,34 @@ pub fn update_submodule(
 	Ok(())
 }
 
+/// initializes and checks out the submodule at `path` by running
+/// `git submodule update --init`, unlike `update_submodule` this
+/// also works for submodules that were never cloned
+pub fn init_submodule_cmd(
+	repo_path: &RepoPath,
+	path: &Path,
+) -> Result<()> {
+	scope_time!("init_submodule_cmd");
+
+	let repo = repo(repo_path)?;
+
+	let out = Command::new("git")
+		.current_dir(work_dir(&repo)?)
+		.args(["submodule", "update", "--init", "--"])
+		.arg(path)
+		.output()?;
+
+	if out.status.success() {
+		Ok(())
+	} else {
+		Err(Error::Generic(format!(
+			"{}: {}",
+			out.status,
+			String::from_utf8_lossy(&out.stderr).trim()
+		)))
+	}
+}
+
 /// query whether `repo_path` points to a repo that is part of a parent git which contains it as a submodule
 pub fn submodule_parent_info(
 	repo_path: &RepoPath,
@@ -155,13 +186,14 @@ pub fn submodule_parent_info(
 
 #[cfg(test)]
 mod tests {
-	use super::get_submodules;
+	use super::{get_submodules, init_submodule_cmd};
 	use crate::sync::{
-		submodules::submodule_parent_info, tests::repo_init, RepoPath,
+		submodules::submodule_parent_info, tests::repo_init,
+		RepoPath, SubmoduleStatus,
 	};
 	use git2::Repository;
 	use pretty_assertions::assert_eq;
-	use std::path::Path;
+	use std::{path::Path, process::Command};
 
 	#[test]
 	fn test_smoke() {
@@ -198,4 +230,44 @@ mod tests {
 
 		assert_eq!(&info.submodule_info.name, "foo/bar");
 	}
+
+	#[test]
+	fn test_init_submodule() {
+		let (sub_dir, _sub) = repo_init().unwrap();
+		let (dir, _r) = repo_init().unwrap();
+
+		let git = |args: &[&str]| {
+			let status = Command::new("git")
+				.current_dir(dir.path())
+				.args(["-c", "protocol.file.allow=always"])
+				.args(args)
+				.output()
+				.unwrap()
+				.status;
+			assert!(status.success(), "git {args:?}");
+		};
+		git(&[
+			"submodule",
+			"add",
+			"-q",
+			sub_dir.path().to_str().unwrap(),
+			"sub",
+		]);
+		git(&["commit", "-q", "-m", "add sub"]);
+		git(&["submodule", "deinit", "-q", "sub"]);
+
+		let repo_p: RepoPath = dir.path().to_str().unwrap().into();
+		let sub_git = dir.path().join("sub").join(".git");
+		assert!(get_submodules(&repo_p).unwrap()[0]
+			.status
+			.contains(SubmoduleStatus::WD_UNINITIALIZED));
+		assert!(!sub_git.exists());
+
+		init_submodule_cmd(&repo_p, Path::new("sub")).unwrap();
+
+		assert!(get_submodules(&repo_p).unwrap()[0]
+			.status
+			.is_in_wd());
+		assert!(sub_git.exists());
+	}
 }
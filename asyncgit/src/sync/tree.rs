@@ -7,6 +7,8 @@ use git2::{Oid, Repository, Tree};
 use scopetime::scope_time;
 use std::{
 	collections::HashSet,
+	fs::File,
+	io::Read,
 	path::{Path, PathBuf},
 };
 use walkdir::WalkDir;
@@ -110,6 +112,64 @@ pub fn tree_file_content(
 	Ok(content)
 }
 
+/// bytes looked at by `tree_file_head`
+const FILE_HEAD_BYTES_MAX: usize = 16 * 1024;
+
+/// up to `max_lines` lines from the start of `file`, taken from its
+/// blob or, for files listed by `repo_files`, from the worktree.
+/// directories have no content
+pub fn tree_file_head(
+	repo_path: &RepoPath,
+	file: &TreeFile,
+	max_lines: usize,
+) -> Result<String> {
+	scope_time!("tree_file_head");
+
+	let content = if file.id.is_zero() {
+		if !file.path.is_absolute() || !file.path.is_file() {
+			return Ok(String::new());
+		}
+
+		read_head(File::open(&file.path)?)?
+	} else {
+		let repo = repo(repo_path)?;
+		let odb = repo.odb()?;
+		// only loose objects can be streamed, packed ones have to be
+		// inflated as a whole
+		let content = match odb.reader(file.id) {
+			Ok((mut reader, size, _)) => {
+				// the stream claims to fill every buffer it is given,
+				// so ask for exactly what is there in a single read
+				let mut content =
+					vec![0; size.min(FILE_HEAD_BYTES_MAX)];
+				reader.read_exact(&mut content)?;
+				content
+			}
+			Err(_) => read_head(odb.read(file.id)?.data())?,
+		};
+		content
+	};
+
+	// same heuristic git uses
+	if content.contains(&0) {
+		return Err(Error::BinaryFile);
+	}
+
+	Ok(String::from_utf8_lossy(&content)
+		.lines()
+		.take(max_lines)
+		.collect::<Vec<_>>()
+		.join("\n"))
+}
+
+fn read_head(reader: impl Read) -> Result<Vec<u8>> {
+	let mut content = Vec::with_capacity(FILE_HEAD_BYTES_MAX);
+	reader
+		.take(FILE_HEAD_BYTES_MAX as u64)
+		.read_to_end(&mut content)?;
+	Ok(content)
+}
+
 ///
 fn tree_recurse(
 	repo: &Repository,
@@ -171,6 +231,51 @@ mod tests {
 		assert_ne!(files_c2[0], files[0]);
 	}
 
+	#[test]
+	fn test_file_head() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 =
+			write_commit_file(&repo, "test.txt", "a\nb\nc\n", "c1");
+		let files = tree_files(repo_path, c1, false).unwrap();
+		assert_eq!(
+			tree_file_head(repo_path, &files[0], 2).unwrap(),
+			"a\nb"
+		);
+
+		// larger than what is read of it
+		let long = "line\n".repeat(FILE_HEAD_BYTES_MAX);
+		let c2 = write_commit_file(&repo, "long.txt", &long, "c2");
+		let files = tree_files(repo_path, c2, false).unwrap();
+		assert_eq!(
+			tree_file_head(repo_path, &files[0], 3).unwrap(),
+			"line\nline\nline"
+		);
+
+		std::fs::write(root.join("bin"), b"a\0b").unwrap();
+		let worktree_file = |path: &str| TreeFile {
+			path: root.join(path),
+			filemode: 0,
+			id: Oid::zero(),
+		};
+		assert_eq!(
+			tree_file_head(repo_path, &worktree_file("test.txt"), 5)
+				.unwrap(),
+			"a\nb\nc"
+		);
+		assert!(matches!(
+			tree_file_head(repo_path, &worktree_file("bin"), 5),
+			Err(Error::BinaryFile)
+		));
+		assert_eq!(
+			tree_file_head(repo_path, &worktree_file(""), 5).unwrap(),
+			""
+		);
+	}
+
 	#[test]
 	fn test_sorting() {
 		let mut list = vec!["file", "folder/file", "folder/afile"]
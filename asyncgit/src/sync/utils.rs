@@ -79,6 +79,12 @@ pub fn get_head_tuple_branch(repo_path: &RepoPath) -> Result<Head> {
 	Ok(Head { name, id })
 }
 
+/// whether `HEAD` points at a commit instead of a branch
+pub fn is_head_detached(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+	Ok(repo.head_detached()?)
+}
+
 ///
 pub fn get_head_name(repo_path: &RepoPath) -> Result<String> {
 	let repo = repo(repo_path)?;
@@ -139,12 +145,13 @@ pub fn stage_add_file(
 	Ok(())
 }
 
-/// like `stage_add_file` but uses a pattern to match/glob multiple files/folders
+/// like `stage_add_file` but uses a pattern to match/glob multiple files/folders,
+/// returns the number of files staged
 pub fn stage_add_all(
 	repo_path: &RepoPath,
 	pattern: &str,
 	stage_untracked: Option<ShowUntrackedFilesConfig>,
-) -> Result<()> {
+) -> Result<usize> {
 	scope_time!("stage_add_all");
 
 	let repo = repo(repo_path)?;
@@ -157,19 +164,25 @@ pub fn stage_add_all(
 		untracked_files_config_repo(&repo)?
 	};
 
+	let mut staged = 0_usize;
+	let mut count_staged = |_: &Path, _: &[u8]| {
+		staged += 1;
+		0
+	};
+
 	if stage_untracked.include_untracked() {
 		index.add_all(
 			vec![pattern],
 			IndexAddOption::DEFAULT,
-			None,
+			Some(&mut count_staged),
 		)?;
 	} else {
-		index.update_all(vec![pattern], None)?;
+		index.update_all(vec![pattern], Some(&mut count_staged))?;
 	}
 
 	index.write()?;
 
-	Ok(())
+	Ok(staged)
 }
 
 /// Undo last commit in repo
@@ -360,6 +373,31 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_stage_add_all_glob_count() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::create_dir_all(root.join("a"))?;
+		File::create(root.join(Path::new("a/f1.rs")))?
+			.write_all(b"foo")?;
+		File::create(root.join(Path::new("f2.rs")))?
+			.write_all(b"foo")?;
+		File::create(root.join(Path::new("f3.txt")))?
+			.write_all(b"foo")?;
+
+		assert_eq!(stage_add_all(repo_path, "*.rs", None)?, 2);
+		assert_eq!(
+			get_status(repo_path, StatusType::WorkingDir, None)?
+				.len(),
+			1
+		);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_undo_commit_empty_repo() {
 		let (_td, repo) = repo_init().unwrap();
@@ -517,4 +555,19 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_head_detached() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(!is_head_detached(repo_path)?);
+
+		repo.set_head_detached(get_head(repo_path)?.into())?;
+		assert!(is_head_detached(repo_path)?);
+
+		Ok(())
+	}
 }
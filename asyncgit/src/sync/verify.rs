@@ -0,0 +1,95 @@
+//! commit signature verification
+
+use super::{CommitId, RepoPath};
+use crate::{error::Result, sync::repository::repo};
+use git2::ErrorCode;
+use scopetime::scope_time;
+use std::process::{Command, Stdio};
+
+/// signature state of a commit
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SignatureStatus {
+	/// commit carries no signature
+	Unsigned,
+	/// `git verify-commit` accepted the signature
+	Good,
+	/// signature is invalid or made with an unknown/untrusted key
+	Bad,
+}
+
+/// signatures are extracted with libgit2, only signed commits are
+/// handed to `git verify-commit` since libgit2 cannot verify them
+pub fn verify_commits(
+	repo_path: &RepoPath,
+	ids: &[CommitId],
+) -> Result<Vec<(CommitId, SignatureStatus)>> {
+	scope_time!("verify_commits");
+
+	let repo = repo(repo_path)?;
+
+	ids.iter()
+		.map(|id| {
+			let signed = match repo
+				.extract_signature(&(*id).into(), None)
+			{
+				Ok(_) => true,
+				Err(e) if e.code() == ErrorCode::NotFound => false,
+				Err(e) => return Err(e.into()),
+			};
+
+			let signature = if signed {
+				verify_commit_cmd(repo_path, *id)?
+			} else {
+				SignatureStatus::Unsigned
+			};
+
+			Ok((*id, signature))
+		})
+		.collect()
+}
+
+fn verify_commit_cmd(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<SignatureStatus> {
+	let status = Command::new("git")
+		.current_dir(repo_path.gitpath())
+		.arg("verify-commit")
+		.arg(id.to_string())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()?;
+
+	Ok(if status.success() {
+		SignatureStatus::Good
+	} else {
+		SignatureStatus::Bad
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{commit, stage_add_file, tests::repo_init};
+	use std::{fs::File, io::Write, path::Path};
+
+	#[test]
+	fn test_unsigned_commit() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path)?;
+		let id = commit(repo_path, "commit msg")?;
+
+		assert_eq!(
+			verify_commits(repo_path, &[id])?,
+			vec![(id, SignatureStatus::Unsigned)]
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,216 @@
+//! linked worktrees of a repo
+
+use super::{utils::work_dir, CommitId, RepoPath};
+use crate::{
+	error::{Error, Result},
+	sync::repository::repo,
+};
+use git2::Oid;
+use scopetime::scope_time;
+use std::{
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+/// a worktree as listed by `git worktree list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct WorktreeInfo {
+	/// its working directory
+	pub path: PathBuf,
+	/// checked out commit, `None` for a bare repo
+	pub head: Option<CommitId>,
+	/// short name of the checked out branch, `None` if detached
+	pub branch: Option<String>,
+	///
+	pub is_bare: bool,
+	/// `git worktree lock`ed
+	pub locked: bool,
+	/// its directory is gone, `git worktree prune` would drop it
+	pub prunable: bool,
+	/// the worktree currently open
+	pub is_current: bool,
+}
+
+impl WorktreeInfo {
+	/// a bare or pruned worktree has nothing to show
+	pub const fn can_open(&self) -> bool {
+		!self.is_bare && !self.prunable
+	}
+}
+
+/// all worktrees of the repo, the main one first
+pub fn get_worktrees(
+	repo_path: &RepoPath,
+) -> Result<Vec<WorktreeInfo>> {
+	scope_time!("get_worktrees");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let out = Command::new("git")
+		.current_dir(work_dir)
+		.args(["worktree", "list", "--porcelain"])
+		.output()?;
+
+	if !out.status.success() {
+		return Err(Error::Generic(format!(
+			"{}: {}",
+			out.status,
+			String::from_utf8_lossy(&out.stderr).trim()
+		)));
+	}
+
+	Ok(parse_worktree_list(
+		&String::from_utf8_lossy(&out.stdout),
+		work_dir,
+	))
+}
+
+/// whether the repo is a linked worktree (and not the main one)
+pub fn is_linked_worktree(repo_path: &RepoPath) -> Result<bool> {
+	Ok(repo(repo_path)?.is_worktree())
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+	match (a.canonicalize(), b.canonicalize()) {
+		(Ok(a), Ok(b)) => a == b,
+		_ => a == b,
+	}
+}
+
+/// parses the `--porcelain` format: one block of lines per
+/// worktree, separated by empty lines, each line a label optionally
+/// followed by a space and its value
+fn parse_worktree_list(
+	out: &str,
+	current_dir: &Path,
+) -> Vec<WorktreeInfo> {
+	let mut res: Vec<WorktreeInfo> = Vec::new();
+
+	for line in out.lines() {
+		let (label, value) =
+			line.split_once(' ').unwrap_or((line, ""));
+
+		if label == "worktree" {
+			let path = PathBuf::from(value);
+			res.push(WorktreeInfo {
+				is_current: same_dir(&path, current_dir),
+				path,
+				head: None,
+				branch: None,
+				is_bare: false,
+				locked: false,
+				prunable: false,
+			});
+			continue;
+		}
+
+		let worktree = if let Some(worktree) = res.last_mut() {
+			worktree
+		} else {
+			continue;
+		};
+
+		match label {
+			"HEAD" => {
+				worktree.head =
+					Oid::from_str(value).ok().map(CommitId::new);
+			}
+			"branch" => {
+				worktree.branch = Some(
+					value
+						.strip_prefix("refs/heads/")
+						.unwrap_or(value)
+						.to_string(),
+				);
+			}
+			"bare" => worktree.is_bare = true,
+			"locked" => worktree.locked = true,
+			"prunable" => worktree.prunable = true,
+			_ => (),
+		}
+	}
+
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_parse_worktree_list() {
+		let out = "worktree /repo\n\
+			HEAD 0123456789012345678901234567890123456789\n\
+			branch refs/heads/master\n\
+			\n\
+			worktree /repo-wt with space\n\
+			HEAD 0123456789012345678901234567890123456789\n\
+			detached\n\
+			locked reason\n\
+			\n\
+			worktree /gone\n\
+			HEAD 0123456789012345678901234567890123456789\n\
+			branch refs/heads/feature/x\n\
+			prunable gitdir file points to non-existent location\n";
+
+		let worktrees = parse_worktree_list(
+			out,
+			Path::new("/repo-wt with space"),
+		);
+
+		assert_eq!(worktrees.len(), 3);
+
+		assert_eq!(worktrees[0].path, PathBuf::from("/repo"));
+		assert_eq!(worktrees[0].branch.as_deref(), Some("master"));
+		assert!(!worktrees[0].is_current);
+		assert!(worktrees[0].can_open());
+
+		assert_eq!(
+			worktrees[1].path,
+			PathBuf::from("/repo-wt with space")
+		);
+		assert_eq!(worktrees[1].branch, None);
+		assert!(worktrees[1].head.is_some());
+		assert!(worktrees[1].locked);
+		assert!(worktrees[1].is_current);
+
+		assert_eq!(worktrees[2].branch.as_deref(), Some("feature/x"));
+		assert!(worktrees[2].prunable);
+		assert!(!worktrees[2].can_open());
+	}
+
+	#[test]
+	fn test_get_worktrees() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let wt_dir = tempfile::TempDir::new().unwrap();
+		let wt_path = wt_dir.path().join("wt");
+		let status = Command::new("git")
+			.current_dir(td.path())
+			.args(["worktree", "add", "-q", "-b", "wt"])
+			.arg(&wt_path)
+			.status()
+			.unwrap();
+		assert!(status.success());
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert!(worktrees[0].is_current);
+		assert!(!worktrees[1].is_current);
+		assert_eq!(worktrees[1].branch.as_deref(), Some("wt"));
+		assert!(!is_linked_worktree(repo_path).unwrap());
+
+		let wt_repo_path: RepoPath = wt_path.to_str().unwrap().into();
+		assert!(is_linked_worktree(&wt_repo_path).unwrap());
+		assert!(get_worktrees(&wt_repo_path).unwrap()[1].is_current);
+	}
+}
@@ -0,0 +1,94 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		branch_compare_upstream, get_branch_upstream, BranchCompare,
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request(RepoPath),
+	Response(Result<(Option<String>, Option<BranchCompare>)>),
+}
+
+/// looks up the upstream of a branch and how far they diverged in
+/// the background
+#[derive(Clone)]
+pub struct AsyncUpstreamJob {
+	branch: String,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncUpstreamJob {
+	///
+	pub fn new(repo: RepoPath, branch: String) -> Self {
+		Self {
+			branch,
+			state: Arc::new(Mutex::new(Some(JobState::Request(
+				repo,
+			)))),
+		}
+	}
+
+	/// the branch whose upstream is looked up
+	pub fn branch(&self) -> &str {
+		&self.branch
+	}
+
+	/// name of the upstream and the commits ahead/behind it, both
+	/// `None` without an upstream
+	pub fn result(
+		&self,
+	) -> Option<Result<(Option<String>, Option<BranchCompare>)>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(_) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+fn upstream_info(
+	repo: &RepoPath,
+	branch: &str,
+) -> Result<(Option<String>, Option<BranchCompare>)> {
+	let upstream = get_branch_upstream(repo, branch)?;
+	// an upstream gone from the remote still has a name to show
+	let compare = upstream
+		.as_ref()
+		.and_then(|_| branch_compare_upstream(repo, branch).ok());
+
+	Ok((upstream, compare))
+}
+
+impl AsyncJob for AsyncUpstreamJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request(repo) => JobState::Response(
+					upstream_info(&repo, &self.branch),
+				),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Upstream)
+	}
+}
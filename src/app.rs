@@ -1,38 +1,45 @@
-use crate::async_jobs::JobSender;
+use crate::async_jobs::{JobProgress, JobSender};
 use crate::{
 	accessors,
 	cmdbar::CommandBar,
 	components::{
 		event_pump, AppOption, BlameFileComponent, BranchFindPopup,
 		BranchListComponent, CommandBlocking, CommandInfo,
-		CommitComponent, CompareCommitsComponent, Component,
+		CommitComponent, CommitNoteComponent,
+		CompareCommitsComponent, Component,
 		ConfirmComponent, CopyPopupComponent, CreateBranchComponent,
 		DrawableComponent, ExternalCommandPopupComponent,
 		ExternalEditorComponent, FetchComponent, FileFindPopup,
-		FileRevlogComponent, HelpComponent, InspectCommitComponent,
+		FileRevlogComponent, GrepPopup, HelpComponent, InspectCommitComponent,
 		MsgComponent, OptionsPopupComponent, PullComponent,
-		PushComponent, PushTagsComponent, RenameBranchComponent,
+		PullRequestComponent, PushComponent, PushTagsComponent,
+		RebaseTodoComponent, ReflogComponent, RemotesListComponent,
+		RenameBranchComponent, StagePatternComponent,
 		ResetPopupComponent, RevisionFilesPopup, StashMsgComponent,
+		StatusHeaderComponent,
 		SubmodulesListComponent, TagCommitComponent,
-		TagListComponent,rebase_commits_interactive_with_editor, rebase_interactive_abort, rebase_interactive_skip, rebase_commits_continue_with_editor
+		TagListComponent, WorktreesListComponent, rebase_interactive_abort, rebase_interactive_skip, rebase_commits_continue_with_editor, resolve_editor
 	},
 	input::{Input, InputEvent, InputState},
-	keys::{key_match, KeyConfig, SharedKeyConfig},
-	options::{Options, SharedOptions},
+	keys::{key_match, KeyConfig, PendingChord, SharedKeyConfig},
+	options::{Options, SharedOptions, UpdateIntervals, ViewState},
 	popup_stack::PopupStack,
 	queue::{
 		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
 	},
 	setup_popups,
+	string_utils::permalink,
 	strings::{self, ellipsis_trim_start, order},
 	tabs::{FilesTab, RevlogExtern, StashList, Stashing, Status},
 	ui::style::{SharedTheme, Theme},
 	AsyncAppNotification, AsyncNotification,
 };
 use anyhow::{bail, Result};
-use asyncgit::sync::CommitId;
 use asyncgit::{
-	sync::{self, utils::repo_work_dir, RepoPath, RepoPathRef},
+	sync::{
+		self, branch::checkout_branch_cmd, utils::repo_work_dir,
+		CommitId, RepoPath, RepoPathRef,
+	},
 	AsyncGitNotification, PushType,
 };
 use crossbeam_channel::Sender;
@@ -48,8 +55,9 @@ use ratatui::{
 };
 use std::{
 	cell::{Cell, RefCell},
-	path::Path,
+	path::{Path, PathBuf},
 	rc::Rc,
+	time::Duration,
 };
 use unicode_width::UnicodeWidthStr;
 
@@ -57,15 +65,27 @@ use unicode_width::UnicodeWidthStr;
 pub enum QuitState {
 	None,
 	Close,
-	OpenSubmodule(RepoPath),
+	OpenSubmodule(RepoPath, SubmoduleBreadcrumb),
+	OpenParentRepo(RepoPath),
+	OpenWorktree(RepoPath),
+}
+
+/// remembers where a submodule was opened from, so returning to the
+/// parent selects it again
+#[derive(Clone)]
+pub struct SubmoduleBreadcrumb {
+	/// repo the submodule was opened from
+	pub parent: RepoPath,
+	/// path of the submodule inside `parent`
+	pub submodule: PathBuf,
 }
 
 enum ExternalEditorRequest {
 	None,
-	ExternalEditorWithPath(String),
+	ExternalEditorWithPath(String, Option<usize>),
 	ShowEditor,
 	EditorToCommit,
-	RebaseInteractive(CommitId),
+	RebaseTodo(bool),
 	RebaseInteractiveContinue,
 }
 
@@ -87,22 +107,32 @@ pub struct App {
 	revision_files_popup: RevisionFilesPopup,
 	find_file_popup: FileFindPopup,
 	branch_find_popup: BranchFindPopup,
+	///
+	pub grep_popup: GrepPopup,
 	push_popup: PushComponent,
 	push_tags_popup: PushTagsComponent,
 	pull_popup: PullComponent,
+	pull_request_popup: PullRequestComponent,
 	fetch_popup: FetchComponent,
 	tag_commit_popup: TagCommitComponent,
+	commit_note_popup: CommitNoteComponent,
 	create_branch_popup: CreateBranchComponent,
 	rename_branch_popup: RenameBranchComponent,
+	stage_pattern_popup: StagePatternComponent,
 	select_branch_popup: BranchListComponent,
 	options_popup: OptionsPopupComponent,
 	copy_clipboard_popup: CopyPopupComponent,
 	///
 	pub external_command_popup: ExternalCommandPopupComponent,
-	submodule_popup: SubmodulesListComponent,
+	pub submodule_popup: SubmodulesListComponent,
+	remotes_popup: RemotesListComponent,
+	worktrees_popup: WorktreesListComponent,
+	reflog_popup: ReflogComponent,
+	rebase_todo_popup: RebaseTodoComponent,
 	tags_popup: TagListComponent,
 	reset_popup: ResetPopupComponent,
 	cmdbar: RefCell<CommandBar>,
+	status_header: StatusHeaderComponent,
 	tab: usize,
 	revlog: RevlogExtern,
 	status_tab: Status,
@@ -112,11 +142,15 @@ pub struct App {
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	chord: PendingChord,
 	input: Input,
 	popup_stack: PopupStack,
 	options: SharedOptions,
 	repo_path_text: String,
 
+	// progress of the running async job, if it reports any
+	job_progress: Option<JobProgress>,
+
 	// "Flags"
 	requires_redraw: Cell<bool>,
 	external_editor_request: ExternalEditorRequest,
@@ -137,8 +171,13 @@ impl App {
 	) -> Result<Self> {
 		log::trace!("open repo at: {:?}", &repo);
 
-		let repo_path_text =
+		let mut repo_path_text =
 			repo_work_dir(&repo.borrow()).unwrap_or_default();
+		if sync::is_linked_worktree(&repo.borrow())
+			.unwrap_or_default()
+		{
+			repo_path_text += strings::TITLE_LINKED_WORKTREE;
+		}
 
 		let queue = Queue::new();
 		let theme = Rc::new(theme);
@@ -153,6 +192,7 @@ impl App {
 				queue.clone(),
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			commit: CommitComponent::new(
 				repo.clone(),
@@ -168,6 +208,7 @@ impl App {
 				&strings::blame_title(&key_config),
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			file_revlog_popup: FileRevlogComponent::new(
 				&repo,
@@ -197,6 +238,7 @@ impl App {
 				sender,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			compare_commits_popup: CompareCommitsComponent::new(
 				&repo,
@@ -204,6 +246,7 @@ impl App {
 				sender,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			external_editor_popup: ExternalEditorComponent::new(
 				theme.clone(),
@@ -223,12 +266,14 @@ impl App {
 				sender,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			reset_popup: ResetPopupComponent::new(
 				&queue,
 				&repo,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			pull_popup: PullComponent::new(
 				&repo,
@@ -238,6 +283,14 @@ impl App {
 				key_config.clone(),
 				options.clone(),
 			),
+			pull_request_popup: PullRequestComponent::new(
+				&repo,
+				&queue,
+				sender,
+				theme.clone(),
+				key_config.clone(),
+				options.clone(),
+			),
 			fetch_popup: FetchComponent::new(
 				repo.clone(),
 				&queue,
@@ -252,6 +305,12 @@ impl App {
 				theme.clone(),
 				key_config.clone(),
 			),
+			commit_note_popup: CommitNoteComponent::new(
+				repo.clone(),
+				queue.clone(),
+				theme.clone(),
+				key_config.clone(),
+			),
 			create_branch_popup: CreateBranchComponent::new(
 				repo.clone(),
 				queue.clone(),
@@ -264,9 +323,17 @@ impl App {
 				theme.clone(),
 				key_config.clone(),
 			),
+			stage_pattern_popup: StagePatternComponent::new(
+				repo.clone(),
+				queue.clone(),
+				theme.clone(),
+				key_config.clone(),
+				options.clone(),
+			),
 			select_branch_popup: BranchListComponent::new(
 				repo.clone(),
 				queue.clone(),
+				sender,
 				theme.clone(),
 				key_config.clone(),
 				options.clone(),
@@ -285,9 +352,11 @@ impl App {
 				options.clone(),
 			),
 			copy_clipboard_popup: CopyPopupComponent::new(
+				&queue,
 				theme.clone(),
 				key_config.clone(),
 				repo.clone(),
+				options.clone(),
 			),
 			external_command_popup:
 				ExternalCommandPopupComponent::new(
@@ -302,9 +371,36 @@ impl App {
 				&queue,
 				theme.clone(),
 				key_config.clone(),
+				async_job_sender.clone(),
+			),
+			remotes_popup: RemotesListComponent::new(
+				repo.clone(),
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			worktrees_popup: WorktreesListComponent::new(
+				repo.clone(),
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			reflog_popup: ReflogComponent::new(
+				repo.clone(),
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+			),
+			rebase_todo_popup: RebaseTodoComponent::new(
+				repo.clone(),
+				&queue,
+				theme.clone(),
+				key_config.clone(),
 			),
 			find_file_popup: FileFindPopup::new(
+				repo.clone(),
 				&queue,
+				sender,
 				theme.clone(),
 				key_config.clone(),
 			),
@@ -313,11 +409,24 @@ impl App {
 				theme.clone(),
 				key_config.clone(),
 			),
+			grep_popup: GrepPopup::new(
+				repo.clone(),
+				&queue,
+				theme.clone(),
+				key_config.clone(),
+				async_job_sender.clone(),
+			),
 			do_quit: QuitState::None,
 			cmdbar: RefCell::new(CommandBar::new(
 				theme.clone(),
 				key_config.clone(),
 			)),
+			status_header: StatusHeaderComponent::new(
+				repo.clone(),
+				sender,
+				theme.clone(),
+				options.clone(),
+			),
 			help: HelpComponent::new(
 				theme.clone(),
 				key_config.clone(),
@@ -329,6 +438,7 @@ impl App {
 				sender,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			status_tab: Status::new(
 				repo.clone(),
@@ -350,6 +460,7 @@ impl App {
 				&queue,
 				theme.clone(),
 				key_config.clone(),
+				options.clone(),
 			),
 			files_tab: FilesTab::new(
 				repo.clone(),
@@ -363,7 +474,9 @@ impl App {
 			queue,
 			theme,
 			options,
+			chord: PendingChord::new(&key_config.keys),
 			key_config,
+			job_progress: None,
 			requires_redraw: Cell::new(false),
 			external_editor_request: ExternalEditorRequest::None,
 			repo,
@@ -387,6 +500,7 @@ impl App {
 			.direction(Direction::Vertical)
 			.constraints(
 				[
+					Constraint::Length(self.status_header.height()),
 					Constraint::Length(2),
 					Constraint::Min(2),
 					Constraint::Length(self.cmdbar.borrow().height()),
@@ -395,9 +509,10 @@ impl App {
 			)
 			.split(fsize);
 
-		self.cmdbar.borrow().draw(f, chunks_main[2]);
+		self.cmdbar.borrow().draw(f, chunks_main[3]);
 
-		self.draw_top_bar(f, chunks_main[0]);
+		self.status_header.draw(f, chunks_main[0])?;
+		self.draw_top_bar(f, chunks_main[1]);
 
 		//TODO: component property + a macro `fullscreen_popup_open!`
 		// to make this scale better?
@@ -411,11 +526,11 @@ impl App {
 		if !fullscreen_popup_open {
 			//TODO: macro because of generic draw call
 			match self.tab {
-				0 => self.status_tab.draw(f, chunks_main[1])?,
-				1 => self.revlog.draw(f, chunks_main[1])?,
-				2 => self.files_tab.draw(f, chunks_main[1])?,
-				3 => self.stashing_tab.draw(f, chunks_main[1])?,
-				4 => self.stashlist_tab.draw(f, chunks_main[1])?,
+				0 => self.status_tab.draw(f, chunks_main[2])?,
+				1 => self.revlog.draw(f, chunks_main[2])?,
+				2 => self.files_tab.draw(f, chunks_main[2])?,
+				3 => self.stashing_tab.draw(f, chunks_main[2])?,
+				4 => self.stashlist_tab.draw(f, chunks_main[2])?,
 				_ => bail!("unknown tab"),
 			};
 		}
@@ -430,7 +545,12 @@ impl App {
 		log::trace!("event: {:?}", ev);
 
 		if let InputEvent::Input(ev) = ev {
-			if self.check_hard_exit(&ev) || self.check_quit(&ev) {
+			let ev = match ev {
+				Event::Key(k) => Event::Key(self.chord.resolve(k)),
+				ev => ev,
+			};
+
+			if self.check_hard_exit(&ev)? || self.check_quit(&ev)? {
 				return Ok(());
 			}
 
@@ -483,10 +603,23 @@ impl App {
 				) {
 					self.options_popup.show()?;
 					NeedsUpdate::ALL
+				} else if key_match(
+					k,
+					self.key_config.keys.toggle_status_header,
+				) {
+					self.status_header.toggle();
+					NeedsUpdate::COMMANDS
 				} else if key_match(
 					k,
 					self.key_config.keys.run_external_command,
 				) {
+					self.external_command_popup.set_marked_commits(
+						if self.revlog.is_visible() {
+							self.revlog.marked_commits()
+						} else {
+							Vec::new()
+						},
+					);
 					self.external_command_popup.show()?;
 					NeedsUpdate::ALL
 				} else {
@@ -504,9 +637,12 @@ impl App {
 					ExternalEditorRequest::None => Ok(()),
 					ExternalEditorRequest::ExternalEditorWithPath(
 						path,
+						line,
 					) => ExternalEditorComponent::open_file_in_editor(
 						&self.repo.borrow(),
 						Path::new(&path),
+						*line,
+						self.options.borrow().editor(),
 					),
 					ExternalEditorRequest::ShowEditor => {
 						let changes =
@@ -518,13 +654,16 @@ impl App {
 							self.status_tab.get_files_changes()?,
 						)
 					}
-					ExternalEditorRequest::RebaseInteractive(commit_id) => {
-						rebase_commits_interactive_with_editor(&self.repo.borrow().gitpath().to_str().unwrap(), commit_id)?;
+					ExternalEditorRequest::RebaseTodo(edit) => {
+						let result = self.rebase_todo_popup.finish(
+							*edit,
+							self.options.borrow().editor(),
+						);
 						self.revlog.trigger_branch_update();
-						Ok(())
+						result
 					}
 					ExternalEditorRequest::RebaseInteractiveContinue => {
-						rebase_commits_continue_with_editor(&self.repo.borrow().gitpath().to_str().unwrap())?;
+						rebase_commits_continue_with_editor(&self.repo.borrow().gitpath().to_str().unwrap(), &resolve_editor(&self.repo.borrow(), self.options.borrow().editor()))?;
 						self.revlog.trigger_branch_update();
 						Ok(())
 					}
@@ -566,6 +705,7 @@ impl App {
 		self.stashing_tab.update()?;
 		self.stashlist_tab.update()?;
 		self.reset_popup.update()?;
+		self.status_header.update();
 
 		self.update_commands();
 
@@ -590,8 +730,11 @@ impl App {
 			self.push_popup.update_git(ev)?;
 			self.push_tags_popup.update_git(ev)?;
 			self.pull_popup.update_git(ev);
+			self.pull_request_popup.update_git(ev);
 			self.fetch_popup.update_git(ev);
 			self.select_branch_popup.update_git(ev)?;
+			self.status_header.update_git(ev);
+			self.find_file_popup.update_git(ev);
 		}
 
 		self.files_tab.update_async(ev)?;
@@ -616,6 +759,49 @@ impl App {
 		self.do_quit.clone()
 	}
 
+	/// remembers where the log and stashes were left for the next
+	/// launch in this repo
+	pub fn save_view_state(&self) {
+		self.options.borrow_mut().set_view_state(ViewState {
+			log: self.revlog.view_state(),
+			stashes: self.stashlist_tab.view_state(),
+			branches: self.select_branch_popup.view_state(),
+		});
+	}
+
+	/// reopens the submodules popup with the submodule we came back
+	/// from selected
+	pub fn restore_breadcrumb(
+		&mut self,
+		breadcrumb: &SubmoduleBreadcrumb,
+	) -> Result<()> {
+		self.submodule_popup.open_at(&breadcrumb.submodule)
+	}
+
+	/// submodules are usually checked out at a detached `HEAD`,
+	/// commits made there end up on no branch
+	pub fn note_detached_submodule(&self) {
+		let repo = self.repo.borrow();
+		if sync::is_head_detached(&repo).unwrap_or_default()
+			&& sync::submodule_parent_info(&repo)
+				.ok()
+				.flatten()
+				.is_some()
+		{
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::SUBMODULE_DETACHED_HEAD.to_string(),
+			));
+		}
+	}
+
+	/// `None` clears it once the job is done
+	pub fn set_job_progress(
+		&mut self,
+		progress: Option<JobProgress>,
+	) {
+		self.job_progress = progress;
+	}
+
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.status_tab.anything_pending()
@@ -627,13 +813,37 @@ impl App {
 			|| self.inspect_commit_popup.any_work_pending()
 			|| self.compare_commits_popup.any_work_pending()
 			|| self.external_command_popup.any_work_pending()
+			|| self.submodule_popup.any_work_pending()
 			|| self.input.is_state_changing()
 			|| self.push_popup.any_work_pending()
 			|| self.push_tags_popup.any_work_pending()
 			|| self.pull_popup.any_work_pending()
+			|| self.pull_request_popup.any_work_pending()
 			|| self.fetch_popup.any_work_pending()
 			|| self.revision_files_popup.any_work_pending()
 			|| self.tags_popup.any_work_pending()
+			|| self.select_branch_popup.any_work_pending()
+			|| self.grep_popup.any_work_pending()
+	}
+
+	/// configured tick/update cadence for the main loop
+	pub fn update_intervals(&self) -> UpdateIntervals {
+		self.options.borrow().update_intervals()
+	}
+
+	/// spinner frame time, `None` for a still spinner
+	pub fn spinner_interval(&self) -> Option<Duration> {
+		self.options.borrow().spinner_interval()
+	}
+
+	/// debounce window for filesystem change notifications
+	pub fn watcher_debounce(&self) -> Duration {
+		self.options.borrow().watcher_debounce()
+	}
+
+	/// per repo watcher preference, `None` if not overridden
+	pub fn notify_watcher(&self) -> Option<bool> {
+		self.options.borrow().notify_watcher()
 	}
 
 	///
@@ -656,6 +866,7 @@ impl App {
 			external_command_popup,
 			find_file_popup,
 			branch_find_popup,
+			grep_popup,
 			msg,
 			reset,
 			commit,
@@ -668,14 +879,21 @@ impl App {
 			push_popup,
 			push_tags_popup,
 			pull_popup,
+			pull_request_popup,
 			fetch_popup,
 			tag_commit_popup,
+			commit_note_popup,
 			create_branch_popup,
 			rename_branch_popup,
+			stage_pattern_popup,
+			remotes_popup,
+			worktrees_popup,
 			select_branch_popup,
 			revision_files_popup,
 			submodule_popup,
 			tags_popup,
+			reflog_popup,
+			rebase_todo_popup,
 			reset_popup,
 			options_popup,
 			help,
@@ -701,18 +919,26 @@ impl App {
 			file_revlog_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			commit_note_popup,
 			select_branch_popup,
+			remotes_popup,
+			worktrees_popup,
 			submodule_popup,
 			tags_popup,
+			reflog_popup,
+			rebase_todo_popup,
 			reset_popup,
 			create_branch_popup,
 			rename_branch_popup,
+			stage_pattern_popup,
 			revision_files_popup,
 			find_file_popup,
 			branch_find_popup,
+			grep_popup,
 			push_popup,
 			push_tags_popup,
 			pull_popup,
+			pull_request_popup,
 			fetch_popup,
 			options_popup,
 			reset,
@@ -720,27 +946,78 @@ impl App {
 		]
 	);
 
-	fn check_quit(&mut self, ev: &Event) -> bool {
-		if self.any_popup_visible() {
-			return false;
+	fn check_quit(&mut self, ev: &Event) -> Result<bool> {
+		if self.any_popup_visible()
+			&& !self.reset.is_confirming_quit()
+		{
+			return Ok(false);
 		}
 		if let Event::Key(e) = ev {
 			if key_match(e, self.key_config.keys.quit) {
-				self.do_quit = QuitState::Close;
-				return true;
+				self.request_quit()?;
+				return Ok(true);
 			}
 		}
-		false
+		Ok(false)
 	}
 
-	fn check_hard_exit(&mut self, ev: &Event) -> bool {
+	fn check_hard_exit(&mut self, ev: &Event) -> Result<bool> {
 		if let Event::Key(e) = ev {
 			if key_match(e, self.key_config.keys.exit) {
-				self.do_quit = QuitState::Close;
-				return true;
+				self.request_quit()?;
+				return Ok(true);
 			}
 		}
-		false
+		Ok(false)
+	}
+
+	/// quits unless git operations that change the repo or talk to
+	/// a remote are still running, those need a confirmation (or a
+	/// second quit) first
+	fn request_quit(&mut self) -> Result<()> {
+		let pending = self.pending_operations();
+
+		if pending.is_empty() || self.reset.is_confirming_quit() {
+			self.quit()?;
+		} else {
+			self.reset.open(Action::QuitPending(pending))?;
+		}
+
+		Ok(())
+	}
+
+	/// a rebase waiting for its todo list gets aborted first
+	fn quit(&mut self) -> Result<()> {
+		self.rebase_todo_popup.abort()?;
+		self.do_quit = QuitState::Close;
+
+		Ok(())
+	}
+
+	/// names of running operations that are unsafe to interrupt
+	fn pending_operations(&self) -> Vec<&'static str> {
+		[
+			("push", self.push_popup.any_work_pending()),
+			("push tags", self.push_tags_popup.any_work_pending()),
+			("pull", self.pull_popup.any_work_pending()),
+			(
+				"fetch pull request",
+				self.pull_request_popup.any_work_pending(),
+			),
+			("fetch", self.fetch_popup.any_work_pending()),
+			(
+				"external command",
+				self.external_command_popup.any_work_pending(),
+			),
+			("submodule init", self.submodule_popup.any_work_pending()),
+			(
+				"interactive rebase",
+				self.rebase_todo_popup.any_work_pending(),
+			),
+		]
+		.into_iter()
+		.filter_map(|(name, pending)| pending.then_some(name))
+		.collect()
 	}
 
 	fn get_tabs(&mut self) -> Vec<&mut dyn Component> {
@@ -825,6 +1102,12 @@ impl App {
 		if flags.contains(NeedsUpdate::BRANCHES) {
 			self.select_branch_popup.update_branches()?;
 		}
+		if flags.contains(NeedsUpdate::TAGS) {
+			self.revlog.trigger_tags_update()?;
+		}
+		if flags.contains(NeedsUpdate::NOTES) {
+			self.revlog.update_notes();
+		}
 
 		Ok(())
 	}
@@ -903,16 +1186,32 @@ impl App {
 			}
 			InternalEvent::Update(u) => flags.insert(u),
 			InternalEvent::OpenCommit => self.commit.show()?,
+			InternalEvent::OpenCommitMarked(paths) => {
+				self.commit.open_marked(paths)?;
+			}
 			InternalEvent::CommitWithExternalEditor => {
 				self.input.set_polling(false);
 				self.external_editor_request =
 					ExternalEditorRequest::EditorToCommit;
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
-			InternalEvent::RebaseInteractiveWithEditor(id) => {
+			InternalEvent::RebaseInteractive(base) => {
+				let editor = resolve_editor(
+					&self.repo.borrow(),
+					self.options.borrow().editor(),
+				);
+				if let Err(e) =
+					self.rebase_todo_popup.open(base, &editor)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("interactive rebase failed:\n{e}"),
+					));
+				}
+			}
+			InternalEvent::RebaseTodoDone(edit) => {
 				self.input.set_polling(false);
 				self.external_editor_request =
-					ExternalEditorRequest::RebaseInteractive(id);
+					ExternalEditorRequest::RebaseTodo(edit);
 				flags.insert(NeedsUpdate::ALL);
 			}
 			InternalEvent::RebaseInteractiveContinue => {
@@ -932,6 +1231,9 @@ impl App {
 			InternalEvent::RewordCommit(id) => {
 				self.commit.open(Some(id))?;
 			}
+			InternalEvent::AmendCommit => {
+				self.commit.open_amend()?;
+			}
 			InternalEvent::PopupStashing(opts) => {
 				self.stashmsg_popup.options(opts);
 				self.stashmsg_popup.show()?;
@@ -939,9 +1241,15 @@ impl App {
 			InternalEvent::TagCommit(id) => {
 				self.tag_commit_popup.open(id)?;
 			}
+			InternalEvent::EditCommitNote(id) => {
+				self.commit_note_popup.open(id)?;
+			}
 
-			InternalEvent::CreateBranch => {
-				self.create_branch_popup.open()?;
+			InternalEvent::CreateBranch(base) => {
+				self.create_branch_popup.open(base)?;
+			}
+			InternalEvent::OpenStagePattern(stage) => {
+				self.stage_pattern_popup.open(stage)?;
 			}
 			InternalEvent::RenameBranch(branch_ref, cur_name) => {
 				self.rename_branch_popup
@@ -956,6 +1264,28 @@ impl App {
 			InternalEvent::ViewSubmodules => {
 				self.submodule_popup.open()?;
 			}
+			InternalEvent::ViewRemotes => {
+				self.remotes_popup.open()?;
+			}
+			InternalEvent::ViewWorktrees => {
+				self.worktrees_popup.open()?;
+			}
+			InternalEvent::ViewReflog(reference) => {
+				self.reflog_popup.open(reference)?;
+			}
+			InternalEvent::CopyPermalink(commit, path, line) => {
+				if let Err(e) =
+					self.copy_permalink(commit, &path, line)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!(
+							"{}\n{}",
+							strings::POPUP_FAIL_COPY,
+							e
+						),
+					));
+				}
+			}
 			InternalEvent::Tags => {
 				self.tags_popup.open()?;
 			}
@@ -976,13 +1306,52 @@ impl App {
 				self.external_editor_request =
 					if let Some(path) = path {
 						ExternalEditorRequest::ExternalEditorWithPath(
-							path,
+							path, None,
 						)
 					} else {
 						ExternalEditorRequest::ShowEditor
 					};
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::OpenExternalEditorAt(path, line) => {
+				self.input.set_polling(false);
+				self.external_editor_popup.show()?;
+				self.external_editor_request =
+					ExternalEditorRequest::ExternalEditorWithPath(
+						path,
+						Some(line),
+					);
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::RevealFile(path) => {
+				match self.work_dir_path(&path) {
+					Ok(path) => {
+						self.external_command_popup
+							.reveal_file(&path);
+					}
+					Err(e) => {
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							e.to_string(),
+						));
+					}
+				}
+			}
+			InternalEvent::CopyAbsolutePath(path) => {
+				if let Err(e) =
+					self.work_dir_path(&path).and_then(|path| {
+						crate::clipboard::copy_string(
+							&path.to_string_lossy(),
+						)
+					}) {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!(
+							"{}\n{}",
+							strings::POPUP_FAIL_COPY,
+							e
+						),
+					));
+				}
+			}
 			InternalEvent::Push(branch, push_type, force, delete) => {
 				self.push_popup
 					.push(branch, push_type, force, delete)?;
@@ -996,6 +1365,11 @@ impl App {
 				}
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::OpenFetchPullRequest => {
+				self.pull_request_popup.open()?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::FetchRemotes => {
 				if let Err(error) = self.fetch_popup.fetch() {
 					self.queue.push(InternalEvent::ShowErrorMsg(
@@ -1021,13 +1395,27 @@ impl App {
 				flags
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
+			InternalEvent::OpenGrep(commit) => {
+				self.grep_popup.open(commit)?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::OptionSwitched(o) => {
 				match o {
 					AppOption::StatusShowUntracked => {
 						self.status_tab.update()?;
 					}
+					// picked up by the main loop via `notify_watcher`,
+					// autostash is read when a rebase starts, word
+					// highlighting and line numbers when the diff is
+					// drawn
+					AppOption::NotifyWatcher
+					| AppOption::RebaseAutostash
+					| AppOption::DiffWordHighlight
+					| AppOption::DiffLineNumbersAbsolute => (),
 					AppOption::DiffContextLines
 					| AppOption::DiffIgnoreWhitespaces
+					| AppOption::DiffIgnoreBlankLines
 					| AppOption::DiffInterhunkLines => {
 						self.status_tab.update_diff()?;
 					}
@@ -1035,6 +1423,12 @@ impl App {
 
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::ToggleDiffWhitespace(path) => {
+				self.options
+					.borrow_mut()
+					.diff_toggle_whitespace_for(&path);
+				flags.insert(NeedsUpdate::DIFF | NeedsUpdate::COMMANDS);
+			}
 			InternalEvent::FileFinderChanged(file) => {
 				self.files_tab.file_finder_update(&file);
 				self.revision_files_popup.file_finder_update(&file);
@@ -1065,13 +1459,39 @@ impl App {
 					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
 			}
 			InternalEvent::OpenRepo { path } => {
-				let submodule_repo_path = RepoPath::Path(
+				let submodule_dir =
 					Path::new(&repo_work_dir(&self.repo.borrow())?)
-						.join(path),
-				);
-				//TODO: validate this is a valid repo first, so we can show proper error otherwise
+						.join(&path);
+				let submodule_repo_path =
+					RepoPath::Path(submodule_dir.clone());
+
+				// a folder that is no repo of its own would open the
+				// parent again
+				if repo_work_dir(&submodule_repo_path)
+					.ok()
+					.and_then(|wd| Path::new(&wd).canonicalize().ok())
+					== submodule_dir.canonicalize().ok()
+				{
+					self.do_quit = QuitState::OpenSubmodule(
+						submodule_repo_path,
+						SubmoduleBreadcrumb {
+							parent: self.repo.borrow().clone(),
+							submodule: path,
+						},
+					);
+				} else {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						strings::submodule_not_a_repo(&path),
+					));
+				}
+			}
+			InternalEvent::OpenParentRepo { path } => {
+				self.do_quit =
+					QuitState::OpenParentRepo(RepoPath::Path(path));
+			}
+			InternalEvent::OpenWorktree { path } => {
 				self.do_quit =
-					QuitState::OpenSubmodule(submodule_repo_path);
+					QuitState::OpenWorktree(RepoPath::Path(path));
 			}
 			InternalEvent::OpenResetPopup(id) => {
 				self.reset_popup.open(id)?;
@@ -1081,6 +1501,44 @@ impl App {
 		Ok(flags)
 	}
 
+	/// copies the web link to `line` of `path` at `commit` (or
+	/// `HEAD`), just a note if there is no remote to link to
+	fn copy_permalink(
+		&self,
+		commit: Option<CommitId>,
+		path: &str,
+		line: usize,
+	) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		if let Some(remote) = sync::get_default_remote_web_url(&repo)?
+		{
+			let commit = match commit {
+				Some(commit) => commit,
+				None => sync::get_head(&repo)?,
+			};
+
+			crate::clipboard::copy_string(&permalink(
+				self.options.borrow().permalink_template(),
+				&remote,
+				&commit.to_string(),
+				path,
+				line,
+			))?;
+		} else {
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::PERMALINK_NO_REMOTE.to_string(),
+			));
+		}
+
+		Ok(())
+	}
+
+	/// absolute path of `path` relative to the work dir
+	fn work_dir_path(&self, path: &str) -> Result<PathBuf> {
+		Ok(Path::new(&repo_work_dir(&self.repo.borrow())?).join(path))
+	}
+
 	#[allow(clippy::too_many_lines)]
 	fn process_confirmed_action(
 		&mut self,
@@ -1117,6 +1575,35 @@ impl App {
 				)?;
 				flags.insert(NeedsUpdate::ALL);
 			}
+			Action::ResetHard(id) => {
+				sync::reset_repo(
+					&self.repo.borrow(),
+					id,
+					sync::ResetType::Hard,
+				)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::RestoreFiles(tracked, untracked) => {
+				sync::restore_to_head(&self.repo.borrow(), &tracked)?;
+				sync::delete_untracked(
+					&self.repo.borrow(),
+					&untracked,
+				)?;
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::restored_files(
+						tracked.len(),
+						untracked.len(),
+					),
+				));
+				flags.insert(NeedsUpdate::ALL);
+			}
+			Action::DeleteUntracked(paths) => {
+				sync::delete_untracked(&self.repo.borrow(), &paths)?;
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::deleted_untracked_files(paths.len()),
+				));
+				flags.insert(NeedsUpdate::ALL);
+			}
 			Action::DeleteLocalBranch(branch_ref) => {
 				if let Err(e) = sync::delete_branch(
 					&self.repo.borrow(),
@@ -1167,7 +1654,8 @@ impl App {
 						Action::DeleteRemoteTag(tag_name, remote),
 					));
 
-					flags.insert(NeedsUpdate::ALL);
+					flags
+						.insert(NeedsUpdate::ALL | NeedsUpdate::TAGS);
 					self.tags_popup.update_tags()?;
 				}
 			}
@@ -1199,6 +1687,45 @@ impl App {
 				self.status_tab.abort_rebase();
 				flags.insert(NeedsUpdate::ALL);
 			}
+			Action::CheckoutPullRequest(branch) => {
+				let cmd = self
+					.options
+					.borrow()
+					.git_extern_commands()
+					.checkout_base
+					.clone();
+				let res = match cmd {
+					Some(cmd) => checkout_branch_cmd(cmd, &branch),
+					None => sync::checkout_branch(
+						&self.repo.borrow(),
+						&format!("refs/heads/{branch}"),
+					),
+				};
+				if let Err(e) = res {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("Checkout failed\n{e}"),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL | NeedsUpdate::BRANCHES);
+			}
+			Action::InitSubmodule(path) => {
+				self.submodule_popup.init_submodule(path);
+			}
+			Action::QuitPending(_) => {
+				self.quit()?;
+			}
+			Action::DeleteRemote(name) => {
+				if let Err(e) =
+					sync::delete_remote(&self.repo.borrow(), &name)
+				{
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("delete remote failed:\n{e}"),
+					));
+				}
+				self.remotes_popup.update_remotes()?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::BRANCHES);
+			}
 		};
 
 		Ok(())
@@ -1252,6 +1779,18 @@ impl App {
 			.order(order::NAV),
 		);
 
+		res.push(
+			CommandInfo::new(
+				strings::commands::toggle_status_header(
+					&self.key_config,
+					self.status_header.is_visible(),
+				),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+
 		res.push(
 			CommandInfo::new(
 				strings::commands::quit(&self.key_config),
@@ -1321,13 +1860,19 @@ impl App {
 			table_area,
 		);
 
-		let title_str = if let Ok(head) =
+		let mut title_str =
 			asyncgit::sync::utils::get_head_name(&self.repo.borrow())
-		{
-			format!("{}|{}", head, self.repo_path_text)
-		} else {
-			self.repo_path_text.clone()
-		};
+				.map_or_else(
+					|_| self.repo_path_text.clone(),
+					|head| {
+						format!("{}|{}", head, self.repo_path_text)
+					},
+				);
+		// at the end, the start gets trimmed if space is short
+		if let Some(progress) = self.job_progress {
+			title_str =
+				format!("{title_str} [{}%]", progress.percent());
+		}
 
 		f.render_widget(
 			Paragraph::new(Spans::from(vec![Span::styled(
@@ -1,9 +1,9 @@
 use crate::bug_report;
 use anyhow::{anyhow, Result};
-use asyncgit::sync::RepoPath;
+use asyncgit::sync::{extern_git::RebaseTransform, RepoPath};
 use clap::{
 	crate_authors, crate_description, crate_name, crate_version, Arg,
-	Command as ClapApp,
+	ArgAction, ArgMatches, Command as ClapApp,
 };
 use simplelog::{Config, LevelFilter, WriteLogger};
 use std::{
@@ -16,6 +16,29 @@ pub struct CliArgs {
 	pub theme: PathBuf,
 	pub repo_path: RepoPath,
 	pub notify_watcher: bool,
+	pub rebase: Option<RebaseArgs>,
+	pub json: Option<JsonQuery>,
+}
+
+/// `gitui rebase`: rebase onto `base` without the UI
+pub struct RebaseArgs {
+	pub base: String,
+	pub transforms: Vec<(String, RebaseTransform)>,
+}
+
+/// `gitui json`: print `query` as JSON without the UI
+#[derive(Debug, PartialEq, Eq)]
+pub enum JsonQuery {
+	Branches {
+		remote: bool,
+	},
+	Log {
+		max_count: usize,
+		path: Option<String>,
+		author: Option<String>,
+		grep: Option<String>,
+	},
+	Status,
 }
 
 pub fn process_cmdline() -> Result<CliArgs> {
@@ -57,13 +80,91 @@ pub fn process_cmdline() -> Result<CliArgs> {
 	let notify_watcher: bool =
 		*arg_matches.get_one("watcher").unwrap_or(&false);
 
+	let rebase = arg_matches
+		.subcommand_matches("rebase")
+		.map(rebase_args)
+		.transpose()?;
+
+	let json =
+		arg_matches.subcommand_matches("json").and_then(json_query);
+
 	Ok(CliArgs {
 		theme,
 		repo_path,
 		notify_watcher,
+		rebase,
+		json,
+	})
+}
+
+fn json_query(matches: &ArgMatches) -> Option<JsonQuery> {
+	let query = match matches.subcommand()? {
+		("branches", m) => JsonQuery::Branches {
+			remote: m.get_flag("remote"),
+		},
+		("log", m) => JsonQuery::Log {
+			max_count: m
+				.get_one::<usize>("max-count")
+				.copied()
+				.unwrap_or(JSON_LOG_MAX_COUNT),
+			path: m.get_one::<String>("path").cloned(),
+			author: m.get_one::<String>("author").cloned(),
+			grep: m.get_one::<String>("grep").cloned(),
+		},
+		("status", _) => JsonQuery::Status,
+		_ => return None,
+	};
+
+	Some(query)
+}
+
+fn rebase_args(matches: &ArgMatches) -> Result<RebaseArgs> {
+	let revs = |id: &str| {
+		matches
+			.get_many::<String>(id)
+			.into_iter()
+			.flatten()
+			.cloned()
+			.collect::<Vec<_>>()
+	};
+
+	let mut transforms = Vec::new();
+	for rev in revs("drop") {
+		transforms.push((rev, RebaseTransform::Drop));
+	}
+	for rev in revs("fixup") {
+		transforms.push((rev, RebaseTransform::Fixup));
+	}
+	for reword in revs("reword") {
+		transforms.push(parse_reword(&reword)?);
+	}
+
+	Ok(RebaseArgs {
+		base: matches
+			.get_one::<String>("base")
+			.cloned()
+			.unwrap_or_default(),
+		transforms,
 	})
 }
 
+/// splits `COMMIT=MESSAGE`
+fn parse_reword(arg: &str) -> Result<(String, RebaseTransform)> {
+	arg.split_once('=')
+		.filter(|(rev, msg)| !rev.is_empty() && !msg.is_empty())
+		.map(|(rev, msg)| {
+			(
+				rev.to_string(),
+				RebaseTransform::Reword(msg.to_string()),
+			)
+		})
+		.ok_or_else(|| {
+			anyhow!("--reword expects COMMIT=MESSAGE, got '{arg}'")
+		})
+}
+
+const JSON_LOG_MAX_COUNT: usize = 100;
+
 fn app() -> ClapApp {
 	ClapApp::new(crate_name!())
 		.author(crate_authors!())
@@ -123,6 +224,88 @@ fn app() -> ClapApp {
 				.env("GIT_WORK_TREE")
 				.num_args(1),
 		)
+		.subcommand(
+			ClapApp::new("rebase")
+				.about("Run an interactive rebase without the UI, dropping, fixing up or rewording the given commits, then exit")
+				.arg(
+					Arg::new("base")
+						.help("Rebase the commits after BASE, like `git rebase -i BASE`")
+						.value_name("BASE")
+						.required(true),
+				)
+				.arg(
+					Arg::new("drop")
+						.help("Drop COMMIT")
+						.long("drop")
+						.value_name("COMMIT")
+						.action(ArgAction::Append),
+				)
+				.arg(
+					Arg::new("fixup")
+						.help("Squash COMMIT into the one before it, keeping that message")
+						.long("fixup")
+						.value_name("COMMIT")
+						.action(ArgAction::Append),
+				)
+				.arg(
+					Arg::new("reword")
+						.help("Replace the message of COMMIT")
+						.long("reword")
+						.value_name("COMMIT=MESSAGE")
+						.action(ArgAction::Append),
+				),
+		)
+		.subcommand(json_subcommand())
+}
+
+fn json_subcommand() -> ClapApp {
+	ClapApp::new("json")
+		.about("Print branches, log or status as JSON without the UI, then exit")
+		.subcommand_required(true)
+		.subcommand(
+			ClapApp::new("branches")
+				.about("List local branches")
+				.arg(
+					Arg::new("remote")
+						.help("List remote branches instead")
+						.long("remote")
+						.action(ArgAction::SetTrue),
+				),
+		)
+		.subcommand(
+			ClapApp::new("log")
+				.about("List commits reachable from HEAD, newest first")
+				.arg(
+					Arg::new("max-count")
+						.help("Stop after N matching commits (defaults to 100, 0 lists all)")
+						.short('n')
+						.long("max-count")
+						.value_name("N")
+						.value_parser(clap::value_parser!(usize)),
+				)
+				.arg(
+					Arg::new("path")
+						.help("Only commits touching PATH")
+						.long("path")
+						.value_name("PATH"),
+				)
+				.arg(
+					Arg::new("author")
+						.help("Only commits whose author contains TEXT, ignoring case")
+						.long("author")
+						.value_name("TEXT"),
+				)
+				.arg(
+					Arg::new("grep")
+						.help("Only commits whose message contains TEXT, ignoring case")
+						.long("grep")
+						.value_name("TEXT"),
+				),
+		)
+		.subcommand(
+			ClapApp::new("status")
+				.about("List changed files of the working dir and the stage"),
+		)
 }
 
 fn setup_logging() -> Result<()> {
@@ -166,3 +349,73 @@ pub fn get_app_config_path() -> Result<PathBuf> {
 fn verify_app() {
 	app().debug_assert();
 }
+
+#[test]
+fn test_parse_reword() {
+	assert_eq!(
+		parse_reword("abc123=new: message").unwrap(),
+		(
+			"abc123".to_string(),
+			RebaseTransform::Reword("new: message".to_string())
+		)
+	);
+	assert!(parse_reword("abc123").is_err());
+	assert!(parse_reword("=message").is_err());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{app, json_query, JsonQuery, JSON_LOG_MAX_COUNT};
+
+	fn json_args(args: &[&str]) -> clap::error::Result<JsonQuery> {
+		let matches = app().try_get_matches_from(
+			["gitui", "json"].iter().chain(args),
+		)?;
+		Ok(matches
+			.subcommand_matches("json")
+			.and_then(json_query)
+			.unwrap())
+	}
+
+	#[test]
+	fn test_json_args() {
+		assert_eq!(
+			json_args(&["log"]).unwrap(),
+			JsonQuery::Log {
+				max_count: JSON_LOG_MAX_COUNT,
+				path: None,
+				author: None,
+				grep: None,
+			}
+		);
+		assert_eq!(
+			json_args(&[
+				"log", "-n", "0", "--path", "src", "--author",
+				"alice", "--grep", "fix"
+			])
+			.unwrap(),
+			JsonQuery::Log {
+				max_count: 0,
+				path: Some(String::from("src")),
+				author: Some(String::from("alice")),
+				grep: Some(String::from("fix")),
+			}
+		);
+		assert_eq!(
+			json_args(&["branches"]).unwrap(),
+			JsonQuery::Branches { remote: false }
+		);
+		assert_eq!(
+			json_args(&["branches", "--remote"]).unwrap(),
+			JsonQuery::Branches { remote: true }
+		);
+		assert_eq!(
+			json_args(&["status"]).unwrap(),
+			JsonQuery::Status
+		);
+
+		assert!(json_args(&[]).is_err());
+		assert!(json_args(&["log", "-n", "many"]).is_err());
+		assert!(json_args(&["tags"]).is_err());
+	}
+}
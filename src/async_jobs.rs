@@ -4,10 +4,13 @@ use crate::AsyncAppNotification;
 
 pub type BoxFeedback = Box<dyn AsyncJobFeedback + Send + Sync>;
 pub type BoxJob = Box<dyn AsyncDynJob + Send + Sync>;
-pub type JobFeedbackSender = mpsc::Sender<BoxFeedback>;
 pub type JobFeedbackReceiver = mpsc::Receiver<BoxFeedback>;
 pub type JobReceiver = mpsc::Receiver<BoxJob>;
 pub type JobSender = mpsc::Sender<BoxJob>;
+
+/// a job either returns its final feedback from `run` or
+/// additionally reports intermediate feedback (like progress)
+/// through the `sender` while running
 pub trait AsyncDynJob {
 	fn run(
 		&mut self,
@@ -20,6 +23,58 @@ pub trait AsyncJobFeedback {
 	fn visit(&mut self, app: &mut crate::app::App);
 }
 
+/// how far the running job got
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JobProgress {
+	pub done: usize,
+	pub total: usize,
+}
+
+impl JobProgress {
+	///
+	pub fn percent(self) -> u8 {
+		if self.total == 0 {
+			return 100;
+		}
+
+		u8::try_from(self.done.min(self.total) * 100 / self.total)
+			.unwrap_or(100)
+	}
+}
+
+/// `None` once the job finished
+struct JobProgressFeedback(Option<JobProgress>);
+
+impl AsyncJobFeedback for JobProgressFeedback {
+	fn visit(&mut self, app: &mut crate::app::App) {
+		app.set_job_progress(self.0);
+	}
+}
+
+/// hands feedback of a job to the app and wakes it up to
+/// process it
+#[derive(Clone)]
+pub struct JobFeedbackSender {
+	feedback: mpsc::Sender<BoxFeedback>,
+	tx_app: crossbeam_channel::Sender<AsyncAppNotification>,
+}
+
+impl JobFeedbackSender {
+	/// returns false if the app is gone
+	pub fn send(&self, feedback: BoxFeedback) -> bool {
+		self.feedback.send(feedback).is_ok()
+			&& self.tx_app.send(AsyncAppNotification::Notify).is_ok()
+	}
+
+	/// reports `done` of `total` steps of the running job
+	pub fn progress(&self, done: usize, total: usize) -> bool {
+		self.send(Box::new(JobProgressFeedback(Some(JobProgress {
+			done,
+			total,
+		}))))
+	}
+}
+
 pub struct AsyncStopJob {}
 impl AsyncDynJob for AsyncStopJob {
 	fn run(
@@ -45,13 +100,18 @@ impl AsyncJobList {
 		let (send_job_feeback, receive_job_feedback) =
 			mpsc::channel();
 		let t = std::thread::spawn(move || {
-			l.run_loop(tx_app, send_job_feeback, receive_job);
+			l.run_loop(
+				JobFeedbackSender {
+					feedback: send_job_feeback,
+					tx_app,
+				},
+				receive_job,
+			);
 		});
 		(t, send_job, receive_job_feedback)
 	}
 	pub fn run_loop(
 		&mut self,
-		tx_app: crossbeam_channel::Sender<AsyncAppNotification>,
 		sender: JobFeedbackSender,
 		receiver: JobReceiver,
 	) {
@@ -59,13 +119,12 @@ impl AsyncJobList {
 			if let Ok(mut j) = receiver.recv() {
 				let j = j.as_mut();
 				if let Some(r) = j.run(sender.clone()) {
-					if let Err(_) = sender.send(r) {
+					if !sender.send(r) {
 						break;
 					}
 				}
-				if let Err(_) =
-					tx_app.send(AsyncAppNotification::Notify)
-				{
+				// also wakes up the app for jobs without feedback
+				if !sender.send(Box::new(JobProgressFeedback(None))) {
 					break;
 				}
 				if j.should_stop() {
@@ -78,3 +137,20 @@ impl AsyncJobList {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::JobProgress;
+
+	#[test]
+	fn test_progress_percent() {
+		let percent =
+			|done, total| JobProgress { done, total }.percent();
+
+		assert_eq!(percent(0, 4), 0);
+		assert_eq!(percent(1, 3), 33);
+		assert_eq!(percent(4, 4), 100);
+		assert_eq!(percent(5, 4), 100);
+		assert_eq!(percent(0, 0), 100);
+	}
+}
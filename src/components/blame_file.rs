@@ -4,8 +4,14 @@ use super::{
 	InspectCommitOpen,
 };
 use crate::{
-	components::{utils::string_width_align, ScrollType},
+	components::{
+		utils::{
+			goto_line::GotoLine, number_of_digits, string_width_align,
+		},
+		ScrollType,
+	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{InternalEvent, Queue, StackablePopupOpen},
 	string_utils::tabs_to_spaces,
 	strings,
@@ -13,26 +19,28 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{filter_by_path, BlameHunk, CommitId, FileBlame, RepoPathRef, RepoPath, LogWalker},
-	AsyncBlame, AsyncGitNotification, BlameParams,
+	asyncjob::AsyncSingleJob,
+	sync::{BlameHunk, CommitId, FileBlame, RepoPath, RepoPathRef},
+	AsyncBlame, AsyncCommitInfoJob, AsyncGitNotification,
+	BlameParams,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use crossterm::event::KeyCode;
 use ratatui::{
 	backend::Backend,
-	layout::{Constraint, Rect},
+	layout::{Constraint, Direction, Layout, Rect},
 	symbols::line::VERTICAL,
 	text::{Span, Spans},
-	widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+	widgets::{
+		Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState,
+	},
 	Frame,
 };
-use std::convert::TryInto;
+use std::{collections::HashMap, convert::TryInto};
 
 static NO_COMMIT_ID: &str = "0000000";
 static NO_AUTHOR: &str = "<no author>";
-static MIN_AUTHOR_WIDTH: usize = 3;
-static MAX_AUTHOR_WIDTH: usize = 20;
 
 #[derive(Clone, Debug)]
 pub struct BlameFileOpen {
@@ -58,6 +66,8 @@ struct SearchState
 	pub str: Option<String>,
 	pub start: LinePos,
 	pub found: Option<LinePos>,
+	/// the search being typed looks upwards from `start`
+	pub backward: bool,
 }
 
 impl SearchState {
@@ -66,6 +76,7 @@ impl SearchState {
 			str: None,
 			start: LinePos{line:0, offset:0},
 			found: None,
+			backward: false,
 		}
 	}
 
@@ -92,9 +103,13 @@ pub struct BlameFileComponent {
 	current_height: std::cell::Cell<usize>,
 	previous_request_stack: Vec<(BlameFileOpen, TableState)>,
 	repo: RepoPath,
-	temp_buf: Option<String>,
+	goto_line: GotoLine,
 	search: SearchState,
-	state: BlameState 
+	state: BlameState,
+	// message summaries of the commits selected lines belong to
+	summaries: HashMap<CommitId, String>,
+	git_commit_info: AsyncSingleJob<AsyncCommitInfoJob>,
+	options: SharedOptions,
 }
 impl DrawableComponent for BlameFileComponent {
 	fn draw<B: Backend>(
@@ -105,8 +120,23 @@ impl DrawableComponent for BlameFileComponent {
 		if self.is_visible() {
 			let title = self.get_title();
 
+			let summary = self.get_selected_summary();
+			let (area, summary_area) = if summary.is_some() {
+				let chunks = Layout::default()
+					.direction(Direction::Vertical)
+					.constraints(
+						[Constraint::Min(3), Constraint::Length(3)]
+							.as_ref(),
+					)
+					.split(area);
+				(chunks[0], Some(chunks[1]))
+			} else {
+				(area, None)
+			};
+
 			let rows = self.get_rows(area.width.into());
-			let author_width = get_author_width(area.width.into());
+			let author_width =
+				self.get_author_width(area.width.into());
 			let constraints = [
 				// commit id
 				Constraint::Length(7),
@@ -171,6 +201,31 @@ impl DrawableComponent for BlameFileComponent {
 
 			self.table_state.set(table_state);
 			self.current_height.set(area.height.into());
+
+			if let (Some(summary), Some(summary_area)) =
+				(summary, summary_area)
+			{
+				f.render_widget(Clear, summary_area);
+				f.render_widget(
+					Paragraph::new(Spans::from(vec![
+						Span::styled(
+							summary.0,
+							self.theme.commit_hash(false),
+						),
+						Span::raw(" "),
+						Span::styled(
+							summary.1,
+							self.theme.text(true, false),
+						),
+					]))
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.border_style(self.theme.block(true)),
+					),
+					summary_area,
+				);
+			}
 		}
 
 		Ok(())
@@ -246,10 +301,31 @@ impl Component for BlameFileComponent {
 				CommandInfo::new(
 					strings::commands::jump_to_line(
 						&self.key_config,
-						self.temp_buf.as_ref().map(|s|s.as_str()).unwrap_or("")
+						self.goto_line.as_str(),
+					),
+					true,
+					!self.goto_line.is_empty() && is_normal,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::copy_permalink(
+						&self.key_config,
+					),
+					true,
+					self.file_blame.is_some() && is_normal,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_toggle_author(
+						&self.key_config,
+						self.options.borrow().blame_author(),
 					),
 					true,
-					self.temp_buf.is_some() && is_normal,
+					self.file_blame.is_some() && is_normal,
 				)
 				.order(1),
 			);
@@ -263,7 +339,17 @@ impl Component for BlameFileComponent {
 			);
 			out.push(
 				CommandInfo::new(
-					strings::commands::cancel_search_init(&self.key_config),
+					strings::commands::start_search_backward(
+						&self.key_config,
+					),
+					true,
+					is_normal,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::cancel_search_init(&self.key_config, self.search.backward),
 					true,
 					is_searching,
 				)
@@ -273,7 +359,7 @@ impl Component for BlameFileComponent {
 				CommandInfo::new(
 					strings::commands::search_for_text(
 						&self.key_config,
-						self.search.str.as_ref().map(|s|s.as_str()).unwrap_or("")
+						self.search.str.as_deref().unwrap_or("")
 					),
 					true,
 					self.search.str.is_some() && is_searching,
@@ -284,7 +370,8 @@ impl Component for BlameFileComponent {
 				CommandInfo::new(
 					strings::commands::search_for_text_next(
 						&self.key_config,
-						self.search.str.as_ref().map(|s|s.as_str()).unwrap_or("")
+						self.search.str.as_deref().unwrap_or(""),
+						"",
 					),
 					true,
 					self.search.str.is_some() && is_normal,
@@ -295,7 +382,8 @@ impl Component for BlameFileComponent {
 				CommandInfo::new(
 					strings::commands::search_for_text_prev(
 						&self.key_config,
-						self.search.str.as_ref().map(|s|s.as_str()).unwrap_or("")
+						self.search.str.as_deref().unwrap_or(""),
+						"",
 					),
 					true,
 					self.search.str.is_some() && is_normal,
@@ -313,9 +401,11 @@ impl Component for BlameFileComponent {
 	) -> Result<EventState> {
 		if self.is_visible() {
 			if let Event::Key(key) = event {
-				let temp_buf = self.temp_buf.take();
+				let mut goto_line = std::mem::take(&mut self.goto_line);
 				if self.state == BlameState::SearchEditing{
-					return self.event_search_edit_state(key);
+					let state = self.event_search_edit_state(key)?;
+					self.update_summary();
+					return Ok(state);
 				}
 
 				if key_match(key, self.key_config.keys.exit_popup) {
@@ -344,16 +434,13 @@ impl Component for BlameFileComponent {
 					self.key_config.keys.end,
 				) {
 					let mut default_jump_to_end = true;
-					if let Some(s) = temp_buf {
-						if let Ok(line) = s.parse::<usize>() {
-							//attempt to parse line number
-							let mut table_state = self.table_state.take();
-							table_state.select(Some(line));
-							self.table_state.set(table_state);
-							default_jump_to_end = false;
-							if self.search.is_valid() {
-								self.search.start = LinePos{line, offset: 0};
-							}
+					if let Some(line) = goto_line.take() {
+						let mut table_state = self.table_state.take();
+						table_state.select(Some(line));
+						self.table_state.set(table_state);
+						default_jump_to_end = false;
+						if self.search.is_valid() {
+							self.search.start = LinePos{line, offset: 0};
 						}
 					}
 
@@ -370,7 +457,10 @@ impl Component for BlameFileComponent {
 					self.move_selection(ScrollType::PageUp);
 				} else if key_match(key, self.key_config.keys.start_search_forward_init)
 				{
-					self.enter_search_mode();
+					self.enter_search_mode(false);
+				} else if key_match(key, self.key_config.keys.start_search_backward_init)
+				{
+					self.enter_search_mode(true);
 				} else if key_match(key, self.key_config.keys.search_next)
 				{
 					self.search_next();
@@ -380,20 +470,17 @@ impl Component for BlameFileComponent {
 				} else if key_match(key, self.key_config.keys.generic_push)
 				{
 					let commit = self.selected_commit();
-					if commit.is_some() && self.open_request.is_some() {
-						let f_path = self.open_request.as_ref().unwrap().file_path.clone();
-						let commits_only_for_file = Some(filter_by_path(f_path.clone(), true));
-						if let Ok(commits) = LogWalker::new_with_start_by_path(&self.repo, commit.as_ref(), |w|{
-							w.filter_with_limit(commits_only_for_file, 2)
-						}) {
-							if commits.len() == 2 {
-								self.push_request(BlameFileOpen{
-									file_path:f_path, 
-									commit_id: Some(commits[1]), 
-									selection: self.get_selection()}
-									);
-							}
-						}
+					if let (Some(commit), Some(request)) = (commit, self.open_request.as_ref()) {
+						// the blame job looks up the revision before the
+						// selected commit, following a rename on the way
+						self.push_request(
+							BlameFileOpen {
+								file_path: request.file_path.clone(),
+								commit_id: Some(commit),
+								selection: self.get_selection(),
+							},
+							true,
+						);
 					}
 				} else if key_match(key, self.key_config.keys.generic_pop)
 				{
@@ -426,16 +513,23 @@ impl Component for BlameFileComponent {
 							),
 						));
 					}
+				} else if key_match(
+					key,
+					self.key_config.keys.copy_permalink,
+				) {
+					self.copy_permalink();
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_toggle_author,
+				) {
+					self.options.borrow_mut().cycle_blame_author();
 				} else if let KeyCode::Char(c) = key.code {
-					if c == 'G' {
-					}else if c >= '0' && c <='9' {
-						self.temp_buf = match temp_buf {
-							None => Some(format!("{}", c)),
-							Some(mut s) => {s.push(c); Some(s)}
-						};
+					if goto_line.push(c) {
+						self.goto_line = goto_line;
 					}
 				}
 
+				self.update_summary();
 				return Ok(EventState::Consumed);
 			}
 		}
@@ -463,6 +557,7 @@ impl BlameFileComponent {
 		title: &str,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			title: String::from(title),
@@ -481,9 +576,12 @@ impl BlameFileComponent {
 			current_height: std::cell::Cell::new(0),
 			previous_request_stack: Vec::new(),
 			repo: repo.borrow().clone(),
-			temp_buf: None,
+			goto_line: GotoLine::default(),
 			search: SearchState::new(),
-			state: BlameState::Normal
+			state: BlameState::Normal,
+			summaries: HashMap::new(),
+			git_commit_info: AsyncSingleJob::new(sender.clone()),
+			options,
 		}
 	}
 
@@ -500,6 +598,8 @@ impl BlameFileComponent {
 				));
 			}
 		} else {
+			// a blame opened later starts a new history
+			self.previous_request_stack.clear();
 			self.queue.push(InternalEvent::PopupStackPop);
 		}
 	}
@@ -512,6 +612,7 @@ impl BlameFileComponent {
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
 			commit_id: open.commit_id,
+			previous: false,
 		});
 		self.file_blame = None;
 		self.table_state.get_mut().select(Some(0));
@@ -521,7 +622,7 @@ impl BlameFileComponent {
 		Ok(())
 	}
 
-	fn push_request(&mut self, open: BlameFileOpen)
+	fn push_request(&mut self, open: BlameFileOpen, previous: bool)
 	{
 		if let Some(current_request) = self.open_request.as_mut() {
 			self.previous_request_stack.push((current_request.clone(), self.table_state.get_mut().clone()));
@@ -530,6 +631,7 @@ impl BlameFileComponent {
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
 			commit_id: open.commit_id,
+			previous,
 		});
 		self.file_blame = None;
 		self.table_state.get_mut().select(Some(0));
@@ -544,6 +646,7 @@ impl BlameFileComponent {
 			self.params = Some(BlameParams {
 				file_path: prev.0.file_path,
 				commit_id: prev.0.commit_id,
+				previous: false,
 			});
 			self.file_blame = None;
 			self.table_state = prev.1.into();
@@ -566,32 +669,88 @@ impl BlameFileComponent {
 			self.update()?;
 		}
 
+		if event == AsyncGitNotification::CommitInfo {
+			if let Some(job) = self.git_commit_info.take_last() {
+				if let Some(result) = job.result() {
+					// a failed lookup is not retried
+					let summary = result
+						.map(|info| {
+							info.message
+								.lines()
+								.next()
+								.unwrap_or_default()
+								.to_string()
+						})
+						.unwrap_or_default();
+					self.summaries.insert(job.commit(), summary);
+				}
+			}
+			// the selection may have moved on meanwhile
+			self.update_summary();
+		}
+
 		Ok(())
 	}
 
 	fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
-			if let Some(params) = &self.params {
+			if let Some(params) = self.params.clone() {
 				if let Some((
 					previous_blame_params,
 					last_file_blame,
 				)) = self.async_blame.last()?
 				{
-					if previous_blame_params == *params {
+					if previous_blame_params == params {
+						if params.previous {
+							// the revision stepped back to is only known
+							// once the job found it
+							if let Some(request) =
+								self.open_request.as_mut()
+							{
+								request
+									.file_path
+									.clone_from(&last_file_blame.path);
+								request.commit_id =
+									Some(last_file_blame.commit_id);
+							}
+						}
 						self.file_blame = Some(last_file_blame);
 						self.set_open_selection();
+						self.update_summary();
 
 						return Ok(());
 					}
 				}
 
-				self.async_blame.request(params.clone())?;
+				if self.async_blame.request(params.clone())?.is_none()
+					&& params.previous
+					&& !self.async_blame.is_pending()
+				{
+					// the job is done but found no earlier revision
+					self.pop_back();
+				}
 			}
 		}
 
 		Ok(())
 	}
 
+	/// path of the blamed revision, mentions the path the blame was
+	/// opened with if a rename was followed
+	fn get_title_path(&self) -> String {
+		let path = self
+			.open_request
+			.as_ref()
+			.map(|request| request.file_path.as_str())
+			.unwrap_or_default();
+		match self.previous_request_stack.first() {
+			Some((first, _)) if first.file_path != path => {
+				format!("{} (now {})", path, first.file_path)
+			}
+			_ => path.to_string(),
+		}
+	}
+
 	///
 	fn get_title(&self) -> String {
 		match (
@@ -599,24 +758,26 @@ impl BlameFileComponent {
 			self.params.as_ref(),
 			self.file_blame.as_ref(),
 		) {
-			(true, Some(params), _) => {
+			(true, Some(_), _) => {
 				format!(
 					"{} -- {} -- <calculating.. (who is to blame?)>",
-					self.title, params.file_path
+					self.title,
+					self.get_title_path()
 				)
 			}
-			(false, Some(params), Some(file_blame)) => {
+			(false, Some(_), Some(file_blame)) => {
 				format!(
 					"{} -- {} -- {}",
 					self.title,
-					params.file_path,
+					self.get_title_path(),
 					file_blame.commit_id.get_short_string()
 				)
 			}
-			(false, Some(params), None) => {
+			(false, Some(_), None) => {
 				format!(
 					"{} -- {} -- <no blame available>",
-					self.title, params.file_path
+					self.title,
+					self.get_title_path()
 				)
 			}
 			_ => format!("{} -- <no blame available>", self.title),
@@ -704,6 +865,14 @@ impl BlameFileComponent {
 		Row::new(cells)
 	}
 
+	fn get_author_width(&self, width: usize) -> usize {
+		let options = self.options.borrow();
+		options.author_width().scaled_columns(
+			width,
+			options.blame_author().width_factor(),
+		)
+	}
+
 	fn get_metadata_for_line_blame(
 		&self,
 		width: usize,
@@ -713,12 +882,17 @@ impl BlameFileComponent {
 			|| NO_COMMIT_ID.into(),
 			|hunk| hunk.commit_id.get_short_string(),
 		);
-		let author_width = get_author_width(width);
-		let truncated_author: String = blame_hunk.map_or_else(
+		let author_width = self.get_author_width(width);
+		let shown = self.options.borrow().blame_author();
+		let author = blame_hunk.map_or_else(
 			|| NO_AUTHOR.into(),
-			|hunk| string_width_align(&hunk.author, author_width),
+			|hunk| {
+				string_width_align(
+					&shown.text(&hunk.author, &hunk.email),
+					author_width,
+				)
+			},
 		);
-		let author = format!("{truncated_author:MAX_AUTHOR_WIDTH$}");
 		let time = blame_hunk.map_or_else(String::new, |hunk| {
 			utils::time_to_string(hunk.time, true)
 		});
@@ -835,17 +1009,78 @@ impl BlameFileComponent {
 		})
 	}
 
-	fn enter_search_mode(&mut self)
+	/// link to the selected line in the blamed revision
+	fn copy_permalink(&self) {
+		if let (Some(file_blame), Some(selection)) =
+			(self.file_blame.as_ref(), self.get_selection())
+		{
+			self.queue.push(InternalEvent::CopyPermalink(
+				Some(file_blame.commit_id),
+				file_blame.path.clone(),
+				selection + 1,
+			));
+		}
+	}
+
+	/// short hash and message summary of the selected line's
+	/// commit, the summary stays empty until it was looked up
+	fn get_selected_summary(&self) -> Option<(String, String)> {
+		let commit_id = self.selected_commit()?;
+
+		Some((
+			commit_id.get_short_string(),
+			self.summaries
+				.get(&commit_id)
+				.cloned()
+				.unwrap_or_default(),
+		))
+	}
+
+	/// looks up the summary of the selected line's commit unless it
+	/// is known already
+	fn update_summary(&mut self) {
+		if let Some(commit_id) = self.selected_commit() {
+			if !self.summaries.contains_key(&commit_id)
+				&& !self.git_commit_info.is_pending()
+			{
+				self.git_commit_info.spawn(AsyncCommitInfoJob::new(
+					self.repo.clone(),
+					commit_id,
+				));
+			}
+		}
+	}
+
+	fn enter_search_mode(&mut self, backward: bool)
 	{
 		self.state = BlameState::SearchEditing;
 		self.search.str = Some(String::new());
 		self.search.start = LinePos{line: self.get_selection().unwrap_or(0), offset: 0};
+		self.search.backward = backward;
+	}
+
+	/// finds the text typed so far in the direction the search was
+	/// started in, back at the start line if there is no match
+	fn search_incremental(&mut self)
+	{
+		let found = if self.search.backward {
+			self.search_only_back()
+		} else {
+			self.search_only()
+		};
+		if let Some(r) = found {
+			let l = r.line;
+			self.search.found = Some(r);
+			self.move_selection_to(l);
+		}else{
+			self.move_selection_to(self.search.start.line);
+		}
 	}
 
 	fn search_only(&mut self) -> Option<LinePos>
 	{
 		if let Some(b) = self.file_blame.as_ref() {
-			let substr = self.search.str.as_ref().map(|s|s.as_str()).unwrap_or("");
+			let substr = self.search.str.as_deref().unwrap_or("");
 			let mut from = self.search.start.clone();
 			if let Some(f) = self.search.found.as_ref() {
 				if from == *f {
@@ -877,7 +1112,7 @@ impl BlameFileComponent {
 	fn search_only_back(&mut self) -> Option<LinePos>
 	{
 		if let Some(b) = self.file_blame.as_ref() {
-			let substr = self.search.str.as_ref().map(|s|s.as_str()).unwrap_or("");
+			let substr = self.search.str.as_deref().unwrap_or("");
 			let from = self.search.start.clone();
 
 			let r = b.lines[from.line].1.as_str()[..from.offset].rfind(substr);
@@ -885,13 +1120,13 @@ impl BlameFileComponent {
 				return Some(LinePos{line: from.line, offset});
 			}
 
-			//wrap-around
 			for i in (0..from.line).rev() {
 				if let Some(offset) = b.lines[i].1.as_str().rfind(substr) {
 					return Some(LinePos{line: i, offset});
 				}
 			}
 
+			//wrap-around
 			for i in (from.line + 1..b.lines.len()).rev() {
 				if let Some(offset) = b.lines[i].1.as_str().rfind(substr) {
 					return Some(LinePos{line: i, offset});
@@ -948,14 +1183,7 @@ impl BlameFileComponent {
 			}else{
 				Some(format!("{}", c))
 			};
-			//inc search here
-			if let Some(r) = self.search_only() {
-				let l = r.line;
-				self.search.found = Some(r);
-				self.move_selection_to(l);
-			}else{
-				self.move_selection_to(self.search.start.line);
-			}
+			self.search_incremental();
 		}else if let KeyCode::Backspace = key.code {
 			self.search.str = if let Some(mut s) = self.search.str.take() {
 				s.pop();
@@ -963,32 +1191,170 @@ impl BlameFileComponent {
 			}else{
 				Some(String::new())
 			};
-			//inc search here
-			if let Some(r) = self.search_only() {
-				let l = r.line;
-				self.search.found = Some(r);
-				self.move_selection_to(l);
-			}else{
-				self.move_selection_to(self.search.start.line);
-			}
+			self.search_incremental();
 		}
 		return Ok(EventState::Consumed);
 	}
 }
 
-fn get_author_width(width: usize) -> usize {
-	(width.saturating_sub(19) / 3)
-		.clamp(MIN_AUTHOR_WIDTH, MAX_AUTHOR_WIDTH)
-}
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		keys::GituiKeyEvent,
+		options::Options,
+		test_utils::{git, temp_repo},
+	};
+	use crossbeam_channel::{unbounded, Receiver};
+	use crossterm::event::{KeyEvent, KeyModifiers};
+	use std::{cell::RefCell, time::Duration};
+
+	/// blame of `file.txt` in a repo where each of `lines` was
+	/// committed on its own, with the commit message `msg <line>`
+	fn blame_of(
+		lines: &[&str],
+	) -> (
+		tempfile::TempDir,
+		BlameFileComponent,
+		Receiver<AsyncGitNotification>,
+	) {
+		let (td, repo_path) = temp_repo();
+		let mut content = String::new();
+		for line in lines {
+			content.push_str(line);
+			content.push('\n');
+			std::fs::write(td.path().join("file.txt"), &content)
+				.unwrap();
+			git(td.path(), &["add", "file.txt"]);
+			git(
+				td.path(),
+				&["commit", "-q", "-m", &format!("msg {line}")],
+			);
+		}
+
+		let (tx, rx) = unbounded();
+		let mut blame = BlameFileComponent::new(
+			&RefCell::new(repo_path),
+			&Queue::new(),
+			&tx,
+			"",
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			Options::in_memory(),
+		);
+		blame
+			.open(BlameFileOpen {
+				file_path: String::from("file.txt"),
+				commit_id: None,
+				selection: None,
+			})
+			.unwrap();
+
+		(td, blame, rx)
+	}
+
+	/// handles notifications until `done` holds
+	fn settle(
+		blame: &mut BlameFileComponent,
+		rx: &Receiver<AsyncGitNotification>,
+		done: impl Fn(&BlameFileComponent) -> bool,
+	) {
+		while !done(blame) {
+			let ev = rx
+				.recv_timeout(Duration::from_secs(10))
+				.expect("no blame notification");
+			blame.update_git(ev).unwrap();
+		}
+	}
+
+	fn press(blame: &mut BlameFileComponent, key: GituiKeyEvent) {
+		blame
+			.event(&Event::Key(KeyEvent::new(
+				key.code,
+				key.modifiers,
+			)))
+			.unwrap();
+	}
 
-const fn number_of_digits(number: usize) -> usize {
-	let mut rest = number;
-	let mut result = 0;
+	fn type_text(blame: &mut BlameFileComponent, text: &str) {
+		for c in text.chars() {
+			press(
+				blame,
+				GituiKeyEvent::new(
+					KeyCode::Char(c),
+					KeyModifiers::empty(),
+				),
+			);
+		}
+	}
 
-	while rest > 0 {
-		rest /= 10;
-		result += 1;
+	#[test]
+	fn test_search_wraps_around() {
+		let (_td, mut blame, rx) =
+			blame_of(&["alpha", "beta", "alpha two", "gamma"]);
+		settle(&mut blame, &rx, |b| b.file_blame.is_some());
+		let keys = blame.key_config.keys.clone();
+
+		// forward from the last line continues at the top
+		blame.move_selection_to(3);
+		press(&mut blame, keys.start_search_forward_init);
+		type_text(&mut blame, "beta");
+		assert_eq!(blame.get_selection(), Some(1));
+		press(&mut blame, keys.enter);
+
+		// backward from the first match continues at the bottom
+		blame.move_selection_to(0);
+		press(&mut blame, keys.start_search_backward_init);
+		type_text(&mut blame, "alpha");
+		assert_eq!(blame.get_selection(), Some(2));
+		press(&mut blame, keys.enter);
+		press(&mut blame, keys.search_prev);
+		assert_eq!(blame.get_selection(), Some(0));
+		press(&mut blame, keys.search_prev);
+		assert_eq!(blame.get_selection(), Some(2));
 	}
 
-	result
+	#[test]
+	fn test_search_esc_restores_start_line() {
+		let (_td, mut blame, rx) =
+			blame_of(&["alpha", "beta", "gamma", "delta"]);
+		settle(&mut blame, &rx, |b| b.file_blame.is_some());
+		let keys = blame.key_config.keys.clone();
+
+		blame.move_selection_to(2);
+		press(&mut blame, keys.start_search_backward_init);
+		type_text(&mut blame, "delta");
+		assert_eq!(blame.get_selection(), Some(3));
+
+		press(&mut blame, keys.exit_popup);
+		assert_eq!(blame.search.start.line, 2);
+		assert_eq!(blame.get_selection(), Some(2));
+		assert_eq!(blame.search.str, None);
+		assert!(blame.state == BlameState::Normal);
+	}
+
+	#[test]
+	fn test_summary_looked_up_in_background() {
+		let (_td, mut blame, rx) = blame_of(&["one", "two"]);
+
+		settle(&mut blame, &rx, |b| b.file_blame.is_some());
+		// shown right away, filled in once the lookup is done
+		assert_eq!(
+			blame.get_selected_summary().map(|s| s.1),
+			Some(String::new())
+		);
+		settle(&mut blame, &rx, |b| !b.summaries.is_empty());
+		assert_eq!(
+			blame.get_selected_summary().map(|s| s.1),
+			Some(String::from("msg one"))
+		);
+
+		blame.move_selection(ScrollType::Down);
+		blame.update_summary();
+		settle(&mut blame, &rx, |b| b.summaries.len() == 2);
+		assert_eq!(
+			blame.get_selected_summary().map(|s| s.1),
+			Some(String::from("msg two"))
+		);
+	}
 }
@@ -1,12 +1,15 @@
 use super::{
-	utils::scroll_vertical::VerticalScroll, visibility_blocking,
-	CommandBlocking, CommandInfo, Component, DrawableComponent,
-	EventState, InspectCommitOpen,
+	utils::{
+		scroll_vertical::VerticalScroll, string_width_align,
+		time_to_age_string,
+	},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InspectCommitOpen,
 };
 use crate::{
 	components::ScrollType,
 	keys::{key_match, SharedKeyConfig},
-	options::SharedOptions,
+	options::{BranchListState, SharedOptions},
 	queue::{
 		Action, InternalEvent, LocalEvent, NeedsUpdate, Queue,
 		SharedLocalQueue, StackablePopupOpen,
@@ -16,18 +19,21 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	sync::{
 		self,
 		branch::{
 			checkout_branch_cmd, checkout_remote_branch,
 			BranchDetails, LocalBranch, RemoteBranch,
 		},
-		checkout_branch, get_branches_info, BranchInfo, BranchType,
-		CommitId, RepoPathRef, RepoState,
+		checkout_branch, BranchInfo, BranchType, CommitId,
+		RepoPathRef, RepoState,
 	},
-	AsyncGitNotification,
+	AsyncBranchesJob, AsyncGitNotification,
 };
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossbeam_channel::Sender;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use fuzzy_matcher::FuzzyMatcher;
 use ratatui::{
 	backend::Backend,
 	layout::{
@@ -53,10 +59,22 @@ enum Mode {
 	Pick,
 }
 
+#[derive(PartialEq)]
+enum FilterState {
+	/// keys act on the list
+	Idle,
+	/// keys go into `filter`
+	Typing,
+}
+
 ///
 pub struct BranchListComponent {
 	repo: RepoPathRef,
+	/// the ones matching `filter`
 	branches: Vec<BranchInfo>,
+	unfiltered: Vec<BranchInfo>,
+	filter: String,
+	filter_state: FilterState,
 	local: bool,
 	has_remotes: bool,
 	visible: bool,
@@ -70,6 +88,9 @@ pub struct BranchListComponent {
 	shortcut_state: ShortcutState,
 	mode: Mode,
 	response_queue: Option<SharedLocalQueue>,
+	git_branches: AsyncSingleJob<AsyncBranchesJob>,
+	/// selection of the last session, applied on the first load
+	restore: Option<BranchListState>,
 }
 
 impl DrawableComponent for BranchListComponent {
@@ -101,7 +122,7 @@ impl DrawableComponent for BranchListComponent {
 
 			f.render_widget(
 				Block::default()
-					.title(strings::title_branches())
+					.title(self.title())
 					.border_type(BorderType::Thick)
 					.borders(Borders::ALL),
 				area,
@@ -202,6 +223,14 @@ impl Component for BranchListComponent {
 				self.local,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::open_branch_create_from_selected_popup(
+					&self.key_config,
+				),
+				self.valid_selection(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::delete_branch_popup(
 					&self.key_config,
@@ -234,18 +263,67 @@ impl Component for BranchListComponent {
 				self.local,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::copy_branch_name(&self.key_config),
+				self.valid_selection(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::copy_branch_short_name(
+					&self.key_config,
+				),
+				self.valid_selection(),
+				!self.local,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::fetch_remotes(&self.key_config),
 				self.has_remotes,
 				!self.local,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::view_remotes(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::view_reflog(&self.key_config),
+				self.valid_selection(),
+				self.local,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_branch_details(
+					&self.key_config,
+					self.options.borrow().branch_details(),
+				),
+				true,
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::find_branch(&self.key_config),
 				true,
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::filter_branches(&self.key_config),
+				true,
+				self.filter_state == FilterState::Idle,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::clear_branch_filter(
+					&self.key_config,
+				),
+				true,
+				self.is_filtered(),
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::assign_shortcut(&self.key_config),
 				true,
@@ -324,6 +402,12 @@ impl Component for BranchListComponent {
 				_ => {}
 			}
 
+			if self.is_filtered()
+				&& self.filter_event(e)?.is_consumed()
+			{
+				return Ok(EventState::Consumed);
+			}
+
 			if self.move_event(e)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
@@ -337,7 +421,15 @@ impl Component for BranchListComponent {
 			} else if key_match(e, self.key_config.keys.create_branch)
 				&& self.local
 			{
-				self.queue.push(InternalEvent::CreateBranch);
+				self.queue.push(InternalEvent::CreateBranch(None));
+			} else if key_match(
+				e,
+				self.key_config.keys.create_branch_from_selected,
+			) && self.valid_selection()
+			{
+				self.queue.push(InternalEvent::CreateBranch(
+					self.get_selected(),
+				));
 			} else if key_match(e, self.key_config.keys.rename_branch)
 				&& self.valid_selection()
 			{
@@ -382,10 +474,44 @@ impl Component for BranchListComponent {
 						),
 					));
 				}
+			} else if key_match(e, self.key_config.keys.copy)
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					strings::POPUP_FAIL_COPY,
+					self.copy_branch_name(false)
+				);
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_copy_short_name,
+			) && !self.local && self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					strings::POPUP_FAIL_COPY,
+					self.copy_branch_name(true)
+				);
 			} else if key_match(e, self.key_config.keys.fetch)
 				&& !self.local && self.has_remotes
 			{
 				self.queue.push(InternalEvent::FetchRemotes);
+			} else if key_match(e, self.key_config.keys.view_remotes)
+			{
+				self.queue.push(InternalEvent::ViewRemotes);
+			} else if key_match(e, self.key_config.keys.view_reflog)
+				&& self.local && self.valid_selection()
+			{
+				self.queue.push(InternalEvent::ViewReflog(
+					self.branches[self.selection as usize]
+						.reference
+						.clone(),
+				));
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_toggle_details,
+			) {
+				self.options.borrow_mut().toggle_branch_details();
 			} else if key_match(
 				e,
 				self.key_config.keys.cmd_bar_toggle,
@@ -420,6 +546,11 @@ impl Component for BranchListComponent {
 				self.key_config.keys.trigger_branch_shortcut,
 			) {
 				self.shortcut_state = ShortcutState::Trigger;
+			} else if key_match(
+				e,
+				self.key_config.keys.filter_branches_init,
+			) {
+				self.filter_state = FilterState::Typing;
 			} else if key_match(
 				e,
 				self.key_config.keys.start_search_forward_init,
@@ -456,13 +587,19 @@ impl BranchListComponent {
 	pub fn new(
 		repo: RepoPathRef,
 		queue: Queue,
+		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 		options: SharedOptions,
 	) -> Self {
+		let restore = options.borrow().view_state().branches.clone();
+
 		Self {
 			branches: Vec::new(),
-			local: true,
+			unfiltered: Vec::new(),
+			filter: String::new(),
+			filter_state: FilterState::Idle,
+			local: !restore.remote,
 			has_remotes: false,
 			visible: false,
 			selection: 0,
@@ -476,9 +613,126 @@ impl BranchListComponent {
 			options,
 			mode: Mode::Checkout,
 			response_queue: None,
+			git_branches: AsyncSingleJob::new(sender.clone()),
+			restore: Some(restore),
 		}
 	}
 
+	/// selected branch and whether the remote ones are listed. the
+	/// state the popup was opened with while it is not loaded yet
+	pub fn view_state(&self) -> BranchListState {
+		self.restore.clone().unwrap_or_else(|| BranchListState {
+			branch: self
+				.branches
+				.get(usize::from(self.selection))
+				.map(|b| b.name.clone())
+				.unwrap_or_default(),
+			remote: !self.local,
+			selection: usize::from(self.selection),
+		})
+	}
+
+	fn title(&self) -> String {
+		let mut title = strings::title_branches();
+		if self.is_filtered() {
+			title = format!(
+				"{} {}",
+				title,
+				strings::title_branches_filter(
+					&self.filter,
+					self.filter_state == FilterState::Typing
+				)
+			);
+		}
+		if self.git_branches.is_pending() {
+			title = format!(
+				"{} {}",
+				title,
+				strings::loading_text(&self.key_config)
+			);
+		}
+		title
+	}
+
+	fn is_filtered(&self) -> bool {
+		self.filter_state == FilterState::Typing
+			|| !self.filter.is_empty()
+	}
+
+	/// while typing every char goes into the filter, enter stops
+	/// typing (keeping the filter) and escape clears it
+	fn filter_event(&mut self, e: &KeyEvent) -> Result<EventState> {
+		if key_match(e, self.key_config.keys.exit_popup) {
+			self.clear_filter()?;
+		} else if self.filter_state == FilterState::Idle {
+			return Ok(EventState::NotConsumed);
+		} else if key_match(e, self.key_config.keys.enter) {
+			self.filter_state = FilterState::Idle;
+		} else if e.code == KeyCode::Backspace {
+			self.filter.pop();
+			self.apply_filter();
+			self.set_selection(0)?;
+		} else if let KeyCode::Char(c) = e.code {
+			if (e.modifiers - KeyModifiers::SHIFT).is_empty() {
+				self.filter.push(c);
+				self.apply_filter();
+				self.set_selection(0)?;
+			} else {
+				return Ok(EventState::NotConsumed);
+			}
+		} else {
+			return Ok(EventState::NotConsumed);
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn clear_filter(&mut self) -> Result<()> {
+		let selected = self
+			.branches
+			.get(usize::from(self.selection))
+			.map(|b| b.name.clone());
+
+		self.filter.clear();
+		self.filter_state = FilterState::Idle;
+		self.apply_filter();
+		self.select_by_name(selected)
+	}
+
+	/// narrows `branches` down to the ones fuzzy matching `filter`,
+	/// best match first
+	fn apply_filter(&mut self) {
+		if self.filter.is_empty() {
+			self.branches = self.unfiltered.clone();
+			return;
+		}
+
+		let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+		let mut hits = self
+			.unfiltered
+			.iter()
+			.filter_map(|b| {
+				matcher
+					.fuzzy_match(&b.name, &self.filter)
+					.map(|score| (score, b))
+			})
+			.collect::<Vec<_>>();
+		hits.sort_by(|(score1, _), (score2, _)| score2.cmp(score1));
+
+		self.branches =
+			hits.into_iter().map(|(_, b)| b.clone()).collect();
+	}
+
+	/// keeps the branch `name` selected if it is still listed
+	fn select_by_name(&mut self, name: Option<String>) -> Result<()> {
+		let selection = name
+			.and_then(|name| {
+				self.branches.iter().position(|b| b.name == name)
+			})
+			.map_or(Ok(self.selection), u16::try_from)?;
+		self.set_selection(selection)
+	}
+
 	fn move_event(&mut self, e: &KeyEvent) -> Result<EventState> {
 		if key_match(e, self.key_config.keys.exit_popup) {
 			self.hide();
@@ -508,7 +762,10 @@ impl BranchListComponent {
 				.map(Into::into);
 		} else if key_match(e, self.key_config.keys.tab_toggle) {
 			self.local = !self.local;
-			self.check_remotes();
+			self.restore = None;
+			// do not act on the other tab's branches while loading
+			self.branches.clear();
+			self.unfiltered.clear();
 			self.update_branches()?;
 		}
 		Ok(EventState::NotConsumed)
@@ -521,6 +778,8 @@ impl BranchListComponent {
 	) -> Result<()> {
 		self.mode = Mode::Pick;
 		self.response_queue = Some(q);
+		self.filter.clear();
+		self.filter_state = FilterState::Idle;
 		self.show()?;
 		self.update_branches()?;
 
@@ -531,6 +790,8 @@ impl BranchListComponent {
 	pub fn open(&mut self) -> Result<()> {
 		self.mode = Mode::Checkout;
 		self.response_queue = None;
+		self.filter.clear();
+		self.filter_state = FilterState::Idle;
 		self.show()?;
 		self.update_branches()?;
 
@@ -547,46 +808,80 @@ impl BranchListComponent {
 		Ok(())
 	}
 
-	fn check_remotes(&mut self) {
-		if !self.local && self.visible {
-			self.has_remotes =
-				get_branches_info(&self.repo.borrow(), false)
-					.map(|branches| !branches.is_empty())
-					.unwrap_or(false);
-		}
-	}
-
 	fn update_auto_shortcuts(&mut self) {
 		//self.auto_shortcuts.clear();
 		//self.auto_shortcuts.reserve(self.branches.len());
 	}
 
-	/// fetch list of branches
+	/// fetch list of branches in the background
 	pub fn update_branches(&mut self) -> Result<()> {
 		if self.is_visible() {
-			self.check_remotes();
-			self.branches =
-				get_branches_info(&self.repo.borrow(), self.local)?;
-			//remove remote branch called `HEAD`
-			if !self.local {
-				self.branches
-					.iter()
-					.position(|b| b.name.ends_with("/HEAD"))
-					.map(|idx| self.branches.remove(idx));
-			}
-			self.set_selection(self.selection)?;
-			self.update_auto_shortcuts();
+			self.git_branches.spawn(AsyncBranchesJob::new(
+				self.repo.borrow().clone(),
+				self.local,
+			));
 		}
 		Ok(())
 	}
 
+	/// branches are still being loaded
+	pub fn any_work_pending(&self) -> bool {
+		self.git_branches.is_pending()
+	}
+
+	fn branches_loaded(
+		&mut self,
+		branches: Vec<BranchInfo>,
+	) -> Result<()> {
+		let selected = if let Some(state) = self.restore.take() {
+			// a branch gone since keeps the old index instead
+			self.selection =
+				u16::try_from(state.selection).unwrap_or(u16::MAX);
+			Some(state.branch)
+		} else {
+			self.branches
+				.get(usize::from(self.selection))
+				.map(|b| b.name.clone())
+		};
+
+		if !self.local {
+			self.has_remotes = !branches.is_empty();
+		}
+		self.unfiltered = branches;
+		//remove remote branch called `HEAD`
+		if !self.local {
+			self.unfiltered
+				.iter()
+				.position(|b| b.name.ends_with("/HEAD"))
+				.map(|idx| self.unfiltered.remove(idx));
+		}
+		self.apply_filter();
+
+		// keep the same branch selected if it is still there
+		self.select_by_name(selected)?;
+		self.update_auto_shortcuts();
+
+		Ok(())
+	}
+
 	///
 	pub fn update_git(
 		&mut self,
 		ev: AsyncGitNotification,
 	) -> Result<()> {
-		if self.is_visible() && ev == AsyncGitNotification::Push {
-			self.update_branches()?;
+		if self.is_visible() {
+			if ev == AsyncGitNotification::Push {
+				self.update_branches()?;
+			} else if ev == AsyncGitNotification::Branches {
+				if let Some(job) = self.git_branches.take_last() {
+					// a result for the other tab is outdated
+					if job.is_local() == self.local {
+						if let Some(branches) = job.result() {
+							self.branches_loaded(branches?)?;
+						}
+					}
+				}
+			}
 		}
 
 		Ok(())
@@ -628,6 +923,26 @@ impl BranchListComponent {
 		Ok(())
 	}
 
+	/// `strip_remote` turns `origin/feature` into `feature`
+	fn copy_branch_name(&self, strip_remote: bool) -> Result<()> {
+		if let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		{
+			let name = if strip_remote {
+				branch
+					.name
+					.split_once('/')
+					.map_or(branch.name.as_str(), |(_, name)| name)
+			} else {
+				branch.name.as_str()
+			};
+
+			crate::clipboard::copy_string(name)?;
+		}
+
+		Ok(())
+	}
+
 	fn inspect_head_of_branch(&mut self) {
 		if let Some(commit_id) = self.get_selected() {
 			self.hide();
@@ -735,9 +1050,20 @@ impl BranchListComponent {
 		const COMMIT_HASH_LENGTH: usize = 8;
 		const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
 		const SHORTCUT_WIDTH: usize = 4; // "*  "
+		const AUTHOR_LENGTH: usize = 12;
+		const AGE_LENGTH: usize = 8; // "11mo ago"
+		const DETAILS_MIN_WIDTH: u16 = 70; // leaves room for the msg
 
 		let opts = self.options.borrow();
 		let has_shortcuts = opts.has_any_branch_shortcuts();
+		let show_details = opts.branch_details()
+			&& width_available >= DETAILS_MIN_WIDTH;
+		let details_length = if show_details {
+			AGE_LENGTH + AUTHOR_LENGTH + 2
+		} else {
+			0
+		};
+		let now = chrono::Local::now().timestamp();
 
 		let branch_name_length: usize =
 			(width_available as usize * 40 / 100)
@@ -747,6 +1073,7 @@ impl BranchListComponent {
 			.saturating_sub(COMMIT_HASH_LENGTH)
 			.saturating_sub(branch_name_length)
 			.saturating_sub(IS_HEAD_STAR_LENGTH)
+			.saturating_sub(details_length)
 			.saturating_sub(THREE_DOTS_LENGTH);
 		let mut txt = Vec::new();
 
@@ -831,31 +1158,44 @@ impl BranchListComponent {
 				theme.branch(selected, is_head),
 			);
 
+			let mut spans = vec![span_prefix];
 			if has_shortcuts {
 				shortcut.extend(
 					[' '].iter().cycle().take(
 						SHORTCUT_WIDTH - shortcut.chars().count(),
 					),
 				);
-				let span_shortcut = Span::styled(
+				spans.push(Span::styled(
 					shortcut,
 					theme.branch(selected, is_head),
-				);
-				txt.push(Spans::from(vec![
-					span_prefix,
-					span_shortcut,
-					span_name,
-					span_hash,
-					span_msg,
-				]));
-			} else {
-				txt.push(Spans::from(vec![
-					span_prefix,
-					span_name,
-					span_hash,
-					span_msg,
-				]));
+				));
+			}
+			spans.push(span_name);
+			spans.push(span_hash);
+			if show_details {
+				spans.push(Span::styled(
+					format!(
+						"{:>AGE_LENGTH$} ",
+						time_to_age_string(
+							displaybranch.top_commit_time,
+							now
+						)
+					),
+					theme.commit_time(selected),
+				));
+				spans.push(Span::styled(
+					format!(
+						"{} ",
+						string_width_align(
+							&displaybranch.top_commit_author,
+							AUTHOR_LENGTH
+						)
+					),
+					theme.commit_author(selected),
+				));
 			}
+			spans.push(span_msg);
+			txt.push(Spans::from(spans));
 		}
 
 		Text::from(txt)
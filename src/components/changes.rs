@@ -5,8 +5,8 @@ use super::{
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
-	is_among_tracked_lfs_files,
 	keys::{key_match, SharedKeyConfig},
+	lfs::is_among_tracked_lfs_files,
 	options::SharedOptions,
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
 	strings, try_or_popup,
@@ -21,11 +21,18 @@ use crossterm::event::Event;
 use ratatui::{backend::Backend, layout::Rect, Frame};
 use std::path::Path;
 
+/// more untracked files than this get collapsed into the title
+const UNTRACKED_COLLAPSE_THRESHOLD: usize = 1000;
+
 ///
 pub struct ChangesComponent {
 	repo: RepoPathRef,
 	files: StatusTreeComponent,
+	title: String,
 	is_working_dir: bool,
+	untracked_expanded: bool,
+	// untracked files left out of the tree, 0 if none
+	untracked_hidden: usize,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
@@ -53,7 +60,10 @@ impl ChangesComponent {
 				theme,
 				key_config.clone(),
 			),
+			title: title.to_string(),
 			is_working_dir,
+			untracked_expanded: false,
+			untracked_hidden: 0,
 			queue,
 			key_config,
 			options,
@@ -64,10 +74,52 @@ impl ChangesComponent {
 	///
 	pub fn set_items(&mut self, list: &[StatusItem]) -> Result<()> {
 		self.files.show()?;
-		self.files.update(list)?;
+
+		let untracked = if self.is_working_dir {
+			list.iter()
+				.filter(|item| item.status == StatusItemType::New)
+				.count()
+		} else {
+			0
+		};
+
+		self.untracked_hidden = if untracked
+			> UNTRACKED_COLLAPSE_THRESHOLD
+			&& !self.untracked_expanded
+		{
+			untracked
+		} else {
+			0
+		};
+
+		if self.untracked_hidden > 0 {
+			let tracked: Vec<StatusItem> = list
+				.iter()
+				.filter(|item| item.status != StatusItemType::New)
+				.cloned()
+				.collect();
+			self.files.update(&tracked)?;
+			self.files.set_title(
+				strings::changes_untracked_collapsed_title(
+					&self.title,
+					self.untracked_hidden,
+					&self.key_config,
+				),
+			);
+		} else {
+			self.files.update(list)?;
+			self.files.set_title(self.title.clone());
+		}
+
 		Ok(())
 	}
 
+	/// whether there are too many untracked files to list them
+	/// all, this is independent of them being expanded right now
+	fn has_many_untracked(&self) -> bool {
+		self.untracked_hidden > 0 || self.untracked_expanded
+	}
+
 	///
 	pub fn selection(&self) -> Option<FileTreeItem> {
 		self.files.selection()
@@ -79,9 +131,15 @@ impl ChangesComponent {
 		self.files.show_selection(focus);
 	}
 
-	/// returns true if list is empty
+	/// returns true if list is empty, collapsed untracked files
+	/// still count
 	pub fn is_empty(&self) -> bool {
-		self.files.is_empty()
+		self.files.is_empty() && self.untracked_hidden == 0
+	}
+
+	/// marked staged files, a commit takes only these if any
+	pub fn marked_paths(&self) -> Vec<String> {
+		self.files.marked_paths()
 	}
 
 	///
@@ -102,9 +160,13 @@ impl ChangesComponent {
 							)?;
 						}
 						_ => {
+							// while the lfs files are still loading
+							// 'git add' is the safe bet
 							if is_among_tracked_lfs_files(
 								&tree_item.info.full_path,
-							) {
+							)
+							.unwrap_or(true)
+							{
 								//fallback to 'git add'
 								let o =
 									std::process::Command::new("git")
@@ -131,12 +193,20 @@ impl ChangesComponent {
 						}
 					};
 				} else {
+					if is_among_tracked_lfs_files(
+						&tree_item.info.full_path,
+					)
+					.is_none()
+					{
+						bail!("Still looking for LFS-tracked files, try again in a moment")
+					}
 					if self.files.get_tree().check_all_files_for_path(
 						&tree_item.info.full_path,
 						&|i: &FileTreeItem| -> bool {
 							is_among_tracked_lfs_files(
 								&i.info.full_path,
 							)
+							.unwrap_or_default()
 						},
 					) {
 						bail!("One of the files is LFS-tracked. Stage one by one")
@@ -209,6 +279,41 @@ impl ChangesComponent {
 		false
 	}
 
+	/// restores the selected files to `HEAD`, untracked ones get
+	/// deleted instead
+	fn dispatch_restore(&self) -> bool {
+		if let Some(tree_item) = self.selection() {
+			let files = self
+				.files
+				.get_tree()
+				.files_at_path(&tree_item.info.full_path);
+
+			let (untracked, tracked): (Vec<_>, Vec<_>) =
+				files.into_iter().partition(|item| {
+					self.is_working_dir
+						&& item.status == StatusItemType::New
+				});
+
+			let paths = |items: Vec<StatusItem>| {
+				items.into_iter().map(|item| item.path).collect()
+			};
+
+			let action = if tracked.is_empty() {
+				if untracked.is_empty() {
+					return false;
+				}
+				Action::DeleteUntracked(paths(untracked))
+			} else {
+				Action::RestoreFiles(paths(tracked), paths(untracked))
+			};
+
+			self.queue.push(InternalEvent::ConfirmAction(action));
+
+			return true;
+		}
+		false
+	}
+
 	fn add_to_ignore(&mut self) -> bool {
 		if let Some(tree_item) = self.selection() {
 			if let Err(e) = sync::add_to_ignore(
@@ -259,7 +364,15 @@ impl Component for ChangesComponent {
 			out.push(CommandInfo::new(
 				strings::commands::stage_all(&self.key_config),
 				true,
-				some_selection && self.focused(),
+				!self.is_empty() && self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::toggle_untracked(
+					&self.key_config,
+					self.untracked_expanded,
+				),
+				true,
+				self.has_many_untracked() && self.focused(),
 			));
 			out.push(CommandInfo::new(
 				strings::commands::stage_item(&self.key_config),
@@ -271,11 +384,24 @@ impl Component for ChangesComponent {
 				true,
 				some_selection && self.focused(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::restore_item(&self.key_config),
+				true,
+				some_selection && self.focused(),
+			));
 			out.push(CommandInfo::new(
 				strings::commands::ignore_item(&self.key_config),
 				true,
 				some_selection && self.focused(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stage_pattern(
+					&self.key_config,
+					true,
+				),
+				true,
+				some_selection && self.focused(),
+			));
 		} else {
 			out.push(CommandInfo::new(
 				strings::commands::unstage_item(&self.key_config),
@@ -287,6 +413,24 @@ impl Component for ChangesComponent {
 				true,
 				some_selection && self.focused(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::mark_file(&self.key_config),
+				self.files.selection_file().is_some(),
+				some_selection && self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::restore_item(&self.key_config),
+				true,
+				some_selection && self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stage_pattern(
+					&self.key_config,
+					false,
+				),
+				true,
+				some_selection && self.focused(),
+			));
 		}
 
 		CommandBlocking::PassingOn
@@ -330,12 +474,43 @@ impl Component for ChangesComponent {
 					self.queue
 						.push(InternalEvent::StatusLastFileMoved);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.status_toggle_untracked,
+				) && self.has_many_untracked()
+				{
+					self.untracked_expanded =
+						!self.untracked_expanded;
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.status_stage_pattern,
+				) && !self.is_empty()
+				{
+					self.queue.push(InternalEvent::OpenStagePattern(
+						self.is_working_dir,
+					));
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.status_mark_file,
+				) && !self.is_working_dir
+				{
+					Ok(self.files.toggle_mark_selected().into())
 				} else if key_match(
 					e,
 					self.key_config.keys.status_reset_item,
 				) && self.is_working_dir
 				{
 					Ok(self.dispatch_reset_workdir().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_restore_item,
+				) {
+					Ok(self.dispatch_restore().into())
 				} else if key_match(
 					e,
 					self.key_config.keys.status_ignore_file,
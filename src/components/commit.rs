@@ -19,8 +19,8 @@ use anyhow::{bail, Ok, Result};
 use asyncgit::{
 	cached, message_prettify,
 	sync::{
-		self, get_config_string, CommitId, HookResult, RepoPathRef,
-		RepoState,
+		self, get_config_string, CommitId, CommitMessage, HookResult,
+		RepoPath, RepoPathRef, RepoState,
 	},
 	StatusItem, StatusItemType,
 };
@@ -33,21 +33,62 @@ use ratatui::{
 	Frame,
 };
 use std::{
-	fs::{read_to_string, File},
+	fs::File,
 	io::{Read, Write},
 };
 
-enum CommitResult {
+pub enum CommitResult {
 	ComitDone,
 	Aborted,
 }
 
+/// runs `commit` with `msg` between the pre-commit and commit-msg
+/// hooks and the post-commit hook, failing hooks are reported on
+/// `queue`
+pub fn commit_with_hooks(
+	repo: &RepoPath,
+	queue: &Queue,
+	msg: String,
+	commit: impl FnOnce(&str) -> Result<()>,
+) -> Result<CommitResult> {
+	if let HookResult::NotOk(e) = sync::hooks_pre_commit(repo)? {
+		log::error!("pre-commit hook error: {}", e);
+		queue.push(InternalEvent::ShowErrorMsg(format!(
+			"pre-commit hook error:\n{e}"
+		)));
+		return Ok(CommitResult::Aborted);
+	}
+	let mut msg = message_prettify(msg, Some(b'#'))?;
+	if let HookResult::NotOk(e) =
+		sync::hooks_commit_msg(repo, &mut msg)?
+	{
+		log::error!("commit-msg hook error: {}", e);
+		queue.push(InternalEvent::ShowErrorMsg(format!(
+			"commit-msg hook error:\n{e}"
+		)));
+		return Ok(CommitResult::Aborted);
+	}
+
+	commit(&msg)?;
+
+	if let HookResult::NotOk(e) = sync::hooks_post_commit(repo)? {
+		log::error!("post-commit hook error: {}", e);
+		queue.push(InternalEvent::ShowErrorMsg(format!(
+			"post-commit hook error:\n{e}"
+		)));
+	}
+
+	Ok(CommitResult::ComitDone)
+}
+
 enum Mode {
 	Normal,
 	Amend(CommitId),
 	Merge(Vec<CommitId>),
 	Revert,
 	Reword(CommitId),
+	/// only the staged changes of these files
+	Marked(Vec<String>),
 }
 
 pub struct CommitComponent {
@@ -60,12 +101,39 @@ pub struct CommitComponent {
 	commit_template: Option<String>,
 	theme: SharedTheme,
 	commit_msg_history_idx: usize,
+	/// typed text the history is filtered by while cycling
+	commit_msg_history_query: String,
+	/// message put into the input when opening (template, merge
+	/// message...), not part of the history query
+	commit_msg_prefill: String,
 	options: SharedOptions,
 	verify: bool,
 }
 
 const FIRST_LINE_LIMIT: usize = 50;
 
+/// what was typed into `text` around the untouched `prefill`
+fn typed_text<'a>(text: &'a str, prefill: &str) -> &'a str {
+	let prefix = text
+		.char_indices()
+		.zip(prefill.chars())
+		.find(|((_, a), b)| a != b)
+		.map_or_else(
+			|| text.len().min(prefill.len()),
+			|((idx, _), _)| idx,
+		);
+	let (text, prefill) = (&text[prefix..], &prefill[prefix..]);
+	let suffix: usize = text
+		.chars()
+		.rev()
+		.zip(prefill.chars().rev())
+		.take_while(|(a, b)| a == b)
+		.map(|(c, _)| c.len_utf8())
+		.sum();
+
+	text[..text.len() - suffix].trim()
+}
+
 impl CommitComponent {
 	///
 	pub fn new(
@@ -91,6 +159,8 @@ impl CommitComponent {
 			theme,
 			repo,
 			commit_msg_history_idx: 0,
+			commit_msg_history_query: String::new(),
+			commit_msg_prefill: String::new(),
 			options,
 			verify: true,
 		}
@@ -193,6 +263,8 @@ impl CommitComponent {
 		ExternalEditorComponent::open_file_in_editor(
 			&self.repo.borrow(),
 			&file_path,
+			None,
+			self.options.borrow().editor(),
 		)?;
 
 		let mut message = String::new();
@@ -261,7 +333,7 @@ impl CommitComponent {
 
 			self.hide();
 			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
-			self.input.clear();
+			self.set_prefill(String::new());
 		}
 
 		Ok(())
@@ -276,38 +348,12 @@ impl CommitComponent {
 			self.verify = true;
 			return Ok(CommitResult::ComitDone);
 		}
-		if let HookResult::NotOk(e) =
-			sync::hooks_pre_commit(&self.repo.borrow())?
-		{
-			log::error!("pre-commit hook error: {}", e);
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"pre-commit hook error:\n{e}"
-			)));
-			return Ok(CommitResult::Aborted);
-		}
-		let mut msg = message_prettify(msg, Some(b'#'))?;
-		if let HookResult::NotOk(e) =
-			sync::hooks_commit_msg(&self.repo.borrow(), &mut msg)?
-		{
-			log::error!("commit-msg hook error: {}", e);
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"commit-msg hook error:\n{e}"
-			)));
-			return Ok(CommitResult::Aborted);
-		}
-
-		self.do_commit(&msg)?;
-
-		if let HookResult::NotOk(e) =
-			sync::hooks_post_commit(&self.repo.borrow())?
-		{
-			log::error!("post-commit hook error: {}", e);
-			self.queue.push(InternalEvent::ShowErrorMsg(format!(
-				"post-commit hook error:\n{e}"
-			)));
-		}
-
-		Ok(CommitResult::ComitDone)
+		commit_with_hooks(
+			&self.repo.borrow(),
+			&self.queue,
+			msg,
+			|msg| self.do_commit(msg),
+		)
 	}
 
 	fn do_commit(&self, msg: &str) -> Result<()> {
@@ -322,6 +368,9 @@ impl CommitComponent {
 			Mode::Revert => {
 				sync::commit_revert(&self.repo.borrow(), msg)?
 			}
+			Mode::Marked(paths) => {
+				sync::commit_paths(&self.repo.borrow(), msg, paths)?
+			}
 			Mode::Reword(id) => {
 				let commit =
 					sync::reword(&self.repo.borrow(), *id, msg)?;
@@ -369,14 +418,96 @@ impl CommitComponent {
 
 		Ok(())
 	}
+	/// rewording HEAD is a plain amend as long as nothing is staged
+	/// that would end up in the amended commit
+	fn can_reword_by_amend(&self, id: CommitId) -> Result<bool> {
+		let repo = self.repo.borrow();
+		Ok(sync::get_head(&repo)? == id
+			&& sync::status::get_status(
+				&repo,
+				sync::status::StatusType::Stage,
+				None,
+			)?
+			.is_empty())
+	}
+
 	fn toggle_verify(&mut self) {
 		self.verify = !self.verify;
 	}
 
+	/// opens the popup to amend `HEAD` with what is staged, the
+	/// message prefilled from `HEAD`
+	pub fn open_amend(&mut self) -> Result<()> {
+		if sync::repo_state(&self.repo.borrow())? != RepoState::Clean
+		{
+			bail!("cannot amend while repo is not in a clean state");
+		}
+
+		let id = sync::get_head(&self.repo.borrow())?;
+		let details =
+			sync::get_commit_details(&self.repo.borrow(), id)?;
+
+		self.mode = Mode::Amend(id);
+		self.input.set_title(strings::commit_title_amend());
+		self.set_prefill(
+			details
+				.message
+				.map(CommitMessage::combine)
+				.unwrap_or_default(),
+		);
+
+		self.commit_msg_history_idx = 0;
+		self.input.show()?;
+
+		Ok(())
+	}
+
+	/// sets a message the user did not type, which is then left out
+	/// of the history query
+	fn set_prefill(&mut self, msg: String) {
+		self.input.set_text(msg.clone());
+		self.commit_msg_prefill = msg;
+	}
+
+	/// cycles through the stored messages matching what the user
+	/// typed, every edit of the input restarts the cycle
+	fn next_msg_from_history(&mut self) {
+		if self.commit_msg_history_idx == 0 {
+			self.commit_msg_history_query = typed_text(
+				self.input.get_text(),
+				&self.commit_msg_prefill,
+			)
+			.to_string();
+		}
+		if let Some(msg) = self.options.borrow().commit_msg_matching(
+			&self.commit_msg_history_query,
+			self.commit_msg_history_idx,
+		) {
+			self.input.set_text(msg);
+			self.commit_msg_history_idx += 1;
+		}
+	}
+
+	/// opens the popup to commit only the staged changes of `paths`
+	pub fn open_marked(&mut self, paths: Vec<String>) -> Result<()> {
+		if sync::repo_state(&self.repo.borrow())? != RepoState::Clean
+		{
+			bail!("cannot commit marked files while repo is not in a clean state");
+		}
+
+		self.open(None)?;
+
+		self.input
+			.set_title(strings::commit_title_marked(paths.len()));
+		self.mode = Mode::Marked(paths);
+
+		Ok(())
+	}
+
 	pub fn open(&mut self, reword: Option<CommitId>) -> Result<()> {
 		//only clear text if it was not a normal commit dlg before, so to preserve old commit msg that was edited
-		if !matches!(self.mode, Mode::Normal) {
-			self.input.clear();
+		if !matches!(self.mode, Mode::Normal | Mode::Marked(_)) {
+			self.set_prefill(String::new());
 		}
 
 		self.mode = Mode::Normal;
@@ -387,17 +518,20 @@ impl CommitComponent {
 			if repo_state != RepoState::Clean && reword.is_some() {
 				bail!("cannot reword while repo is not in a clean state");
 			} else if let Some(reword_id) = reword {
-				self.input.set_text(
-					sync::get_commit_details(
-						&self.repo.borrow(),
-						reword_id,
-					)?
-					.message
-					.unwrap_or_default()
-					.combine(),
-				);
+				let msg = sync::get_commit_details(
+					&self.repo.borrow(),
+					reword_id,
+				)?
+				.message
+				.unwrap_or_default()
+				.combine();
+				self.set_prefill(msg);
 				self.input.set_title(strings::commit_reword_title());
-				Mode::Reword(reword_id)
+				if self.can_reword_by_amend(reword_id)? {
+					Mode::Amend(reword_id)
+				} else {
+					Mode::Reword(reword_id)
+				}
 			} else {
 				match repo_state {
 					RepoState::Merge => {
@@ -405,32 +539,28 @@ impl CommitComponent {
 							sync::mergehead_ids(&self.repo.borrow())?;
 						self.input
 							.set_title(strings::commit_title_merge());
-						self.input.set_text(sync::merge_msg(
-							&self.repo.borrow(),
-						)?);
+						let msg = sync::merge_msg(&self.repo.borrow())?;
+						self.set_prefill(msg);
 						Mode::Merge(ids)
 					}
 					RepoState::Revert => {
 						self.input
 							.set_title(strings::commit_title_revert());
-						self.input.set_text(sync::merge_msg(
-							&self.repo.borrow(),
-						)?);
+						let msg = sync::merge_msg(&self.repo.borrow())?;
+						self.set_prefill(msg);
 						Mode::Revert
 					}
 
 					_ => {
-						self.commit_template = get_config_string(
-							&self.repo.borrow(),
-							"commit.template",
-						)
-						.ok()
-						.flatten()
-						.and_then(|path| read_to_string(path).ok());
+						self.commit_template =
+							sync::commit_template(&self.repo.borrow())
+								.ok()
+								.flatten();
 
 						if self.is_empty() {
-							if let Some(s) = &self.commit_template {
-								self.input.set_text(s.clone());
+							if let Some(s) = self.commit_template.clone()
+							{
+								self.set_prefill(s);
 							}
 						}
 						self.input.set_title(strings::commit_title());
@@ -514,7 +644,11 @@ impl Component for CommitComponent {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			let text_before = self.input.get_text().to_string();
 			if self.input.event(ev)?.is_consumed() {
+				if self.input.get_text() != text_before {
+					self.commit_msg_history_idx = 0;
+				}
 				return Ok(EventState::Consumed);
 			}
 
@@ -551,14 +685,7 @@ impl Component for CommitComponent {
 					e,
 					self.key_config.keys.commit_history_next,
 				) {
-					if let Some(msg) = self
-						.options
-						.borrow()
-						.commit_msg(self.commit_msg_history_idx)
-					{
-						self.input.set_text(msg);
-						self.commit_msg_history_idx += 1;
-					}
+					self.next_msg_from_history();
 				} else {
 				}
 				// stop key event propagation
@@ -582,3 +709,83 @@ impl Component for CommitComponent {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{typed_text, CommitComponent};
+	use crate::{
+		components::Component, keys::SharedKeyConfig,
+		options::Options, queue::Queue, ui::style::SharedTheme,
+	};
+	use asyncgit::sync::RepoPath;
+	use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+	use std::cell::RefCell;
+
+	fn press(commit: &mut CommitComponent, code: KeyCode) {
+		commit
+			.event(&Event::Key(KeyEvent::new(
+				code,
+				KeyModifiers::empty(),
+			)))
+			.unwrap();
+	}
+
+	fn type_text(commit: &mut CommitComponent, text: &str) {
+		for c in text.chars() {
+			press(commit, KeyCode::Char(c));
+		}
+	}
+
+	fn next_msg(commit: &mut CommitComponent) {
+		let key = commit.key_config.keys.commit_history_next;
+		commit
+			.event(&Event::Key(KeyEvent::new(
+				key.code,
+				key.modifiers,
+			)))
+			.unwrap();
+	}
+
+	#[test]
+	fn test_typed_text() {
+		assert_eq!(
+			typed_text("fix\n\n# template", "\n\n# template"),
+			"fix"
+		);
+		assert_eq!(
+			typed_text("# tem fix plate", "# template"),
+			"fix"
+		);
+		assert_eq!(typed_text("# template", "# template"), "");
+		assert_eq!(typed_text("fix", ""), "fix");
+	}
+
+	#[test]
+	fn test_history_query_ignores_prefill() {
+		let options = Options::in_memory();
+		options.borrow_mut().add_commit_msg("fix: crash on start");
+		options.borrow_mut().add_commit_msg("feat: add log");
+
+		let mut commit = CommitComponent::new(
+			RefCell::new(RepoPath::from("")),
+			Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			options,
+		);
+		commit.set_prefill("\n\n# template".to_string());
+		commit.input.show().unwrap();
+
+		type_text(&mut commit, "crash");
+		next_msg(&mut commit);
+		assert_eq!(commit.input.get_text(), "fix: crash on start");
+
+		// editing mid cycle starts over with the new query
+		for _ in 0.."fix: crash on start".len() {
+			press(&mut commit, KeyCode::Delete);
+		}
+		type_text(&mut commit, "log");
+		next_msg(&mut commit);
+		assert_eq!(commit.input.get_text(), "feat: add log");
+	}
+}
@@ -142,7 +142,8 @@ impl CommitDetailsComponent {
 		&self.file_tree
 	}
 
-	fn details_focused(&self) -> bool {
+	///
+	pub fn details_focused(&self) -> bool {
 		self.single_details.focused()
 			|| self.compare_details.focused()
 	}
@@ -0,0 +1,155 @@
+use super::{
+	textinput::TextInputComponent, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{backend::Backend, layout::Rect, Frame};
+
+/// shows and edits the git note of a commit
+pub struct CommitNoteComponent {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commit_id: Option<CommitId>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CommitNoteComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for CommitNoteComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::note_save(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.save();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl CommitNoteComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			queue,
+			input: TextInputComponent::new(
+				theme,
+				key_config.clone(),
+				"",
+				&strings::note_popup_msg(),
+				true,
+			),
+			commit_id: None,
+			key_config,
+			repo,
+		}
+	}
+
+	/// opens the popup with the current note of `id`, if any
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		let note = sync::get_note(&self.repo.borrow(), id)?;
+
+		self.commit_id = Some(id);
+		self.input.set_title(strings::note_popup_title(&id));
+		self.input.set_text(
+			note.map(|note| note.trim_end().to_string())
+				.unwrap_or_default(),
+		);
+		self.show()?;
+
+		Ok(())
+	}
+
+	/// an empty note removes it
+	fn save(&mut self) {
+		if let Some(commit_id) = self.commit_id {
+			let result = sync::set_note(
+				&self.repo.borrow(),
+				commit_id,
+				self.input.get_text().trim(),
+			);
+			match result {
+				Ok(()) => {
+					self.input.clear();
+					self.hide();
+
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL | NeedsUpdate::NOTES,
+					));
+				}
+				Err(e) => {
+					self.hide();
+
+					log::error!("e: {}", e);
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("note error:\n{e}"),
+					));
+				}
+			}
+		}
+	}
+}
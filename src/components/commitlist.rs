@@ -1,7 +1,7 @@
-use super::filter_options::FilterOptionsPopupComponent;
+use super::filter_options::{FilterOptionsPopupComponent, MergeFilter};
 use super::search_options::SearchOptionsPopupComponent;
 use super::utils::logitems::{ItemBatch, LogEntry};
-use super::TextInputComponent;
+use super::{commit_with_hooks, CommitResult, TextInputComponent};
 use crate::queue::{
 	create_local_queue, CustomConfirmData, InternalEvent, LocalEvent,
 	NeedsUpdate, SharedLocalQueue,
@@ -12,6 +12,7 @@ use crate::{
 		Component, DrawableComponent, EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::{ListState, LogRefs, SharedOptions},
 	queue::Queue,
 	strings::{self, symbol},
 	try_or_popup,
@@ -20,8 +21,9 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::sync::branch::checkout_branch_cmd;
+use asyncgit::CommitSearchPredicate;
 use asyncgit::sync::{
-	self, checkout_commit, cherrypick, filter_by_path, get_commit_info, get_head, BranchDetails, BranchInfo, CommitId, LogWalkerFilter, RepoPathRef, RepoState, Tags
+	self, checkout_commit, cherrypick, filter_by_path, get_commit_info, get_head, BranchDetails, BranchInfo, CommitId, LogWalkerFilter, RepoPathRef, RepoState, SignatureStatus, Tags
 };
 
 use chrono::{DateTime, Local};
@@ -37,8 +39,13 @@ use ratatui::{
 };
 use std::path::PathBuf;
 use std::{
-	borrow::Cow, cell::Cell, cmp, collections::BTreeMap,
-	convert::TryFrom, time::Instant,
+	borrow::Cow,
+	cell::Cell,
+	cmp,
+	collections::{BTreeMap, HashMap, HashSet},
+	convert::TryFrom,
+	sync::Arc,
+	time::Instant,
 };
 
 const ELEMENTS_PER_LINE: usize = 9;
@@ -74,6 +81,7 @@ pub enum ExternalSearchRequest {
 }
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct CommitList {
 	repo: RepoPathRef,
 	title: Box<str>,
@@ -88,6 +96,8 @@ pub struct CommitList {
 	remote_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
 	current_size: Cell<Option<(u16, u16)>>,
 	scroll_top: Cell<usize>,
+	/// line to put the selection on when next drawn
+	restore_row: Cell<Option<usize>>,
 	theme: SharedTheme,
 	queue: Queue,
 	key_config: SharedKeyConfig,
@@ -105,6 +115,17 @@ pub struct CommitList {
 	local_queue: SharedLocalQueue,
 	path_filter: PathBuf,
 	branches_update_needed: bool,
+	show_committer: bool,
+	show_signature: bool,
+	show_file_count: bool,
+	refs: LogRefs,
+	signatures: HashMap<CommitId, SignatureStatus>,
+	file_counts: HashMap<CommitId, usize>,
+	notes: HashSet<CommitId>,
+	options: SharedOptions,
+	range_mark_start: Option<usize>,
+	range_mark_request: Option<(usize, usize)>,
+	search_progress: Option<u8>,
 }
 
 impl CommitList {
@@ -115,7 +136,9 @@ impl CommitList {
 		theme: SharedTheme,
 		queue: Queue,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
+		let refs = options.borrow().log_refs();
 		Self {
 			repo,
 			items: ItemBatch::default(),
@@ -129,6 +152,7 @@ impl CommitList {
 			remote_branches: BTreeMap::default(),
 			current_size: Cell::new(None),
 			scroll_top: Cell::new(0),
+			restore_row: Cell::new(None),
 			theme: theme.clone(),
 			queue,
 			key_config: key_config.clone(),
@@ -169,6 +193,17 @@ impl CommitList {
 			local_queue: create_local_queue(),
 			path_filter: PathBuf::new(),
 			branches_update_needed: false,
+			show_committer: false,
+			show_signature: false,
+			show_file_count: false,
+			refs,
+			signatures: HashMap::new(),
+			file_counts: HashMap::new(),
+			notes: HashSet::new(),
+			options,
+			range_mark_start: None,
+			range_mark_request: None,
+			search_progress: None,
 		}
 	}
 
@@ -204,15 +239,21 @@ impl CommitList {
 						if s == "cherrypick" =>
 					{
 						self.branches_update_needed = true;
-						self.cherrypick_marked()
+						self.with_autostash(Self::cherrypick_marked);
 					}
 					LocalEvent::Confirmed(ref s) if s == "drop" => {
 						self.branches_update_needed = true;
-						self.drop_marked()
+						self.with_autostash(Self::drop_marked);
 					}
 					LocalEvent::Confirmed(ref s) if s == "fixup" => {
 						self.branches_update_needed = true;
-						self.fixup_marked()
+						self.with_autostash(Self::fixup_marked);
+					}
+					LocalEvent::Confirmed(ref s)
+						if s == "autosquash" =>
+					{
+						self.branches_update_needed = true;
+						self.autosquash_marked();
 					}
 					LocalEvent::PickFile(p) => {
 						self.update_path_filter(p);
@@ -230,7 +271,9 @@ impl CommitList {
 	///
 	pub fn show_search(&mut self) {
 		if let Ok(_) = self.search_field.show() {
-			self.search_field.clear();
+			self.search_field.set_text_cursor_end(
+				self.options.borrow().last_search().to_string(),
+			);
 			self.focused_field = Focused::InputSearch;
 		}
 	}
@@ -243,7 +286,9 @@ impl CommitList {
 
 	pub fn show_filter(&mut self) {
 		if let Ok(_) = self.filter_field.show() {
-			self.filter_field.clear();
+			self.filter_field.set_text_cursor_end(
+				self.options.borrow().last_filter().to_string(),
+			);
 			self.focused_field = Focused::InputFilter;
 		}
 	}
@@ -280,18 +325,79 @@ impl CommitList {
 		&mut self.items
 	}
 
+	fn is_path_filter_active(&self) -> bool {
+		!self.path_filter.as_os_str().is_empty()
+	}
+
+	/// selects the closest loaded commit older (or newer) than the
+	/// selection that touched the filtered path
+	fn jump_path_commit(&mut self, older: bool) -> Result<bool> {
+		if !self.is_path_filter_active() {
+			return Ok(false);
+		}
+
+		let path = self.path_filter_spec();
+		let repo = self.repo.borrow().clone();
+		let local_selection =
+			self.selection.saturating_sub(self.items.index_offset());
+		let candidates: Vec<(usize, CommitId)> = if older {
+			self.items
+				.iter()
+				.enumerate()
+				.skip(local_selection + 1)
+				.map(|(idx, e)| (idx, e.id))
+				.collect()
+		} else {
+			self.items
+				.iter()
+				.enumerate()
+				.take(local_selection)
+				.rev()
+				.map(|(idx, e)| (idx, e.id))
+				.collect()
+		};
+
+		for (idx, id) in candidates {
+			if sync::commit_touches_path(&repo, id, &path)? {
+				self.select_entry(self.items.index_offset() + idx);
+				return Ok(true);
+			}
+		}
+
+		Ok(false)
+	}
+
+	/// header line for the path filter: path, number of commits
+	/// touching it and the position of the selection among them
+	fn path_filter_header(&self) -> String {
+		let path = self.path_filter.to_str().unwrap_or("");
+		if self.count_total == 0 {
+			format!("{path} (no commits)")
+		} else {
+			format!(
+				"{path} ({} commits, {}/{})",
+				self.count_total,
+				self.selection + 1,
+				self.count_total,
+			)
+		}
+	}
+
+	/// `path_filter` as pathspec, without a leading `./`
+	fn path_filter_spec(&self) -> String {
+		self.path_filter
+			.iter()
+			.skip_while(|i| i.to_str().unwrap_or("") == ".")
+			.map(|i| i.to_str().unwrap_or(""))
+			.collect::<Vec<_>>()
+			.join(std::path::MAIN_SEPARATOR_STR)
+	}
+
 	pub fn get_path_filter(&self) -> Option<LogWalkerFilter> {
 		if self.path_filter.as_os_str().is_empty() {
 			None
 		} else {
-			let p: String = self
-				.path_filter
-				.iter()
-				.skip_while(|i| i.to_str().unwrap_or("") == ".")
-				.map(|i| i.to_str().unwrap_or(""))
-				.collect::<Vec<_>>()
-				.join(std::path::MAIN_SEPARATOR_STR);
-			Some(filter_by_path(p, true))
+			Some(filter_by_path(self.path_filter_spec(), true))
 		}
 	}
 
@@ -299,35 +405,43 @@ impl CommitList {
 		if self.filter_field.is_visible()
 			&& !self.filter_field.get_text().is_empty()
 		{
-			let filter_txt =
-				self.filter_field.get_text().to_lowercase();
+			let terms: Vec<String> = self
+				.filter_field
+				.get_text()
+				.to_lowercase()
+				.split_whitespace()
+				.map(String::from)
+				.collect();
 			let filter_author = self.filter_options.author;
 			let filter_msg = self.filter_options.message;
+			let match_all = self.filter_options.match_all_terms;
 			Some(std::sync::Arc::new(Box::new(
 				move |_repo,
 				      _commit_id: &CommitId,
 				      commit: &asyncgit::sync::Commit|
 				      -> Result<bool, asyncgit::Error> {
-					if filter_author
-						&& commit
+					let author = if filter_author {
+						commit
 							.author()
 							.name()
-							.unwrap()
+							.unwrap_or_default()
 							.to_lowercase()
-							.contains(&filter_txt)
-					{
-						Ok(true)
-					} else if filter_msg
-						&& commit
+					} else {
+						String::new()
+					};
+					let msg = if filter_msg {
+						commit
 							.message()
-							.unwrap()
+							.unwrap_or_default()
 							.to_lowercase()
-							.contains(&filter_txt)
-					{
-						Ok(true)
 					} else {
-						Ok(false)
-					}
+						String::new()
+					};
+
+					Ok(filter_terms_match(&terms, match_all, |term| {
+						(filter_author && author.contains(term))
+							|| (filter_msg && msg.contains(term))
+					}))
 				},
 			)))
 		} else {
@@ -335,6 +449,74 @@ impl CommitList {
 		}
 	}
 
+	/// only active while the filter input is shown
+	pub fn get_merge_filter(&self) -> Option<LogWalkerFilter> {
+		if !self.filter_field.is_visible() {
+			return None;
+		}
+
+		let only_merges = match self.filter_options.merges {
+			MergeFilter::All => return None,
+			MergeFilter::OnlyMerges => true,
+			MergeFilter::NoMerges => false,
+		};
+
+		Some(std::sync::Arc::new(Box::new(
+			move |_repo,
+			      _commit_id: &CommitId,
+			      commit: &asyncgit::sync::Commit|
+			      -> Result<bool, asyncgit::Error> {
+				Ok((commit.parent_count() > 1) == only_merges)
+			},
+		)))
+	}
+
+	/// runs `f` between stashing and restoring local changes if
+	/// `rebase_autostash` is set, like `git rebase --autostash`
+	fn with_autostash(&mut self, f: impl FnOnce(&mut Self)) {
+		if !self.options.borrow().rebase_autostash() {
+			f(self);
+			return;
+		}
+
+		let stash = match sync::stash_save_auto(&self.repo.borrow()) {
+			Ok(stash) => stash,
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("autostash failed: {e}"),
+				));
+				return;
+			}
+		};
+
+		f(self);
+
+		if let Some(stash) = stash {
+			self.restore_autostash(stash);
+		}
+	}
+
+	/// a stash that cannot be popped is kept and reported
+	fn restore_autostash(&self, stash: CommitId) {
+		let repo = self.repo.borrow();
+
+		// popping into an unfinished rebase would mix both changes
+		let res = match sync::repo_state(&repo) {
+			Ok(RepoState::Clean) => sync::stash_pop(&repo, stash),
+			Ok(state) => Err(asyncgit::Error::Generic(format!(
+				"repo is in state {state:?}"
+			))),
+			Err(e) => Err(e),
+		};
+
+		if let Err(e) = res {
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"restoring autostashed changes failed: {e}\nthey are kept in stash {}",
+				stash.get_short_string()
+			)));
+		}
+	}
+
 	fn cherrypick_marked(&mut self) {
 		//implement
 		let repo = self.repo.borrow();
@@ -360,24 +542,66 @@ impl CommitList {
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 	}
 
-	fn fixup_marked(&mut self) {
-		let oldest_commit = self
-			.marked
-			.iter()
-			.max_by(|x, y| x.0.cmp(&y.0))
-			.unwrap()
-			.1
-			.clone();
-		let oldest_to_use: CommitId = asyncgit::sync::parent_ids(
+	/// starts an interactive rebase of `HEAD` onto the single marked
+	/// commit, which has to be one of its ancestors
+	fn rebase_onto_marked(&mut self) {
+		let base = self.marked[0].1;
+		match sync::is_ancestor_of_head(&self.repo.borrow(), base) {
+			Ok(true) => {
+				self.marked.clear();
+				self.queue.push(
+					InternalEvent::RebaseInteractive(base),
+				);
+			}
+			Ok(false) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(format!(
+					"cannot rebase onto {}: it is not an ancestor of HEAD",
+					base.get_short_string()
+				)));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(format!(
+					"cannot rebase: {e}"
+				)));
+			}
+		}
+	}
+
+	/// first-parent ancestor `depth` levels below the oldest marked
+	/// commit, reports an error and returns `None` if there is none
+	fn marked_rebase_base(&self, depth: usize) -> Option<CommitId> {
+		let oldest_commit =
+			self.marked.iter().max_by(|x, y| x.0.cmp(&y.0))?.1;
+		match asyncgit::sync::first_parent_ancestor(
 			&self.repo.borrow(),
 			oldest_commit,
-		)
-		.unwrap()[0];
-		let base: CommitId = asyncgit::sync::parent_ids(
-			&self.repo.borrow(),
-			oldest_to_use,
-		)
-		.unwrap()[0];
+			depth,
+		) {
+			Ok(base) => Some(base),
+			Err(asyncgit::Error::NoParent) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					"cannot rebase: selection includes the initial commit"
+						.into(),
+				));
+				None
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(format!(
+					"cannot find rebase base: {}",
+					e
+				)));
+				None
+			}
+		}
+	}
+
+	fn fixup_marked(&mut self) {
+		// the oldest marked commit gets squashed into its parent,
+		// so the rebase has to start one level further down
+		let base = match self.marked_rebase_base(2) {
+			Some(base) => base,
+			None => return,
+		};
 		let list: Vec<_> = self.marked.iter().map(|i| &i.1).collect();
 		if let Err(e) =
 			asyncgit::sync::extern_git::rebase_fixup_commits(
@@ -395,19 +619,75 @@ impl CommitList {
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 	}
 
+	fn autosquash_marked(&mut self) {
+		let list: Vec<_> = self.marked.iter().map(|i| &i.1).collect();
+		match asyncgit::sync::extern_git::rebase_autosquash_commits(
+			self.repo.borrow().gitpath().to_str().unwrap(),
+			&list,
+		) {
+			Ok(squashed) => {
+				self.marked.clear();
+				self.queue.push(InternalEvent::ShowInfoMsg(format!(
+					"{squashed} commit(s) squashed into their targets"
+				)));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(format!(
+					"Autosquash failed: {e}"
+				)));
+			}
+		}
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	/// commits the staged changes as a fixup of the selected commit,
+	/// running the same hooks as a normal commit
+	fn commit_fixup_selected(&self) {
+		let id = match self.selected_entry() {
+			Some(entry) => entry.id,
+			None => return,
+		};
+		let repo = self.repo.borrow();
+		let res = sync::fixup_message(&repo, id)
+			.map_err(anyhow::Error::from)
+			.and_then(|msg| {
+				commit_with_hooks(&repo, &self.queue, msg, |msg| {
+					sync::commit(&repo, msg)?;
+					Ok(())
+				})
+			});
+		match res {
+			Ok(CommitResult::ComitDone) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(format!(
+					"Created fixup! commit for {}",
+					id.get_short_string()
+				)));
+			}
+			// the failing hook is reported already
+			Ok(CommitResult::Aborted) => (),
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(format!(
+					"Fixup commit failed: {e}"
+				)));
+			}
+		}
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	/// whether any marked commit is a `fixup!`/`squash!` commit
+	fn marked_has_autosquash(&self) -> bool {
+		self.marked.iter().any(|i| {
+			self.get_commit_short_summary(&i.1).map_or(false, |s| {
+				s.starts_with("fixup! ") || s.starts_with("squash! ")
+			})
+		})
+	}
+
 	fn drop_marked(&mut self) {
-		let oldest_commit = self
-			.marked
-			.iter()
-			.max_by(|x, y| x.0.cmp(&y.0))
-			.unwrap()
-			.1
-			.clone();
-		let base: CommitId = asyncgit::sync::parent_ids(
-			&self.repo.borrow(),
-			oldest_commit,
-		)
-		.unwrap()[0];
+		let base = match self.marked_rebase_base(1) {
+			Some(base) => base,
+			None => return,
+		};
 		let list: Vec<_> = self.marked.iter().map(|i| &i.1).collect();
 		if let Err(e) =
 			asyncgit::sync::extern_git::rebase_drop_commits(
@@ -425,6 +705,107 @@ impl CommitList {
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 	}
 
+	/// commits around the selection whose signature was not
+	/// verified yet, empty unless signatures are shown
+	pub fn missing_signatures(&self) -> Vec<CommitId> {
+		if !self.show_signature {
+			return Vec::new();
+		}
+
+		self.commits_around_selection()
+			.filter(|id| !self.signatures.contains_key(id))
+			.collect()
+	}
+
+	/// commits around the selection whose changed files were not
+	/// counted yet, empty unless the count is shown
+	pub fn missing_file_counts(&self) -> Vec<CommitId> {
+		if !self.show_file_count {
+			return Vec::new();
+		}
+
+		self.commits_around_selection()
+			.filter(|id| !self.file_counts.contains_key(id))
+			.collect()
+	}
+
+	/// one page above and below the selection
+	fn commits_around_selection(
+		&self,
+	) -> impl Iterator<Item = CommitId> + '_ {
+		let height = self
+			.current_size
+			.get()
+			.map_or(0, |size| usize::from(size.1));
+
+		self.items
+			.iter()
+			.skip(self.relative_selection().saturating_sub(height))
+			.take(height.saturating_mul(2).saturating_add(1))
+			.map(|e| e.id)
+	}
+
+	///
+	pub fn set_signatures(
+		&mut self,
+		signatures: Vec<(CommitId, SignatureStatus)>,
+	) {
+		self.signatures.extend(signatures);
+	}
+
+	/// shows the signature column or hides it
+	pub fn toggle_signatures(&mut self) {
+		self.show_signature = !self.show_signature;
+	}
+
+	///
+	pub const fn shows_signatures(&self) -> bool {
+		self.show_signature
+	}
+
+	///
+	pub fn hide_signatures(&mut self) {
+		self.show_signature = false;
+	}
+
+	///
+	pub fn set_file_counts(
+		&mut self,
+		counts: Vec<(CommitId, usize)>,
+	) {
+		self.file_counts.extend(counts);
+	}
+
+	/// shows the changed files column or hides it
+	pub fn toggle_file_counts(&mut self) {
+		self.show_file_count = !self.show_file_count;
+	}
+
+	///
+	pub const fn shows_file_counts(&self) -> bool {
+		self.show_file_count
+	}
+
+	///
+	pub fn hide_file_counts(&mut self) {
+		self.show_file_count = false;
+	}
+
+	/// commits to mark as having a note attached
+	pub fn set_notes(&mut self, notes: HashSet<CommitId>) {
+		self.notes = notes;
+	}
+
+	/// shows the committer in place of the author or back
+	pub fn toggle_committer(&mut self) {
+		self.show_committer = !self.show_committer;
+	}
+
+	///
+	pub const fn shows_committer(&self) -> bool {
+		self.show_committer
+	}
+
 	///
 	pub fn set_title(&mut self, t: Box<str>) {
 		self.title = t;
@@ -475,6 +856,35 @@ impl CommitList {
 		)
 	}
 
+	/// selection and scroll position to persist across launches
+	pub fn view_state(&self) -> ListState {
+		ListState {
+			commit: self
+				.selected_entry()
+				.map(|e| e.id.to_string())
+				.unwrap_or_default(),
+			selection: self.selection,
+			row: self
+				.relative_selection()
+				.saturating_sub(self.scroll_top.get()),
+		}
+	}
+
+	/// selects `position`, falling back to the stored index, clamped
+	/// to the current length in case the list got shorter since
+	pub fn restore_view_state(
+		&mut self,
+		position: Option<usize>,
+		state: &ListState,
+	) {
+		self.select_entry(
+			position
+				.unwrap_or(state.selection)
+				.min(self.selection_max()),
+		);
+		self.restore_row.set(Some(state.row));
+	}
+
 	pub fn clear_last_selected_commit(&mut self) {
 		self.last_selected_commit = None;
 	}
@@ -573,12 +983,80 @@ impl CommitList {
 		}
 	}
 
-	fn update_scroll_speed(&mut self) {
-		const REPEATED_SCROLL_THRESHOLD_MILLIS: u128 = 300;
-		const SCROLL_SPEED_START: f32 = 0.1_f32;
-		const SCROLL_SPEED_MAX: f32 = 10_f32;
-		const SCROLL_SPEED_MULTIPLIER: f32 = 1.05_f32;
+	fn start_mark_range(&mut self) {
+		self.range_mark_start = Some(self.selection);
+	}
+
+	/// toggles all commits between the range start and the selection,
+	/// without a range start this behaves like a single `mark`
+	fn mark_range(&mut self) {
+		if let Some(start) = self.range_mark_start.take() {
+			self.mark_range_from(start);
+		} else {
+			self.mark();
+		}
+	}
+
+	/// asks for the ids to be loaded if they are not in our batch
+	fn mark_range_from(&mut self, start: usize) {
+		let first = start.min(self.selection);
+		let last = start.max(self.selection);
+
+		if first >= self.items.index_offset()
+			&& last < self.items.last_idx()
+		{
+			let ids: Vec<CommitId> = self
+				.items
+				.iter()
+				.skip(first - self.items.index_offset())
+				.take(last - first + 1)
+				.map(|e| e.id)
+				.collect();
+			self.toggle_marked_range(first, &ids);
+		} else {
+			self.range_mark_request = Some((first, last));
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+	}
+
+	/// pending (first, last) range whose ids are not loaded yet
+	pub fn take_range_mark_request(
+		&mut self,
+	) -> Option<(usize, usize)> {
+		self.range_mark_request.take()
+	}
 
+	/// marks `ids` (starting at absolute index `first`),
+	/// unless all of them already are marked, then unmarks them.
+	/// like `mark` this keeps their index within the loaded batch
+	pub fn toggle_marked_range(
+		&mut self,
+		first: usize,
+		ids: &[CommitId],
+	) {
+		let all_marked = ids
+			.iter()
+			.all(|id| self.marked.iter().any(|m| m.1 == *id));
+
+		if all_marked {
+			self.marked.retain(|m| !ids.contains(&m.1));
+		} else {
+			for (idx, id) in ids.iter().enumerate() {
+				if !self.marked.iter().any(|m| m.1 == *id) {
+					self.marked.push((
+						(first + idx).saturating_sub(
+							self.items.index_offset(),
+						),
+						*id,
+					));
+				}
+			}
+			// stable, commits above the batch all share index 0
+			self.marked.sort_by_key(|marked| marked.0);
+		}
+	}
+
+	fn update_scroll_speed(&mut self) {
 		let now = Instant::now();
 
 		let since_last_scroll =
@@ -586,15 +1064,11 @@ impl CommitList {
 
 		self.scroll_state.0 = now;
 
-		let speed = if since_last_scroll.as_millis()
-			< REPEATED_SCROLL_THRESHOLD_MILLIS
-		{
-			self.scroll_state.1 * SCROLL_SPEED_MULTIPLIER
-		} else {
-			SCROLL_SPEED_START
-		};
-
-		self.scroll_state.1 = speed.min(SCROLL_SPEED_MAX);
+		self.scroll_state.1 = self
+			.options
+			.borrow()
+			.scroll_speed()
+			.next(self.scroll_state.1, since_last_scroll);
 	}
 
 	fn is_marked(&self, id: &CommitId) -> Option<bool> {
@@ -607,7 +1081,10 @@ impl CommitList {
 		}
 	}
 
-	#[allow(clippy::too_many_arguments)]
+	#[allow(
+		clippy::too_many_arguments,
+		clippy::fn_params_excessive_bools
+	)]
 	fn get_entry_to_add<'a>(
 		e: &'a LogEntry,
 		selected: bool,
@@ -619,9 +1096,21 @@ impl CommitList {
 		width: usize,
 		now: DateTime<Local>,
 		marked: Option<bool>,
+		show_committer: bool,
+		show_signature: bool,
+		// `None` until verified
+		signature: Option<SignatureStatus>,
+		show_file_count: bool,
+		// `None` until counted
+		file_count: Option<usize>,
+		author_width: usize,
+		has_note: bool,
 	) -> Spans<'a> {
 		let mut txt: Vec<Span> = Vec::with_capacity(
-			ELEMENTS_PER_LINE + if marked.is_some() { 2 } else { 0 },
+			ELEMENTS_PER_LINE
+				+ if marked.is_some() { 2 } else { 0 }
+				+ if show_signature { 2 } else { 0 }
+				+ if show_file_count { 2 } else { 0 },
 		);
 
 		let splitter_txt = Cow::from(symbol::EMPTY_SPACE);
@@ -651,6 +1140,34 @@ impl CommitList {
 
 		txt.push(splitter.clone());
 
+		// signature status
+		if show_signature {
+			txt.push(Span::styled(
+				Cow::from(match signature {
+					Some(SignatureStatus::Good) => symbol::CHECKMARK,
+					Some(SignatureStatus::Bad) => symbol::CROSSMARK,
+					Some(SignatureStatus::Unsigned) => {
+						symbol::WHITESPACE
+					}
+					None => symbol::EMPTY_SPACE,
+				}),
+				theme.commit_signature(signature, selected),
+			));
+			txt.push(splitter.clone());
+		}
+
+		// number of changed files
+		if show_file_count {
+			txt.push(Span::styled(
+				Cow::from(file_count.map_or_else(
+					|| format!("{:>4}", symbol::ELLIPSIS),
+					|count| format!("{count:>4}"),
+				)),
+				theme.commit_time(selected),
+			));
+			txt.push(splitter.clone());
+		}
+
 		// commit timestamp
 		txt.push(Span::styled(
 			Cow::from(e.time_to_string(now)),
@@ -659,11 +1176,14 @@ impl CommitList {
 
 		txt.push(splitter.clone());
 
-		let author_width =
-			(width.saturating_sub(19) / 3).clamp(3, 20);
-		let author = string_width_align(&e.author, author_width);
+		let author = if show_committer {
+			e.committer.as_deref().unwrap_or(&e.author)
+		} else {
+			&e.author
+		};
+		let author = string_width_align(author, author_width);
 
-		// commit author
+		// commit author (or committer)
 		txt.push(Span::styled::<String>(
 			author,
 			theme.commit_author(selected),
@@ -693,6 +1213,14 @@ impl CommitList {
 			));
 		}
 
+		if has_note {
+			txt.push(splitter.clone());
+			txt.push(Span::styled(
+				Cow::from(symbol::NOTE),
+				theme.tags(selected),
+			));
+		}
+
 		txt.push(splitter);
 
 		let message_width = width.saturating_sub(
@@ -717,6 +1245,9 @@ impl CommitList {
 
 		let any_marked = !self.marked.is_empty();
 
+		let author_width =
+			self.options.borrow().author_width().columns(width);
+
 		for (idx, e) in self
 			.items
 			.iter()
@@ -724,17 +1255,22 @@ impl CommitList {
 			.take(height)
 			.enumerate()
 		{
-			let tags =
-				self.tags.as_ref().and_then(|t| t.get(&e.id)).map(
-					|tags| {
-						tags.iter()
-							.map(|t| format!("<{}>", t.name))
-							.join(" ")
-					},
-				);
+			let tags = self
+				.tags
+				.as_ref()
+				.filter(|_| self.refs.tags)
+				.and_then(|t| t.get(&e.id))
+				.map(|tags| {
+					tags.iter()
+						.map(|t| format!("<{}>", t.name))
+						.join(" ")
+				});
 
-			let local_branches =
-				self.local_branches.get(&e.id).map(|local_branch| {
+			let local_branches = self
+				.local_branches
+				.get(&e.id)
+				.filter(|_| self.refs.local_branches)
+				.map(|local_branch| {
 					local_branch
 						.iter()
 						.map(|local_branch| {
@@ -746,6 +1282,7 @@ impl CommitList {
 			let remote_branches = self
 				.remote_branches
 				.get(&e.id)
+				.filter(|_| self.refs.remote_branches)
 				.and_then(|remote_branches| {
 					let filtered_branches: Vec<_> = remote_branches
 						.iter()
@@ -799,12 +1336,24 @@ impl CommitList {
 				width,
 				now,
 				marked,
+				self.show_committer,
+				self.show_signature,
+				self.signatures.get(&e.id).copied(),
+				self.show_file_count,
+				self.file_counts.get(&e.id).copied(),
+				author_width,
+				self.notes.contains(&e.id),
 			));
 		}
 
 		txt
 	}
 
+	fn toggle_refs(&mut self, f: impl FnOnce(&mut LogRefs)) {
+		f(&mut self.refs);
+		self.options.borrow_mut().set_log_refs(self.refs);
+	}
+
 	#[allow(clippy::missing_const_for_fn)]
 	fn relative_selection(&self) -> usize {
 		self.selection.saturating_sub(self.items.index_offset())
@@ -869,18 +1418,26 @@ impl CommitList {
 			&& self.search_options.sha;
 	}
 
-	pub fn search_commit_check(
-		&self,
-		needle: &str,
-		author: &str,
-		message: &str,
-		hash: &str,
-	) -> bool {
-		(self.search_options.message
-			&& message.to_lowercase().contains(needle))
-			|| (self.search_options.author
-				&& author.to_lowercase().contains(needle))
-			|| (self.search_options.sha && hash.contains(&needle))
+	/// matches a commit by `(author, message, hash)` against the
+	/// current search, for the loaded items and background searches
+	pub fn search_predicate(&self) -> CommitSearchPredicate {
+		let needle = self.get_search_needle();
+		let message = self.search_options.message;
+		let author = self.search_options.author;
+		let sha = self.search_options.sha;
+
+		Arc::new(move |commit_author, commit_message, hash| {
+			(message
+				&& commit_message.to_lowercase().contains(&needle))
+				|| (author
+					&& commit_author.to_lowercase().contains(&needle))
+				|| (sha && hash.contains(&needle))
+		})
+	}
+
+	/// `Some(percent)` while a background search is running
+	pub fn set_search_progress(&mut self, progress: Option<u8>) {
+		self.search_progress = progress;
 	}
 
 	pub fn search_commit_forward(&mut self) {
@@ -889,19 +1446,14 @@ impl CommitList {
 		}
 		let local_selection =
 			self.selection - self.items.index_offset();
-		let needle = self.get_search_needle();
+		let check = self.search_predicate();
 		let res = self
 			.items
 			.iter()
 			.enumerate()
 			.skip(local_selection + 1)
 			.filter(|item| {
-				self.search_commit_check(
-					&needle,
-					&item.1.author,
-					&item.1.msg,
-					&item.1.hash_full,
-				)
+				check(&item.1.author, &item.1.msg, &item.1.hash_full)
 			})
 			.map(|item| item.0)
 			.nth(0);
@@ -920,7 +1472,7 @@ impl CommitList {
 		}
 		let local_selection =
 			self.selection - self.items.index_offset();
-		let needle = self.current_search.to_lowercase();
+		let check = self.search_predicate();
 		let res = self
 			.items
 			.iter()
@@ -928,12 +1480,7 @@ impl CommitList {
 			.enumerate()
 			.rev()
 			.filter(|item| {
-				self.search_commit_check(
-					&needle,
-					&item.1.author,
-					&item.1.msg,
-					&item.1.hash_full,
-				)
+				check(&item.1.author, &item.1.msg, &item.1.hash_full)
 			})
 			.map(|item| item.0)
 			.nth(0);
@@ -1020,7 +1567,10 @@ impl CommitList {
 					k,
 					self.key_config.keys.start_search_forward_init,
 				) {
-					self.search_options.enable_all();
+					// one-shot keys below override the scope
+					// without replacing the remembered one
+					let scope = self.options.borrow().search_scope();
+					self.search_options.set_scope(scope);
 					self.show_search();
 					Some(EventState::Consumed)
 				} else if key_match(
@@ -1107,25 +1657,82 @@ impl CommitList {
 				} else if key_match(k, self.key_config.keys.page_down)
 				{
 					self.move_selection(ScrollType::PageDown)?
+				} else if key_match(
+					k,
+					self.key_config.keys.log_path_next,
+				) {
+					self.jump_path_commit(true)?
+				} else if key_match(
+					k,
+					self.key_config.keys.log_path_prev,
+				) {
+					self.jump_path_commit(false)?
 				} else if key_match(
 					k,
 					self.key_config.keys.log_mark_commit,
 				) {
 					self.mark();
 					true
+				} else if key_match(
+					k,
+					self.key_config.keys.log_mark_range_start,
+				) {
+					self.start_mark_range();
+					true
+				} else if key_match(
+					k,
+					self.key_config.keys.log_mark_range,
+				) {
+					self.mark_range();
+					true
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_local_branches,
+				) {
+					self.toggle_refs(|refs| {
+						refs.local_branches = !refs.local_branches;
+					});
+					true
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_remote_branches,
+				) {
+					self.toggle_refs(|refs| {
+						refs.remote_branches = !refs.remote_branches;
+					});
+					true
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_tags,
+				) {
+					self.toggle_refs(|refs| refs.tags = !refs.tags);
+					true
 				} else if key_match(
 					k,
 					self.key_config.keys.rebase_interactive,
 				) && git_state == RepoState::Clean {
 					if let Some(id) = self.get_last_selected_commit() {
-						let base: CommitId = asyncgit::sync::parent_ids(
+						match asyncgit::sync::first_parent_ancestor(
 							&self.repo.borrow(),
 							*id,
-						)
-						.unwrap()[0];
-						self.queue.push(InternalEvent::RebaseInteractiveWithEditor(base));
+							1,
+						) {
+							Ok(base) => self.queue.push(InternalEvent::RebaseInteractive(base)),
+							Err(e) => self.queue.push(InternalEvent::ShowErrorMsg(format!(
+								"cannot rebase: {}",
+								e
+							))),
+						}
 					}
 					true
+				} else if key_match(
+					k,
+					self.key_config.keys.rebase_interactive_onto_marked,
+				) && git_state == RepoState::Clean
+					&& self.marked_count() == 1
+				{
+					self.rebase_onto_marked();
+					true
 				} else if key_match(
 					k,
 					self.key_config.keys.rebase_continue,
@@ -1198,6 +1805,45 @@ impl CommitList {
 						);
 					}
 					true
+				} else if key_match(
+					k,
+					self.key_config.keys.rebase_autosquash_marked,
+				) {
+					let auto_marked = self.marked.is_empty();
+					if auto_marked {
+						self.mark();
+					}
+					if self.marked_has_autosquash() {
+						self.queue.push(
+							InternalEvent::ConfirmCustom(
+								CustomConfirmData {
+									title: "Autosquash commits?"
+										.to_string(),
+									msg: self.get_marked_summary(),
+									confirm: "autosquash"
+										.to_string(),
+									q: self.local_queue.clone(),
+								},
+							),
+						);
+					} else {
+						if auto_marked {
+							self.marked.clear();
+						}
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							String::from(
+								"No fixup!/squash! commits selected",
+							),
+						));
+					}
+					true
+				} else if key_match(
+					k,
+					self.key_config.keys.commit_fixup,
+				) && git_state == RepoState::Clean
+				{
+					self.commit_fixup_selected();
+					true
 				} else if key_match(
 					k,
 					self.key_config.keys.cherrypick,
@@ -1341,6 +1987,10 @@ impl CommitList {
 				self.focused_field = Focused::List;
 				self.current_search =
 					self.search_field.get_text().to_string();
+				// submitting an empty search forgets the stored one
+				self.options
+					.borrow_mut()
+					.set_last_search(&self.current_search);
 				//start actual search
 				self.search_commit_forward();
 				Ok(EventState::Consumed)
@@ -1375,6 +2025,10 @@ impl CommitList {
 			if key_match(k, self.key_config.keys.enter) {
 				self.focused_field = Focused::List;
 				self.filter_updated = true;
+				// submitting an empty filter forgets the stored one
+				self.options
+					.borrow_mut()
+					.set_last_filter(self.filter_field.get_text());
 				Ok(EventState::Consumed)
 			} else if key_match(k, self.key_config.keys.exit_popup) {
 				self.stop_filter();
@@ -1436,13 +2090,26 @@ impl CommitList {
 	}
 }
 
+/// combines per term matches, a single term behaves the same either way
+fn filter_terms_match<F: Fn(&str) -> bool>(
+	terms: &[String],
+	match_all: bool,
+	matches: F,
+) -> bool {
+	if match_all {
+		terms.iter().all(|t| matches(t))
+	} else {
+		terms.iter().any(|t| matches(t))
+	}
+}
+
 impl DrawableComponent for CommitList {
 	fn draw<B: Backend>(
 		&self,
 		f: &mut Frame<B>,
 		area: Rect,
 	) -> Result<()> {
-		let path_visible = !self.path_filter.as_os_str().is_empty();
+		let path_visible = self.is_path_filter_active();
 		let original_area = area.clone();
 		let v_size_path = if path_visible { 2 } else { 0 };
 		let v_size_search =
@@ -1467,8 +2134,8 @@ impl DrawableComponent for CommitList {
 		let list_area = v_blocks[3];
 
 		if path_visible {
-			let p_filter = self.path_filter.to_str().unwrap_or("");
-			let path_ui = Paragraph::new(Span::raw(p_filter)).block(
+			let header = self.path_filter_header();
+			let path_ui = Paragraph::new(Span::raw(header)).block(
 				Block::default()
 					.borders(
 						Borders::TOP | Borders::RIGHT | Borders::LEFT,
@@ -1506,6 +2173,10 @@ impl DrawableComponent for CommitList {
 		let height_in_lines = current_size.1 as usize;
 		let selection = self.relative_selection();
 
+		if let Some(row) = self.restore_row.take() {
+			self.scroll_top.set(selection.saturating_sub(row));
+		}
+
 		self.scroll_top.set(calc_scroll_top(
 			self.scroll_top.get(),
 			height_in_lines,
@@ -1513,10 +2184,13 @@ impl DrawableComponent for CommitList {
 		));
 
 		let title = format!(
-			"{} {}/{}",
+			"{} {}/{}{}",
 			self.title,
 			self.count_total.saturating_sub(self.selection),
 			self.count_total,
+			self.search_progress
+				.map(|p| format!(" searching... {p}%"))
+				.unwrap_or_default(),
 		);
 
 		f.render_widget(
@@ -1568,9 +2242,22 @@ impl Component for CommitList {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.search_options.is_visible() {
 			self.search_options.event(ev)?;
+			if !self.search_options.is_visible() {
+				self.options
+					.borrow_mut()
+					.set_search_scope(self.search_options.scope());
+			}
 			return Ok(EventState::Consumed);
 		} else if self.filter_options.is_visible() {
 			self.filter_options.event(ev)?;
+			if !self.filter_options.is_visible()
+				&& self.filter_field.is_visible()
+			{
+				// options closed: re-apply with the new settings
+				self.filter_updated = true;
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
 			return Ok(EventState::Consumed);
 		}
 		match self.focused_field {
@@ -1604,11 +2291,49 @@ impl Component for CommitList {
 			true,
 			self.combo_state == KeyComboState::Empty,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_mark_range(
+				&self.key_config,
+				self.range_mark_start.is_some(),
+			),
+			true,
+			self.combo_state == KeyComboState::Empty,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_local_branches(
+				&self.key_config,
+				self.refs.local_branches,
+			),
+			true,
+			self.combo_state == KeyComboState::Empty,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_remote_branches(
+				&self.key_config,
+				self.refs.remote_branches,
+			),
+			true,
+			self.combo_state == KeyComboState::Empty,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_tags(
+				&self.key_config,
+				self.refs.tags,
+			),
+			true,
+			self.combo_state == KeyComboState::Empty,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::filter_by_path(&self.key_config),
 			true,
 			self.combo_state == KeyComboState::Empty,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::path_filter_jump(&self.key_config),
+			self.count_total > 1,
+			self.is_path_filter_active()
+				&& self.combo_state == KeyComboState::Empty,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::start_search(&self.key_config),
 			true,
@@ -1620,7 +2345,7 @@ impl Component for CommitList {
 			self.combo_state == KeyComboState::Empty,
 		));
 		out.push(CommandInfo::new(
-			strings::commands::search_all(&self.key_config),
+			strings::commands::search_last_scope(&self.key_config),
 			true,
 			self.combo_state == KeyComboState::SearchInitForward,
 		));
@@ -1663,6 +2388,13 @@ impl Component for CommitList {
 			self.is_list_focused() && is_clean,
 			self.is_list_focused() && is_clean,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::rebase_interactive_onto_marked(
+				&self.key_config,
+			),
+			self.marked_count() == 1,
+			self.is_list_focused() && is_clean,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::abort_rebase(&self.key_config),
 			rebase_active,
@@ -1683,6 +2415,17 @@ impl Component for CommitList {
 			self.is_list_focused() && is_clean,
 			self.is_list_focused() && is_clean
 		));
+		out.push(CommandInfo::new(
+			strings::commands::rebase_autosquash(&self.key_config),
+			self.is_list_focused() && is_clean,
+			self.is_list_focused() && is_clean,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::commit_fixup(&self.key_config),
+			self.is_list_focused()
+				&& is_clean && self.selected_entry().is_some(),
+			self.is_list_focused() && is_clean,
+		));
 
 		if self.combo_state == KeyComboState::Empty && git_state != RepoState::Rebase {
 			CommandBlocking::PassingOn
@@ -1703,6 +2446,276 @@ impl Component for CommitList {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{
+		keys::GituiKeyEvent,
+		options::{Options, SearchScope},
+		test_utils::{git, temp_repo},
+	};
+	use asyncgit::sync::RepoPath;
+	use crossterm::event::{KeyCode, KeyModifiers};
+	use std::{cell::RefCell, path::Path};
+
+	fn commit(
+		dir: &Path,
+		repo_path: &RepoPath,
+		msg: &str,
+	) -> CommitId {
+		git(dir, &["commit", "-q", "--allow-empty", "-m", msg]);
+		get_head(repo_path).unwrap()
+	}
+
+	fn text_lines(list: &CommitList) -> Vec<String> {
+		list.get_text(10, 200)
+			.iter()
+			.map(|line| {
+				line.0
+					.iter()
+					.map(|span| span.content.as_ref())
+					.collect()
+			})
+			.collect()
+	}
+
+	/// three commits, the oldest tagged `v1`, the second `v2`
+	fn tagged_list() -> (tempfile::TempDir, CommitList) {
+		let (td, repo_path) = temp_repo();
+		let dir = td.path();
+		let first = commit(dir, &repo_path, "first");
+		let second = commit(dir, &repo_path, "second");
+		let head = commit(dir, &repo_path, "third");
+
+		sync::tag_commit(&repo_path, &first, "v1", None).unwrap();
+		sync::tag_commit(
+			&repo_path,
+			&second,
+			"v2",
+			Some("annotated"),
+		)
+		.unwrap();
+
+		let mut list = CommitList::new(
+			RefCell::new(repo_path.clone()),
+			"",
+			SharedTheme::default(),
+			Queue::new(),
+			SharedKeyConfig::default(),
+			Options::in_memory(),
+		);
+		list.items().set_items(
+			0,
+			sync::get_commits_info(
+				&repo_path,
+				&[head, second, first],
+				50,
+			)
+			.unwrap(),
+		);
+		list.set_count_total(3);
+		list.set_tags(sync::get_tags(&repo_path).unwrap());
+
+		(td, list)
+	}
+
+	#[test]
+	fn test_tag_shown_at_tagged_commit() {
+		let (_td, list) = tagged_list();
+
+		let lines = text_lines(&list);
+		assert!(!lines[0].contains("<v"));
+		assert!(
+			lines[1].contains("<v2>") && !lines[1].contains("<v1>")
+		);
+		assert!(
+			lines[2].contains("<v1>") && !lines[2].contains("<v2>")
+		);
+	}
+
+	#[test]
+	fn test_hide_tags() {
+		let (_td, mut list) = tagged_list();
+
+		list.toggle_refs(|refs| refs.tags = false);
+		assert!(text_lines(&list).iter().all(|l| !l.contains("<v")));
+
+		list.toggle_refs(|refs| refs.tags = true);
+		assert!(text_lines(&list)[2].contains("<v1>"));
+	}
+
+	#[test]
+	fn test_note_marker() {
+		let (_td, mut list) = tagged_list();
+		let repo_path = list.repo.borrow().clone();
+		let second = list.items().iter().nth(1).unwrap().id;
+
+		sync::set_note(&repo_path, second, "reviewed").unwrap();
+		list.set_notes(
+			sync::get_commits_with_notes(&repo_path).unwrap(),
+		);
+
+		let lines = text_lines(&list);
+		assert!(!lines[0].contains(symbol::NOTE));
+		assert!(lines[1].contains(symbol::NOTE));
+		assert!(!lines[2].contains(symbol::NOTE));
+	}
+
+	#[test]
+	fn test_with_autostash() {
+		let (td, mut list) = tagged_list();
+		let dir = td.path();
+		let file = dir.join("foo.txt");
+		std::fs::write(&file, "committed").unwrap();
+		git(dir, &["add", "foo.txt"]);
+		git(dir, &["commit", "-q", "-m", "foo"]);
+		std::fs::write(&file, "local").unwrap();
+
+		list.options.borrow_mut().toggle_rebase_autostash();
+		list.with_autostash(|_| {
+			assert_eq!(
+				std::fs::read_to_string(&file).unwrap(),
+				"committed"
+			);
+		});
+
+		assert_eq!(std::fs::read_to_string(&file).unwrap(), "local");
+		assert!(sync::get_stashes(&list.repo.borrow())
+			.unwrap()
+			.is_empty());
+	}
+
+	#[test]
+	fn test_file_count_placeholder_until_loaded() {
+		let (_td, mut list) = tagged_list();
+		assert!(list.missing_file_counts().is_empty());
+
+		list.toggle_file_counts();
+		list.current_size.set(Some((200, 10)));
+		let missing = list.missing_file_counts();
+		assert_eq!(missing.len(), 3);
+		assert!(text_lines(&list)[0].contains(symbol::ELLIPSIS));
+
+		list.set_file_counts(vec![(missing[0], 12)]);
+		assert!(text_lines(&list)[0].contains("  12 "));
+		assert!(text_lines(&list)[1].contains(symbol::ELLIPSIS));
+		assert_eq!(list.missing_file_counts().len(), 2);
+	}
+
+	#[test]
+	fn test_drop_root_commit() {
+		let (_td, mut list) = tagged_list();
+		let head = list.items().iter().next().unwrap().id;
+		let press = |list: &mut CommitList, key: GituiKeyEvent| {
+			list.event(&Event::Key(KeyEvent::new(
+				key.code,
+				key.modifiers,
+			)))
+			.unwrap();
+		};
+		let keys = list.key_config.keys.clone();
+
+		list.select_entry(2);
+		press(&mut list, keys.log_mark_commit);
+		press(&mut list, keys.delete_generic);
+		assert!(matches!(
+			list.queue.pop(),
+			Some(InternalEvent::ConfirmCustom(confirm))
+				if confirm.confirm == "drop"
+		));
+		list.local_queue
+			.borrow_mut()
+			.push_back(LocalEvent::Confirmed("drop".into()));
+		list.update().unwrap();
+
+		assert!(matches!(
+			list.queue.pop(),
+			Some(InternalEvent::ShowErrorMsg(msg))
+				if msg == "cannot rebase: selection includes the initial commit"
+		));
+		assert_eq!(get_head(&list.repo.borrow()).unwrap(), head);
+	}
+
+	#[test]
+	fn test_search_restores_last_scope() {
+		let (_td, mut list) = tagged_list();
+		let press = |list: &mut CommitList, c| {
+			list.event(&Event::Key(KeyEvent::new(
+				KeyCode::Char(c),
+				KeyModifiers::empty(),
+			)))
+			.unwrap();
+		};
+		let remembered = SearchScope {
+			author: false,
+			message: true,
+			sha: true,
+		};
+		list.options.borrow_mut().set_search_scope(remembered);
+
+		press(&mut list, '/');
+		press(&mut list, 'a');
+		assert!(!list.search_options.message);
+		assert_eq!(list.options.borrow().search_scope(), remembered);
+
+		list.stop_search();
+		press(&mut list, '/');
+		press(&mut list, '/');
+		assert_eq!(list.search_options.scope(), remembered);
+	}
+
+	#[test]
+	fn test_path_filter_jump() {
+		let (td, repo_path) = temp_repo();
+		let dir = td.path();
+		let commit_file = |file: &str, msg: &str| {
+			std::fs::write(dir.join(file), msg).unwrap();
+			git(dir, &["add", file]);
+			commit(dir, &repo_path, msg)
+		};
+		let first = commit_file("foo.txt", "first");
+		let second = commit_file("bar.txt", "second");
+		let third = commit_file("foo.txt", "third");
+		let head = commit_file("bar.txt", "fourth");
+
+		let mut list = CommitList::new(
+			RefCell::new(repo_path.clone()),
+			"",
+			SharedTheme::default(),
+			Queue::new(),
+			SharedKeyConfig::default(),
+			Options::in_memory(),
+		);
+		list.items().set_items(
+			0,
+			sync::get_commits_info(
+				&repo_path,
+				&[head, third, second, first],
+				50,
+			)
+			.unwrap(),
+		);
+		list.set_count_total(4);
+		assert!(!list.jump_path_commit(true).unwrap());
+
+		list.path_filter = PathBuf::from("foo.txt");
+		assert_eq!(
+			list.path_filter_header(),
+			"foo.txt (4 commits, 1/4)"
+		);
+
+		// `second` did not touch it
+		assert!(list.jump_path_commit(true).unwrap());
+		assert_eq!(list.selection(), 1);
+		assert!(list.jump_path_commit(true).unwrap());
+		assert_eq!(list.selection(), 3);
+		assert!(!list.jump_path_commit(true).unwrap());
+		assert_eq!(
+			list.path_filter_header(),
+			"foo.txt (4 commits, 4/4)"
+		);
+
+		assert!(list.jump_path_commit(false).unwrap());
+		assert_eq!(list.selection(), 1);
+		assert!(!list.jump_path_commit(false).unwrap());
+	}
 
 	#[test]
 	fn test_string_width_align() {
@@ -1713,6 +2726,21 @@ mod tests {
 		assert_eq!(string_width_align("1234556", 4), "12..");
 	}
 
+	#[test]
+	fn test_filter_terms_match() {
+		let terms: Vec<String> =
+			vec![String::from("foo"), String::from("bar")];
+		let text = "foo baz";
+		let m = |t: &str| text.contains(t);
+
+		assert!(!filter_terms_match(&terms, true, m));
+		assert!(filter_terms_match(&terms, false, m));
+
+		let single = vec![String::from("foo")];
+		assert!(filter_terms_match(&single, true, m));
+		assert!(filter_terms_match(&single, false, m));
+	}
+
 	#[test]
 	fn test_string_width_align_unicode() {
 		assert_eq!(string_width_align("äste", 3), "ä..");
@@ -6,13 +6,14 @@ use super::{
 use crate::{
 	accessors,
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{InternalEvent, Queue, StackablePopupOpen},
 	strings,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, diff::DiffOptions, CommitId, RepoPathRef},
+	sync::{self, CommitId, RepoPathRef},
 	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
 	DiffType,
 };
@@ -34,6 +35,7 @@ pub struct CompareCommitsComponent {
 	visible: bool,
 	key_config: SharedKeyConfig,
 	queue: Queue,
+	options: SharedOptions,
 }
 
 impl DrawableComponent for CompareCommitsComponent {
@@ -172,6 +174,7 @@ impl CompareCommitsComponent {
 		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			repo: repo.clone(),
@@ -187,6 +190,7 @@ impl CompareCommitsComponent {
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			open_request: None,
@@ -194,6 +198,7 @@ impl CompareCommitsComponent {
 			visible: false,
 			key_config,
 			queue: queue.clone(),
+			options,
 		}
 	}
 
@@ -262,13 +267,17 @@ impl CompareCommitsComponent {
 					let diff_params = DiffParams {
 						path: f.path.clone(),
 						diff_type: DiffType::Commits(ids),
-						options: DiffOptions::default(),
+						options: self
+							.options
+							.borrow()
+							.diff_options_for(&f.path),
 					};
 
 					if let Some((params, last)) =
 						self.git_diff.last()?
 					{
 						if params == diff_params {
+							self.diff.set_params(params);
 							self.diff.update(f.path, false, last);
 							return Ok(());
 						}
@@ -287,6 +296,14 @@ impl CompareCommitsComponent {
 	}
 
 	fn update(&mut self) -> Result<()> {
+		if let Some(ids) = self.get_ids() {
+			let (old, new) = sync::compare_commits_sides(
+				&self.repo.borrow(),
+				ids,
+			)?;
+			self.diff.set_revisions(Some(old), Some(new));
+		}
+
 		self.details.set_commits(
 			self.get_ids().map(CommitFilesParams::from),
 			&None,
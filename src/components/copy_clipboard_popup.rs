@@ -3,8 +3,11 @@ use super::{
 	DrawableComponent, EventState,
 };
 use crate::{
-	components::utils::string_width_align,
+	components::utils::{string_width_align, time_to_string},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, Queue},
+	string_utils::expand_placeholders,
 	strings::{self},
 	ui::{self, style::SharedTheme},
 };
@@ -13,7 +16,6 @@ use asyncgit::sync::{
 	get_commit_info, CommitId, CommitInfo, RepoPathRef,
 };
 use crossterm::event::Event;
-use itertools::Itertools;
 use ratatui::{
 	backend::Backend,
 	layout::{Alignment, Rect},
@@ -22,6 +24,24 @@ use ratatui::{
 	Frame,
 };
 
+/// fills `{short_hash}`, `{hash}`, `{summary}`, `{author}`,
+/// `{email}` and `{date}` of `template` from `info`
+fn changelog_line(template: &str, info: &CommitInfo) -> String {
+	let fields = [
+		("short_hash", info.id.get_short_string()),
+		("hash", info.id.to_string()),
+		(
+			"summary",
+			info.message.lines().next().unwrap_or_default().into(),
+		),
+		("author", info.author.clone()),
+		("email", info.email.clone()),
+		("date", time_to_string(info.time, false)),
+	];
+
+	expand_placeholders(template, &fields)
+}
+
 #[derive(Debug)]
 pub struct CopyClipboardOpen {
 	commit_ids: Vec<CommitId>,
@@ -44,21 +64,27 @@ pub struct CopyPopupComponent {
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
 	repo: RepoPathRef,
+	queue: Queue,
+	options: SharedOptions,
 	copy_request: Option<CopyClipboardOpen>,
 }
 
 impl CopyPopupComponent {
 	///
 	pub fn new(
+		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 		repo: RepoPathRef,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			visible: false,
 			key_config,
 			theme,
 			repo,
+			queue: queue.clone(),
+			options,
 			copy_request: None,
 		}
 	}
@@ -74,6 +100,38 @@ impl CopyPopupComponent {
 		Ok(get_commit_info(&self.repo.borrow(), oid)?)
 	}
 
+	/// copies `field` of all requested commits (one per line),
+	/// confirms it and closes the popup
+	fn copy_field<F>(&mut self, name: &str, field: F) -> Result<()>
+	where
+		F: Fn(CommitInfo) -> String,
+	{
+		if let Some(r) = &self.copy_request {
+			let values = r
+				.commit_ids
+				.iter()
+				.filter_map(|i| self.get_commit_info(i).ok())
+				.map(field)
+				.collect::<Vec<_>>();
+
+			crate::clipboard::copy_string(&values.join("\n"))?;
+
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::copy_clipboard_done(name, values.len()),
+			));
+		}
+
+		self.close();
+
+		Ok(())
+	}
+
+	/// hides and returns to the popup we were opened from (if any)
+	fn close(&mut self) {
+		self.hide();
+		self.queue.push(InternalEvent::PopupStackPop);
+	}
+
 	fn get_text(&self, width: u16) -> Vec<Spans> {
 		let mut txt: Vec<Spans> = Vec::with_capacity(10);
 
@@ -88,8 +146,10 @@ impl CopyPopupComponent {
 		self.add_action(txt, width, "s", "Copy SHA");
 		self.add_action(txt, width, "e", "Copy e-mail");
 		self.add_action(txt, width, "a", "Copy author");
+		self.add_action(txt, width, "d", "Copy date");
 		self.add_action(txt, width, "m", "Copy message");
 		self.add_action(txt, width, "S", "Copy summary");
+		self.add_action(txt, width, "c", "Copy changelog");
 	}
 
 	fn add_action(
@@ -121,7 +181,7 @@ impl DrawableComponent for CopyPopupComponent {
 		area: Rect,
 	) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 10);
+			const SIZE: (u16, u16) = (50, 12);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
@@ -181,6 +241,13 @@ impl Component for CopyPopupComponent {
 				true,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::copy_clipboard_date(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::copy_clipboard_message(
 					&self.key_config,
@@ -195,6 +262,13 @@ impl Component for CopyPopupComponent {
 				true,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::copy_clipboard_changelog(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -207,84 +281,53 @@ impl Component for CopyPopupComponent {
 		if self.is_visible() {
 			if let Event::Key(key) = &event {
 				if key_match(key, self.key_config.keys.exit_popup) {
-					self.hide();
+					self.close();
 				} else if key_match(
 					key,
 					self.key_config.keys.copy_clipboard_sha,
 				) {
-					if let Some(r) = &self.copy_request {
-						crate::clipboard::copy_string(
-							&r.commit_ids
-								.iter()
-								.map(|i| i.to_string())
-								.join("\n"),
-						)?;
-					}
-					self.hide();
+					self.copy_field("SHA", |i| i.id.to_string())?;
 				} else if key_match(
 					key,
 					self.key_config.keys.copy_clipboard_email,
 				) {
-					if let Some(r) = &self.copy_request {
-						crate::clipboard::copy_string(
-							&r.commit_ids
-								.iter()
-								.filter_map(|i| {
-									self.get_commit_info(i).ok()
-								})
-								.map(|i| i.email)
-								.join("\n"),
-						)?;
-					}
-					self.hide();
+					self.copy_field("e-mail", |i| i.email)?;
 				} else if key_match(
 					key,
 					self.key_config.keys.copy_clipboard_author,
 				) {
-					if let Some(r) = &self.copy_request {
-						crate::clipboard::copy_string(
-							&r.commit_ids
-								.iter()
-								.filter_map(|i| {
-									self.get_commit_info(i).ok()
-								})
-								.map(|i| i.author)
-								.join("\n"),
-						)?;
-					}
-					self.hide();
+					self.copy_field("author", |i| i.author)?;
+				} else if key_match(
+					key,
+					self.key_config.keys.copy_clipboard_date,
+				) {
+					self.copy_field("date", |i| {
+						time_to_string(i.time, false)
+					})?;
 				} else if key_match(
 					key,
 					self.key_config.keys.copy_clipboard_message,
 				) {
-					if let Some(r) = &self.copy_request {
-						crate::clipboard::copy_string(
-							&r.commit_ids
-								.iter()
-								.filter_map(|i| {
-									self.get_commit_info(i).ok()
-								})
-								.map(|i| i.message)
-								.join("\n"),
-						)?;
-					}
-					self.hide();
+					self.copy_field("message", |i| i.message)?;
 				} else if key_match(
 					key,
 					self.key_config.keys.copy_clipboard_summary,
 				) {
-					if let Some(r) = &self.copy_request {
-						crate::clipboard::copy_string(
-							&r.commit_ids
-								.iter()
-								.filter_map(|i| {
-									self.get_commit_info(i).ok()
-								})
-								.map(|i| i.get_clipboard_summary())
-								.join("\n"),
-						)?;
-					}
-					self.hide();
+					self.copy_field("summary", |i| {
+						i.get_clipboard_summary()
+					})?;
+				} else if key_match(
+					key,
+					self.key_config.keys.copy_clipboard_changelog,
+				) {
+					let template = self
+						.options
+						.borrow()
+						.changelog_template()
+						.to_string();
+					self.copy_field("changelog", |i| {
+						changelog_line(&template, &i)
+					})?;
 				}
 			}
 
@@ -308,3 +351,36 @@ impl Component for CopyPopupComponent {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::changelog_line;
+	use asyncgit::sync::{CommitId, CommitInfo};
+
+	#[test]
+	fn test_changelog_line() {
+		let info = CommitInfo {
+			message: String::from("fix the thing\n\nbecause"),
+			time: 0,
+			author: String::from("jane"),
+			id: CommitId::default(),
+			email: String::from("jane@example.com"),
+			committer: None,
+		};
+
+		assert_eq!(
+			changelog_line(
+				"- {short_hash} {summary} ({author})",
+				&info
+			),
+			format!(
+				"- {} fix the thing (jane)",
+				info.id.get_short_string()
+			)
+		);
+		assert_eq!(
+			changelog_line("* {summary} <{email}>", &info),
+			"* fix the thing <jane@example.com>"
+		);
+	}
+}
@@ -10,7 +10,7 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{self, CommitId, RepoPathRef};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{
@@ -23,6 +23,7 @@ pub struct CreateBranchComponent {
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
+	base: Option<CommitId>,
 }
 
 impl DrawableComponent for CreateBranchComponent {
@@ -113,11 +114,13 @@ impl CreateBranchComponent {
 			theme,
 			key_config,
 			repo,
+			base: None,
 		}
 	}
 
-	///
-	pub fn open(&mut self) -> Result<()> {
+	/// `base` of `None` creates the branch on HEAD and checks it out
+	pub fn open(&mut self, base: Option<CommitId>) -> Result<()> {
+		self.base = base;
 		self.show()?;
 
 		Ok(())
@@ -125,10 +128,17 @@ impl CreateBranchComponent {
 
 	///
 	pub fn create_branch(&mut self) {
-		let res = sync::create_branch(
-			&self.repo.borrow(),
-			self.input.get_text(),
-		);
+		let res = match self.base {
+			Some(commit) => sync::create_branch_at_commit(
+				&self.repo.borrow(),
+				self.input.get_text(),
+				commit,
+			),
+			None => sync::create_branch(
+				&self.repo.borrow(),
+				self.input.get_text(),
+			),
+		};
 
 		self.input.clear();
 		self.hide();
@@ -23,6 +23,10 @@ pub struct CredComponent {
 	input_username: TextInputComponent,
 	input_password: TextInputComponent,
 	cred: BasicAuthCredential,
+	/// prompts after rejected attempts so far
+	retries: usize,
+	/// user of the last attempt, kept when asking again
+	username: Option<String>,
 }
 
 impl CredComponent {
@@ -51,9 +55,45 @@ impl CredComponent {
 			.with_input_type(InputType::Password),
 			key_config,
 			cred: BasicAuthCredential::new(None, None),
+			retries: 0,
+			username: None,
 		}
 	}
 
+	/// a new remote operation starts without retries used up
+	pub fn reset_retries(&mut self) {
+		self.retries = 0;
+		self.username = None;
+	}
+
+	/// remembers who an attempt is made as
+	pub fn attempt_with(
+		&mut self,
+		cred: Option<&BasicAuthCredential>,
+	) {
+		self.username = cred.and_then(|cred| cred.username.clone());
+	}
+
+	/// asks for the password again after an attempt got rejected,
+	/// `false` once `max` retries are used up
+	pub fn retry(&mut self, max: usize) -> Result<bool> {
+		if self.retries >= max {
+			return Ok(false);
+		}
+
+		self.retries += 1;
+		self.cred =
+			BasicAuthCredential::new(self.username.clone(), None);
+		self.show()?;
+
+		Ok(true)
+	}
+
+	/// the prompt asks again after a rejected attempt
+	pub const fn is_retry(&self) -> bool {
+		self.retries > 0
+	}
+
 	pub fn set_cred(&mut self, cred: BasicAuthCredential) {
 		self.cred = cred;
 	}
@@ -168,3 +208,75 @@ impl Component for CredComponent {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::CredComponent;
+	use crate::{
+		components::Component, keys::SharedKeyConfig,
+		ui::style::SharedTheme,
+	};
+	use asyncgit::sync::cred::BasicAuthCredential;
+	use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+	fn cred_component() -> CredComponent {
+		CredComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		)
+	}
+
+	fn press(cred: &mut CredComponent, code: KeyCode) {
+		cred.event(&Event::Key(KeyEvent::new(
+			code,
+			KeyModifiers::empty(),
+		)))
+		.unwrap();
+	}
+
+	#[test]
+	fn test_retry_asks_for_password_only() {
+		let mut cred = cred_component();
+		cred.reset_retries();
+		cred.attempt_with(Some(&BasicAuthCredential::new(
+			Some("me".into()),
+			Some("wrong".into()),
+		)));
+
+		assert!(cred.retry(3).unwrap());
+		assert!(cred.is_visible());
+		assert!(cred.is_retry());
+		assert!(!cred.input_username.is_visible());
+		assert!(cred.input_password.is_visible());
+
+		press(&mut cred, KeyCode::Char('p'));
+		press(&mut cred, KeyCode::Char('w'));
+		press(&mut cred, KeyCode::Enter);
+
+		assert_eq!(
+			cred.get_cred(),
+			&BasicAuthCredential::new(
+				Some("me".into()),
+				Some("pw".into())
+			)
+		);
+	}
+
+	#[test]
+	fn test_retry_counter() {
+		let mut cred = cred_component();
+		assert!(!cred.retry(0).unwrap());
+		assert!(!cred.is_retry());
+
+		for _ in 0..2 {
+			assert!(cred.retry(2).unwrap());
+			cred.hide();
+		}
+		assert!(!cred.retry(2).unwrap());
+		assert!(!cred.is_visible());
+
+		cred.reset_retries();
+		assert!(!cred.is_retry());
+		assert!(cred.retry(2).unwrap());
+	}
+}
@@ -1,22 +1,33 @@
 use super::{
+	utils::goto_line::GotoLine,
+	utils::number_of_digits,
 	utils::scroll_horizontal::HorizontalScroll,
-	utils::scroll_vertical::VerticalScroll, CommandBlocking,
-	Direction, DrawableComponent, HorizontalScrollType, ScrollType,
+	utils::scroll_vertical::VerticalScroll,
+	utils::search_pattern::SearchPattern, CommandBlocking, Direction,
+	DrawableComponent, HorizontalScrollType, ScrollType,
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	string_utils::mark_whitespace,
 	string_utils::tabs_to_spaces,
 	string_utils::trim_offset,
+	string_utils::word_diff,
+	string_utils::wrap_parts,
+	string_utils::WordRanges,
 	strings, try_or_popup,
 	ui::style::SharedTheme,
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
 use asyncgit::{
 	hash,
-	sync::{self, diff::DiffLinePosition, RepoPathRef},
-	DiffLine, DiffLineType, FileDiff,
+	sync::{
+		self, diff::DiffLinePosition, utils::repo_work_dir, CommitId,
+		RepoPathRef,
+	},
+	DiffLine, DiffLineType, DiffParams, FileDiff,
 };
 use bytesize::ByteSize;
 use crossterm::event::Event;
@@ -25,13 +36,23 @@ use crossterm::event::KeyCode;
 use ratatui::{
 	backend::Backend,
 	layout::Rect,
+	style::{Modifier, Style},
 	symbols,
 	text::{Span, Spans},
 	widgets::{Block, Borders, Paragraph},
 	Frame,
 };
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use std::{
+	borrow::Cow,
+	cell::Cell,
+	cmp,
+	collections::{HashMap, VecDeque},
+	fs,
+	ops::Range,
+	path::{Path, PathBuf},
+};
 use std::time::SystemTime;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Default)]
 struct Current {
@@ -40,6 +61,19 @@ struct Current {
 	hash: u64,
 }
 
+/// files whose position is remembered, the least recently left
+/// one is forgotten first
+const POSITIONS_MAX: usize = 100;
+
+/// where we left the diff of a file, restored when coming back
+#[derive(Clone, Copy)]
+struct ViewPosition {
+	hash: u64,
+	selection: Selection,
+	top: usize,
+	right: usize,
+}
+
 ///
 #[derive(Clone, Copy)]
 enum Selection {
@@ -103,6 +137,14 @@ impl Selection {
 	}
 }
 
+/// vim like copy grammar, `y` being the copy key and `N` a count:
+/// * `yy` copies the current line (or the selection)
+/// * `y` + hunk key copies the selected hunk
+/// * `yk`/`yj` copies the current line plus the one above/below,
+///   `yNk`/`yNj` plus `N` lines above/below
+/// * `Nyy` or `yNy` copies `N` lines from the current one downwards
+/// * `Nyk`/`Nyj` is the same as `yNk`/`yNj`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CopyState
 {
 	None,
@@ -114,6 +156,82 @@ enum CopyState
 	Hunk
 }
 
+/// meaning of a key while a copy is pending
+#[derive(Clone, Copy)]
+enum CopyInput {
+	Copy,
+	Hunk,
+	Up,
+	Down,
+	Digit(isize),
+	Other,
+}
+
+impl CopyState {
+	/// anything not fitting the grammar cancels the copy
+	fn next(self, input: CopyInput) -> Self {
+		match (self, input) {
+			(Self::Pending, CopyInput::Copy) => Self::Line,
+			(Self::Size(s), CopyInput::Copy) => {
+				Self::LinesDown(s.saturating_sub(1).max(0))
+			}
+			(Self::Pending, CopyInput::Hunk) => Self::Hunk,
+			(Self::Pending, CopyInput::Up) => Self::LinesUp(1),
+			(Self::Size(s), CopyInput::Up) => Self::LinesUp(s),
+			(Self::Pending, CopyInput::Down) => Self::LinesDown(1),
+			(Self::Size(s), CopyInput::Down) => Self::LinesDown(s),
+			(Self::Pending, CopyInput::Digit(d)) => Self::Size(d),
+			(Self::Size(s), CopyInput::Digit(d)) => {
+				Self::Size(s.saturating_mul(10).saturating_add(d))
+			}
+			_ => Self::None,
+		}
+	}
+}
+
+/// what the number column left of each diff line shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LineNumbers {
+	/// distance to the selected line (vim's `relativenumber`)
+	Relative,
+	/// the line number in the new file, drawn instead of `Relative`
+	/// with the `diff_line_numbers_absolute` option
+	Absolute,
+	/// the line numbers in the old and the new file
+	OldNew,
+}
+
+impl LineNumbers {
+	const fn toggle(self) -> Self {
+		match self {
+			Self::Relative | Self::Absolute => Self::OldNew,
+			Self::OldNew => Self::Relative,
+		}
+	}
+}
+
+/// how tabs and trailing spaces are drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Whitespace {
+	/// as plain spaces
+	Plain,
+	/// as distinctly styled markers
+	Markers,
+}
+
+impl Whitespace {
+	const fn toggle(self) -> Self {
+		match self {
+			Self::Plain => Self::Markers,
+			Self::Markers => Self::Plain,
+		}
+	}
+
+	const fn markers(self) -> bool {
+		matches!(self, Self::Markers)
+	}
+}
+
 enum SearchState
 {
 	IncSearch(String, usize),
@@ -126,29 +244,136 @@ enum SearchDirection
 	Backward
 }
 
+/// which lines a search looks at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SearchScope
+{
+	All,
+	Added,
+	Deleted,
+	/// added and deleted ones
+	Changed
+}
+
+impl SearchScope {
+	const fn next(self) -> Self {
+		match self {
+			Self::All => Self::Added,
+			Self::Added => Self::Deleted,
+			Self::Deleted => Self::Changed,
+			Self::Changed => Self::All,
+		}
+	}
+
+	const fn contains(self, line_type: DiffLineType) -> bool {
+		match self {
+			Self::All => true,
+			Self::Added => matches!(line_type, DiffLineType::Add),
+			Self::Deleted => matches!(line_type, DiffLineType::Delete),
+			Self::Changed => matches!(line_type, DiffLineType::Add | DiffLineType::Delete),
+		}
+	}
+
+	const fn label(self) -> &'static str {
+		match self {
+			Self::All => "all lines",
+			Self::Added => "added lines",
+			Self::Deleted => "deleted lines",
+			Self::Changed => "changed lines",
+		}
+	}
+}
+
 struct Search
 {
 	pub search: Option<SearchState>,
 	pub direction: SearchDirection,
 	pub smart_case: bool,
-	pub start_line: usize
+	pub start_line: usize,
+	pub scope: SearchScope,
+	/// diff lines the needle is found in, see `update_search_matches`
+	pub matches: Vec<usize>,
+	/// the needle is a regular expression
+	pub regex: bool,
+	/// the needle compiled, `None` if it is an invalid regex
+	pub pattern: Option<SearchPattern>,
 }
 
 impl Search{
 	pub fn is_active(&self) -> bool { self.search.is_some() }
 	pub fn find_in_str(&self, line: &str) -> bool {
-		if self.smart_case {
-			match self.search.as_ref().unwrap() {
-				SearchState::IncSearch(s, _) => line.to_lowercase().find(&s.to_lowercase()).is_some(),
-				SearchState::Search(s) => line.to_lowercase().find(&s.to_lowercase()).is_some(),
-			}
-		}else{
-			match self.search.as_ref().unwrap() {
-				SearchState::IncSearch(s, _) => line.find(s).is_some(),
-				SearchState::Search(s) => line.find(s).is_some(),
-			}
+		self.find_range(line).is_some()
+	}
+	pub fn find_in_line(&self, line: &DiffLine) -> bool {
+		self.scope.contains(line.line_type) && self.find_in_str(&line.content)
+	}
+	/// suffix for the search hints, empty when searching everything
+	pub fn scope_hint(&self) -> String {
+		if self.scope == SearchScope::All {
+			String::new()
+		} else {
+			format!(" in {}", self.scope.label())
+		}
+	}
+	/// marks regex mode and a needle that does not compile
+	pub fn regex_hint(&self) -> &'static str {
+		if !self.regex {
+			""
+		} else if self.pattern.is_none()
+			&& self.needle().map_or(false, |s| !s.is_empty())
+		{
+			" (invalid regex)"
+		} else {
+			" (regex)"
+		}
+	}
+	fn needle(&self) -> Option<&str> {
+		match self.search.as_ref()? {
+			SearchState::IncSearch(s, _) | SearchState::Search(s) => Some(s),
+		}
+	}
+	/// ` 3/12` on the 3rd of 12 matching lines, ` -/12` off them,
+	/// ` 0/0` without any
+	pub fn match_hint(&self, line: usize) -> String {
+		if self.needle().map_or(true, str::is_empty) {
+			return String::new();
+		}
+		if self.matches.is_empty() {
+			return String::from(" 0/0");
 		}
+		let current = self.matches.binary_search(&line).map_or_else(
+			|_| String::from("-"),
+			|idx| (idx + 1).to_string(),
+		);
+		format!(" {}/{}", current, self.matches.len())
+	}
+	/// compiles the needle once it changed, so every line is matched
+	/// against the same pattern
+	pub fn update_pattern(&mut self) {
+		self.pattern = self.needle().and_then(|s| {
+			SearchPattern::new(s, self.smart_case, self.regex).ok()
+		});
 	}
+	/// byte range of the first match in `line`
+	pub fn find_range(&self, line: &str) -> Option<(usize, usize)> {
+		self.pattern.as_ref()?.find(line)
+	}
+}
+
+/// how a single diff line is drawn, scrolled or wrapped
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct LineDraw<'a> {
+	selected: bool,
+	copied: bool,
+	selected_hunk: bool,
+	end_of_hunk: bool,
+	line_number: String,
+	show_whitespace: bool,
+	/// the match the search is at, if on this line
+	search: Option<&'a Search>,
+	/// changed words to emphasize
+	words: Option<&'a [Range<usize>]>,
 }
 
 ///
@@ -167,11 +392,28 @@ pub struct DiffComponent {
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
 	is_immutable: bool,
 	copy_op: CopyState,
 	copied_region: Option<(Selection, SystemTime)>,
-	pending_movement: Option<usize>,
-	search: Search
+	pending_movement: GotoLine,
+	search: Search,
+	/// needle of the last committed search, kept across files
+	last_search: Option<String>,
+	conflict_markers: Vec<usize>,
+	line_numbers: LineNumbers,
+	max_line_number: u32,
+	whitespace: Whitespace,
+	/// changed words of paired deleted and added lines, keyed by
+	/// line index
+	word_diff: HashMap<usize, WordRanges>,
+	/// keyed by path and `is_stage`, the most recently left last
+	positions: VecDeque<((String, bool), ViewPosition)>,
+	/// commits of the old and new side, `None` for `HEAD` and the
+	/// work dir
+	revisions: (Option<CommitId>, Option<CommitId>),
+	/// what the shown diff was requested with
+	params: Option<DiffParams>,
 }
 
 impl DiffComponent {
@@ -181,6 +423,7 @@ impl DiffComponent {
 		queue: Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 		is_immutable: bool,
 	) -> Self {
 		Self {
@@ -197,12 +440,22 @@ impl DiffComponent {
 			horizontal_scroll: HorizontalScroll::new(),
 			theme,
 			key_config,
+			options,
 			is_immutable,
 			repo,
 			copy_op: CopyState::None,
 			copied_region: None,
-			pending_movement: None,
-			search: Search{search: None, direction: SearchDirection::Forward, smart_case: true, start_line: 0}
+			pending_movement: GotoLine::default(),
+			search: Search{search: None, direction: SearchDirection::Forward, smart_case: true, start_line: 0, scope: SearchScope::All, matches: Vec::new(), regex: false, pattern: None},
+			last_search: None,
+			conflict_markers: Vec::new(),
+			line_numbers: LineNumbers::Relative,
+			max_line_number: 0,
+			whitespace: Whitespace::Plain,
+			word_diff: HashMap::new(),
+			positions: VecDeque::new(),
+			revisions: (None, None),
+			params: None,
 		}
 	}
 	///
@@ -216,15 +469,35 @@ impl DiffComponent {
 	pub fn current(&self) -> (String, bool) {
 		(self.current.path.clone(), self.current.is_stage)
 	}
+	/// commits the old and new side of the diff are taken from,
+	/// permalinks point into these
+	pub fn set_revisions(
+		&mut self,
+		old: Option<CommitId>,
+		new: Option<CommitId>,
+	) {
+		self.revisions = (old, new);
+	}
+	/// what the diff about to be shown was requested with, saving
+	/// it as a patch asks git for it again
+	pub fn set_params(&mut self, params: DiffParams) {
+		self.params = Some(params);
+	}
 	///
 	pub fn clear(&mut self, pending: bool) {
+		self.remember_position();
 		self.current = Current::default();
 		self.diff = None;
+		self.params = None;
 		self.longest_line = 0;
+		self.max_line_number = 0;
 		self.vertical_scroll.reset();
 		self.horizontal_scroll.reset();
 		self.selection = Selection::Single(0);
 		self.selected_hunk = None;
+		self.conflict_markers.clear();
+		self.word_diff.clear();
+		self.search.matches.clear();
 		self.pending = pending;
 	}
 
@@ -256,6 +529,19 @@ impl DiffComponent {
 		if self.current.hash != hash {
 			let reset_selection = self.current.path != path;
 
+			if reset_selection {
+				self.remember_position();
+				// a search still being typed stays with its file
+				if let Some(SearchState::IncSearch(..)) =
+					self.search.search
+				{
+					self.search.search = self
+						.last_search
+						.clone()
+						.map(SearchState::Search);
+				}
+			}
+
 			self.current = Current {
 				path,
 				is_stage,
@@ -283,10 +569,44 @@ impl DiffComponent {
 					len + 1
 				});
 
+			self.max_line_number = self
+				.diff
+				.iter()
+				.flat_map(|diff| diff.hunks.iter())
+				.flat_map(|hunk| hunk.lines.iter())
+				.flat_map(|line| {
+					[
+						line.position.old_lineno,
+						line.position.new_lineno,
+					]
+				})
+				.flatten()
+				.max()
+				.unwrap_or_default();
+
+			self.conflict_markers = self
+				.diff
+				.iter()
+				.flat_map(|diff| diff.hunks.iter())
+				.flat_map(|hunk| hunk.lines.iter())
+				.enumerate()
+				.filter(|(_idx, line)| {
+					Self::is_conflict_marker(&line.content)
+				})
+				.map(|(idx, _line)| idx)
+				.collect();
+
+			self.word_diff = self
+				.diff
+				.as_ref()
+				.map(Self::word_diff_ranges)
+				.unwrap_or_default();
+
+			self.update_search_matches();
+
 			if reset_selection {
-				self.vertical_scroll.reset();
-				self.selection = Selection::Single(0);
-				self.update_selection(0);
+				self.restore_position();
+				self.search_new_diff();
 			} else {
 				let old_selection = match self.selection {
 					Selection::Single(line) => line,
@@ -356,6 +676,58 @@ impl DiffComponent {
 		}
 	}
 
+	fn remember_position(&mut self) {
+		if self.current.path.is_empty() {
+			return;
+		}
+
+		let key = (self.current.path.clone(), self.current.is_stage);
+		self.take_position(&key);
+		if self.positions.len() >= POSITIONS_MAX {
+			self.positions.pop_front();
+		}
+		self.positions.push_back((
+			key,
+			ViewPosition {
+				hash: self.current.hash,
+				selection: self.selection,
+				top: self.vertical_scroll.get_top(),
+				right: self.horizontal_scroll.get_right(),
+			},
+		));
+	}
+
+	fn take_position(
+		&mut self,
+		key: &(String, bool),
+	) -> Option<ViewPosition> {
+		let idx =
+			self.positions.iter().position(|(k, _)| k == key)?;
+		self.positions.remove(idx).map(|(_, position)| position)
+	}
+
+	/// restores the position we left `current` at unless its diff
+	/// changed since, otherwise starts at the top
+	fn restore_position(&mut self) {
+		let key = (self.current.path.clone(), self.current.is_stage);
+		let position = self
+			.take_position(&key)
+			.filter(|position| position.hash == self.current.hash);
+
+		if let Some(position) = position {
+			// same hash, same lines: the old selection is still valid
+			self.update_selection(position.selection.get_start());
+			self.selection = position.selection;
+			self.vertical_scroll.set_top(position.top);
+			self.horizontal_scroll.set_right(position.right);
+		} else {
+			self.vertical_scroll.reset();
+			self.horizontal_scroll.reset();
+			self.selection = Selection::Single(0);
+			self.update_selection(0);
+		}
+	}
+
 	fn update_selection(&mut self, new_start: usize) {
 		if let Some(diff) = &self.diff {
 			let max = diff.lines.saturating_sub(1);
@@ -370,11 +742,108 @@ impl DiffComponent {
 		self.diff.as_ref().map_or(0, |diff| diff.lines)
 	}
 
+	/// row showing `line` of the new file (of the old one for
+	/// deleted lines), the next shown line if it is not part of
+	/// any hunk
+	fn row_of_line(diff: &FileDiff, line: u32) -> usize {
+		let rows = || {
+			diff.hunks
+				.iter()
+				.flat_map(|hunk| hunk.lines.iter())
+				.map(|line| line.position)
+				.enumerate()
+		};
+
+		rows()
+			.find(|(_, pos)| pos.new_lineno == Some(line))
+			.or_else(|| {
+				rows().find(|(_, pos)| pos.old_lineno == Some(line))
+			})
+			.or_else(|| {
+				rows().find(|(_, pos)| {
+					pos.new_lineno
+						.or(pos.old_lineno)
+						.map_or(false, |lineno| lineno > line)
+				})
+			})
+			.map_or_else(
+				|| diff.lines.saturating_sub(1),
+				|(row, _)| row,
+			)
+	}
+
 	fn max_scroll_right(&self) -> usize {
+		if self.wrap_lines() {
+			return 0;
+		}
+
 		self.longest_line
 			.saturating_sub(self.current_size.get().0.into())
 	}
 
+	fn wrap_lines(&self) -> bool {
+		self.options.borrow().diff_wrap_lines()
+	}
+
+	fn toggle_wrap_lines(&self) {
+		self.options.borrow_mut().toggle_diff_wrap_lines();
+		self.horizontal_scroll.reset();
+	}
+
+	/// columns left for the line content next to line numbers and
+	/// hunk marker
+	fn content_width(&self) -> usize {
+		usize::from(
+			self.current_size
+				.get()
+				.0
+				.saturating_sub(self.line_number_width() + 1),
+		)
+	}
+
+	/// long lines above the selection take up more than one row
+	/// when wrapped and may push it out of view, scroll down then
+	fn scroll_wrapped_selection_into_view(&self, height: usize) {
+		if let Some(diff) = &self.diff {
+			let selection = self.selection.get_end();
+			let mut top = self.vertical_scroll.get_top().min(selection);
+			let width = self.content_width();
+
+			let rows: Vec<usize> = diff
+				.hunks
+				.iter()
+				.flat_map(|hunk| hunk.lines.iter())
+				.skip(top)
+				.take(selection + 1 - top)
+				.map(|line| {
+					wrap_parts(
+						&Self::line_parts(
+							line,
+							self.whitespace.markers(),
+							Style::default(),
+							Style::default(),
+						),
+						width,
+					)
+					.len()
+				})
+				.collect();
+
+			let mut total: usize = rows.iter().sum();
+			for line_rows in
+				rows.iter().take(rows.len().saturating_sub(1))
+			{
+				if total <= height {
+					break;
+				}
+				total -= line_rows;
+				top += 1;
+			}
+
+			self.vertical_scroll.set_top(top);
+		}
+	}
+
 	fn modify_selection(&mut self, direction: Direction) {
 		if self.diff.is_some() {
 			self.selection.modify(direction, self.lines_count());
@@ -409,6 +878,123 @@ impl DiffComponent {
 		}
 	}
 
+	/// writes the whole diff to `<file name>.patch` in the repo root
+	fn save_patch(&self) {
+		if let Some(params) = &self.params {
+			try_or_popup!(
+				self,
+				"save patch error:",
+				self.write_patch(params)
+			);
+		}
+	}
+
+	fn write_patch(&self, params: &DiffParams) -> Result<()> {
+		let repo = self.repo.borrow();
+		let name = Path::new(&params.path)
+			.file_name()
+			.map(|name| name.to_string_lossy())
+			.unwrap_or_default();
+		let file = PathBuf::from(repo_work_dir(&repo)?)
+			.join(format!("{name}.patch"));
+
+		if file.exists() {
+			bail!("{} already exists", file.display());
+		}
+
+		fs::write(&file, sync::diff::get_diff_patch(&repo, params)?)?;
+
+		self.queue.push(InternalEvent::ShowInfoMsg(format!(
+			"diff written to:\n{}",
+			file.display()
+		)));
+
+		Ok(())
+	}
+
+	/// link to the selected line in the new side's commit, or in
+	/// the old side's by its old line number if the line was removed
+	fn copy_permalink(&self) {
+		let position = self.diff.as_ref().and_then(|diff| {
+			diff.hunks
+				.iter()
+				.flat_map(|hunk| hunk.lines.iter())
+				.nth(self.selection.get_start())
+				.map(|line| line.position)
+		});
+
+		match position.and_then(|pos| self.permalink_target(&pos)) {
+			Some((commit, line)) => {
+				if let Ok(line) = usize::try_from(line) {
+					self.queue.push(InternalEvent::CopyPermalink(
+						commit,
+						self.current.path.clone(),
+						line,
+					));
+				}
+			}
+			None => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					strings::PERMALINK_NOT_COMMITTED.to_string(),
+				));
+			}
+		}
+	}
+
+	/// commit (`None` for `HEAD`) and line a permalink points to,
+	/// nothing for lines that are not committed
+	fn permalink_target(
+		&self,
+		position: &DiffLinePosition,
+	) -> Option<(Option<CommitId>, u32)> {
+		match self.revisions {
+			(_, Some(new)) if position.new_lineno.is_some() => {
+				position.new_lineno.map(|line| (Some(new), line))
+			}
+			(Some(old), _) => {
+				position.old_lineno.map(|line| (Some(old), line))
+			}
+			// in the status only the old side of the staged diff is
+			// committed, the workdir diff counts lines of the index
+			(None, None) if self.current.is_stage => {
+				position.old_lineno.map(|line| (None, line))
+			}
+			_ => None,
+		}
+	}
+
+	/// mode, scope and match count appended to the search hints
+	fn search_hint(&self) -> String {
+		format!(
+			"{}{}{}",
+			self.search.regex_hint(),
+			self.search.scope_hint(),
+			self.search.match_hint(self.selection.get_start())
+		)
+	}
+
+	/// rescans the diff for lines matching the needle
+	fn update_search_matches(&mut self) {
+		self.search.update_pattern();
+		let matches = if self
+			.search
+			.needle()
+			.map_or(true, str::is_empty)
+		{
+			Vec::new()
+		} else {
+			self.diff
+				.iter()
+				.flat_map(|diff| diff.hunks.iter())
+				.flat_map(|hunk| hunk.lines.iter())
+				.enumerate()
+				.filter(|(_idx, line)| self.search.find_in_line(line))
+				.map(|(idx, _line)| idx)
+				.collect()
+		};
+		self.search.matches = matches;
+	}
+
 	fn search_forward(&mut self, start: Option<usize>)
 	{
 		let start_index = start.unwrap_or(self.selection.get_start());
@@ -420,10 +1006,24 @@ impl DiffComponent {
 			.enumerate()
 			.skip(start_index + 1)
 			.find(|(_idx, line)|{
-				self.search.find_in_str(&*line.content)
+				self.search.find_in_line(line)
 			})
 		.map_or(start_index, |(idx, _line)| { idx });
 		self.update_selection(line_num);
+		self.scroll_to_match(line_num);
+	}
+
+	/// moves to the next match of a committed search in a newly
+	/// loaded file unless the selection is on one already
+	fn search_new_diff(&mut self) {
+		if matches!(self.search.search, Some(SearchState::Search(_)))
+			&& !self
+				.search
+				.matches
+				.contains(&self.selection.get_start())
+		{
+			self.search_forward(None);
+		}
 	}
 
 	fn search_backwards(&mut self, start: Option<usize>)
@@ -437,11 +1037,158 @@ impl DiffComponent {
 			.enumerate()
 			.take(start_index)
 			.filter(|(_idx, line)|{
-				self.search.find_in_str(&*line.content)
+				self.search.find_in_line(line)
 			})
 		.last()
 			.map_or(start_index, |(idx, _line)| { idx });
 		self.update_selection(line_num);
+		self.scroll_to_match(line_num);
+	}
+
+	/// lines starting a conflict region, separating both sides or
+	/// ending it
+	fn is_conflict_marker(content: &str) -> bool {
+		["<<<<<<<", "=======", ">>>>>>>"].iter().any(|marker| {
+			content.strip_prefix(marker).map_or(false, |rest| {
+				rest.is_empty() || rest.starts_with(char::is_whitespace)
+			})
+		})
+	}
+
+	/// next marker line after (or before) `current`, wraps around
+	fn next_conflict_marker(
+		markers: &[usize],
+		current: usize,
+		forward: bool,
+	) -> Option<usize> {
+		if forward {
+			markers
+				.iter()
+				.find(|line| **line > current)
+				.or_else(|| markers.first())
+				.copied()
+		} else {
+			markers
+				.iter()
+				.rev()
+				.find(|line| **line < current)
+				.or_else(|| markers.last())
+				.copied()
+		}
+	}
+
+	fn move_to_conflict_marker(&mut self, forward: bool) {
+		if let Some(line) = Self::next_conflict_marker(
+			&self.conflict_markers,
+			self.selection.get_start(),
+			forward,
+		) {
+			self.update_selection(line);
+		}
+	}
+
+	/// brings the search match in line `line_num` into view horizontally
+	fn scroll_to_match(&self, line_num: usize) {
+		if self.wrap_lines() {
+			return;
+		}
+
+		let content = self
+			.diff
+			.iter()
+			.flat_map(|diff| diff.hunks.iter())
+			.flat_map(|hunk| hunk.lines.iter())
+			.nth(line_num)
+			.map(|line| {
+				tabs_to_spaces(line.content.as_ref().to_string())
+			});
+		let columns = content.as_deref().and_then(|content| {
+			let (start, end) = self.search.find_range(content)?;
+			// lowercasing may shift byte offsets, bail out then
+			let start_col = content.get(..start)?.width();
+			let match_width = content.get(start..end)?.width();
+			Some((start_col, start_col + match_width))
+		});
+
+		if let Some((start, end)) = columns {
+			// content is drawn right of line numbers and hunk marker
+			let visual_width = usize::from(
+				self.current_size
+					.get()
+					.0
+					.saturating_sub(self.line_number_width() + 1),
+			);
+			self.horizontal_scroll
+				.scroll_into_view(start, end, visual_width);
+		}
+	}
+
+	/// `line_numbers` with the absolute numbers option applied
+	fn effective_line_numbers(&self) -> LineNumbers {
+		let absolute =
+			self.options.borrow().diff_line_numbers_absolute();
+		match self.line_numbers {
+			LineNumbers::Relative if absolute => {
+				LineNumbers::Absolute
+			}
+			line_numbers => line_numbers,
+		}
+	}
+
+	fn line_number_width(&self) -> u16 {
+		match self.effective_line_numbers() {
+			LineNumbers::Relative => {
+				u16::try_from(number_of_digits(self.lines_count()).max(1))
+					.unwrap_or_default()
+			}
+			LineNumbers::Absolute => {
+				u16::try_from(self.max_line_number.to_string().len())
+					.unwrap_or_default()
+			}
+			// old and new column separated by a space
+			LineNumbers::OldNew => {
+				let digits = self.max_line_number.to_string().len();
+				u16::try_from(digits * 2 + 1).unwrap_or_default()
+			}
+		}
+	}
+
+	fn line_number_text(
+		&self,
+		line: &DiffLine,
+		line_cursor: usize,
+	) -> String {
+		let width = usize::from(self.line_number_width());
+		match self.effective_line_numbers() {
+			LineNumbers::Relative => {
+				let number =
+					if let Selection::Single(pos) = self.selection {
+						line_cursor.abs_diff(pos)
+					} else {
+						line_cursor + 1
+					};
+				format!("{number:width$}")
+			}
+			// deleted lines have no number in the new file
+			LineNumbers::Absolute => format!(
+				"{:>width$}",
+				line.position
+					.new_lineno
+					.map(|n| n.to_string())
+					.unwrap_or_default()
+			),
+			LineNumbers::OldNew => {
+				let column = |n: Option<u32>| {
+					n.map(|n| n.to_string()).unwrap_or_default()
+				};
+				let w = width / 2;
+				format!(
+					"{:>w$} {:>w$}",
+					column(line.position.old_lineno),
+					column(line.position.new_lineno),
+				)
+			}
+		}
 	}
 
 	fn search_event(&mut self, e: &KeyEvent) -> Result<EventState> {
@@ -451,16 +1198,49 @@ impl DiffComponent {
 				Some(SearchState::Search(s)) => Some(SearchState::Search(s.to_string())),
 				None => None
 			};
+			self.last_search = match &self.search.search {
+				Some(SearchState::Search(s)) => Some(s.clone()),
+				_ => None
+			};
 			return Ok(EventState::Consumed);
 		}else if key_match(e, self.key_config.keys.exit_popup)
 		{
 			let was_active = self.search.is_active();
 			if let Some(SearchState::IncSearch(_,p)) = self.search.search.take() {
+				// back to the committed search, a second esc clears it
+				self.search.search = self.last_search.clone().map(SearchState::Search);
+				self.update_search_matches();
 				self.update_selection(p);
 				return Ok(EventState::Consumed);
 			}
+			self.last_search = None;
 			return if was_active { Ok(EventState::Consumed) } else { Ok(EventState::NotConsumed) };
-		} 
+		}else if key_match(e, self.key_config.keys.diff_search_scope)
+			&& self.search.is_active()
+		{
+			self.search.scope = self.search.scope.next();
+			self.update_search_matches();
+			if let Some(SearchState::IncSearch(_, opos)) = &self.search.search {
+				let opos = *opos;
+				match self.search.direction {
+					SearchDirection::Forward => self.search_forward(Some(opos)),
+					SearchDirection::Backward => self.search_backwards(Some(opos)),
+				}
+			}
+			return Ok(EventState::Consumed);
+		}else if key_match(e, self.key_config.keys.diff_search_regex)
+		{
+			self.search.regex = !self.search.regex;
+			self.update_search_matches();
+			if let Some(SearchState::IncSearch(_, opos)) = &self.search.search {
+				let opos = *opos;
+				match self.search.direction {
+					SearchDirection::Forward => self.search_forward(Some(opos)),
+					SearchDirection::Backward => self.search_backwards(Some(opos)),
+				}
+			}
+			return Ok(EventState::Consumed);
+		}
 		if let Some(SearchState::Search(_s)) = &self.search.search {
 			if key_match(e, self.key_config.keys.search_next) { 
 				match self.search.direction {
@@ -479,12 +1259,10 @@ impl DiffComponent {
 		}else if let Some(SearchState::IncSearch(s, orig_pos)) = &mut self.search.search {
 			if let KeyCode::Char(c) = e.code {
 				if !c.is_control() {
-					if c.is_uppercase() {
-						self.search.smart_case = false;
-					}
 					let cs = c.to_string();
 					*s += &cs;
 					let opos = *orig_pos;
+					self.update_search_matches();
 					match self.search.direction {
 						SearchDirection::Forward => self.search_forward(Some(opos)),
 						SearchDirection::Backward => self.search_backwards(Some(opos)),
@@ -494,6 +1272,7 @@ impl DiffComponent {
 			}else if let KeyCode::Backspace = e.code {
 				s.remove(s.len() - 1);
 				let opos = *orig_pos;
+				self.update_search_matches();
 				match self.search.direction {
 					SearchDirection::Forward => self.search_forward(Some(opos)),
 					SearchDirection::Backward => self.search_backwards(Some(opos)),
@@ -505,76 +1284,67 @@ impl DiffComponent {
 
 	fn movement_event(&mut self, e: &KeyEvent) -> Result<EventState> {
 		if key_match(e, self.key_config.keys.move_up) { 
-			if let Some(s) = self.pending_movement {
+			if let Some(s) = self.pending_movement.take() {
 				self.update_selection(self.selection.get_start().saturating_sub(s));
-				self.pending_movement = None;
 				return Ok(EventState::Consumed);
 			}
 		}else if key_match(e, self.key_config.keys.move_down) { 
-			if let Some(s) = self.pending_movement {
+			if let Some(s) = self.pending_movement.take() {
 				self.update_selection(self.selection.get_start().saturating_add(s).min(self.lines_count() - 1));
-				self.pending_movement = None;
 				return Ok(EventState::Consumed);
 			}
-		}else if let KeyCode::Char(c) = e.code {
-			self.pending_movement = if let Some(d) = c.to_digit(10) {
-				let d : usize = d.try_into().unwrap();
-				match self.pending_movement {
-					None => Some(d),
-					Some(s) => Some(s * 10 + d)
-				}
-			}else {
-				None
-			};
-
-			if self.pending_movement.is_some() {
+		}else if key_match(e, self.key_config.keys.copy) {
+			// leading count of `Nyy`
+			if let Some(s) = self.pending_movement.take() {
+				self.copy_op = CopyState::Size(
+					s.try_into().unwrap_or(isize::MAX),
+				);
 				return Ok(EventState::Consumed);
 			}
-		}else
+		}else if key_match(e, self.key_config.keys.shift_down)
+			|| key_match(e, self.key_config.keys.end)
 		{
-			self.pending_movement = None;
-		}
-		Ok(EventState::NotConsumed)
+			// `NG` jumps to line N of the file, not to row N of the diff
+			if let Some(line) = self.pending_movement.take() {
+				if let Some(diff) = &self.diff {
+					let row = Self::row_of_line(
+						diff,
+						line.try_into().unwrap_or(u32::MAX),
+					);
+					self.update_selection(row);
+				}
+				return Ok(EventState::Consumed);
+			}
+		}else if let KeyCode::Char(c) = e.code {
+			if self.pending_movement.push(c) {
+				return Ok(EventState::Consumed);
+			}
+			self.pending_movement.clear();
+		}else
+		{
+			self.pending_movement.clear();
+		}
+		Ok(EventState::NotConsumed)
 	}
 
 	fn copy_event(&mut self, e: &KeyEvent) -> Result<EventState> {
-		if key_match(e, self.key_config.keys.copy) {
-			self.copy_op  = match self.copy_op {
-				CopyState::Pending => CopyState::Line,
-				_ => CopyState::None
-			};
+		let input = if key_match(e, self.key_config.keys.copy) {
+			CopyInput::Copy
 		}else if key_match(e, self.key_config.keys.copy_hunk) {
-			self.copy_op  = match self.copy_op {
-				CopyState::Pending => CopyState::Hunk,
-				_ => CopyState::None
-			};
+			CopyInput::Hunk
 		}else if key_match(e, self.key_config.keys.move_up) { 
-			self.copy_op  = match self.copy_op {
-				CopyState::Pending => CopyState::LinesUp(1),
-				CopyState::Size(s) => CopyState::LinesUp(s),
-				_ => CopyState::None
-			};
+			CopyInput::Up
 		}else if key_match(e, self.key_config.keys.move_down) { 
-			self.copy_op  = match self.copy_op {
-				CopyState::Pending => CopyState::LinesDown(1),
-				CopyState::Size(s) => CopyState::LinesDown(s),
-				_ => CopyState::None
-			};
+			CopyInput::Down
 		}else if let KeyCode::Char(c) = e.code {
-			self.copy_op = if let Some(d) = c.to_digit(10) {
-				let d : isize = d.try_into().unwrap();
-				match self.copy_op {
-					CopyState::Pending => CopyState::Size(d),
-					CopyState::Size(s) => CopyState::Size(s * 10 + d),
-					_ => CopyState::None
-				}
-			}else {
-				CopyState::None
-			};
+			c.to_digit(10).map_or(CopyInput::Other, |d| {
+				CopyInput::Digit(d.try_into().unwrap_or_default())
+			})
 		}else
 		{
-			self.copy_op = CopyState::None;
-		}
+			CopyInput::Other
+		};
+		self.copy_op = self.copy_op.next(input);
 
 		let orig_start = self.selection.get_start();
 		//try execute
@@ -598,18 +1368,15 @@ impl DiffComponent {
 				}
 				self.copy_op = CopyState::None;
 			},
-			CopyState::LinesUp(s) => {
-				let start = self.selection.get_start();
-				self.selection = Selection::Multiple(start, start.saturating_sub(s.try_into().unwrap()));
-				self.copied_region = Some((self.selection, SystemTime::now())).into();
-				self.copy_selection();
-				self.update_selection(orig_start);
-				self.copy_op = CopyState::None;
-			},
-			CopyState::LinesDown(s) => {
-				let start = self.selection.get_start();
-				let n_lines = self.lines_count();
-				self.selection = Selection::Multiple(start, cmp::min(start.saturating_add(s.try_into().unwrap()), n_lines));
+			CopyState::LinesUp(s) | CopyState::LinesDown(s) => {
+				let down = matches!(self.copy_op, CopyState::LinesDown(_));
+				let (start, end) = Self::copy_lines_range(
+					self.selection.get_start(),
+					s.try_into().unwrap_or_default(),
+					down,
+					self.lines_count(),
+				);
+				self.selection = Selection::Multiple(start, end);
 				self.copied_region = Some((self.selection, SystemTime::now())).into();
 				self.copy_selection();
 				self.update_selection(orig_start);
@@ -621,6 +1388,26 @@ impl DiffComponent {
 		Ok(EventState::Consumed)
 	}
 
+	/// inclusive range of the current line plus `count` lines
+	/// above or below it, clamped to the last line of the diff
+	fn copy_lines_range(
+		start: usize,
+		count: usize,
+		down: bool,
+		lines: usize,
+	) -> (usize, usize) {
+		let end = if down {
+			cmp::min(
+				start.saturating_add(count),
+				lines.saturating_sub(1),
+			)
+		} else {
+			start.saturating_sub(count)
+		};
+
+		(start, end)
+	}
+
 	fn find_selected_hunk(
 		diff: &FileDiff,
 		line_selected: usize,
@@ -672,6 +1459,51 @@ impl DiffComponent {
 		None
 	}
 
+	/// pairs each run of deleted lines in a hunk with the run of
+	/// added lines right after it and diffs their words
+	fn word_diff_ranges(
+		diff: &FileDiff,
+	) -> HashMap<usize, WordRanges> {
+		let mut res = HashMap::new();
+		let mut offset = 0;
+
+		for hunk in &diff.hunks {
+			let lines = &hunk.lines;
+			let run = |start: usize, typ: DiffLineType| {
+				lines[start..]
+					.iter()
+					.take_while(|line| line.line_type == typ)
+					.count()
+			};
+			let content = |idx: usize| {
+				tabs_to_spaces(lines[idx].content.to_string())
+			};
+
+			let mut idx = 0;
+			while idx < lines.len() {
+				let deleted = run(idx, DiffLineType::Delete);
+				let added = run(idx + deleted, DiffLineType::Add);
+
+				for pair in 0..deleted.min(added) {
+					let (old, new) =
+						(idx + pair, idx + deleted + pair);
+					if let Some((old_words, new_words)) =
+						word_diff(&content(old), &content(new))
+					{
+						res.insert(offset + old, old_words);
+						res.insert(offset + new, new_words);
+					}
+				}
+
+				idx += (deleted + added).max(1);
+			}
+
+			offset += lines.len();
+		}
+
+		res
+	}
+
 	fn get_text(&self, width: u16, height: u16) -> Vec<Spans> {
 		let mut res: Vec<Spans> = Vec::new();
 		if let Some(diff) = &self.diff {
@@ -715,7 +1547,11 @@ impl DiffComponent {
 					Span::raw(Cow::from(")")),
 				])]);
 			} else {
-				let num_width = (self.lines_count() as f32).log10() as u16 + 1;
+				let num_width = self.line_number_width();
+				let wrap = self.wrap_lines();
+				let word_highlight =
+					self.options.borrow().diff_word_highlight();
+				let content_width = self.content_width();
 				let min = self.vertical_scroll.get_top();
 				let max = min + height as usize;
 
@@ -745,29 +1581,59 @@ impl DiffComponent {
 								&& line_cursor <= max
 							{
 								let &selection = if let Some(copied) = self.copied_region.as_ref() { &copied.0 } else { &self.selection };
-								let copied = self.copied_region.is_some();
-								let line_number = if let Selection::Single(pos) = &self.selection { 
-									((line_cursor as isize) - (*pos as isize)).abs() as usize
-								} else { 
-									line_cursor + 1
-								};
-
-								res.push(Self::get_line_to_add(
-									width - num_width,
-									line,
-									self.focused()
+								let draw = LineDraw {
+									selected: self.focused()
 										&& selection
 											.contains(line_cursor),
-											copied,
-									hunk_selected,
-									i == hunk_len - 1,
-									&self.theme,
-									self.horizontal_scroll
-										.get_right(),
-										num_width,
-										line_number
-								));
-								lines_added += 1;
+									copied: self
+										.copied_region
+										.is_some(),
+									selected_hunk: hunk_selected,
+									end_of_hunk: i == hunk_len - 1,
+									line_number: self
+										.line_number_text(
+											line,
+											line_cursor,
+										),
+									show_whitespace: self
+										.whitespace
+										.markers(),
+									// the match the search is at
+									search: (self.search.is_active()
+										&& line_cursor
+											== self
+												.selection
+												.get_start())
+									.then_some(&self.search),
+									words: word_highlight
+										.then(|| {
+											self.word_diff
+												.get(&line_cursor)
+										})
+										.flatten()
+										.map(Vec::as_slice),
+								};
+
+								if wrap {
+									let rows = Self::get_wrapped_lines_to_add(
+										content_width,
+										line,
+										draw,
+										&self.theme,
+									);
+									lines_added += rows.len();
+									res.extend(rows);
+								} else {
+									res.push(Self::get_line_to_add(
+										width - num_width,
+										line,
+										draw,
+										&self.theme,
+										self.horizontal_scroll
+											.get_right(),
+									));
+									lines_added += 1;
+								}
 							}
 
 							line_cursor += 1;
@@ -784,18 +1650,23 @@ impl DiffComponent {
 	fn get_line_to_add<'a>(
 		width: u16,
 		line: &'a DiffLine,
-		selected: bool,
-		copied : bool,
-		selected_hunk: bool,
-		end_of_hunk: bool,
+		draw: LineDraw,
 		theme: &SharedTheme,
 		scrolled_right: usize,
-		line_number_width: u16,
-		line_index: usize
 	) -> Spans<'a> {
+		let LineDraw {
+			selected,
+			copied,
+			selected_hunk,
+			end_of_hunk,
+			line_number,
+			show_whitespace,
+			search,
+			words,
+		} = draw;
 		let style = theme.diff_hunk_marker(selected_hunk);
 
-		let num_block = Span::styled(format!("{line_index:w$}", w = line_number_width as usize), style);
+		let num_block = Span::styled(line_number, style);
 
 		let left_side_of_line = if end_of_hunk {
 			Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
@@ -812,6 +1683,53 @@ impl DiffComponent {
 			}
 		};
 
+		let copied_color = selected && copied;
+		let line_style =
+			theme.diff_line(line.line_type, selected, copied_color);
+
+		if show_whitespace || search.is_some() || words.is_some() {
+			let parts = Self::line_parts(
+				line,
+				show_whitespace,
+				line_style,
+				theme.diff_whitespace(selected, copied_color),
+			);
+			let parts = match words {
+				Some(words) => Self::emphasize_words(parts, words),
+				None => parts,
+			};
+			let parts = match search {
+				Some(search) => Self::highlight_match(parts, search),
+				None => parts,
+			};
+			let mut spans = vec![num_block, left_side_of_line];
+			let mut offset = scrolled_right;
+			let mut len = 0;
+			for (text, part_style) in parts {
+				let visible = trim_offset(&text, offset);
+				offset = if visible.is_empty() {
+					offset.saturating_sub(text.width())
+				} else {
+					0
+				};
+				len += visible.chars().count();
+				spans.push(Span::styled(
+					visible.to_string(),
+					part_style,
+				));
+			}
+			let fill = if selected {
+				usize::from(width).saturating_sub(len)
+			} else {
+				0
+			};
+			spans.push(Span::styled(
+				format!("{:fill$}\n", ""),
+				line_style,
+			));
+			return Spans::from(spans);
+		}
+
 		let content =
 			tabs_to_spaces(line.content.as_ref().to_string());
 		let content = trim_offset(&content, scrolled_right);
@@ -824,17 +1742,206 @@ impl DiffComponent {
 			format!("{content}\n")
 		};
 
-		let copied_color = selected && copied;
 		Spans::from(vec![
 					num_block,
 			left_side_of_line,
-			Span::styled(
-				Cow::from(filled),
-				theme.diff_line(line.line_type, selected, copied_color),
-			),
+			Span::styled(Cow::from(filled), line_style),
 		])
 	}
 
+	/// content of `line` in `style`, with `show_whitespace` tabs and
+	/// trailing spaces are split off as markers in `marker_style`
+	fn line_parts(
+		line: &DiffLine,
+		show_whitespace: bool,
+		style: Style,
+		marker_style: Style,
+	) -> Vec<(String, Style)> {
+		if show_whitespace {
+			mark_whitespace(&line.content)
+				.into_iter()
+				.map(|(text, is_marker)| {
+					(text, if is_marker { marker_style } else { style })
+				})
+				.collect()
+		} else {
+			vec![(tabs_to_spaces(line.content.as_ref().to_string()), style)]
+		}
+	}
+
+	/// splits `parts` at the char ranges of changed `words`, which
+	/// get emphasized while the rest of the line is muted
+	fn emphasize_words(
+		parts: Vec<(String, Style)>,
+		words: &[Range<usize>],
+	) -> Vec<(String, Style)> {
+		let emphasis = |style: Style, changed: bool| {
+			style.add_modifier(if changed {
+				Modifier::BOLD
+			} else {
+				Modifier::DIM
+			})
+		};
+
+		let mut res =
+			Vec::with_capacity(parts.len() + words.len() * 2);
+		let mut pos = 0;
+		for (text, style) in parts {
+			let mut piece = String::new();
+			let mut piece_changed = false;
+			for c in text.chars() {
+				let changed = words.iter().any(|w| w.contains(&pos));
+				if changed != piece_changed && !piece.is_empty() {
+					res.push((
+						std::mem::take(&mut piece),
+						emphasis(style, piece_changed),
+					));
+				}
+				piece_changed = changed;
+				piece.push(c);
+				pos += 1;
+			}
+			if !piece.is_empty() {
+				res.push((piece, emphasis(style, piece_changed)));
+			}
+		}
+		res
+	}
+
+	/// splits the first match of `search` off `parts` to emphasize it
+	fn highlight_match(
+		parts: Vec<(String, Style)>,
+		search: &Search,
+	) -> Vec<(String, Style)> {
+		let text = parts
+			.iter()
+			.map(|(text, _)| text.as_str())
+			.collect::<String>();
+		let (start, end) = match search.find_range(&text) {
+			// lowercasing may shift byte offsets, bail out then
+			Some((start, end))
+				if start < end
+					&& text.is_char_boundary(start)
+					&& text.is_char_boundary(end) =>
+			{
+				(start, end)
+			}
+			_ => return parts,
+		};
+
+		let mut res = Vec::with_capacity(parts.len() + 2);
+		let mut part_start = 0;
+		for (text, style) in parts {
+			let part_end = part_start + text.len();
+			let cut = |pos: usize| {
+				pos.clamp(part_start, part_end) - part_start
+			};
+			let (match_start, match_end) = (cut(start), cut(end));
+			for (range, style) in [
+				(0..match_start, style),
+				(
+					match_start..match_end,
+					style.add_modifier(
+						Modifier::BOLD | Modifier::UNDERLINED,
+					),
+				),
+				(match_end..text.len(), style),
+			] {
+				if !range.is_empty() {
+					res.push((text[range].to_string(), style));
+				}
+			}
+			part_start = part_end;
+		}
+		res
+	}
+
+	/// like `get_line_to_add` but wraps the content into as many
+	/// rows of `width` columns as it takes
+	fn get_wrapped_lines_to_add<'a>(
+		width: usize,
+		line: &DiffLine,
+		draw: LineDraw,
+		theme: &SharedTheme,
+	) -> Vec<Spans<'a>> {
+		let LineDraw {
+			selected,
+			copied,
+			selected_hunk,
+			end_of_hunk,
+			line_number,
+			show_whitespace,
+			search,
+			words,
+		} = draw;
+		let style = theme.diff_hunk_marker(selected_hunk);
+		let copied_color = selected && copied;
+		let line_style =
+			theme.diff_line(line.line_type, selected, copied_color);
+		let parts = Self::line_parts(
+			line,
+			show_whitespace,
+			line_style,
+			theme.diff_whitespace(selected, copied_color),
+		);
+		let parts = match words {
+			Some(words) => Self::emphasize_words(parts, words),
+			None => parts,
+		};
+		let parts = match search {
+			Some(search) => Self::highlight_match(parts, search),
+			None => parts,
+		};
+
+		let rows = wrap_parts(&parts, width);
+		let last_row = rows.len() - 1;
+		let number_width = line_number.width();
+		let mut line_number = Some(line_number);
+
+		rows.into_iter()
+			.enumerate()
+			.map(|(idx, row)| {
+				let marker = if end_of_hunk && idx == last_row {
+					symbols::line::BOTTOM_LEFT
+				} else if idx == 0
+					&& line.line_type == DiffLineType::Header
+				{
+					symbols::line::TOP_LEFT
+				} else {
+					symbols::line::VERTICAL
+				};
+
+				let mut spans = vec![
+					Span::styled(
+						line_number.take().unwrap_or_else(|| {
+							format!("{:number_width$}", "")
+						}),
+						style,
+					),
+					Span::styled(Cow::from(marker), style),
+				];
+
+				let mut len = 0;
+				for (text, part_style) in row {
+					len += text.width();
+					spans.push(Span::styled(text, part_style));
+				}
+				if selected {
+					spans.push(Span::styled(
+						format!(
+							"{:fill$}",
+							"",
+							fill = width.saturating_sub(len)
+						),
+						line_style,
+					));
+				}
+
+				Spans::from(spans)
+			})
+			.collect()
+	}
+
 	const fn hunk_visible(
 		hunk_min: usize,
 		hunk_max: usize,
@@ -990,6 +2097,49 @@ impl DiffComponent {
 		Ok(())
 	}
 
+	/// (un)stages hunk by hunk, last first so the headers of the
+	/// ones before stay valid
+	fn stage_unstage_all_hunks(&self) -> Result<()> {
+		if let Some(diff) = &self.diff {
+			if diff.hunks.is_empty() {
+				return Ok(());
+			}
+
+			if self.current.is_stage {
+				for hunk in diff.hunks.iter().rev() {
+					sync::unstage_hunk(
+						&self.repo.borrow(),
+						&self.current.path,
+						hunk.header_hash,
+					)?;
+				}
+			} else if diff.untracked {
+				sync::stage_add_file(
+					&self.repo.borrow(),
+					Path::new(&self.current.path),
+				)?;
+			} else {
+				for hunk in diff.hunks.iter().rev() {
+					sync::stage_hunk(
+						&self.repo.borrow(),
+						&self.current.path,
+						hunk.header_hash,
+					)?;
+				}
+			}
+
+			self.queue_update();
+		}
+
+		Ok(())
+	}
+
+	fn has_hunks(&self) -> bool {
+		self.diff
+			.as_ref()
+			.map_or(false, |diff| !diff.hunks.is_empty())
+	}
+
 	const fn is_stage(&self) -> bool {
 		self.current.is_stage
 	}
@@ -1015,8 +2165,14 @@ impl DrawableComponent for DiffComponent {
 			usize::from(current_height),
 		);
 
+		if self.wrap_lines() {
+			self.scroll_wrapped_selection_into_view(
+				usize::from(current_height),
+			);
+		}
+
 		self.horizontal_scroll.update_no_selection(
-			self.longest_line,
+			if self.wrap_lines() { 0 } else { self.longest_line },
 			current_width.into(),
 		);
 
@@ -1079,6 +2235,13 @@ impl Component for DiffComponent {
 					true,
 					self.focused(),
 				));
+				out.push(CommandInfo::new(
+					strings::commands::copy_permalink(
+						&self.key_config,
+					),
+					self.diff.is_some(),
+					self.focused(),
+				));
 			},
 			_ => {
 				out.push(CommandInfo::new(
@@ -1112,25 +2275,37 @@ impl Component for DiffComponent {
 			match self.search.search.as_ref() {
 				Some(SearchState::IncSearch(s, _l)) => {
 					out.push(CommandInfo::new(
-						strings::commands::cancel_search_init_str(&self.key_config, s),
+						strings::commands::cancel_search_init_str(&self.key_config, s, &self.search_hint()),
 						true,
 						self.focused(),
 					));
 				},
 				Some(SearchState::Search(s)) => {
+					// dimmed without hits, there is nowhere to jump to
+					let has_matches = !self.search.matches.is_empty();
 					out.push(CommandInfo::new(
-						strings::commands::search_for_text_next(&self.key_config, s),
-						true,
+						strings::commands::search_for_text_next(&self.key_config, s, &self.search_hint()),
+						has_matches,
 						self.focused(),
 					));
 					out.push(CommandInfo::new(
-						strings::commands::search_for_text_prev(&self.key_config, s),
-						true,
+						strings::commands::search_for_text_prev(&self.key_config, s, &self.search_hint()),
+						has_matches,
 						self.focused(),
 					));
 				}
 				_ => ()
 			}
+			out.push(CommandInfo::new(
+				strings::commands::diff_search_scope(&self.key_config, self.search.scope.next().label()),
+				true,
+				self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_search_regex(&self.key_config, self.search.regex),
+				true,
+				self.focused(),
+			));
 		}
 		out.push(CommandInfo::new(
 			strings::commands::start_search(&self.key_config),
@@ -1147,6 +2322,67 @@ impl Component for DiffComponent {
 			.hidden(),
 		);
 
+		out.push(CommandInfo::new(
+			strings::commands::jump_to_line(
+				&self.key_config,
+				self.pending_movement.as_str(),
+			),
+			true,
+			self.focused() && !self.pending_movement.is_empty(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_conflict_marker(&self.key_config),
+			!self.conflict_markers.is_empty(),
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_whitespace(
+				&self.key_config,
+			),
+			!self.current.path.is_empty(),
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_line_numbers(
+				&self.key_config,
+			),
+			true,
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_show_whitespace(
+				&self.key_config,
+				self.whitespace.markers(),
+			),
+			true,
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_toggle_wrap(
+				&self.key_config,
+				self.wrap_lines(),
+			),
+			true,
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_save_patch(&self.key_config),
+			self.params.is_some(),
+			self.focused(),
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::diff_scroll_reset(&self.key_config),
+			self.horizontal_scroll.get_right() > 0,
+			self.focused(),
+		));
+
 		if !self.is_immutable {
 			out.push(CommandInfo::new(
 				strings::commands::diff_hunk_remove(&self.key_config),
@@ -1158,6 +2394,20 @@ impl Component for DiffComponent {
 				self.selected_hunk.is_some(),
 				self.focused() && !self.is_stage(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunks_remove_all(
+					&self.key_config,
+				),
+				self.has_hunks(),
+				self.focused() && self.is_stage(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunks_add_all(
+					&self.key_config,
+				),
+				self.has_hunks(),
+				self.focused() && !self.is_stage(),
+			));
 			out.push(CommandInfo::new(
 				strings::commands::diff_hunk_revert(&self.key_config),
 				self.selected_hunk.is_some(),
@@ -1205,7 +2455,7 @@ impl Component for DiffComponent {
 					_ => return self.copy_event(e)
 				}
 
-				if self.pending_movement.is_some() {
+				if !self.pending_movement.is_empty() {
 					return self.movement_event(e);
 				}
 
@@ -1241,17 +2491,74 @@ impl Component for DiffComponent {
 				} else if key_match(e, self.key_config.keys.page_up) {
 					self.move_selection(ScrollType::PageUp);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_scroll_reset,
+				) {
+					self.horizontal_scroll.reset();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_conflict_next,
+				) {
+					self.move_to_conflict_marker(true);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_conflict_prev,
+				) {
+					self.move_to_conflict_marker(false);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_whitespace,
+				) && !self.current.path.is_empty()
+				{
+					self.queue.push(
+						InternalEvent::ToggleDiffWhitespace(
+							self.current.path.clone(),
+						),
+					);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_line_numbers,
+				) {
+					self.line_numbers = self.line_numbers.toggle();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_show_whitespace,
+				) {
+					self.whitespace = self.whitespace.toggle();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_toggle_wrap,
+				) {
+					self.toggle_wrap_lines();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_save_patch,
+				) {
+					self.save_patch();
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.start_search_forward_init) {
 					self.search.search = Some(SearchState::IncSearch(String::new(), self.selection.get_start()));
 					self.search.direction = SearchDirection::Forward;
 					self.search.smart_case = true;
+					self.search.scope = SearchScope::All;
 					self.search.start_line = self.selection.get_start();
+					self.update_search_matches();
 					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.start_search_backward_init) {
 					self.search.search = Some(SearchState::IncSearch(String::new(), self.selection.get_start()));
 					self.search.direction = SearchDirection::Backward;
 					self.search.smart_case = true;
+					self.search.scope = SearchScope::All;
 					self.search.start_line = self.selection.get_start();
+					self.update_search_matches();
 					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.page_down)
 				{
@@ -1280,6 +2587,18 @@ impl Component for DiffComponent {
 						self.stage_unstage_hunk()
 					);
 
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_stage_all_hunks,
+				) && !self.is_immutable
+				{
+					try_or_popup!(
+						self,
+						"hunk error:",
+						self.stage_unstage_all_hunks()
+					);
+
 					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
@@ -1315,6 +2634,12 @@ impl Component for DiffComponent {
 						}
 					}
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.copy_permalink,
+				) {
+					self.copy_permalink();
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.copy) {
 					if let Selection::Multiple(_, _) = &self.selection {
 						self.copy_selection();
@@ -1350,3 +2675,723 @@ impl Component for DiffComponent {
 		self.focused = focus;
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		CopyInput, CopyState, DiffComponent, LineDraw, LineNumbers,
+		Search, SearchDirection, SearchScope, SearchState,
+		POSITIONS_MAX,
+	};
+	use crate::{
+		components::Component,
+		keys::SharedKeyConfig,
+		options::Options,
+		queue::Queue,
+		ui::style::SharedTheme,
+	};
+	use asyncgit::{
+		sync::{
+			diff::{DiffLinePosition, Hunk},
+			CommitId,
+		},
+		DiffLine, DiffLineType, FileDiff,
+	};
+	use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+	use ratatui::style::{Modifier, Style};
+	use std::cell::RefCell;
+
+	/// unchanged lines numbered from 1
+	fn file_diff(lines: usize) -> FileDiff {
+		FileDiff {
+			hunks: vec![Hunk {
+				header_hash: 0,
+				lines: (1..=lines)
+					.map(|line| {
+						let line = line.try_into().ok();
+						DiffLine {
+							position: DiffLinePosition {
+								old_lineno: line,
+								new_lineno: line,
+							},
+							..DiffLine::default()
+						}
+					})
+					.collect(),
+			}],
+			lines,
+			..FileDiff::default()
+		}
+	}
+
+	fn diff_with_lines(lines: usize) -> DiffComponent {
+		let mut diff = DiffComponent::new(
+			RefCell::new("".into()),
+			Queue::new(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			Options::in_memory(),
+			true,
+		);
+		diff.update("foo".into(), false, file_diff(lines));
+		diff.focus(true);
+		diff
+	}
+
+	fn press(diff: &mut DiffComponent, code: KeyCode) {
+		diff.event(&Event::Key(KeyEvent::new(
+			code,
+			KeyModifiers::empty(),
+		)))
+		.unwrap();
+	}
+
+	#[test]
+	fn test_goto_line() {
+		let line = |line_type, old_lineno, new_lineno| DiffLine {
+			line_type,
+			position: DiffLinePosition {
+				old_lineno,
+				new_lineno,
+			},
+			..DiffLine::default()
+		};
+		let mut lines = vec![line(DiffLineType::Header, None, None)];
+		lines.extend((40..45).map(|n| {
+			line(DiffLineType::None, Some(n - 2), Some(n))
+		}));
+		lines.push(line(DiffLineType::Delete, Some(43), None));
+		lines.push(line(DiffLineType::Add, None, Some(45)));
+		let count = lines.len();
+
+		let mut diff = diff_with_lines(0);
+		diff.update(
+			"foo".into(),
+			false,
+			FileDiff {
+				hunks: vec![Hunk {
+					header_hash: 0,
+					lines,
+				}],
+				lines: count,
+				..FileDiff::default()
+			},
+		);
+
+		// the header row does not count, the hunk starts at 40
+		press(&mut diff, KeyCode::Char('4'));
+		press(&mut diff, KeyCode::Char('2'));
+		press(&mut diff, KeyCode::End);
+		assert_eq!(diff.selection.get_start(), 3);
+
+		// a line before the hunk selects its first line
+		press(&mut diff, KeyCode::Char('1'));
+		press(&mut diff, KeyCode::End);
+		assert_eq!(diff.selection.get_start(), 1);
+
+		// past the end selects the last line
+		press(&mut diff, KeyCode::Char('9'));
+		press(&mut diff, KeyCode::Char('9'));
+		press(&mut diff, KeyCode::End);
+		assert_eq!(diff.selection.get_start(), count - 1);
+
+		// without a number `End` keeps jumping to the end
+		press(&mut diff, KeyCode::Home);
+		press(&mut diff, KeyCode::End);
+		assert_eq!(diff.selection.get_start(), count - 1);
+	}
+
+	#[test]
+	fn test_restore_position() {
+		let mut diff = diff_with_lines(20);
+
+		press(&mut diff, KeyCode::Char('1'));
+		press(&mut diff, KeyCode::Char('2'));
+		press(&mut diff, KeyCode::End);
+
+		diff.update("bar".into(), false, file_diff(5));
+		assert_eq!(diff.selection.get_start(), 0);
+
+		diff.clear(true);
+		diff.update("foo".into(), false, file_diff(20));
+		assert_eq!(diff.selection.get_start(), 11);
+
+		// a changed diff starts from the top again
+		diff.update("bar".into(), false, file_diff(5));
+		diff.update("foo".into(), false, file_diff(21));
+		assert_eq!(diff.selection.get_start(), 0);
+	}
+
+	#[test]
+	fn test_restore_position_forgets_oldest() {
+		let mut diff = diff_with_lines(20);
+		press(&mut diff, KeyCode::End);
+
+		// different diffs, an unchanged one is not updated
+		for i in 0..POSITIONS_MAX {
+			diff.update(i.to_string(), false, file_diff(i + 1));
+		}
+		assert_eq!(diff.positions.len(), POSITIONS_MAX);
+
+		diff.update("foo".into(), false, file_diff(20));
+		assert_eq!(diff.selection.get_start(), 0);
+	}
+
+	#[test]
+	fn test_search_match_count() {
+		let mut diff = diff_with_lines(0);
+		let lines = ["foo", "bar", "foo bar", "baz"]
+			.into_iter()
+			.map(|content| DiffLine {
+				content: content.into(),
+				..DiffLine::default()
+			})
+			.collect::<Vec<_>>();
+		diff.update(
+			"foo".into(),
+			false,
+			FileDiff {
+				hunks: vec![Hunk {
+					header_hash: 0,
+					lines,
+				}],
+				lines: 4,
+				..FileDiff::default()
+			},
+		);
+
+		press(&mut diff, KeyCode::Char('/'));
+		assert_eq!(diff.search_hint(), "");
+
+		press(&mut diff, KeyCode::Char('b'));
+		assert_eq!(diff.search.matches, vec![1, 2, 3]);
+		assert_eq!(diff.search_hint(), " 1/3");
+
+		press(&mut diff, KeyCode::Char('a'));
+		press(&mut diff, KeyCode::Char('r'));
+		assert_eq!(diff.search.matches, vec![1, 2]);
+
+		press(&mut diff, KeyCode::Backspace);
+		assert_eq!(diff.search.matches, vec![1, 2, 3]);
+
+		diff.update_selection(0);
+		assert_eq!(diff.search_hint(), " -/3");
+
+		press(&mut diff, KeyCode::Char('x'));
+		press(&mut diff, KeyCode::Enter);
+		assert!(diff.search.matches.is_empty());
+		assert_eq!(diff.search_hint(), " 0/0");
+
+		// a newly loaded diff is searched again
+		diff.update(
+			"bar".into(),
+			false,
+			FileDiff {
+				hunks: vec![Hunk {
+					header_hash: 0,
+					lines: vec![DiffLine {
+						content: "bax".into(),
+						..DiffLine::default()
+					}],
+				}],
+				lines: 1,
+				..FileDiff::default()
+			},
+		);
+		assert_eq!(diff.search.matches, vec![0]);
+	}
+
+	#[test]
+	fn test_search_across_files() {
+		let file = |lines: &[&str]| FileDiff {
+			hunks: vec![Hunk {
+				header_hash: 0,
+				lines: lines
+					.iter()
+					.map(|content| DiffLine {
+						content: (*content).into(),
+						..DiffLine::default()
+					})
+					.collect(),
+			}],
+			lines: lines.len(),
+			..FileDiff::default()
+		};
+		let mut diff = diff_with_lines(0);
+		diff.update("foo".into(), false, file(&["foo", "bar"]));
+
+		press(&mut diff, KeyCode::Char('/'));
+		press(&mut diff, KeyCode::Char('b'));
+		press(&mut diff, KeyCode::Char('a'));
+		press(&mut diff, KeyCode::Enter);
+		assert_eq!(diff.selection.get_start(), 1);
+
+		// the committed term is searched in the next file right away
+		diff.update("bar".into(), false, file(&["x", "y", "bar"]));
+		assert_eq!(diff.selection.get_start(), 2);
+		assert_eq!(diff.search.matches, vec![2]);
+
+		// a search being typed is dropped for the committed one
+		press(&mut diff, KeyCode::Char('/'));
+		press(&mut diff, KeyCode::Char('x'));
+		diff.update("baz".into(), false, file(&["x", "ba"]));
+		assert!(matches!(
+			diff.search.search,
+			Some(SearchState::Search(ref s)) if s == "ba"
+		));
+		assert_eq!(diff.selection.get_start(), 1);
+
+		// esc leaves the typed search, a second one clears the term
+		press(&mut diff, KeyCode::Char('/'));
+		press(&mut diff, KeyCode::Char('x'));
+		press(&mut diff, KeyCode::Esc);
+		assert!(matches!(
+			diff.search.search,
+			Some(SearchState::Search(_))
+		));
+		assert_eq!(diff.search.matches, vec![1]);
+		press(&mut diff, KeyCode::Esc);
+		assert!(!diff.search.is_active());
+		assert_eq!(diff.last_search, None);
+	}
+
+	#[test]
+	#[allow(clippy::single_range_in_vec_init)]
+	fn test_word_diff_ranges() {
+		let line = |line_type, content: &str| DiffLine {
+			content: content.into(),
+			line_type,
+			..DiffLine::default()
+		};
+		let diff = FileDiff {
+			hunks: vec![Hunk {
+				header_hash: 0,
+				lines: vec![
+					line(DiffLineType::Header, "@@ -1,4 +1,3 @@"),
+					line(DiffLineType::Delete, "let a = 1;"),
+					line(DiffLineType::Delete, "let b = 2;"),
+					line(DiffLineType::Add, "let a = 3;"),
+					line(DiffLineType::None, "x"),
+					line(DiffLineType::Delete, "foo bar"),
+					line(DiffLineType::Add, "baz qux"),
+				],
+			}],
+			lines: 7,
+			..FileDiff::default()
+		};
+
+		let ranges = DiffComponent::word_diff_ranges(&diff);
+
+		// the second deleted line has no added line to pair with and
+		// lines without a word in common are left alone
+		assert_eq!(ranges.len(), 2);
+		assert_eq!(ranges[&1], vec![8..10]);
+		assert_eq!(ranges[&3], vec![8..10]);
+	}
+
+	#[test]
+	fn test_search_regex() {
+		let mut search = Search {
+			search: Some(SearchState::Search("b.r".into())),
+			direction: SearchDirection::Forward,
+			smart_case: true,
+			start_line: 0,
+			scope: SearchScope::All,
+			matches: Vec::new(),
+			regex: false,
+			pattern: None,
+		};
+		search.update_pattern();
+		assert_eq!(search.find_range("foo bar"), None);
+
+		search.regex = true;
+		search.update_pattern();
+		assert_eq!(search.find_range("foo bar"), Some((4, 7)));
+		assert_eq!(search.find_range("foo BAR"), Some((4, 7)));
+		assert_eq!(search.regex_hint(), " (regex)");
+
+		// an uppercase char makes it case sensitive
+		search.search = Some(SearchState::Search("B.r".into()));
+		search.update_pattern();
+		assert_eq!(search.find_range("foo bar"), None);
+		assert_eq!(search.find_range("foo Bar"), Some((4, 7)));
+
+		// an invalid pattern matches nothing
+		search.search = Some(SearchState::Search("b(r".into()));
+		search.update_pattern();
+		assert_eq!(search.find_range("b(r"), None);
+		assert_eq!(search.regex_hint(), " (invalid regex)");
+	}
+
+	#[test]
+	fn test_search_scope_key_needs_search() {
+		let mut diff = diff_with_lines(3);
+		let scope_key = Event::Key(KeyEvent::new(
+			KeyCode::Char('s'),
+			KeyModifiers::ALT,
+		));
+
+		assert!(!diff.event(&scope_key).unwrap().is_consumed());
+		assert_eq!(diff.search.scope, SearchScope::All);
+
+		diff.search.search = Some(SearchState::Search("x".into()));
+		assert!(diff.event(&scope_key).unwrap().is_consumed());
+		assert_eq!(diff.search.scope, SearchScope::Added);
+	}
+
+	#[test]
+	fn test_highlight_match() {
+		let mut search = Search {
+			search: Some(SearchState::Search("bc".into())),
+			direction: SearchDirection::Forward,
+			smart_case: true,
+			start_line: 0,
+			scope: SearchScope::All,
+			matches: Vec::new(),
+			regex: false,
+			pattern: None,
+		};
+		search.update_pattern();
+		let plain = Style::default();
+		let marker = plain.add_modifier(Modifier::DIM);
+		let emphasized = |style: Style| {
+			style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+		};
+
+		assert_eq!(
+			DiffComponent::highlight_match(
+				vec![("ab".into(), plain), ("cd".into(), marker)],
+				&search,
+			),
+			vec![
+				("a".to_string(), plain),
+				("b".to_string(), emphasized(plain)),
+				("c".to_string(), emphasized(marker)),
+				("d".to_string(), marker),
+			]
+		);
+	}
+
+	#[test]
+	fn test_conflict_marker() {
+		assert!(DiffComponent::is_conflict_marker("<<<<<<< HEAD"));
+		assert!(DiffComponent::is_conflict_marker("======="));
+		assert!(DiffComponent::is_conflict_marker(">>>>>>> topic\n"));
+		assert!(!DiffComponent::is_conflict_marker("========"));
+		assert!(!DiffComponent::is_conflict_marker(" <<<<<<< x"));
+	}
+
+	#[test]
+	fn test_next_conflict_marker_wraps() {
+		let markers = [2, 5, 9];
+		assert_eq!(
+			DiffComponent::next_conflict_marker(&markers, 2, true),
+			Some(5)
+		);
+		assert_eq!(
+			DiffComponent::next_conflict_marker(&markers, 9, true),
+			Some(2)
+		);
+		assert_eq!(
+			DiffComponent::next_conflict_marker(&markers, 2, false),
+			Some(9)
+		);
+		assert_eq!(
+			DiffComponent::next_conflict_marker(&markers, 7, false),
+			Some(5)
+		);
+		assert_eq!(
+			DiffComponent::next_conflict_marker(&[], 0, true),
+			None
+		);
+	}
+
+	fn copy_keys(keys: &[CopyInput]) -> CopyState {
+		keys.iter()
+			.fold(CopyState::Pending, |op, input| op.next(*input))
+	}
+
+	#[test]
+	fn test_copy_grammar() {
+		assert_eq!(copy_keys(&[CopyInput::Copy]), CopyState::Line);
+		assert_eq!(copy_keys(&[CopyInput::Hunk]), CopyState::Hunk);
+		assert_eq!(
+			copy_keys(&[CopyInput::Down]),
+			CopyState::LinesDown(1)
+		);
+		assert_eq!(
+			copy_keys(&[CopyInput::Digit(5), CopyInput::Up]),
+			CopyState::LinesUp(5)
+		);
+		assert_eq!(
+			copy_keys(&[
+				CopyInput::Digit(1),
+				CopyInput::Digit(2),
+				CopyInput::Down
+			]),
+			CopyState::LinesDown(12)
+		);
+		assert_eq!(
+			copy_keys(&[CopyInput::Digit(5), CopyInput::Other]),
+			CopyState::None
+		);
+		assert_eq!(
+			copy_keys(&[CopyInput::Hunk, CopyInput::Copy]),
+			CopyState::None
+		);
+	}
+
+	#[test]
+	fn test_copy_grammar_count_then_copy() {
+		// `3yy`: the count ends up as `Size` before the copy key
+		assert_eq!(
+			CopyState::Size(3).next(CopyInput::Copy),
+			CopyState::LinesDown(2)
+		);
+		// `yy` with a count of one is the current line only
+		assert_eq!(
+			CopyState::Size(1).next(CopyInput::Copy),
+			CopyState::LinesDown(0)
+		);
+		assert_eq!(
+			CopyState::Size(0).next(CopyInput::Copy),
+			CopyState::LinesDown(0)
+		);
+		assert_eq!(
+			CopyState::Size(isize::MAX).next(CopyInput::Digit(9)),
+			CopyState::Size(isize::MAX)
+		);
+	}
+
+	#[test]
+	fn test_copy_count_at_boundaries() {
+		// `3yy` on the second to last line
+		assert_eq!(
+			DiffComponent::copy_lines_range(8, 2, true, 10),
+			(8, 9)
+		);
+		// `y5k` on the second line
+		assert_eq!(
+			DiffComponent::copy_lines_range(1, 5, false, 10),
+			(1, 0)
+		);
+	}
+
+	#[test]
+	fn test_copy_lines_range() {
+		assert_eq!(
+			DiffComponent::copy_lines_range(5, 2, true, 10),
+			(5, 7)
+		);
+		assert_eq!(
+			DiffComponent::copy_lines_range(5, 2, false, 10),
+			(5, 3)
+		);
+	}
+
+	#[test]
+	fn test_copy_lines_range_at_last_line() {
+		assert_eq!(
+			DiffComponent::copy_lines_range(9, 1, true, 10),
+			(9, 9)
+		);
+		assert_eq!(
+			DiffComponent::copy_lines_range(9, 1, false, 10),
+			(9, 8)
+		);
+	}
+
+	#[test]
+	fn test_copy_lines_range_count_too_large() {
+		assert_eq!(
+			DiffComponent::copy_lines_range(3, 100, true, 10),
+			(3, 9)
+		);
+		assert_eq!(
+			DiffComponent::copy_lines_range(3, 100, false, 10),
+			(3, 0)
+		);
+	}
+
+	#[test]
+	fn test_line_numbers_old_new() {
+		let mut diff = diff_with_lines(3);
+		assert_eq!(diff.line_number_width(), 1);
+
+		let line = |old, new| DiffLine {
+			position: DiffLinePosition {
+				old_lineno: old,
+				new_lineno: new,
+			},
+			..DiffLine::default()
+		};
+		diff.max_line_number = 120;
+
+		let toggle = Event::Key(KeyEvent::new(
+			KeyCode::Char('L'),
+			KeyModifiers::SHIFT,
+		));
+		diff.event(&toggle).unwrap();
+		assert_eq!(diff.line_numbers, LineNumbers::OldNew);
+		assert_eq!(diff.line_number_width(), 7);
+		assert_eq!(
+			diff.line_number_text(&line(Some(9), Some(120)), 0),
+			"  9 120"
+		);
+		assert_eq!(
+			diff.line_number_text(&line(None, Some(12)), 0),
+			"     12"
+		);
+		assert_eq!(
+			diff.line_number_text(&line(Some(100), None), 0),
+			"100    "
+		);
+
+		diff.event(&toggle).unwrap();
+		assert_eq!(diff.line_numbers, LineNumbers::Relative);
+		assert_eq!(diff.line_number_text(&line(None, None), 2), "2");
+	}
+
+	#[test]
+	fn test_line_numbers_absolute() {
+		let mut diff = diff_with_lines(3);
+		diff.options
+			.borrow_mut()
+			.toggle_diff_line_numbers_absolute();
+
+		let line = |old, new| DiffLine {
+			position: DiffLinePosition {
+				old_lineno: old,
+				new_lineno: new,
+			},
+			..DiffLine::default()
+		};
+		diff.max_line_number = 120;
+
+		assert_eq!(
+			diff.effective_line_numbers(),
+			LineNumbers::Absolute
+		);
+		assert_eq!(diff.line_number_width(), 3);
+		assert_eq!(
+			diff.line_number_text(&line(Some(9), Some(12)), 0),
+			" 12"
+		);
+		assert_eq!(
+			diff.line_number_text(&line(Some(100), None), 0),
+			"   "
+		);
+
+		// old/new numbers are still a toggle away
+		diff.line_numbers = diff.line_numbers.toggle();
+		assert_eq!(
+			diff.effective_line_numbers(),
+			LineNumbers::OldNew
+		);
+	}
+
+	#[test]
+	fn test_show_whitespace() {
+		let line = DiffLine {
+			content: "\tx = 1;  ".into(),
+			..DiffLine::default()
+		};
+		let text = |show_whitespace| -> String {
+			DiffComponent::get_line_to_add(
+				20,
+				&line,
+				LineDraw {
+					show_whitespace,
+					..LineDraw::default()
+				},
+				&SharedTheme::default(),
+				0,
+			)
+			.0
+			.iter()
+			.skip(2)
+			.map(|span| span.content.as_ref())
+			.collect()
+		};
+
+		assert_eq!(text(false), "  x = 1;  \n");
+		assert_eq!(text(true), "→ x = 1;··\n");
+	}
+
+	#[test]
+	fn test_wrapped_lines() {
+		let line = DiffLine {
+			content: "0123456789abcdef".into(),
+			..DiffLine::default()
+		};
+		let rows: Vec<Vec<String>> =
+			DiffComponent::get_wrapped_lines_to_add(
+				6,
+				&line,
+				LineDraw {
+					end_of_hunk: true,
+					line_number: String::from("12"),
+					..LineDraw::default()
+				},
+				&SharedTheme::default(),
+			)
+			.iter()
+			.map(|row| {
+				row.0
+					.iter()
+					.map(|span| span.content.to_string())
+					.collect()
+			})
+			.collect();
+
+		// the line number only goes on the first row and the end of
+		// the hunk is marked on the last one
+		assert_eq!(
+			rows,
+			vec![
+				vec!["12", "│", "012345"],
+				vec!["  ", "│", "6789ab"],
+				vec!["  ", "└", "cdef"],
+			]
+		);
+	}
+
+	#[test]
+	fn test_permalink_target_picks_side() {
+		let mut diff = diff_with_lines(1);
+		let old: CommitId = "1".repeat(40).parse().unwrap();
+		let new: CommitId = "2".repeat(40).parse().unwrap();
+		let position = |old_lineno, new_lineno| DiffLinePosition {
+			old_lineno,
+			new_lineno,
+		};
+
+		// the workdir diff is not committed on either side
+		assert_eq!(
+			diff.permalink_target(&position(Some(3), Some(5))),
+			None
+		);
+
+		// the staged diff links its old side into `HEAD`, added lines
+		// are not committed yet
+		diff.update("foo".into(), true, file_diff(2));
+		assert_eq!(
+			diff.permalink_target(&position(Some(3), Some(5))),
+			Some((None, 3))
+		);
+		assert_eq!(
+			diff.permalink_target(&position(None, Some(5))),
+			None
+		);
+
+		diff.set_revisions(Some(old), Some(new));
+		assert_eq!(
+			diff.permalink_target(&position(Some(3), Some(5))),
+			Some((Some(new), 5))
+		);
+		assert_eq!(
+			diff.permalink_target(&position(Some(3), None)),
+			Some((Some(old), 3))
+		);
+	}
+}
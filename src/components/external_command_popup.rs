@@ -1,4 +1,13 @@
-use std::{cell::RefCell, process::Command};
+use std::{
+	cell::RefCell,
+	fmt::Write,
+	path::Path,
+	process::Command,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
 
 use super::{
 	utils::string_width_align, visibility_blocking, CommandBlocking,
@@ -14,7 +23,9 @@ use crate::{
 	ui::{self, show_message_in_center, style::SharedTheme},
 };
 use anyhow::Result;
+use asyncgit::sync::CommitId;
 use crossterm::event::{Event, KeyCode};
+use fuzzy_matcher::FuzzyMatcher;
 use ratatui::{
 	backend::Backend,
 	layout::{/*Alignment,*/ Constraint, Layout, Margin, Rect},
@@ -32,18 +43,58 @@ use crate::async_jobs::{
 
 type CmdResult = Result<std::process::Output, std::io::Error>;
 
+/// placeholder replaced by the commit hash in batch runs
+const COMMIT_PLACEHOLDER: &str = "{commit}";
+
+/// indices of the `commands` fuzzy matching `query` (best first)
+/// along with the matched char positions, all of them in order if
+/// `query` is empty
+fn filter_commands<'a>(
+	commands: impl Iterator<Item = &'a str>,
+	query: &str,
+) -> Vec<(usize, Vec<usize>)> {
+	if query.is_empty() {
+		return commands
+			.enumerate()
+			.map(|(idx, _)| (idx, Vec::new()))
+			.collect();
+	}
+
+	let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+	let mut hits = commands
+		.enumerate()
+		.filter_map(|(idx, cmd)| {
+			matcher
+				.fuzzy_indices(cmd, query)
+				.map(|(score, indices)| (score, idx, indices))
+		})
+		.collect::<Vec<_>>();
+
+	// stable, so equally good matches stay most recent first
+	hits.sort_by(|(score1, _, _), (score2, _, _)| score2.cmp(score1));
+
+	hits.into_iter()
+		.map(|(_, idx, indices)| (idx, indices))
+		.collect()
+}
+
 struct AsyncJobExternCmd {
 	cmd: String,
+	/// only reports a failure, neither in the history nor pending
+	quiet: bool,
 }
 
 impl AsyncJobExternCmd {
 	pub fn new(cmd: String) -> Self {
-		Self { cmd }
+		Self { cmd, quiet: false }
+	}
+
+	pub fn quiet(cmd: String) -> Self {
+		Self { cmd, quiet: true }
 	}
 
 	#[cfg(unix)]
 	fn do_exec_command(
-		&self,
 		cmd: &str,
 	) -> Result<std::process::Output, std::io::Error> {
 		Command::new("sh").args(["-c", cmd]).output()
@@ -51,7 +102,6 @@ impl AsyncJobExternCmd {
 
 	#[cfg(windows)]
 	fn do_exec_command(
-		&self,
 		cmd: &str,
 	) -> Result<std::process::Output, std::io::Error> {
 		Command::new("cmd.exe").args(["/C", cmd]).output()
@@ -61,11 +111,23 @@ impl AsyncJobExternCmd {
 impl AsyncDynJob for AsyncJobExternCmd {
 	fn run(
 		&mut self,
-		_sender: JobFeedbackSender,
+		sender: JobFeedbackSender,
 	) -> Option<BoxFeedback> {
-		Some(Box::new(AsyncJobExternCmdFeedback::new(
-			self.do_exec_command(&self.cmd),
-		)))
+		if !self.quiet {
+			sender.progress(0, 1);
+		}
+
+		let res = Self::do_exec_command(&self.cmd);
+
+		if !self.quiet {
+			sender.progress(1, 1);
+		}
+
+		Some(Box::new(AsyncJobExternCmdFeedback {
+			cmd: self.cmd.clone(),
+			res,
+			quiet: self.quiet,
+		}))
 	}
 
 	fn should_stop(&self) -> bool {
@@ -74,18 +136,102 @@ impl AsyncDynJob for AsyncJobExternCmd {
 }
 
 struct AsyncJobExternCmdFeedback {
+	cmd: String,
 	res: CmdResult,
+	quiet: bool,
 }
 
-impl AsyncJobExternCmdFeedback {
-	pub fn new(res: CmdResult) -> Self {
-		Self { res }
+impl AsyncJobFeedback for AsyncJobExternCmdFeedback {
+	fn visit(&mut self, app: &mut crate::app::App) {
+		if self.quiet {
+			app.external_command_popup
+				.finish_quiet_command(&self.cmd, &self.res);
+		} else {
+			app.external_command_popup
+				.finish_pending_command(&self.cmd, &self.res);
+		}
 	}
 }
 
-impl AsyncJobFeedback for AsyncJobExternCmdFeedback {
+/// shell argument taken literally by `sh -c`
+#[cfg(unix)]
+fn quote_arg(arg: &str) -> String {
+	format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// `cmd /C` argument, paths cannot contain quotes on windows
+#[cfg(windows)]
+fn quote_arg(arg: &str) -> String {
+	format!("\"{arg}\"")
+}
+
+/// opens `dir` in the file manager of the platform
+fn reveal_command(dir: &Path) -> String {
+	let dir = quote_arg(&dir.to_string_lossy());
+	if cfg!(windows) {
+		format!("start \"\" {dir}")
+	} else if cfg!(target_os = "macos") {
+		format!("open {dir}")
+	} else {
+		format!("xdg-open {dir}")
+	}
+}
+
+/// runs one command per commit, one after the other
+struct AsyncJobExternCmdBatch {
+	cmd: String,
+	commits: Vec<CommitId>,
+	abort: Arc<AtomicBool>,
+}
+
+impl AsyncDynJob for AsyncJobExternCmdBatch {
+	fn run(
+		&mut self,
+		sender: JobFeedbackSender,
+	) -> Option<BoxFeedback> {
+		let total = self.commits.len();
+		let mut results = Vec::with_capacity(total);
+		sender.progress(0, total);
+		for id in &self.commits {
+			if self.abort.load(Ordering::Relaxed) {
+				break;
+			}
+
+			let cmd =
+				self.cmd.replace(COMMIT_PLACEHOLDER, &id.to_string());
+			results.push((
+				*id,
+				AsyncJobExternCmd::do_exec_command(&cmd),
+			));
+
+			sender.progress(results.len(), total);
+		}
+
+		Some(Box::new(AsyncJobExternCmdBatchFeedback {
+			cmd: self.cmd.clone(),
+			results,
+			total,
+		}))
+	}
+
+	fn should_stop(&self) -> bool {
+		false
+	}
+}
+
+struct AsyncJobExternCmdBatchFeedback {
+	cmd: String,
+	results: Vec<(CommitId, CmdResult)>,
+	total: usize,
+}
+
+impl AsyncJobFeedback for AsyncJobExternCmdBatchFeedback {
 	fn visit(&mut self, app: &mut crate::app::App) {
-		app.external_command_popup.finish_pending_command(&self.res);
+		app.external_command_popup.finish_pending_batch(
+			&self.cmd,
+			&self.results,
+			self.total,
+		);
 	}
 }
 
@@ -110,12 +256,18 @@ pub struct ExternalCommandPopupComponent {
 	options: SharedOptions,
 	async_job_sender: JobSender,
 
+	/// index into `filtered`
 	selected_idx: usize,
 	visible_idx: RefCell<usize>,
 	focused: Focused,
+	/// saved commands matching the input, see `filter_commands`
+	filtered: Vec<(usize, Vec<usize>)>,
+	filter_query: String,
 
 	cmd_pending: bool,
 	shortcut_state: ShortcutState,
+	marked_commits: Vec<CommitId>,
+	batch_abort: Option<Arc<AtomicBool>>,
 }
 
 impl ExternalCommandPopupComponent {
@@ -145,9 +297,13 @@ impl ExternalCommandPopupComponent {
 			selected_idx: 0,
 			visible_idx: 0.into(),
 			focused: Focused::Input,
+			filtered: Vec::new(),
+			filter_query: String::new(),
 			cmd_pending: false,
 			async_job_sender,
 			shortcut_state: ShortcutState::Idle,
+			marked_commits: Vec::new(),
+			batch_abort: None,
 		}
 	}
 
@@ -155,61 +311,264 @@ impl ExternalCommandPopupComponent {
 		self.cmd_pending
 	}
 
-	pub fn finish_pending_command(&mut self, res: &CmdResult) {
+	pub fn finish_pending_command(
+		&mut self,
+		cmd: &str,
+		res: &CmdResult,
+	) {
 		self.cmd_pending = false;
-		self.post_run_command_ui(res);
+		self.post_run_command_ui(cmd, res);
+	}
+
+	/// nothing to show unless it failed
+	pub fn finish_quiet_command(&self, cmd: &str, res: &CmdResult) {
+		if !res.as_ref().map_or(false, |o| o.status.success()) {
+			self.post_run_command_ui(cmd, res);
+		}
+	}
+
+	/// opens the folder containing `file` in the file manager
+	pub fn reveal_file(&self, file: &Path) {
+		let dir = file.parent().unwrap_or(file);
+		let cmd = reveal_command(dir);
+		if self
+			.async_job_sender
+			.send(Box::new(AsyncJobExternCmd::quiet(cmd.clone())))
+			.is_err()
+		{
+			self.post_run_command_ui(
+				&cmd,
+				&Err(std::io::Error::from(std::io::ErrorKind::Other)),
+			);
+		}
+	}
+
+	/// commits a batch run started from this popup iterates over
+	pub fn set_marked_commits(&mut self, commits: Vec<CommitId>) {
+		self.marked_commits = commits;
 	}
 
-	fn post_run_command_ui(&self, _res: &CmdResult) {
-		if let Err(e) = _res {
-			self.queue.push(
-				crate::queue::InternalEvent::ShowErrorMsg(format!(
-					"{}\n{}",
-					"Command failed", e
-				)),
+	pub fn finish_pending_batch(
+		&mut self,
+		cmd: &str,
+		results: &[(CommitId, CmdResult)],
+		total: usize,
+	) {
+		self.cmd_pending = false;
+		self.batch_abort = None;
+
+		let succeeded = results
+			.iter()
+			.filter(|(_, res)| {
+				res.as_ref().map_or(false, |o| o.status.success())
+			})
+			.count();
+
+		let mut msg = if self.options.borrow().show_cmd_status() {
+			format!("command: {cmd}\n")
+		} else {
+			String::new()
+		};
+		let _ignore = write!(
+			msg,
+			"{} of {} commits succeeded",
+			succeeded,
+			results.len()
+		);
+		if results.len() < total {
+			let _ignore = write!(
+				msg,
+				" (aborted, {} not run)",
+				total - results.len()
+			);
+		}
+
+		for (id, res) in results {
+			// no exit code if the command was killed by a signal
+			let status = match res {
+				Ok(o) => o.status.code().map_or_else(
+					|| o.status.to_string(),
+					|code| format!("exit code: {code}"),
+				),
+				Err(e) => format!("failed ({e})"),
+			};
+			let _ignore = write!(
+				msg,
+				"\n{}: {}",
+				id.get_short_string(),
+				status
 			);
+		}
+
+		self.queue.push(if succeeded == total {
+			crate::queue::InternalEvent::ShowInfoMsg(msg)
 		} else {
-			let o = _res.as_ref().unwrap();
-			if !o.stderr.is_empty() && !o.status.success() {
-				self.queue.push(
-					crate::queue::InternalEvent::ShowErrorMsg(
-						format!(
-							"{}",
-							std::str::from_utf8(o.stderr.as_slice())
-								.unwrap_or_default()
-						),
-					),
-				);
-			} else {
-				let out_str = if !o.stdout.is_empty() {
-					o.stdout.as_slice()
+			crate::queue::InternalEvent::ShowErrorMsg(msg)
+		});
+	}
+
+	fn post_run_command_ui(&self, cmd: &str, res: &CmdResult) {
+		let show_status = self.options.borrow().show_cmd_status();
+		match res {
+			Err(e) => {
+				let msg = if show_status {
+					format!("command: {cmd}\nfailed to run: {e}")
 				} else {
-					o.stderr.as_slice()
+					format!("failed to run: {e}")
 				};
 				self.queue.push(
-					crate::queue::InternalEvent::ShowInfoMsg(
-						format!(
-							"{}",
-							std::str::from_utf8(out_str)
-								.unwrap_or_default()
-						),
-					),
+					crate::queue::InternalEvent::ShowErrorMsg(msg),
 				);
 			}
+			Ok(o) => {
+				let msg = Self::format_output(cmd, o, show_status);
+				self.queue.push(if o.status.success() {
+					crate::queue::InternalEvent::ShowInfoMsg(msg)
+				} else {
+					crate::queue::InternalEvent::ShowErrorMsg(msg)
+				});
+			}
+		}
+	}
+
+	/// the command and its exit code (if `show_status`) followed
+	/// by both output streams, each labeled
+	fn format_output(
+		cmd: &str,
+		o: &std::process::Output,
+		show_status: bool,
+	) -> String {
+		let mut sections = Vec::new();
+		if show_status {
+			// no exit code if the command was killed by a signal
+			sections.push(o.status.code().map_or_else(
+				|| format!("command: {cmd}\n{}", o.status),
+				|code| format!("command: {cmd}\nexit code: {code}"),
+			));
+		}
+		for (label, stream) in
+			[("stdout", &o.stdout), ("stderr", &o.stderr)]
+		{
+			if !stream.is_empty() {
+				sections.push(format!(
+					"--- {} ---\n{}",
+					label,
+					String::from_utf8_lossy(stream).trim_end()
+				));
+			}
 		}
+
+		sections.join("\n\n")
 	}
 
-	fn run_command_ui(&mut self, cmd: String) {
+	fn run_command_marked_ui(&mut self, cmd: &str) {
+		let abort = Arc::new(AtomicBool::new(false));
 		self.cmd_pending = true;
-		self.options.borrow_mut().add_extern_command(cmd.as_str());
+		self.batch_abort = Some(abort.clone());
+		self.options.borrow_mut().add_extern_command(cmd);
+		if self
+			.async_job_sender
+			.send(Box::new(AsyncJobExternCmdBatch {
+				cmd: cmd.to_string(),
+				commits: self.marked_commits.clone(),
+				abort,
+			}))
+			.is_err()
+		{
+			self.cmd_pending = false;
+			self.batch_abort = None;
+			self.post_run_command_ui(
+				cmd,
+				&Err(std::io::Error::from(std::io::ErrorKind::Other)),
+			);
+		}
+	}
+
+	/// stops a running batch after the current commit
+	fn abort_batch(&self) {
+		if let Some(abort) = &self.batch_abort {
+			abort.store(true, Ordering::Relaxed);
+		}
+	}
+
+	/// refilters the saved commands by what is typed, typing moves
+	/// the focus back to the input and the selection to the best
+	/// match (or to the saved command typed out exactly)
+	fn update_filter(&mut self) {
+		let query = self.cmdline.get_text();
+		self.filtered = filter_commands(
+			self.options
+				.borrow()
+				.extern_commands()
+				.iter()
+				.map(|cmd| cmd.0.as_str()),
+			query,
+		);
+
+		if query != self.filter_query {
+			self.filter_query = query.to_string();
+			self.focused = Focused::Input;
+			self.selected_idx = self.exact_match_pos().unwrap_or(0);
+		}
+		self.selected_idx = self
+			.selected_idx
+			.min(self.filtered.len().saturating_sub(1));
+	}
+
+	/// position in `filtered` of the saved command equal to what is
+	/// typed
+	fn exact_match_pos(&self) -> Option<usize> {
+		let opts = self.options.borrow();
+		let commands = opts.extern_commands();
+		self.filtered.iter().position(|(idx, _)| {
+			commands
+				.get(*idx)
+				.map_or(false, |cmd| cmd.0 == self.filter_query)
+		})
+	}
+
+	/// a list entry is only highlighted once the list is focused or
+	/// if what is typed is a saved command, otherwise the typed text
+	/// is run as a new command
+	fn is_list_highlighted(&self) -> bool {
+		self.focused == Focused::List
+			|| self.exact_match_pos() == Some(self.selected_idx)
+	}
+
+	/// index into `extern_commands` of the highlighted list entry
+	fn selected_list_idx(&self) -> Option<usize> {
+		if self.is_list_highlighted() {
+			self.filtered.get(self.selected_idx).map(|f| f.0)
+		} else {
+			None
+		}
+	}
+
+	/// the highlighted saved command, what is typed if none is
+	fn selected_command(&self) -> String {
+		self.selected_list_idx()
+			.and_then(|idx| {
+				self.options
+					.borrow()
+					.extern_commands()
+					.get(idx)
+					.map(|cmd| cmd.0.clone())
+			})
+			.unwrap_or_else(|| self.cmdline.get_text().to_string())
+	}
+
+	fn run_command_ui(&mut self, cmd: &str) {
+		self.cmd_pending = true;
+		self.options.borrow_mut().add_extern_command(cmd);
 		if let Err(_) = self
 			.async_job_sender
-			.send(Box::new(AsyncJobExternCmd::new(cmd)))
+			.send(Box::new(AsyncJobExternCmd::new(cmd.to_string())))
 		{
 			self.cmd_pending = false;
-			self.post_run_command_ui(&Err(std::io::Error::from(
-				std::io::ErrorKind::Other,
-			)));
+			self.post_run_command_ui(
+				cmd,
+				&Err(std::io::Error::from(std::io::ErrorKind::Other)),
+			);
 		}
 	}
 }
@@ -267,63 +626,61 @@ impl DrawableComponent for ExternalCommandPopupComponent {
 
 			let w = v_blocks[1].width;
 			let opts = self.options.borrow();
-			let prev_command_spans = opts
-				.extern_commands()
+			let commands = opts.extern_commands();
+			let prev_command_spans = self
+				.filtered
 				.iter()
 				.enumerate()
 				.skip(vis_idx)
 				.take(xh)
-				.map(|i| {
-					let s = if i.1 .0.len() <= w.into() {
-						&i.1 .0
-					} else {
-						&i.1 .0[0..w.into()]
-					};
-					let selected = if self.focused == Focused::List
-						&& i.0 == self.selected_idx
-					{
-						true
-					} else {
-						false
-					};
+				.map(|(list_idx, (idx, indices))| {
+					let (cmd, shortcut) = &commands[*idx];
+					let selected = self.is_list_highlighted()
+						&& list_idx == self.selected_idx;
 
 					const KEY_WIDTH: usize = 4;
-					if let Some(shortcut) = i.1 .1 {
-						Spans::from(vec![
-							Span::styled(
-								string_width_align(
-									&self
-										.key_config
-										.get_hint(shortcut),
-									KEY_WIDTH,
-								),
-								self.theme.text(true, selected),
-							),
-							Span::styled(
-								s,
-								self.theme.text(true, selected),
-							),
-						])
-					} else {
-						Spans::from(vec![
-							Span::styled(
-								string_width_align(" ", KEY_WIDTH),
-								self.theme.text(true, selected),
-							),
-							Span::styled(
-								s,
-								self.theme.text(true, selected),
-							),
-						])
-					}
+					let hint = shortcut.map_or_else(
+						|| " ".to_string(),
+						|shortcut| self.key_config.get_hint(shortcut),
+					);
+					let mut spans = vec![Span::styled(
+						string_width_align(&hint, KEY_WIDTH),
+						self.theme.text(true, selected),
+					)];
+					spans.extend(
+						cmd.chars().take(w.into()).enumerate().map(
+							|(c_idx, c)| {
+								Span::styled(
+									c.to_string(),
+									self.theme.text(
+										true,
+										selected
+											|| indices
+												.contains(&c_idx),
+									),
+								)
+							},
+						),
+					);
+					Spans::from(spans)
 				});
 
+			let title = if self.cmdline.get_text().is_empty() {
+				"Prev commands".to_string()
+			} else {
+				format!(
+					"Prev commands: {}/{}",
+					self.filtered.len(),
+					commands.len()
+				)
+			};
+
 			ui::draw_list_block(
 				f,
 				v_blocks[1],
 				Block::default()
 					.title(Span::styled(
-						"Prev commands",
+						title,
 						self.theme.title(true),
 					))
 					.borders(Borders::TOP),
@@ -366,6 +723,16 @@ impl Component for ExternalCommandPopupComponent {
 				true,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::execute_command_marked(
+					&self.key_config,
+					self.batch_abort.is_some(),
+				),
+				self.batch_abort.is_some()
+					|| (!self.cmd_pending
+						&& !self.marked_commits.is_empty()),
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::delete_command(&self.key_config),
 				true,
@@ -398,127 +765,159 @@ impl Component for ExternalCommandPopupComponent {
 		event: &crossterm::event::Event,
 	) -> Result<EventState> {
 		if self.is_visible() {
-			let opts = self.options.borrow();
-			let consumed = if let Event::Key(key) = &event {
-				if self.shortcut_state != ShortcutState::Assign
-					&& key_match(key, self.key_config.keys.exit_popup)
-				{
-					drop(opts);
-					self.hide();
-					true
-				} else if key_match(
-					key,
-					self.key_config.keys.toggle_workarea,
-				) {
-					self.focused = if self.focused == Focused::Input {
-						Focused::List
-					} else {
-						Focused::Input
-					};
-					true
-				} else if key_match(key, self.key_config.keys.enter) {
-					if self.focused == Focused::List {
-						let cmdstr = opts.extern_commands()
-							[self.selected_idx]
-							.clone();
+			let consumed = {
+				let opts = self.options.borrow();
+				if let Event::Key(key) = &event {
+					if self.shortcut_state != ShortcutState::Assign
+						&& key_match(
+							key,
+							self.key_config.keys.exit_popup,
+						) {
 						drop(opts);
-						self.run_command_ui(cmdstr.0.to_string());
-					} else {
-						drop(opts);
-						self.run_command_ui(
-							self.cmdline.get_text().to_string(),
-						);
-					}
-					self.hide();
-					true
-				} else if self.focused == Focused::List
-					&& !opts.extern_commands().is_empty()
-				{
-					if self.shortcut_state == ShortcutState::Assign {
-						drop(opts);
-						let mut opts = self.options.borrow_mut();
-						self.shortcut_state = ShortcutState::Idle;
-						if key.code != KeyCode::Esc {
-							opts.assign_shortcut_for_extern_command(
-								self.selected_idx,
-								Some(key.into()),
-							);
-						}
-						true
-					} else if key_match(
-						key,
-						self.key_config.keys.move_down,
-					) {
-						if self.selected_idx
-							< opts.extern_commands().len() - 1
-						{
-							self.selected_idx += 1;
-						}
-						true
-					} else if key_match(
-						key,
-						self.key_config.keys.move_up,
-					) {
-						if self.selected_idx > 0 {
-							self.selected_idx -= 1;
-						}
-						true
-					} else if key_match(
-						key,
-						self.key_config.keys.delete_generic,
-					) {
-						drop(opts);
-						self.selected_idx = self
-							.options
-							.borrow_mut()
-							.remove_extern_command(self.selected_idx);
+						self.hide();
 						true
 					} else if key_match(
 						key,
-						self.key_config.keys.assign_shortcut,
+						self.key_config.keys.toggle_workarea,
 					) {
-						self.shortcut_state = ShortcutState::Assign;
+						self.focused =
+							if self.focused == Focused::Input {
+								Focused::List
+							} else {
+								Focused::Input
+							};
 						true
 					} else if key_match(
 						key,
-						self.key_config.keys.clear_shortcut,
+						self.key_config.keys.enter,
 					) {
 						drop(opts);
-						self.options
-							.borrow_mut()
-							.assign_shortcut_for_extern_command(
-								self.selected_idx,
-								None,
-							);
+						let cmd = self.selected_command();
+						self.run_command_ui(&cmd);
+						self.hide();
 						true
 					} else if key_match(
 						key,
-						self.key_config.keys.clear_all_shortcut,
+						self.key_config
+							.keys
+							.run_external_command_marked,
 					) {
 						drop(opts);
-						self.options
-							.borrow_mut()
-							.clear_all_shortcuts_for_extern_commands(
-							);
+						if self.batch_abort.is_some() {
+							self.abort_batch();
+							self.hide();
+						} else if !self.cmd_pending
+							&& !self.marked_commits.is_empty()
+						{
+							let cmd = self.selected_command();
+							self.run_command_marked_ui(&cmd);
+							self.hide();
+						}
 						true
-					} else if let Some(cmd) =
-						opts.find_extern_cmd_for_shortcut(key.into())
+					} else if self.focused == Focused::List
+						&& !opts.extern_commands().is_empty()
 					{
-						drop(opts);
-						self.run_command_ui(cmd);
-						self.hide();
-						true
+						if self.shortcut_state
+							== ShortcutState::Assign
+						{
+							drop(opts);
+							self.shortcut_state = ShortcutState::Idle;
+							let idx = self
+								.selected_list_idx()
+								.filter(|_| key.code != KeyCode::Esc);
+							if let Some(idx) = idx {
+								self.options
+									.borrow_mut()
+									.assign_shortcut_for_extern_command(
+										idx,
+										Some(key.into()),
+									);
+							}
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.move_down,
+						) {
+							if self.selected_idx + 1
+								< self.filtered.len()
+							{
+								self.selected_idx += 1;
+							}
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.move_up,
+						) {
+							if self.selected_idx > 0 {
+								self.selected_idx -= 1;
+							}
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.delete_generic,
+						) {
+							drop(opts);
+							if let Some(idx) =
+								self.selected_list_idx()
+							{
+								self.options
+									.borrow_mut()
+									.remove_extern_command(idx);
+								self.update_filter();
+							}
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.assign_shortcut,
+						) {
+							self.shortcut_state =
+								ShortcutState::Assign;
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.clear_shortcut,
+						) {
+							drop(opts);
+							if let Some(idx) =
+								self.selected_list_idx()
+							{
+								self.options
+									.borrow_mut()
+									.assign_shortcut_for_extern_command(
+										idx, None,
+									);
+							}
+							true
+						} else if key_match(
+							key,
+							self.key_config.keys.clear_all_shortcut,
+						) {
+							drop(opts);
+							self.options
+								.borrow_mut()
+								.clear_all_shortcuts_for_extern_commands(
+								);
+							true
+						} else if let Some(cmd) = opts
+							.find_extern_cmd_for_shortcut(key.into())
+						{
+							drop(opts);
+							self.run_command_ui(&cmd);
+							self.hide();
+							true
+						} else {
+							false
+						}
 					} else {
 						false
 					}
 				} else {
 					false
 				}
-			} else {
-				false
 			};
 
 			if !consumed && self.cmdline.event(event)?.is_consumed() {
+				self.update_filter();
 			}
 
 			return Ok(EventState::Consumed);
@@ -539,15 +938,183 @@ impl Component for ExternalCommandPopupComponent {
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
 		self.shortcut_state = ShortcutState::Idle;
+		self.cmdline.clear();
+		self.cmdline.show()?;
+		self.update_filter();
 		self.focused =
 			if self.options.borrow().extern_commands().is_empty() {
 				Focused::Input
 			} else {
 				Focused::List
 			};
-		self.cmdline.clear();
-		self.cmdline.show()?;
 
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		filter_commands, reveal_command,
+		ExternalCommandPopupComponent, Focused,
+	};
+	use crate::{
+		async_jobs::JobReceiver, components::Component,
+		keys::SharedKeyConfig, options::Options, queue::Queue,
+		ui::style::SharedTheme,
+	};
+	use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+	use std::{path::Path, sync::mpsc};
+
+	const COMMANDS: [&str; 3] =
+		["cargo test", "git log --oneline", "cargo clippy"];
+
+	#[test]
+	fn test_filter_empty_query_keeps_all() {
+		assert_eq!(
+			filter_commands(COMMANDS.into_iter(), "")
+				.into_iter()
+				.map(|(idx, _)| idx)
+				.collect::<Vec<_>>(),
+			vec![0, 1, 2]
+		);
+	}
+
+	#[test]
+	fn test_filter_fuzzy() {
+		let hits = filter_commands(COMMANDS.into_iter(), "cgcl");
+		assert_eq!(hits.len(), 1);
+		assert_eq!(hits[0].0, 2);
+		assert_eq!(hits[0].1.len(), 4);
+
+		assert!(
+			filter_commands(COMMANDS.into_iter(), "xyz").is_empty()
+		);
+	}
+
+	fn type_text(
+		popup: &mut ExternalCommandPopupComponent,
+		text: &str,
+	) {
+		for c in text.chars() {
+			popup
+				.event(&Event::Key(KeyEvent::new(
+					KeyCode::Char(c),
+					KeyModifiers::empty(),
+				)))
+				.unwrap();
+		}
+	}
+
+	fn popup_with_commands(
+	) -> (ExternalCommandPopupComponent, JobReceiver) {
+		let options = Options::in_memory();
+		for cmd in COMMANDS.iter().rev() {
+			options.borrow_mut().add_extern_command(cmd);
+		}
+		let (sender, receiver) = mpsc::channel();
+		let mut popup = ExternalCommandPopupComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			Queue::new(),
+			options,
+			sender,
+		);
+		popup.show().unwrap();
+		(popup, receiver)
+	}
+
+	fn press(
+		popup: &mut ExternalCommandPopupComponent,
+		code: KeyCode,
+	) {
+		popup
+			.event(&Event::Key(KeyEvent::new(
+				code,
+				KeyModifiers::empty(),
+			)))
+			.unwrap();
+	}
+
+	#[test]
+	fn test_enter_runs_highlighted_match() {
+		let (mut popup, _receiver) = popup_with_commands();
+		assert!(popup.focused == Focused::List);
+
+		type_text(&mut popup, "log");
+		assert!(popup.focused == Focused::Input);
+		assert_eq!(popup.selected_list_idx(), None);
+		assert_eq!(popup.selected_command(), "log");
+
+		// moving into the list highlights the best match
+		let toggle = popup.key_config.keys.toggle_workarea;
+		press(&mut popup, toggle.code);
+		assert_eq!(popup.selected_command(), "git log --oneline");
+
+		// typing leaves the list again
+		type_text(&mut popup, "yz");
+		assert!(popup.focused == Focused::Input);
+		assert_eq!(popup.selected_command(), "logyz");
+	}
+
+	#[test]
+	fn test_exact_command_is_highlighted() {
+		let (mut popup, _receiver) = popup_with_commands();
+
+		type_text(&mut popup, "cargo clippy");
+		assert_eq!(popup.selected_list_idx(), Some(2));
+		assert_eq!(popup.selected_command(), "cargo clippy");
+	}
+
+	#[test]
+	fn test_enter_runs_typed_command() {
+		let (mut popup, _receiver) = popup_with_commands();
+
+		type_text(&mut popup, "git log");
+		press(&mut popup, KeyCode::Enter);
+
+		assert!(!popup.is_visible());
+		assert_eq!(
+			popup.options.borrow().extern_commands()[0].0,
+			"git log"
+		);
+	}
+
+	#[cfg(all(unix, not(target_os = "macos")))]
+	#[test]
+	fn test_reveal_command_quotes_path() {
+		assert_eq!(
+			reveal_command(Path::new("/tmp/it's here")),
+			"xdg-open '/tmp/it'\\''s here'"
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_format_output_shows_command_and_exit_code() {
+		use std::os::unix::process::ExitStatusExt;
+
+		let output = std::process::Output {
+			status: std::process::ExitStatus::from_raw(2 << 8),
+			stdout: Vec::new(),
+			stderr: b"boom\n".to_vec(),
+		};
+
+		assert_eq!(
+			ExternalCommandPopupComponent::format_output(
+				"make check",
+				&output,
+				true
+			),
+			"command: make check\nexit code: 2\n\n--- stderr ---\nboom"
+		);
+		assert_eq!(
+			ExternalCommandPopupComponent::format_output(
+				"make check",
+				&output,
+				false
+			),
+			"--- stderr ---\nboom"
+		);
+	}
+}
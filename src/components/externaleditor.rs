@@ -27,6 +27,47 @@ use scopeguard::defer;
 use std::ffi::OsStr;
 use std::{env, io, path::Path, process::Command};
 
+/// checked in this order after the configured editor
+const EDITOR_ENV_VARS: [&str; 3] = ["GIT_EDITOR", "VISUAL", "EDITOR"];
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// editor command to launch, resolved in this order: `configured`
+/// (the `editor` option), `$GIT_EDITOR`, `$VISUAL`, `$EDITOR`,
+/// git's `core.editor` and finally a platform default
+pub fn resolve_editor(
+	repo: &RepoPath,
+	configured: Option<&str>,
+) -> String {
+	pick_editor(
+		configured,
+		|var| env::var(var).ok(),
+		|| get_config_string(repo, "core.editor").ok().flatten(),
+	)
+}
+
+fn pick_editor(
+	configured: Option<&str>,
+	env_var: impl Fn(&str) -> Option<String>,
+	core_editor: impl FnOnce() -> Option<String>,
+) -> String {
+	let not_empty = |editor: &String| !editor.trim().is_empty();
+
+	configured
+		.map(String::from)
+		.filter(not_empty)
+		.or_else(|| {
+			EDITOR_ENV_VARS
+				.iter()
+				.find_map(|var| env_var(var).filter(not_empty))
+		})
+		.or_else(|| core_editor().filter(not_empty))
+		.unwrap_or_else(|| String::from(DEFAULT_EDITOR))
+}
+
 ///
 pub struct ExternalEditorComponent {
 	visible: bool,
@@ -47,10 +88,13 @@ impl ExternalEditorComponent {
 		}
 	}
 
-	/// opens file at given `path` in an available editor
+	/// opens file at given `path` in the editor picked by
+	/// [`resolve_editor`], at `line` (1-based) if given
 	pub fn open_file_in_editor(
 		repo: &RepoPath,
 		path: &Path,
+		line: Option<usize>,
+		configured: Option<&str>,
 	) -> Result<()> {
 		let work_dir = repo_work_dir(repo)?;
 
@@ -69,16 +113,7 @@ impl ExternalEditorComponent {
 			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
 		}
 
-		let environment_options = ["GIT_EDITOR", "VISUAL", "EDITOR"];
-
-		let editor = env::var(environment_options[0])
-			.ok()
-			.or_else(|| {
-				get_config_string(repo, "core.editor").ok()?
-			})
-			.or_else(|| env::var(environment_options[1]).ok())
-			.or_else(|| env::var(environment_options[2]).ok())
-			.unwrap_or_else(|| String::from("vi"));
+		let editor = resolve_editor(repo, configured);
 
 		// TODO: proper handling arguments containing whitespaces
 		// This does not do the right thing if the input is `editor --something "with spaces"`
@@ -88,12 +123,9 @@ impl ExternalEditorComponent {
 		// does not address spaces in pn
 		let mut echars = editor.chars().peekable();
 
-		let first_char = *echars.peek().ok_or_else(|| {
-			anyhow!(
-				"editor env variable found empty: {}",
-				environment_options.join(" or ")
-			)
-		})?;
+		let first_char = *echars
+			.peek()
+			.ok_or_else(|| anyhow!("editor command found empty"))?;
 		let command: String = if first_char == '\"' {
 			echars
 				.by_ref()
@@ -110,6 +142,11 @@ impl ExternalEditorComponent {
 		let mut args: Vec<&OsStr> =
 			remainder.map(OsStr::new).collect();
 
+		// `+N` is understood by vi, emacs, nano and most others
+		let line = line.map(|line| format!("+{line}"));
+		if let Some(line) = &line {
+			args.push(OsStr::new(line));
+		}
 		args.push(path.as_os_str());
 
 		Command::new(command.clone())
@@ -192,3 +229,64 @@ impl Component for ExternalEditorComponent {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{pick_editor, DEFAULT_EDITOR};
+
+	fn env_with(
+		vars: &'static [(&'static str, &'static str)],
+	) -> impl Fn(&str) -> Option<String> {
+		move |var| {
+			vars.iter()
+				.find(|(name, _)| *name == var)
+				.map(|(_, value)| String::from(*value))
+		}
+	}
+
+	#[test]
+	fn test_configured_editor_wins() {
+		assert_eq!(
+			pick_editor(
+				Some("hx"),
+				env_with(&[("EDITOR", "nano")]),
+				|| Some(String::from("vim"))
+			),
+			"hx"
+		);
+	}
+
+	#[test]
+	fn test_env_before_core_editor() {
+		assert_eq!(
+			pick_editor(
+				None,
+				env_with(&[("EDITOR", "nano"), ("VISUAL", "emacs")]),
+				|| Some(String::from("vim"))
+			),
+			"emacs"
+		);
+		assert_eq!(
+			pick_editor(
+				Some(" "),
+				env_with(&[("VISUAL", ""), ("EDITOR", "nano")]),
+				|| Some(String::from("vim"))
+			),
+			"nano"
+		);
+	}
+
+	#[test]
+	fn test_core_editor_then_default() {
+		assert_eq!(
+			pick_editor(None, env_with(&[]), || Some(String::from(
+				"vim"
+			))),
+			"vim"
+		);
+		assert_eq!(
+			pick_editor(None, env_with(&[]), || None),
+			DEFAULT_EDITOR
+		);
+	}
+}
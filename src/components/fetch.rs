@@ -14,8 +14,8 @@ use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			extract_username_password, is_auth_error,
+			need_username_password, BasicAuthCredential,
 		},
 		RepoPathRef,
 	},
@@ -84,6 +84,7 @@ impl FetchComponent {
 
 	///
 	pub fn fetch(&mut self) -> Result<()> {
+		self.input_cred.reset_retries();
 		self.show()?;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
@@ -104,6 +105,7 @@ impl FetchComponent {
 	}
 
 	fn fetch_all(&mut self, cred: Option<BasicAuthCredential>) {
+		self.input_cred.attempt_with(cred.as_ref());
 		self.pending = true;
 		self.progress = None;
 		self.progress = Some(ProgressPercent::empty());
@@ -135,12 +137,18 @@ impl FetchComponent {
 	///
 	pub fn update_git(&mut self, ev: AsyncGitNotification) {
 		if self.is_visible() && ev == AsyncGitNotification::Fetch {
-			self.update();
+			if let Err(error) = self.update() {
+				self.pending = false;
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("fetch failed:\n{error}"),
+				));
+			}
 		}
 	}
 
 	///
-	fn update(&mut self) {
+	fn update(&mut self) -> Result<()> {
 		self.pending = match self.fetch_type {
 			FetchType::Internal => self.async_fetch.is_pending(),
 			FetchType::External => {
@@ -153,10 +161,29 @@ impl FetchComponent {
 		};
 
 		if !self.pending {
+			let result = self
+				.async_fetch
+				.take_last()
+				.and_then(|job| job.result());
+			if let Some(Err(err)) = result {
+				let err = err.to_string();
+				// ask again and fetch with the new password
+				if is_auth_error(&err)
+					&& self
+						.input_cred
+						.retry(self.options.borrow().auth_retries())?
+				{
+					return Ok(());
+				}
+				anyhow::bail!(err);
+			}
+
 			self.hide();
 			self.queue
 				.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
 		}
+
+		Ok(())
 	}
 }
 
@@ -225,7 +252,14 @@ impl Component for FetchComponent {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
-					if self.input_cred.get_cred().is_complete()
+					if !self.input_cred.is_visible()
+						&& self.input_cred.is_retry()
+					{
+						self.hide();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::auth_retry_cancelled("fetch"),
+						));
+					} else if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
 						self.fetch_all(Some(
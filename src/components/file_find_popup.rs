@@ -13,18 +13,26 @@ use crate::{
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::sync::TreeFile;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{RepoPathRef, TreeFile},
+	AsyncFileHeadJob, AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use fuzzy_matcher::FuzzyMatcher;
 use ratatui::{
 	backend::Backend,
 	layout::{Constraint, Direction, Layout, Margin, Rect},
 	text::{Span, Spans},
-	widgets::{Block, Borders, Clear},
+	widgets::{Block, Borders, Clear, Paragraph},
 	Frame,
 };
 use std::borrow::Cow;
 
+/// lines of the selected file shown below the list
+const PREVIEW_LINES: u16 = 10;
+
 #[derive(Eq, PartialEq)]
 enum Focus {
 	Input,
@@ -32,6 +40,7 @@ enum Focus {
 }
 
 pub struct FileFindPopup {
+	repo: RepoPathRef,
 	queue: Queue,
 	visible: bool,
 	find_text: TextInputComponent,
@@ -44,12 +53,18 @@ pub struct FileFindPopup {
 	key_config: SharedKeyConfig,
 	response_queue: Option<SharedLocalQueue>,
 	focused: Focus,
+	show_preview: bool,
+	/// head of the selected file, `None` if there is nothing to show
+	preview: Option<String>,
+	git_preview: AsyncSingleJob<AsyncFileHeadJob>,
 }
 
 impl FileFindPopup {
 	///
 	pub fn new(
+		repo: RepoPathRef,
 		queue: &Queue,
+		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
 	) -> Self {
@@ -64,6 +79,7 @@ impl FileFindPopup {
 		find_text.set_selected(true);
 
 		Self {
+			repo,
 			queue: queue.clone(),
 			visible: false,
 			query: None,
@@ -76,6 +92,9 @@ impl FileFindPopup {
 			selection: 0,
 			response_queue: None,
 			focused: Focus::Input,
+			show_preview: true,
+			preview: None,
+			git_preview: AsyncSingleJob::new(sender.clone()),
 		}
 	}
 
@@ -107,11 +126,10 @@ impl FileFindPopup {
 				.iter()
 				.enumerate()
 				.filter_map(|a| {
-					a.1.path.to_str().and_then(|path| {
-						matcher.fuzzy_indices(path, q).map(
-							|(score, indices)| (score, a.0, indices),
-						)
-					})
+					// lossy to keep non utf8 paths findable
+					matcher
+						.fuzzy_indices(&a.1.path.to_string_lossy(), q)
+						.map(|(score, indices)| (score, a.0, indices))
 				})
 				.collect::<Vec<(_, _, _)>>();
 
@@ -134,6 +152,7 @@ impl FileFindPopup {
 
 		if self.selected_index != selection {
 			self.selected_index = selection;
+			self.update_preview();
 
 			let file = self
 				.selected_index
@@ -147,6 +166,51 @@ impl FileFindPopup {
 		}
 	}
 
+	fn update_preview(&mut self) {
+		self.preview = None;
+
+		if let Some(file) = self
+			.selected_index
+			.and_then(|index| self.files.get(index))
+			.filter(|_| self.show_preview)
+		{
+			self.git_preview.spawn(AsyncFileHeadJob::new(
+				self.repo.borrow().clone(),
+				file.clone(),
+				PREVIEW_LINES.into(),
+			));
+		}
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::FileHead {
+			return;
+		}
+
+		if let Some(job) = self.git_preview.take_last() {
+			// the selection may have moved on since
+			let selected = self
+				.selected_index
+				.and_then(|index| self.files.get(index));
+			if self.show_preview && selected == Some(job.file()) {
+				self.preview = job.result().and_then(Result::ok);
+			}
+		}
+	}
+
+	fn toggle_preview(&mut self) {
+		self.show_preview = !self.show_preview;
+		self.update_preview();
+	}
+
+	/// path of the selected file relative to the work dir
+	fn selected_file(&self) -> Option<String> {
+		self.selected_index
+			.and_then(|index| self.files.get(index))
+			.map(|f| f.path.to_string_lossy().to_string())
+	}
+
 	fn finish_selection(&mut self) {
 		if let Some(q) = self.response_queue.as_mut() {
 			let file = self
@@ -173,6 +237,10 @@ impl FileFindPopup {
 		if self.files != *files {
 			self.files = files.to_owned();
 		}
+		// a reloaded list previews from scratch, the files may have
+		// changed on disk since
+		self.selected_index = None;
+		self.preview = None;
 		self.update_query();
 
 		Ok(())
@@ -218,6 +286,26 @@ impl FileFindPopup {
 	}
 }
 
+impl FileFindPopup {
+	fn draw_preview<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+		f.render_widget(
+			Paragraph::new(
+				self.preview.as_deref().unwrap_or_default(),
+			)
+			.style(self.theme.text(false, false))
+			.block(
+				Block::default()
+					.title(Span::styled(
+						strings::POPUP_TITLE_FUZZY_FIND_PREVIEW,
+						self.theme.title(false),
+					))
+					.borders(Borders::TOP),
+			),
+			area,
+		);
+	}
+}
+
 impl DrawableComponent for FileFindPopup {
 	fn draw<B: Backend>(
 		&self,
@@ -226,11 +314,21 @@ impl DrawableComponent for FileFindPopup {
 	) -> Result<()> {
 		if self.is_visible() {
 			const MAX_SIZE: (u16, u16) = (50, 20);
+			// the list keeps at least this many rows next to a preview
+			const MIN_LIST_HEIGHT: u16 = 5;
 
 			let any_hits = !self.files_filtered.is_empty();
 
+			let preview_height = if self.show_preview {
+				PREVIEW_LINES + 1
+			} else {
+				0
+			};
+
 			let area = ui::centered_rect_absolute(
-				MAX_SIZE.0, MAX_SIZE.1, area,
+				MAX_SIZE.0,
+				MAX_SIZE.1 + preview_height,
+				area,
 			);
 
 			let area = if any_hits {
@@ -276,6 +374,28 @@ impl DrawableComponent for FileFindPopup {
 
 			self.find_text.draw(f, chunks[0])?;
 
+			let chunks = if preview_height > 0
+				&& chunks[1].height
+					>= MIN_LIST_HEIGHT + preview_height
+			{
+				Layout::default()
+					.direction(Direction::Vertical)
+					.constraints(
+						[
+							Constraint::Length(chunks[0].height),
+							Constraint::Min(MIN_LIST_HEIGHT),
+							Constraint::Length(preview_height),
+						]
+						.as_ref(),
+					)
+					.split(area.inner(&Margin {
+						horizontal: 1,
+						vertical: 1,
+					}))
+			} else {
+				chunks
+			};
+
 			if any_hits {
 				let title =
 					format!("Hits: {}", self.files_filtered.len());
@@ -298,13 +418,10 @@ impl DrawableComponent for FileFindPopup {
 						let selected = self
 							.selected_index
 							.map_or(false, |index| index == *idx);
-						let full_text = trim_length_left(
-							self.files[*idx]
-								.path
-								.to_str()
-								.unwrap_or_default(),
-							width,
-						);
+						let path =
+							self.files[*idx].path.to_string_lossy();
+						let full_text =
+							trim_length_left(&path, width);
 						Spans::from(
 							full_text
 								.char_indices()
@@ -333,6 +450,10 @@ impl DrawableComponent for FileFindPopup {
 						.borders(Borders::TOP),
 					items,
 				);
+
+				if let Some(preview_area) = chunks.get(2) {
+					self.draw_preview(f, *preview_area);
+				}
 			}
 		}
 		Ok(())
@@ -358,6 +479,26 @@ impl Component for FileFindPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::toggle_fuzzy_finder_preview(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::copy_abs_path(&self.key_config),
+				self.selected_index.is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::reveal_file(&self.key_config),
+				self.selected_index.is_some(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::close_fuzzy_finder(
 					&self.key_config,
@@ -388,9 +529,31 @@ impl Component for FileFindPopup {
 					};
 					self.find_text
 						.set_selected(self.focused == Focus::Input);
+				} else if key_match(
+					key,
+					self.key_config.keys.file_find_preview,
+				) {
+					self.toggle_preview();
 				} else if key_match(key, self.key_config.keys.enter) {
 					self.finish_selection();
 					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.copy_abs_path,
+				) {
+					if let Some(file) = self.selected_file() {
+						self.queue.push(
+							InternalEvent::CopyAbsolutePath(file),
+						);
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.reveal_file,
+				) {
+					if let Some(file) = self.selected_file() {
+						self.queue
+							.push(InternalEvent::RevealFile(file));
+					}
 				} else {
 					match self.focused {
 						Focus::Input => {
@@ -429,3 +592,130 @@ impl Component for FileFindPopup {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use asyncgit::sync::{repo_files, RepoPath};
+	use crossbeam_channel::{unbounded, Receiver};
+	use std::{
+		cell::RefCell, fs::File, path::PathBuf, time::Duration,
+	};
+
+	#[test]
+	// macOS and most other filesystems refuse non utf8 names
+	#[cfg(target_os = "linux")]
+	fn test_non_utf8_path_is_found() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+		// `repo_files` skips paths with hidden components
+		let td = tempfile::Builder::new()
+			.prefix("find")
+			.tempdir()
+			.unwrap();
+		let name = OsStr::from_bytes(b"caf\xe9.txt");
+		let path = td.path().join(name);
+		File::create(&path).unwrap();
+		File::create(td.path().join("other.rs")).unwrap();
+
+		let repo_path: RepoPath = td.path().to_str().unwrap().into();
+		let files = repo_files(&repo_path, false).unwrap();
+
+		let queue = Queue::new();
+		let (tx, _rx) = unbounded();
+		let mut popup = FileFindPopup::new(
+			RefCell::new(repo_path),
+			&queue,
+			&tx,
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		popup.open(&files, None).unwrap();
+		popup.set_query(Some(String::from("caf.txt")));
+
+		assert_eq!(popup.files_filtered.len(), 1);
+		assert_eq!(
+			popup.selected_index.map(|idx| &files[idx].path),
+			Some(&path)
+		);
+
+		let mut picked: Option<PathBuf> = None;
+		while let Some(ev) = queue.pop() {
+			if let InternalEvent::FileFinderChanged(file) = ev {
+				picked = file;
+			}
+		}
+		assert_eq!(picked, Some(path));
+	}
+
+	fn settle(
+		popup: &mut FileFindPopup,
+		rx: &Receiver<AsyncGitNotification>,
+	) {
+		loop {
+			let ev = rx
+				.recv_timeout(Duration::from_secs(10))
+				.expect("no preview notification");
+			popup.update_git(ev);
+
+			if !popup.git_preview.is_pending() {
+				break;
+			}
+		}
+	}
+
+	#[test]
+	fn test_preview() {
+		let td = tempfile::Builder::new()
+			.prefix("find")
+			.tempdir()
+			.unwrap();
+		let content = (1..=20)
+			.map(|line| format!("line {line}"))
+			.collect::<Vec<_>>()
+			.join("\n");
+		std::fs::write(td.path().join("long.txt"), content).unwrap();
+		std::fs::create_dir(td.path().join("dir")).unwrap();
+
+		let repo_path: RepoPath = td.path().to_str().unwrap().into();
+		let files = repo_files(&repo_path, true).unwrap();
+
+		let (tx, rx) = unbounded();
+		let mut popup = FileFindPopup::new(
+			RefCell::new(repo_path),
+			&Queue::new(),
+			&tx,
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+		);
+		popup.open(&files, None).unwrap();
+
+		popup.set_query(Some(String::from("long.txt")));
+		settle(&mut popup, &rx);
+		let preview = popup.preview.clone().unwrap();
+		assert_eq!(
+			preview.lines().count(),
+			usize::from(PREVIEW_LINES)
+		);
+		assert!(preview.starts_with("line 1\nline 2\n"));
+
+		popup.toggle_preview();
+		assert_eq!(popup.preview, None);
+		popup.toggle_preview();
+		settle(&mut popup, &rx);
+		assert!(popup.preview.is_some());
+
+		popup.set_query(Some(String::from("dir")));
+		settle(&mut popup, &rx);
+		assert_eq!(popup.preview.as_deref(), Some(""));
+
+		// reopening reloads the same list, the file changed meanwhile
+		std::fs::write(td.path().join("long.txt"), "changed")
+			.unwrap();
+		popup.open(&files, None).unwrap();
+		assert_eq!(popup.preview, None);
+		popup.set_query(Some(String::from("long.txt")));
+		settle(&mut popup, &rx);
+		assert_eq!(popup.preview.as_deref(), Some("changed"));
+	}
+}
@@ -16,7 +16,8 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	sync::{
-		diff_contains_file, get_commits_info, CommitId, RepoPathRef,
+		diff_contains_file, get_commits_info, parent_ids, CommitId,
+		RepoPathRef,
 	},
 	AsyncDiff, AsyncGitNotification, AsyncLog, DiffParams, DiffType,
 	FetchStatus,
@@ -88,6 +89,7 @@ impl FileRevlogComponent {
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			git_log: None,
@@ -192,13 +194,29 @@ impl FileRevlogComponent {
 					let diff_params = DiffParams {
 						path: open_request.file_path.clone(),
 						diff_type: DiffType::Commit(commit_id),
-						options: self.options.borrow().diff_options(),
+						options: self
+							.options
+							.borrow()
+							.diff_options_for(
+								&open_request.file_path,
+							),
 					};
 
 					if let Some((params, last)) =
 						self.git_diff.last()?
 					{
 						if params == diff_params {
+							let parent = parent_ids(
+								&self.repo_path.borrow(),
+								commit_id,
+							)?
+							.first()
+							.copied();
+							self.diff.set_revisions(
+								parent,
+								Some(commit_id),
+							);
+							self.diff.set_params(params);
 							self.diff.update(
 								open_request.file_path.to_string(),
 								false,
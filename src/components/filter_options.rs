@@ -20,14 +20,45 @@ use ratatui::{
 //use scopeguard::defer;
 //use std::io;
 
-const FIELD_COUNT: usize = 2;
+const FIELD_COUNT: usize = 4;
 
+/// restricts the commit list by number of parents
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MergeFilter {
+	All,
+	OnlyMerges,
+	NoMerges,
+}
+
+impl MergeFilter {
+	fn next(self) -> Self {
+		match self {
+			Self::All => Self::OnlyMerges,
+			Self::OnlyMerges => Self::NoMerges,
+			Self::NoMerges => Self::All,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::All => "all",
+			Self::OnlyMerges => "only merges",
+			Self::NoMerges => "no merges",
+		}
+	}
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct FilterOptionsPopupComponent {
 	visible: bool,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
 	pub author: bool,
 	pub message: bool,
+	/// with several space separated terms: all must match (AND)
+	/// instead of any (OR)
+	pub match_all_terms: bool,
+	pub merges: MergeFilter,
 	selected_idx: usize,
 	pub title: String,
 }
@@ -44,6 +75,8 @@ impl FilterOptionsPopupComponent {
 			theme,
 			author: true,
 			message: true,
+			match_all_terms: true,
+			merges: MergeFilter::All,
 			selected_idx: 0,
 			title: String::new(),
 		};
@@ -80,6 +113,22 @@ impl FilterOptionsPopupComponent {
 			self.message,
 			self.selected_idx == 1,
 		);
+		self.add_checkbox(
+			&mut txt,
+			"Match all terms (AND, otherwise OR)".to_string(),
+			self.match_all_terms,
+			self.selected_idx == 2,
+		);
+		txt.push(Spans::from(vec![
+			Span::styled(
+				format!("<{}> ", self.merges.name()),
+				self.theme.text(true, false),
+			),
+			Span::styled(
+				"Merge commits",
+				self.theme.text(true, self.selected_idx == 3),
+			),
+		]));
 
 		txt
 	}
@@ -96,6 +145,16 @@ impl FilterOptionsPopupComponent {
 			}
 			self.title += ")";
 		}
+		self.title += if self.match_all_terms {
+			" [all]"
+		} else {
+			" [any]"
+		};
+		if self.merges != MergeFilter::All {
+			self.title += " [";
+			self.title += self.merges.name();
+			self.title += "]";
+		}
 	}
 
 	pub fn enable_all(&mut self) {
@@ -195,6 +254,10 @@ impl Component for FilterOptionsPopupComponent {
 						self.author = !self.author;
 					} else if self.selected_idx == 1 {
 						self.message = !self.message;
+					} else if self.selected_idx == 2 {
+						self.match_all_terms = !self.match_all_terms;
+					} else if self.selected_idx == 3 {
+						self.merges = self.merges.next();
 					}
 					true
 				} else if key_match(
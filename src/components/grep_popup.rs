@@ -0,0 +1,514 @@
+use super::{
+	utils::search_pattern::SearchPattern, visibility_blocking,
+	BlameFileOpen, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ScrollType, TextInputComponent,
+};
+use crate::{
+	async_jobs::{
+		AsyncDynJob, AsyncJobFeedback, BoxFeedback,
+		JobFeedbackSender, JobProgress, JobSender,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, CommitId, GrepMatch, RepoPath, RepoPathRef,
+	GREP_MATCHES_MAX,
+};
+use crossterm::event::Event;
+use ratatui::{
+	backend::Backend,
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Span, Spans},
+	widgets::{Block, Borders, Clear},
+	Frame,
+};
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc,
+};
+
+/// searches the files of a commit (or the worktree) in the
+/// background
+struct AsyncGrepJob {
+	repo: RepoPath,
+	commit: Option<CommitId>,
+	pattern: SearchPattern,
+	abort: Arc<AtomicBool>,
+}
+
+impl AsyncDynJob for AsyncGrepJob {
+	fn run(
+		&mut self,
+		sender: JobFeedbackSender,
+	) -> Option<BoxFeedback> {
+		let mut last_percent = None;
+		let result = sync::grep(
+			&self.repo,
+			self.commit,
+			|line| self.pattern.is_match(line),
+			|done, total| {
+				// every update redraws the app, one per percent will do
+				let percent = JobProgress { done, total }.percent();
+				if last_percent != Some(percent) {
+					last_percent = Some(percent);
+					sender.progress(done, total);
+				}
+				!self.abort.load(Ordering::Relaxed)
+			},
+		);
+
+		Some(Box::new(AsyncGrepFeedback {
+			abort: self.abort.clone(),
+			result: Some(result.map_err(anyhow::Error::from)),
+		}))
+	}
+
+	fn should_stop(&self) -> bool {
+		false
+	}
+}
+
+struct AsyncGrepFeedback {
+	/// tells the search this is the result of apart from older ones
+	abort: Arc<AtomicBool>,
+	result: Option<Result<Vec<GrepMatch>>>,
+}
+
+impl AsyncJobFeedback for AsyncGrepFeedback {
+	fn visit(&mut self, app: &mut crate::app::App) {
+		if let Some(result) = self.result.take() {
+			app.grep_popup.finish_search(&self.abort, result);
+		}
+	}
+}
+
+#[derive(Eq, PartialEq)]
+enum Focus {
+	Input,
+	List,
+}
+
+pub struct GrepPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	async_job_sender: JobSender,
+	focused: Focus,
+	/// commit searched in, the worktree if `None`
+	commit: Option<CommitId>,
+	/// the text is a regular expression
+	regex: bool,
+	matches: Vec<GrepMatch>,
+	selection: usize,
+	/// set while a search runs, aborts it when stored to
+	search_abort: Option<Arc<AtomicBool>>,
+}
+
+impl GrepPopup {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+		async_job_sender: JobSender,
+	) -> Self {
+		let mut input = TextInputComponent::new(
+			theme.clone(),
+			key_config.clone(),
+			"",
+			strings::POPUP_GREP_PLACEHOLDER,
+			false,
+		);
+		input.embed();
+		input.set_selected(true);
+
+		Self {
+			repo,
+			queue: queue.clone(),
+			visible: false,
+			input,
+			theme,
+			key_config,
+			async_job_sender,
+			focused: Focus::Input,
+			commit: None,
+			regex: false,
+			matches: Vec::new(),
+			selection: 0,
+			search_abort: None,
+		}
+	}
+
+	/// searches `commit` or the worktree if `None`, the last
+	/// results are kept when searching the same again
+	pub fn open(&mut self, commit: Option<CommitId>) -> Result<()> {
+		if self.commit != commit {
+			self.abort_search();
+			self.commit = commit;
+			self.matches.clear();
+			self.selection = 0;
+			self.input.set_text(String::new());
+		}
+
+		self.set_focus(Focus::Input);
+		self.show()?;
+		self.input.show()?;
+
+		Ok(())
+	}
+
+	pub fn any_work_pending(&self) -> bool {
+		self.search_abort.is_some()
+	}
+
+	/// takes the result of a search unless a newer one was
+	/// started in the meantime
+	pub fn finish_search(
+		&mut self,
+		abort: &Arc<AtomicBool>,
+		result: Result<Vec<GrepMatch>>,
+	) {
+		if !self
+			.search_abort
+			.as_ref()
+			.map_or(false, |current| Arc::ptr_eq(current, abort))
+		{
+			return;
+		}
+
+		self.search_abort = None;
+
+		match result {
+			Ok(matches) => {
+				self.matches = matches;
+				self.selection = 0;
+				if !self.matches.is_empty() {
+					self.set_focus(Focus::List);
+				}
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("{}:\n{e}", strings::POPUP_FAIL_GREP),
+				));
+			}
+		}
+	}
+
+	fn start_search(&mut self) {
+		let text = self.input.get_text();
+		if text.is_empty() {
+			return;
+		}
+
+		let pattern = match SearchPattern::new(text, true, self.regex)
+		{
+			Ok(pattern) => pattern,
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("{}:\n{e}", strings::POPUP_FAIL_GREP),
+				));
+				return;
+			}
+		};
+
+		self.abort_search();
+
+		let abort = Arc::new(AtomicBool::new(false));
+		self.search_abort = Some(abort.clone());
+		self.matches.clear();
+		self.selection = 0;
+
+		if self
+			.async_job_sender
+			.send(Box::new(AsyncGrepJob {
+				repo: self.repo.borrow().clone(),
+				commit: self.commit,
+				pattern,
+				abort,
+			}))
+			.is_err()
+		{
+			self.search_abort = None;
+		}
+	}
+
+	fn abort_search(&mut self) {
+		if let Some(abort) = self.search_abort.take() {
+			abort.store(true, Ordering::Relaxed);
+		}
+	}
+
+	fn set_focus(&mut self, focus: Focus) {
+		self.focused = focus;
+		self.input.set_selected(self.focused == Focus::Input);
+	}
+
+	/// opens the matching line, of worktree files in the editor and
+	/// of files of a commit in blame
+	fn open_selected(&mut self) {
+		if let Some(m) = self.matches.get(self.selection) {
+			let path = m.path.to_string_lossy().to_string();
+
+			self.queue.push(if let Some(commit) = self.commit {
+				InternalEvent::OpenPopup(
+					StackablePopupOpen::BlameFile(BlameFileOpen {
+						file_path: path,
+						commit_id: Some(commit),
+						selection: Some(m.line.saturating_sub(1)),
+					}),
+				)
+			} else {
+				InternalEvent::OpenExternalEditorAt(path, m.line)
+			});
+
+			self.hide();
+		}
+	}
+
+	fn move_selection(&mut self, move_type: ScrollType) {
+		let new_selection = match move_type {
+			ScrollType::Up => self.selection.saturating_sub(1),
+			ScrollType::Down => self.selection.saturating_add(1),
+			ScrollType::Home => 0,
+			ScrollType::End => self.matches.len().saturating_sub(1),
+			_ => self.selection,
+		};
+
+		self.selection =
+			new_selection.min(self.matches.len().saturating_sub(1));
+	}
+
+	fn list_event(&mut self, key: &crossterm::event::KeyEvent) {
+		if key_match(key, self.key_config.keys.move_down) {
+			self.move_selection(ScrollType::Down);
+		} else if key_match(key, self.key_config.keys.move_up) {
+			self.move_selection(ScrollType::Up);
+		} else if key_match(key, self.key_config.keys.home) {
+			self.move_selection(ScrollType::Home);
+		} else if key_match(key, self.key_config.keys.end) {
+			self.move_selection(ScrollType::End);
+		}
+	}
+
+	fn title(&self) -> String {
+		let scope = self.commit.map_or_else(
+			|| String::from("worktree"),
+			|c| c.get_short_string(),
+		);
+
+		format!(
+			"{} ({scope}){}",
+			strings::POPUP_TITLE_GREP,
+			if self.regex { " (regex)" } else { "" }
+		)
+	}
+
+	fn hits_title(&self) -> String {
+		if self.search_abort.is_some() {
+			String::from("Searching..")
+		} else if self.matches.len() >= GREP_MATCHES_MAX {
+			format!("Hits: {}+", self.matches.len())
+		} else {
+			format!("Hits: {}", self.matches.len())
+		}
+	}
+}
+
+impl DrawableComponent for GrepPopup {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		area: Rect,
+	) -> Result<()> {
+		if self.is_visible() {
+			const MAX_SIZE: (u16, u16) = (100, 25);
+
+			let area = ui::centered_rect_absolute(
+				MAX_SIZE.0, MAX_SIZE.1, area,
+			);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.style(self.theme.title(true))
+					.title(Span::styled(
+						self.title(),
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(1),
+						Constraint::Percentage(100),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(&Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			self.input.draw(f, chunks[0])?;
+
+			let items_height =
+				usize::from(chunks[1].height.saturating_sub(1));
+			let skip = self
+				.selection
+				.checked_div(items_height)
+				.map_or(0, |page| page * items_height);
+
+			let items = self
+				.matches
+				.iter()
+				.enumerate()
+				.skip(skip)
+				.take(items_height)
+				.map(|(idx, m)| {
+					let selected = idx == self.selection
+						&& self.focused == Focus::List;
+					Spans::from(vec![
+						Span::styled(
+							format!(
+								"{}:{}: ",
+								m.path.to_string_lossy(),
+								m.line
+							),
+							self.theme.commit_hash(selected),
+						),
+						Span::styled(
+							m.content.trim().to_string(),
+							self.theme.text(true, selected),
+						),
+					])
+				});
+
+			ui::draw_list_block(
+				f,
+				chunks[1],
+				Block::default()
+					.title(Span::styled(
+						self.hits_title(),
+						self.theme.title(self.focused == Focus::List),
+					))
+					.borders(Borders::TOP),
+				items,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for GrepPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::grep_search(&self.key_config),
+				!self.input.get_text().is_empty(),
+				self.focused == Focus::Input,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::grep_regex(
+					&self.key_config,
+					self.regex,
+				),
+				true,
+				self.focused == Focus::Input,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::grep_open_match(
+					&self.key_config,
+					self.commit.is_some(),
+				),
+				!self.matches.is_empty(),
+				self.focused == Focus::List,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::switch_focus(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.toggle_workarea,
+				) {
+					self.set_focus(match self.focused {
+						Focus::Input => Focus::List,
+						Focus::List => Focus::Input,
+					});
+				} else if key_match(
+					key,
+					self.key_config.keys.diff_search_regex,
+				) && self.focused == Focus::Input
+				{
+					self.regex = !self.regex;
+				} else if key_match(key, self.key_config.keys.enter) {
+					match self.focused {
+						Focus::Input => self.start_search(),
+						Focus::List => self.open_selected(),
+					}
+				} else {
+					match self.focused {
+						Focus::Input => {
+							self.input.event(event)?;
+						}
+						Focus::List => self.list_event(key),
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		Ok(())
+	}
+}
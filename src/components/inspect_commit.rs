@@ -1,22 +1,26 @@
 use super::{
-	command_pump, event_pump, visibility_blocking, CommandBlocking,
-	CommandInfo, CommitDetailsComponent, Component, DiffComponent,
-	DrawableComponent, EventState,
+	command_pump, event_pump, utils::goto_line::GotoLine,
+	visibility_blocking, CommandBlocking, CommandInfo,
+	CommitDetailsComponent, Component, CopyClipboardOpen,
+	DiffComponent, DrawableComponent, EventState,
 };
 use crate::{
 	accessors,
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{InternalEvent, Queue, StackablePopupOpen},
 	strings,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{diff::DiffOptions, CommitId, CommitTags, RepoPathRef},
-	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+	asyncjob::AsyncSingleJob,
+	sync::{self, CommitId, CommitTags, RepoPathRef},
+	AsyncCommitChildJob, AsyncDiff, AsyncGitNotification,
+	CommitFilesParams, DiffParams, DiffType,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyCode};
 use ratatui::{
 	backend::Backend,
 	layout::{Constraint, Direction, Layout, Rect},
@@ -54,13 +58,24 @@ impl InspectCommitOpen {
 }
 
 pub struct InspectCommitComponent {
+	repo: RepoPathRef,
 	queue: Queue,
 	open_request: Option<InspectCommitOpen>,
+	/// parents of the inspected commit
+	parents: Vec<CommitId>,
+	/// commits we stepped to a parent from, most recent last
+	children: Vec<CommitId>,
+	/// number of the merge parent to step to, typed ahead
+	parent_input: GotoLine,
+	/// index into `parents` of the one the diff is taken against
+	diff_parent: usize,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
 	git_diff: AsyncDiff,
+	git_child: AsyncSingleJob<AsyncCommitChildJob>,
 	visible: bool,
 	key_config: SharedKeyConfig,
+	options: SharedOptions,
 }
 
 impl DrawableComponent for InspectCommitComponent {
@@ -131,6 +146,42 @@ impl Component for InspectCommitComponent {
 				self.diff.focused() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::copy_clipboard_info(
+					&self.key_config,
+				),
+				true,
+				self.details.details_focused() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::inspect_parent_commit(
+					&self.key_config,
+					self.selected_parent() + 1,
+					self.parents.len(),
+				),
+				!self.parents.is_empty(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::inspect_diff_parent(
+					&self.key_config,
+					self.diff_parent + 1,
+					self.parents.len(),
+				),
+				true,
+				self.parents.len() > 1,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::inspect_child_commit(
+					&self.key_config,
+				),
+				self.open_request.is_some(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::inspect_file_tree(
 					&self.key_config,
@@ -156,7 +207,31 @@ impl Component for InspectCommitComponent {
 			}
 
 			if let Event::Key(e) = ev {
-				if key_match(e, self.key_config.keys.exit_popup) {
+				let mut parent_input =
+					std::mem::take(&mut self.parent_input);
+
+				if key_match(
+					e,
+					self.key_config.keys.commit_history_next,
+				) {
+					self.parent_input = parent_input;
+					self.goto_parent()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.inspect_child_commit,
+				) {
+					self.goto_child()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.inspect_diff_parent,
+				) && self.parents.len() > 1
+				{
+					self.parent_input = parent_input;
+					self.diff_next_parent()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.exit_popup,
+				) {
 					if self.diff.focused() {
 						self.details.focus(true);
 						self.diff.focus(false);
@@ -173,6 +248,15 @@ impl Component for InspectCommitComponent {
 				} else if key_match(e, self.key_config.keys.move_left)
 				{
 					self.hide_stacked(false);
+				} else if key_match(e, self.key_config.keys.copy)
+					&& self.details.details_focused()
+				{
+					self.open_copy_popup();
+				} else if let KeyCode::Char(c) = e.code {
+					if self.parents.len() > 1 && parent_input.push(c)
+					{
+						self.parent_input = parent_input;
+					}
 				}
 
 				return Ok(EventState::Consumed);
@@ -208,8 +292,10 @@ impl InspectCommitComponent {
 		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
+			repo: repo.clone(),
 			queue: queue.clone(),
 			details: CommitDetailsComponent::new(
 				repo,
@@ -223,12 +309,19 @@ impl InspectCommitComponent {
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				true,
 			),
 			open_request: None,
+			parents: Vec::new(),
+			children: Vec::new(),
+			parent_input: GotoLine::default(),
+			diff_parent: 0,
 			git_diff: AsyncDiff::new(repo.borrow().clone(), sender),
+			git_child: AsyncSingleJob::new(sender.clone()),
 			visible: false,
 			key_config,
+			options,
 		}
 	}
 
@@ -241,6 +334,8 @@ impl InspectCommitComponent {
 	///
 	pub fn open(&mut self, open: InspectCommitOpen) -> Result<()> {
 		self.open_request = Some(open);
+		self.children.clear();
+		self.diff_parent = 0;
 		self.show()?;
 
 		Ok(())
@@ -248,7 +343,9 @@ impl InspectCommitComponent {
 
 	///
 	pub fn any_work_pending(&self) -> bool {
-		self.git_diff.is_pending() || self.details.any_work_pending()
+		self.git_diff.is_pending()
+			|| self.git_child.is_pending()
+			|| self.details.any_work_pending()
 	}
 
 	///
@@ -261,6 +358,8 @@ impl InspectCommitComponent {
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
+			} else if ev == AsyncGitNotification::CommitChild {
+				self.update_child()?;
 			}
 		}
 
@@ -275,16 +374,25 @@ impl InspectCommitComponent {
 				{
 					let diff_params = DiffParams {
 						path: f.path.clone(),
-						diff_type: DiffType::Commit(
-							request.commit_id,
-						),
-						options: DiffOptions::default(),
+						diff_type: if self.diff_parent > 0 {
+							DiffType::CommitParent(
+								request.commit_id,
+								self.diff_parent,
+							)
+						} else {
+							DiffType::Commit(request.commit_id)
+						},
+						options: self
+							.options
+							.borrow()
+							.diff_options_for(&f.path),
 					};
 
 					if let Some((params, last)) =
 						self.git_diff.last()?
 					{
 						if params == diff_params {
+							self.diff.set_params(params);
 							self.diff.update(f.path, false, last);
 							return Ok(());
 						}
@@ -304,8 +412,19 @@ impl InspectCommitComponent {
 
 	fn update(&mut self) -> Result<()> {
 		if let Some(request) = &self.open_request {
+			self.parents = sync::parent_ids(
+				&self.repo.borrow(),
+				request.commit_id,
+			)?;
+			self.diff.set_revisions(
+				self.parents.get(self.diff_parent).copied(),
+				Some(request.commit_id),
+			);
 			self.details.set_commits(
-				Some(request.commit_id.into()),
+				Some(CommitFilesParams::with_parent(
+					request.commit_id,
+					self.diff_parent,
+				)),
 				&request.tags,
 			)?;
 			self.update_diff()?;
@@ -314,6 +433,97 @@ impl InspectCommitComponent {
 		Ok(())
 	}
 
+	/// index into `parents` of the parent typed ahead, the first one
+	/// if nothing (or an out of range number) was typed
+	fn selected_parent(&self) -> usize {
+		self.parent_input
+			.as_str()
+			.parse::<usize>()
+			.ok()
+			.filter(|n| (1..=self.parents.len()).contains(n))
+			.map_or(0, |n| n - 1)
+	}
+
+	fn goto_parent(&mut self) -> Result<()> {
+		let parent =
+			self.parents.get(self.selected_parent()).copied();
+		self.parent_input.clear();
+
+		if let (Some(parent), Some(request)) =
+			(parent, &self.open_request)
+		{
+			self.children.push(request.commit_id);
+			self.goto_commit(parent)?;
+		}
+
+		Ok(())
+	}
+
+	/// diffs against the parent typed ahead, or else the one after
+	/// the current, wrapping around to the first
+	fn diff_next_parent(&mut self) -> Result<()> {
+		self.diff_parent = if self.parent_input.as_str().is_empty() {
+			(self.diff_parent + 1) % self.parents.len().max(1)
+		} else {
+			self.selected_parent()
+		};
+		self.parent_input.clear();
+
+		self.update()
+	}
+
+	fn goto_child(&mut self) -> Result<()> {
+		self.parent_input.clear();
+
+		if let Some(child) = self.children.pop() {
+			self.goto_commit(child)?;
+		} else if let Some(request) = &self.open_request {
+			self.git_child.spawn(AsyncCommitChildJob::new(
+				self.repo.borrow().clone(),
+				request.commit_id,
+			));
+		}
+
+		Ok(())
+	}
+
+	/// steps to the child the background lookup found, unless we
+	/// moved on to another commit in the meantime
+	fn update_child(&mut self) -> Result<()> {
+		if let Some(job) = self.git_child.take_last() {
+			let inspected =
+				self.open_request.as_ref().map(|r| r.commit_id);
+
+			if inspected == Some(job.commit()) {
+				if let Some(Some(child)) = job.result().transpose()? {
+					self.goto_commit(child)?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	fn goto_commit(&mut self, id: CommitId) -> Result<()> {
+		self.open_request = Some(InspectCommitOpen::new(id));
+		self.diff_parent = 0;
+		self.details.focus(true);
+		self.diff.focus(false);
+		self.update()
+	}
+
+	fn open_copy_popup(&mut self) {
+		if let Some(request) = &self.open_request {
+			let id = request.commit_id;
+			self.hide_stacked(true);
+			self.queue.push(InternalEvent::OpenPopup(
+				StackablePopupOpen::CopyClipboardCommit(
+					CopyClipboardOpen::from_commit(id),
+				),
+			));
+		}
+	}
+
 	fn can_focus_diff(&self) -> bool {
 		self.details.files().selection_file().is_some()
 	}
@@ -5,6 +5,7 @@ mod changes;
 mod command;
 mod commit;
 mod commit_details;
+mod commit_note;
 mod commitlist;
 mod compare_commits;
 mod copy_clipboard_popup;
@@ -14,24 +15,31 @@ mod diff;
 mod external_command_popup;
 mod externaleditor;
 mod rebase_editor;
+mod rebase_todo;
 mod fetch;
 mod file_find_popup;
 mod file_revlog;
 mod filter_options;
+mod grep_popup;
 mod help;
 mod inspect_commit;
 mod msg;
 mod options_popup;
 mod pull;
+mod pull_request;
 mod push;
 mod push_tags;
+mod reflog;
+mod remotes;
 mod rename_branch;
 mod reset;
 mod reset_popup;
 mod revision_files;
 mod revision_files_popup;
 mod search_options;
+mod stage_pattern;
 mod stashmsg;
+mod status_header;
 mod status_tree;
 mod submodules;
 mod syntax_text;
@@ -39,6 +47,7 @@ mod tag_commit;
 mod taglist;
 mod textinput;
 mod utils;
+mod worktrees;
 
 pub use self::status_tree::StatusTreeComponent;
 pub use blame_file::{BlameFileComponent, BlameFileOpen};
@@ -46,8 +55,9 @@ pub use branch_find_popup::BranchFindPopup;
 pub use branchlist::BranchListComponent;
 pub use changes::ChangesComponent;
 pub use command::{CommandInfo, CommandText};
-pub use commit::CommitComponent;
+pub use commit::{commit_with_hooks, CommitComponent, CommitResult};
 pub use commit_details::CommitDetailsComponent;
+pub use commit_note::CommitNoteComponent;
 pub use commitlist::{CommitList, ExternalSearchRequest};
 pub use compare_commits::CompareCommitsComponent;
 pub use copy_clipboard_popup::{
@@ -56,30 +66,38 @@ pub use copy_clipboard_popup::{
 pub use create_branch::CreateBranchComponent;
 pub use diff::DiffComponent;
 pub use external_command_popup::ExternalCommandPopupComponent;
-pub use externaleditor::ExternalEditorComponent;
-pub use rebase_editor::{rebase_commits_interactive_with_editor, rebase_interactive_skip, rebase_interactive_abort, rebase_commits_continue_with_editor};
+pub use externaleditor::{resolve_editor, ExternalEditorComponent};
+pub use rebase_editor::{rebase_interactive_skip, rebase_interactive_abort, rebase_commits_continue_with_editor};
 pub use fetch::FetchComponent;
 pub use file_find_popup::FileFindPopup;
 pub use file_revlog::{FileRevOpen, FileRevlogComponent};
+pub use grep_popup::GrepPopup;
 pub use help::HelpComponent;
 pub use inspect_commit::{InspectCommitComponent, InspectCommitOpen};
 pub use msg::MsgComponent;
 pub use options_popup::{AppOption, OptionsPopupComponent};
 pub use pull::PullComponent;
+pub use pull_request::PullRequestComponent;
 pub use push::PushComponent;
 pub use push_tags::PushTagsComponent;
+pub use rebase_todo::RebaseTodoComponent;
+pub use reflog::ReflogComponent;
+pub use remotes::RemotesListComponent;
 pub use rename_branch::RenameBranchComponent;
+pub use stage_pattern::StagePatternComponent;
 pub use reset::ConfirmComponent;
 pub use reset_popup::ResetPopupComponent;
 pub use revision_files::RevisionFilesComponent;
 pub use revision_files_popup::{FileTreeOpen, RevisionFilesPopup};
 pub use stashmsg::StashMsgComponent;
+pub use status_header::StatusHeaderComponent;
 pub use submodules::SubmodulesListComponent;
 pub use syntax_text::SyntaxTextComponent;
 pub use tag_commit::TagCommitComponent;
 pub use taglist::TagListComponent;
 pub use textinput::{InputType, TextInputComponent};
 pub use utils::filetree::FileTreeItemKind;
+pub use worktrees::WorktreesListComponent;
 
 use crate::ui::style::Theme;
 use anyhow::Result;
@@ -1,6 +1,7 @@
 use super::{
-	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState,
+	utils::scroll_vertical::VerticalScroll, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState, ScrollType,
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
@@ -10,8 +11,8 @@ use crossterm::event::Event;
 use ratatui::{
 	backend::Backend,
 	layout::{Alignment, Rect},
-	text::Span,
-	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	text::{Span, Spans},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
 	Frame,
 };
 use std::convert::TryFrom;
@@ -22,6 +23,7 @@ pub struct MsgComponent {
 	visible: bool,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	scroll: VerticalScroll,
 }
 
 use anyhow::Result;
@@ -56,9 +58,23 @@ impl DrawableComponent for MsgComponent {
 		}
 
 		let area = ui::centered_rect_absolute(width, 25, f.size());
+		let text_width = usize::from(area.width.saturating_sub(2));
+		let height = usize::from(area.height.saturating_sub(2));
+
+		// wrap ourselves to know the line count for scrolling
+		let lines = textwrap::wrap(&self.msg, text_width.max(1));
+		self.scroll.update_no_selection(lines.len(), height);
+
+		let txt: Vec<Spans> = lines
+			.into_iter()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.map(|line| Spans::from(line.into_owned()))
+			.collect();
+
 		f.render_widget(Clear, area);
 		f.render_widget(
-			Paragraph::new(self.msg.clone())
+			Paragraph::new(txt)
 				.block(
 					Block::default()
 						.title(Span::styled(
@@ -68,10 +84,10 @@ impl DrawableComponent for MsgComponent {
 						.borders(Borders::ALL)
 						.border_type(BorderType::Thick),
 				)
-				.alignment(Alignment::Left)
-				.wrap(Wrap { trim: true }),
+				.alignment(Alignment::Left),
 			area,
 		);
+		self.scroll.draw(f, area, &self.theme);
 
 		Ok(())
 	}
@@ -88,6 +104,11 @@ impl Component for MsgComponent {
 			true,
 			self.visible,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::scroll(&self.key_config),
+			true,
+			self.visible,
+		));
 
 		visibility_blocking(self)
 	}
@@ -97,6 +118,15 @@ impl Component for MsgComponent {
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter) {
 					self.hide();
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.scroll.move_top(ScrollType::Up);
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.scroll.move_top(ScrollType::Down);
+				} else if key_match(e, self.key_config.keys.home) {
+					self.scroll.move_top(ScrollType::Home);
+				} else if key_match(e, self.key_config.keys.end) {
+					self.scroll.move_top(ScrollType::End);
 				}
 			}
 			Ok(EventState::Consumed)
@@ -115,6 +145,7 @@ impl Component for MsgComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.scroll.reset();
 
 		Ok(())
 	}
@@ -131,6 +162,7 @@ impl MsgComponent {
 			visible: false,
 			theme,
 			key_config,
+			scroll: VerticalScroll::new(),
 		}
 	}
 
@@ -3,7 +3,7 @@ use super::{
 	DrawableComponent, EventState, TextInputComponent,
 };
 use crate::{
-	components::utils::string_width_align,
+	components::utils::{goto_line::GotoLine, string_width_align},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
 	queue::{InternalEvent, Queue},
@@ -12,7 +12,7 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::sync::ShowUntrackedFilesConfig;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyCode};
 use ratatui::{
 	backend::Backend,
 	layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -25,9 +25,14 @@ use ratatui::{
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AppOption {
 	StatusShowUntracked,
+	NotifyWatcher,
 	DiffIgnoreWhitespaces,
+	DiffIgnoreBlankLines,
 	DiffContextLines,
 	DiffInterhunkLines,
+	DiffWordHighlight,
+	DiffLineNumbersAbsolute,
+	RebaseAutostash,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -39,8 +44,8 @@ enum TabType {
 impl TabType {
 	pub fn next(self) -> Self {
 		match self {
-			TabType::Misc => TabType::GitCmds,
-			TabType::GitCmds => TabType::Misc,
+			Self::Misc => Self::GitCmds,
+			Self::GitCmds => Self::Misc,
 		}
 	}
 }
@@ -49,23 +54,26 @@ impl TabType {
 pub enum GitCmdOption {
 	GitPush,
 	GitFetch,
+	GitPull,
 	GitCheckout,
 }
 
 impl GitCmdOption {
 	pub fn next(&mut self) {
 		*self = match self {
-			GitCmdOption::GitPush => GitCmdOption::GitFetch,
-			GitCmdOption::GitFetch => GitCmdOption::GitCheckout,
-			GitCmdOption::GitCheckout => GitCmdOption::GitPush,
+			Self::GitPush => Self::GitFetch,
+			Self::GitFetch => Self::GitPull,
+			Self::GitPull => Self::GitCheckout,
+			Self::GitCheckout => Self::GitPush,
 		}
 	}
 
 	pub fn prev(&mut self) {
 		*self = match self {
-			GitCmdOption::GitPush => GitCmdOption::GitCheckout,
-			GitCmdOption::GitCheckout => GitCmdOption::GitFetch,
-			GitCmdOption::GitFetch => GitCmdOption::GitPush,
+			Self::GitPush => Self::GitCheckout,
+			Self::GitCheckout => Self::GitPull,
+			Self::GitPull => Self::GitFetch,
+			Self::GitFetch => Self::GitPush,
 		}
 	}
 }
@@ -82,9 +90,12 @@ pub struct OptionsPopupComponent {
 
 	input_git_push: TextInputComponent,
 	input_git_fetch: TextInputComponent,
+	input_git_pull: TextInputComponent,
 	input_git_checkout: TextInputComponent,
 
 	git_cmd_editing: bool,
+	/// digits typed on a numeric option, applied on enter
+	number_input: GotoLine,
 }
 
 impl OptionsPopupComponent {
@@ -124,6 +135,17 @@ impl OptionsPopupComponent {
 			.make_embed()
 			.make_visible(),
 
+			input_git_pull: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				"",
+				"git pull command here",
+				false,
+			)
+			.with_input_type(super::InputType::Singleline)
+			.make_embed()
+			.make_visible(),
+
 			input_git_checkout: TextInputComponent::new(
 				theme.clone(),
 				key_config.clone(),
@@ -138,6 +160,7 @@ impl OptionsPopupComponent {
 			key_config,
 			theme,
 			git_cmd_editing: false,
+			number_input: GotoLine::default(),
 		}
 	}
 
@@ -166,6 +189,20 @@ impl OptionsPopupComponent {
 		);
 		Self::add_header(txt, "");
 
+		Self::add_header(txt, "Updates");
+		self.add_entry(
+			txt,
+			width,
+			"Change detection",
+			match self.options.borrow().notify_watcher() {
+				None => "Cli arg",
+				Some(true) => "Watcher",
+				Some(false) => "Ticker",
+			},
+			self.is_select(AppOption::NotifyWatcher),
+		);
+		Self::add_header(txt, "");
+
 		let diff = self.options.borrow().diff_options();
 		Self::add_header(txt, "Diff");
 		self.add_entry(
@@ -175,26 +212,105 @@ impl OptionsPopupComponent {
 			&diff.ignore_whitespace.to_string(),
 			self.is_select(AppOption::DiffIgnoreWhitespaces),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore blank lines",
+			&diff.ignore_blank_lines.to_string(),
+			self.is_select(AppOption::DiffIgnoreBlankLines),
+		);
 		self.add_entry(
 			txt,
 			width,
 			"Context lines",
-			&diff.context.to_string(),
+			&self.number_text(
+				AppOption::DiffContextLines,
+				diff.context,
+			),
 			self.is_select(AppOption::DiffContextLines),
 		);
 		self.add_entry(
 			txt,
 			width,
 			"Inter hunk lines",
-			&diff.interhunk_lines.to_string(),
+			&self.number_text(
+				AppOption::DiffInterhunkLines,
+				diff.interhunk_lines,
+			),
 			self.is_select(AppOption::DiffInterhunkLines),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Highlight words",
+			&self.options.borrow().diff_word_highlight().to_string(),
+			self.is_select(AppOption::DiffWordHighlight),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Absolute line numbers",
+			&self
+				.options
+				.borrow()
+				.diff_line_numbers_absolute()
+				.to_string(),
+			self.is_select(AppOption::DiffLineNumbersAbsolute),
+		);
+		Self::add_header(txt, "");
+
+		Self::add_header(txt, "Rebase");
+		self.add_entry(
+			txt,
+			width,
+			"Auto stash",
+			&self.options.borrow().rebase_autostash().to_string(),
+			self.is_select(AppOption::RebaseAutostash),
+		);
 	}
 
 	fn is_select(&self, kind: AppOption) -> bool {
 		self.selection == kind
 	}
 
+	/// the digits typed so far replace the value while editing
+	fn number_text(&self, kind: AppOption, value: u32) -> String {
+		if self.is_select(kind) && !self.number_input.is_empty() {
+			format!("{}_", self.number_input.as_str())
+		} else {
+			value.to_string()
+		}
+	}
+
+	const fn accepts_number(&self) -> bool {
+		matches!(
+			self.selection,
+			AppOption::DiffContextLines
+				| AppOption::DiffInterhunkLines
+		)
+	}
+
+	fn apply_number_input(&mut self) {
+		if let Some(value) = self.number_input.take() {
+			let value = u32::try_from(value).unwrap_or(u32::MAX);
+
+			match self.selection {
+				AppOption::DiffContextLines => {
+					self.options.borrow_mut().set_diff_context(value);
+				}
+				AppOption::DiffInterhunkLines => {
+					self.options
+						.borrow_mut()
+						.set_diff_hunk_lines(value);
+				}
+				_ => return,
+			}
+
+			self.queue
+				.push(InternalEvent::OptionSwitched(self.selection));
+		}
+	}
+
 	fn add_header(txt: &mut Vec<Spans>, header: &'static str) {
 		txt.push(Spans::from(vec![Span::styled(
 			header,
@@ -283,6 +399,14 @@ impl OptionsPopupComponent {
 			self.git_cmd_selection == GitCmdOption::GitFetch,
 		)?;
 		content_rect.y += 1;
+		self.render_input(
+			f,
+			content_rect,
+			"Git Pull",
+			&self.input_git_pull,
+			self.git_cmd_selection == GitCmdOption::GitPull,
+		)?;
+		content_rect.y += 1;
 		self.render_input(
 			f,
 			content_rect,
@@ -315,36 +439,67 @@ impl OptionsPopupComponent {
 		if up {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
-					AppOption::DiffInterhunkLines
+					AppOption::RebaseAutostash
 				}
-				AppOption::DiffIgnoreWhitespaces => {
+				AppOption::NotifyWatcher => {
 					AppOption::StatusShowUntracked
 				}
-				AppOption::DiffContextLines => {
+				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::NotifyWatcher
+				}
+				AppOption::DiffIgnoreBlankLines => {
 					AppOption::DiffIgnoreWhitespaces
 				}
+				AppOption::DiffContextLines => {
+					AppOption::DiffIgnoreBlankLines
+				}
 				AppOption::DiffInterhunkLines => {
 					AppOption::DiffContextLines
 				}
+				AppOption::DiffWordHighlight => {
+					AppOption::DiffInterhunkLines
+				}
+				AppOption::DiffLineNumbersAbsolute => {
+					AppOption::DiffWordHighlight
+				}
+				AppOption::RebaseAutostash => {
+					AppOption::DiffLineNumbersAbsolute
+				}
 			};
 		} else {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
+					AppOption::NotifyWatcher
+				}
+				AppOption::NotifyWatcher => {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::DiffIgnoreBlankLines
+				}
+				AppOption::DiffIgnoreBlankLines => {
 					AppOption::DiffContextLines
 				}
 				AppOption::DiffContextLines => {
 					AppOption::DiffInterhunkLines
 				}
 				AppOption::DiffInterhunkLines => {
+					AppOption::DiffWordHighlight
+				}
+				AppOption::DiffWordHighlight => {
+					AppOption::DiffLineNumbersAbsolute
+				}
+				AppOption::DiffLineNumbersAbsolute => {
+					AppOption::RebaseAutostash
+				}
+				AppOption::RebaseAutostash => {
 					AppOption::StatusShowUntracked
 				}
 			};
 		}
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn switch_option(&mut self, right: bool) {
 		//let mut opts: std::cell::RefMut<Options> =
 		//	self.options.borrow_mut();
@@ -371,11 +526,31 @@ impl OptionsPopupComponent {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::NotifyWatcher => {
+					let watcher = match self
+						.options
+						.borrow()
+						.notify_watcher()
+					{
+						None => Some(true),
+						Some(true) => Some(false),
+						Some(false) => None,
+					};
+
+					self.options
+						.borrow_mut()
+						.set_notify_watcher(watcher);
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -386,6 +561,21 @@ impl OptionsPopupComponent {
 						.borrow_mut()
 						.diff_hunk_lines_change(true);
 				}
+				AppOption::DiffWordHighlight => {
+					self.options
+						.borrow_mut()
+						.toggle_diff_word_highlight();
+				}
+				AppOption::DiffLineNumbersAbsolute => {
+					self.options
+						.borrow_mut()
+						.toggle_diff_line_numbers_absolute();
+				}
+				AppOption::RebaseAutostash => {
+					self.options
+						.borrow_mut()
+						.toggle_rebase_autostash();
+				}
 			};
 		} else {
 			match self.selection {
@@ -410,11 +600,31 @@ impl OptionsPopupComponent {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::NotifyWatcher => {
+					let watcher = match self
+						.options
+						.borrow()
+						.notify_watcher()
+					{
+						None => Some(false),
+						Some(false) => Some(true),
+						Some(true) => None,
+					};
+
+					self.options
+						.borrow_mut()
+						.set_notify_watcher(watcher);
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -425,6 +635,21 @@ impl OptionsPopupComponent {
 						.borrow_mut()
 						.diff_hunk_lines_change(false);
 				}
+				AppOption::DiffWordHighlight => {
+					self.options
+						.borrow_mut()
+						.toggle_diff_word_highlight();
+				}
+				AppOption::DiffLineNumbersAbsolute => {
+					self.options
+						.borrow_mut()
+						.toggle_diff_line_numbers_absolute();
+				}
+				AppOption::RebaseAutostash => {
+					self.options
+						.borrow_mut()
+						.toggle_rebase_autostash();
+				}
 			};
 		}
 
@@ -437,6 +662,9 @@ impl OptionsPopupComponent {
 		event: &crossterm::event::Event,
 	) -> Result<EventState> {
 		if let Event::Key(key) = event {
+			let mut number_input =
+				std::mem::take(&mut self.number_input);
+
 			if key_match(key, self.key_config.keys.move_up) {
 				self.move_selection(true);
 			} else if key_match(key, self.key_config.keys.move_down) {
@@ -446,6 +674,13 @@ impl OptionsPopupComponent {
 				self.switch_option(true);
 			} else if key_match(key, self.key_config.keys.move_left) {
 				self.switch_option(false);
+			} else if key_match(key, self.key_config.keys.enter) {
+				self.number_input = number_input;
+				self.apply_number_input();
+			} else if let KeyCode::Char(c) = key.code {
+				if self.accepts_number() && number_input.push(c) {
+					self.number_input = number_input;
+				}
 			}
 		}
 
@@ -456,6 +691,7 @@ impl OptionsPopupComponent {
 		match self.git_cmd_selection {
 			GitCmdOption::GitPush => &self.input_git_push,
 			GitCmdOption::GitFetch => &self.input_git_fetch,
+			GitCmdOption::GitPull => &self.input_git_pull,
 			GitCmdOption::GitCheckout => &self.input_git_checkout,
 		}
 	}
@@ -466,6 +702,7 @@ impl OptionsPopupComponent {
 		match self.git_cmd_selection {
 			GitCmdOption::GitPush => &mut self.input_git_push,
 			GitCmdOption::GitFetch => &mut self.input_git_fetch,
+			GitCmdOption::GitPull => &mut self.input_git_pull,
 			GitCmdOption::GitCheckout => &mut self.input_git_checkout,
 		}
 	}
@@ -497,6 +734,10 @@ impl OptionsPopupComponent {
 							.options
 							.borrow_mut()
 							.set_git_extern_fetch(res),
+						GitCmdOption::GitPull => self
+							.options
+							.borrow_mut()
+							.set_git_extern_pull(res),
 						GitCmdOption::GitCheckout => self
 							.options
 							.borrow_mut()
@@ -517,6 +758,9 @@ impl OptionsPopupComponent {
 					GitCmdOption::GitFetch => {
 						self.input_git_fetch.event(event)
 					}
+					GitCmdOption::GitPull => {
+						self.input_git_pull.event(event)
+					}
 					GitCmdOption::GitCheckout => {
 						self.input_git_checkout.event(event)
 					}
@@ -542,6 +786,12 @@ impl OptionsPopupComponent {
 							.borrow_mut()
 							.set_git_extern_fetch(None);
 					}
+					GitCmdOption::GitPull => {
+						self.input_git_pull.clear();
+						self.options
+							.borrow_mut()
+							.set_git_extern_pull(None);
+					}
 					GitCmdOption::GitCheckout => {
 						self.input_git_checkout.clear();
 						self.options
@@ -563,7 +813,7 @@ impl DrawableComponent for OptionsPopupComponent {
 		area: Rect,
 	) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 12);
+			const SIZE: (u16, u16) = (50, 15);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 			f.render_widget(Clear, area);
@@ -631,6 +881,22 @@ impl Component for OptionsPopupComponent {
 				.order(1),
 			);
 
+			if self.current_tab == TabType::Misc
+				&& self.accepts_number()
+			{
+				out.push(
+					CommandInfo::new(
+						strings::commands::options_set_number(
+							&self.key_config,
+							self.number_input.as_str(),
+						),
+						!self.number_input.is_empty(),
+						true,
+					)
+					.order(1),
+				);
+			}
+
 			if self.current_tab == TabType::GitCmds {
 				out.push(
 					CommandInfo::new(
@@ -692,6 +958,7 @@ impl Component for OptionsPopupComponent {
 
 	fn hide(&mut self) {
 		self.visible = false;
+		self.number_input.clear();
 	}
 
 	fn show(&mut self) -> Result<()> {
@@ -702,7 +969,7 @@ impl Component for OptionsPopupComponent {
 				.git_extern_commands()
 				.push_base
 				.as_ref()
-				.map_or(String::new(), |i| i.clone()),
+				.map_or(String::new(), Clone::clone),
 		);
 		self.input_git_fetch.set_text(
 			self.options
@@ -710,7 +977,15 @@ impl Component for OptionsPopupComponent {
 				.git_extern_commands()
 				.fetch_base
 				.as_ref()
-				.map_or(String::new(), |i| i.clone()),
+				.map_or(String::new(), Clone::clone),
+		);
+		self.input_git_pull.set_text(
+			self.options
+				.borrow()
+				.git_extern_commands()
+				.pull_base
+				.as_ref()
+				.map_or(String::new(), Clone::clone),
 		);
 		self.input_git_checkout.set_text(
 			self.options
@@ -718,7 +993,7 @@ impl Component for OptionsPopupComponent {
 				.git_extern_commands()
 				.checkout_base
 				.as_ref()
-				.map_or(String::new(), |i| i.clone()),
+				.map_or(String::new(), Clone::clone),
 		);
 
 		Ok(())
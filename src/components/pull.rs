@@ -15,8 +15,8 @@ use asyncgit::{
 	sync::{
 		self,
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			extract_username_password, is_auth_error,
+			need_username_password, BasicAuthCredential,
 		},
 		get_default_remote, RepoPathRef,
 	},
@@ -78,6 +78,7 @@ impl PullComponent {
 	///
 	pub fn fetch(&mut self, branch: String) -> Result<()> {
 		self.branch = branch;
+		self.input_cred.reset_retries();
 		self.show()?;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
@@ -99,6 +100,7 @@ impl PullComponent {
 		&mut self,
 		cred: Option<BasicAuthCredential>,
 	) -> Result<()> {
+		self.input_cred.attempt_with(cred.as_ref());
 		self.pending = true;
 		self.progress = None;
 		self.git_fetch.set_git_fetch_external(
@@ -108,10 +110,18 @@ impl PullComponent {
 				.fetch_base
 				.clone(),
 		);
+		self.git_fetch.set_git_pull_external(
+			self.options
+				.borrow()
+				.git_extern_commands()
+				.pull_base
+				.clone(),
+		);
 		self.git_fetch.request(FetchRequest {
 			remote: get_default_remote(&self.repo.borrow())?,
 			branch: self.branch.clone(),
 			basic_credential: cred,
+			pull_request: None,
 		})?;
 
 		Ok(())
@@ -146,6 +156,12 @@ impl PullComponent {
 			{
 				if err.is_empty() {
 					self.try_ff_merge()?;
+				} else if is_auth_error(&err)
+					&& self
+						.input_cred
+						.retry(self.options.borrow().auth_retries())?
+				{
+					// fetches again with the new password
 				} else {
 					anyhow::bail!(err);
 				}
@@ -280,7 +296,14 @@ impl Component for PullComponent {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
-					if self.input_cred.get_cred().is_complete()
+					if !self.input_cred.is_visible()
+						&& self.input_cred.is_retry()
+					{
+						self.hide();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::auth_retry_cancelled("pull"),
+						));
+					} else if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
 						self.fetch_from_remote(Some(
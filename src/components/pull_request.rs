@@ -0,0 +1,336 @@
+use super::{textinput::TextInputComponent, PushComponent};
+use crate::{
+	components::{
+		cred::CredComponent, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{
+		cred::{
+			extract_username_password, is_auth_error,
+			need_username_password, BasicAuthCredential,
+		},
+		get_default_remote, RepoPathRef,
+	},
+	AsyncGitNotification, AsyncPull, FetchRequest, RemoteProgress,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use ratatui::{
+	backend::Backend,
+	layout::Rect,
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear, Gauge},
+	Frame,
+};
+
+/// asks for a pull request number and fetches its ref into a
+/// local `pr/<number>` branch
+pub struct PullRequestComponent {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	visible: bool,
+	git_fetch: AsyncPull,
+	progress: Option<RemoteProgress>,
+	pending: bool,
+	branch: String,
+	src_ref: String,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	input_cred: CredComponent,
+	options: SharedOptions,
+}
+
+impl PullRequestComponent {
+	///
+	pub fn new(
+		repo: &RepoPathRef,
+		queue: &Queue,
+		sender: &Sender<AsyncGitNotification>,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+		options: SharedOptions,
+	) -> Self {
+		Self {
+			repo: repo.clone(),
+			input: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				&strings::pull_request_popup_title(&key_config),
+				&strings::pull_request_popup_msg(&key_config),
+				true,
+			),
+			queue: queue.clone(),
+			pending: false,
+			visible: false,
+			branch: String::new(),
+			src_ref: String::new(),
+			git_fetch: AsyncPull::new(repo.borrow().clone(), sender),
+			progress: None,
+			input_cred: CredComponent::new(
+				theme.clone(),
+				key_config.clone(),
+			),
+			theme,
+			key_config,
+			options,
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.input.clear();
+		self.input.show()?;
+
+		Ok(())
+	}
+
+	fn confirm_number(&mut self) -> Result<()> {
+		let number = self.input.get_text().trim().parse::<u64>();
+		self.input.hide();
+
+		if let Ok(number) = number {
+			self.branch = format!("pr/{number}");
+			self.src_ref =
+				self.options.borrow().pull_request_ref(number);
+			self.fetch()
+		} else {
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"invalid pull request number: '{}'",
+				self.input.get_text()
+			)));
+			Ok(())
+		}
+	}
+
+	fn fetch(&mut self) -> Result<()> {
+		self.visible = true;
+		self.input_cred.reset_retries();
+		if need_username_password(&self.repo.borrow())? {
+			let cred = extract_username_password(&self.repo.borrow())
+				.unwrap_or_else(|_| {
+					BasicAuthCredential::new(None, None)
+				});
+			if cred.is_complete() {
+				self.fetch_from_remote(Some(cred))
+			} else {
+				self.input_cred.set_cred(cred);
+				self.input_cred.show()
+			}
+		} else {
+			self.fetch_from_remote(None)
+		}
+	}
+
+	fn fetch_from_remote(
+		&mut self,
+		cred: Option<BasicAuthCredential>,
+	) -> Result<()> {
+		self.input_cred.attempt_with(cred.as_ref());
+		self.pending = true;
+		self.progress = None;
+		self.git_fetch.request(FetchRequest {
+			remote: get_default_remote(&self.repo.borrow())?,
+			branch: self.branch.clone(),
+			basic_credential: cred,
+			pull_request: Some(self.src_ref.clone()),
+		})?;
+
+		Ok(())
+	}
+
+	///
+	pub const fn any_work_pending(&self) -> bool {
+		self.pending
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if self.visible && ev == AsyncGitNotification::Pull {
+			if let Err(error) = self.update() {
+				self.pending = false;
+				self.hide();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"fetching {} failed:\n{error}",
+						self.src_ref
+					),
+				));
+			}
+		}
+	}
+
+	fn update(&mut self) -> Result<()> {
+		self.pending = self.git_fetch.is_pending()?;
+		self.progress = self.git_fetch.progress()?;
+
+		if !self.pending {
+			if let Some((_bytes, err)) =
+				self.git_fetch.last_result()?
+			{
+				// ask again and fetch with the new password
+				if is_auth_error(&err)
+					&& self
+						.input_cred
+						.retry(self.options.borrow().auth_retries())?
+				{
+					return Ok(());
+				}
+				if !err.is_empty() {
+					anyhow::bail!(err);
+				}
+
+				self.hide();
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+				));
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::CheckoutPullRequest(self.branch.clone()),
+				));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl DrawableComponent for PullRequestComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		if self.visible {
+			let (state, progress) =
+				PushComponent::get_progress(&self.progress);
+
+			let area = ui::centered_rect_absolute(30, 3, f.size());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Gauge::default()
+					.label(state.as_str())
+					.block(
+						Block::default()
+							.title(Span::styled(
+								strings::PULL_REQUEST_POPUP_MSG,
+								self.theme.title(true),
+							))
+							.borders(Borders::ALL)
+							.border_type(BorderType::Thick)
+							.border_style(self.theme.block(true)),
+					)
+					.gauge_style(self.theme.push_gauge())
+					.percent(u16::from(progress)),
+				area,
+			);
+			self.input_cred.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for PullRequestComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.input.is_visible() {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::pull_request_confirm_msg(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		} else if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			if self.input_cred.is_visible() {
+				return self.input_cred.commands(out, force_all);
+			}
+			out.push(CommandInfo::new(
+				strings::commands::close_msg(&self.key_config),
+				!self.pending,
+				self.visible,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.input.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.confirm_number()?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		if self.visible {
+			if let Event::Key(_) = ev {
+				if self.input_cred.is_visible() {
+					self.input_cred.event(ev)?;
+
+					if !self.input_cred.is_visible()
+						&& self.input_cred.is_retry()
+					{
+						self.hide();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::auth_retry_cancelled(&format!(
+								"fetching {}",
+								self.src_ref
+							)),
+						));
+					} else if self.input_cred.get_cred().is_complete()
+						|| !self.input_cred.is_visible()
+					{
+						self.fetch_from_remote(Some(
+							self.input_cred.get_cred().clone(),
+						))?;
+						self.input_cred.hide();
+					}
+				}
+			}
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible || self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.open()
+	}
+}
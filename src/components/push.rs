@@ -13,8 +13,8 @@ use anyhow::Result;
 use asyncgit::{
 	sync::{
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			extract_username_password, is_auth_error,
+			need_username_password, BasicAuthCredential,
 		},
 		get_branch_remote, get_default_remote, RepoPathRef,
 	},
@@ -112,6 +112,7 @@ impl PushComponent {
 			(true, false) => PushComponentModifier::Force,
 			(false, false) => PushComponentModifier::None,
 		};
+		self.input_cred.reset_retries();
 
 		self.show()?;
 
@@ -136,6 +137,7 @@ impl PushComponent {
 		cred: Option<BasicAuthCredential>,
 		force: bool,
 	) -> Result<()> {
+		self.input_cred.attempt_with(cred.as_ref());
 		let remote = if let Ok(Some(remote)) =
 			get_branch_remote(&self.repo.borrow(), &self.branch)
 		{
@@ -191,6 +193,14 @@ impl PushComponent {
 
 		if !self.pending {
 			if let Some(err) = self.git_push.last_result()? {
+				// ask again and push with the new password
+				if is_auth_error(&err)
+					&& self
+						.input_cred
+						.retry(self.options.borrow().auth_retries())?
+				{
+					return Ok(());
+				}
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push failed:\n{err}"),
 				));
@@ -314,7 +324,14 @@ impl Component for PushComponent {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
-					if self.input_cred.get_cred().is_complete()
+					if !self.input_cred.is_visible()
+						&& self.input_cred.is_retry()
+					{
+						self.hide();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::auth_retry_cancelled("push"),
+						));
+					} else if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
 						self.push_to_remote(
@@ -4,6 +4,7 @@ use crate::{
 		CommandInfo, Component, DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{InternalEvent, Queue},
 	strings::{self},
 	ui::{self, style::SharedTheme},
@@ -12,8 +13,8 @@ use anyhow::Result;
 use asyncgit::{
 	sync::{
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			extract_username_password, is_auth_error,
+			need_username_password, BasicAuthCredential,
 		},
 		get_default_remote, AsyncProgress, PushTagsProgress,
 		RepoPathRef,
@@ -41,6 +42,7 @@ pub struct PushTagsComponent {
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	input_cred: CredComponent,
+	options: SharedOptions,
 }
 
 impl PushTagsComponent {
@@ -51,6 +53,7 @@ impl PushTagsComponent {
 		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			repo: repo.clone(),
@@ -68,11 +71,13 @@ impl PushTagsComponent {
 			),
 			theme,
 			key_config,
+			options,
 		}
 	}
 
 	///
 	pub fn push_tags(&mut self) -> Result<()> {
+		self.input_cred.reset_retries();
 		self.show()?;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
@@ -94,6 +99,7 @@ impl PushTagsComponent {
 		&mut self,
 		cred: Option<BasicAuthCredential>,
 	) -> Result<()> {
+		self.input_cred.attempt_with(cred.as_ref());
 		self.pending = true;
 		self.progress = None;
 		self.git_push.request(PushTagsRequest {
@@ -122,6 +128,14 @@ impl PushTagsComponent {
 
 		if !self.pending {
 			if let Some(err) = self.git_push.last_result()? {
+				// ask again and push with the new password
+				if is_auth_error(&err)
+					&& self
+						.input_cred
+						.retry(self.options.borrow().auth_retries())?
+				{
+					return Ok(());
+				}
 				self.queue.push(InternalEvent::ShowErrorMsg(
 					format!("push tags failed:\n{err}"),
 				));
@@ -233,7 +247,16 @@ impl Component for PushTagsComponent {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
-					if self.input_cred.get_cred().is_complete()
+					if !self.input_cred.is_visible()
+						&& self.input_cred.is_retry()
+					{
+						self.hide();
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							strings::auth_retry_cancelled(
+								"push tags",
+							),
+						));
+					} else if self.input_cred.get_cred().is_complete()
 						|| !self.input_cred.is_visible()
 					{
 						self.push_to_remote(Some(
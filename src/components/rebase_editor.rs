@@ -1,5 +1,4 @@
 use anyhow::{anyhow, Result};
-use asyncgit::sync::CommitId;
 use crossterm::{
 	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 	ExecutableCommand,
@@ -7,31 +6,10 @@ use crossterm::{
 use scopeguard::defer;
 use std::{io, process::{Command, Stdio}};
 
-///
-pub fn rebase_commits_interactive_with_editor(
-	repo: &str,
-	base: &CommitId,
-) -> Result<()> {
-	io::stdout().execute(LeaveAlternateScreen)?;
-	defer! {
-		io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
-	}
-
-	let base_hash = base.to_string();
-
-	let mut cmd = Command::new("git");
-	cmd.current_dir(repo)
-		.arg("rebase")
-		.arg("-i")
-		.arg(base_hash)
-		.status()
-		.map_err(|e| anyhow!("rebase -i: {}", e))?;
-	Ok(())
-}
-
-///
+/// `editor` is handed to git as `GIT_EDITOR`
 pub fn rebase_commits_continue_with_editor(
 	repo: &str,
+	editor: &str,
 ) -> Result<()> {
 	io::stdout().execute(LeaveAlternateScreen)?;
 	defer! {
@@ -40,6 +18,7 @@ pub fn rebase_commits_continue_with_editor(
 
 	let mut cmd = Command::new("git");
 	cmd.current_dir(repo)
+		.env("GIT_EDITOR", editor)
 		.arg("rebase")
 		.arg("--continue")
 		.status()
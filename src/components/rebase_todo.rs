@@ -0,0 +1,509 @@
+use super::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ExternalEditorComponent,
+};
+use crate::{
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self,
+	extern_git::{
+		parse_rebase_todo, write_rebase_todo, InteractiveOperation,
+		RebaseCommit, RebaseTodoSession,
+	},
+	CommitId, RepoPathRef,
+};
+use crossterm::{
+	event::Event,
+	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+	ExecutableCommand,
+};
+use ratatui::{
+	backend::Backend,
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use scopeguard::defer;
+use std::{io, path::Path};
+use ui::style::SharedTheme;
+
+struct TodoEntry {
+	commit: RebaseCommit,
+	summary: String,
+}
+
+/// the todo list of an interactive rebase, edited in place while
+/// git waits for it
+pub struct RebaseTodoComponent {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	session: Option<RebaseTodoSession>,
+	entries: Vec<TodoEntry>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RebaseTodoComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.size(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.size().into(), area);
+			let area = area.intersection(rect);
+
+			let constraints = [
+				// operation
+				Constraint::Length(6),
+				// commit id
+				Constraint::Length(7),
+				// summary
+				Constraint::Percentage(100),
+			];
+
+			let rows = self.get_rows();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows)
+				.widths(&constraints)
+				.column_spacing(1)
+				.highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_rebase_todo(),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RebaseTodoComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_todo_set_op(
+					&self.key_config,
+				),
+				!self.entries.is_empty(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_todo_move(&self.key_config),
+				self.entries.len() > 1,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_todo_start(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_todo_edit_external(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_todo_abort(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.done(Vec::new(), false)?;
+				} else if key_match(key, self.key_config.keys.enter) {
+					let todo = self.todo();
+					self.done(todo, false)?;
+				} else if key_match(
+					key,
+					self.key_config.keys.open_commit_editor,
+				) {
+					let todo = self.todo();
+					self.done(todo, true)?;
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, self.key_config.keys.home) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(key, self.key_config.keys.end) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(
+					key,
+					self.key_config.keys.rebase_todo_move_up,
+				) {
+					self.move_entry(true);
+				} else if key_match(
+					key,
+					self.key_config.keys.rebase_todo_move_down,
+				) {
+					self.move_entry(false);
+				} else if let Some(op) = self.op_for_key(key) {
+					self.set_op(op);
+				} else if key_match(
+					key,
+					self.key_config.keys.cmd_bar_toggle,
+				) {
+					//do not consume if its the more key
+					return Ok(EventState::NotConsumed);
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl RebaseTodoComponent {
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			theme,
+			queue: queue.clone(),
+			session: None,
+			entries: Vec::new(),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config,
+			repo,
+		}
+	}
+
+	/// starts `git rebase -i <base>` and shows its todo list,
+	/// `editor` is what git uses for reword and squash messages
+	pub fn open(
+		&mut self,
+		base: CommitId,
+		editor: &str,
+	) -> Result<()> {
+		let session = RebaseTodoSession::start(
+			self.repo.borrow().gitpath().to_str().unwrap_or_default(),
+			&base.to_string(),
+			Some(editor),
+		)?;
+
+		let repo = self.repo.borrow();
+		self.entries = parse_rebase_todo(session.todo_file())?
+			.into_iter()
+			.map(|commit| {
+				let summary = commit
+					.id()
+					.and_then(|id| {
+						sync::get_commit_info(&repo, &id).ok()
+					})
+					.map(|info| info.get_summary())
+					.unwrap_or_default();
+				TodoEntry { commit, summary }
+			})
+			.collect();
+		drop(repo);
+
+		self.session = Some(session);
+		self.table_state.get_mut().select(Some(0));
+
+		self.show()
+	}
+
+	/// a rebase waits for its todo list
+	pub const fn any_work_pending(&self) -> bool {
+		self.session.is_some()
+	}
+
+	/// lets a waiting rebase abort, nothing gets rewritten
+	pub fn abort(&mut self) -> Result<()> {
+		self.hide();
+		self.entries.clear();
+		if let Some(session) = self.session.take() {
+			session.abort()?;
+		}
+
+		Ok(())
+	}
+
+	/// hands the todo list back to git, after opening it in
+	/// `editor` if that was asked for, and waits for the rebase
+	pub fn finish(
+		&mut self,
+		edit: bool,
+		editor: Option<&str>,
+	) -> Result<()> {
+		let session = match self.session.take() {
+			Some(session) => session,
+			None => return Ok(()),
+		};
+
+		if edit {
+			let res = ExternalEditorComponent::open_file_in_editor(
+				&self.repo.borrow(),
+				Path::new(session.todo_file()),
+				None,
+				editor,
+			);
+			if let Err(e) = res {
+				// git still waits for the todo list
+				session.abort()?;
+				return Err(e);
+			}
+		}
+
+		io::stdout().execute(LeaveAlternateScreen)?;
+		defer! {
+			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+		}
+
+		session.finish()
+	}
+
+	/// writes `todo` for git to pick up, an empty one aborts
+	fn done(
+		&mut self,
+		todo: Vec<RebaseCommit>,
+		edit: bool,
+	) -> Result<()> {
+		if let Some(session) = &self.session {
+			write_rebase_todo(session.todo_file(), todo)?;
+		}
+
+		self.entries.clear();
+		self.hide();
+		self.queue.push(InternalEvent::RebaseTodoDone(edit));
+
+		Ok(())
+	}
+
+	fn todo(&mut self) -> Vec<RebaseCommit> {
+		std::mem::take(&mut self.entries)
+			.into_iter()
+			.map(|entry| entry.commit)
+			.collect()
+	}
+
+	fn op_for_key(
+		&self,
+		key: &crossterm::event::KeyEvent,
+	) -> Option<InteractiveOperation> {
+		let keys = &self.key_config.keys;
+		[
+			(keys.rebase_todo_pick, InteractiveOperation::Pick),
+			(keys.rebase_todo_reword, InteractiveOperation::Reword),
+			(keys.rebase_todo_edit, InteractiveOperation::Edit),
+			(keys.rebase_todo_squash, InteractiveOperation::Squash),
+			(keys.rebase_todo_fixup, InteractiveOperation::Fixup),
+			(keys.rebase_todo_drop, InteractiveOperation::Drop),
+		]
+		.into_iter()
+		.find(|(binding, _)| key_match(key, *binding))
+		.map(|(_, op)| op)
+	}
+
+	fn set_op(&mut self, op: InteractiveOperation) {
+		if let Some(entry) = self
+			.selected_index()
+			.and_then(|idx| self.entries.get_mut(idx))
+		{
+			entry.commit.set_op(op);
+		}
+	}
+
+	/// swaps the selected entry with its neighbour, the selection
+	/// moves along
+	fn move_entry(&mut self, up: bool) {
+		if let Some(idx) = self.selected_index() {
+			let other = if up {
+				idx.checked_sub(1)
+			} else {
+				Some(idx + 1)
+					.filter(|other| *other < self.entries.len())
+			};
+
+			if let Some(other) = other {
+				self.entries.swap(idx, other);
+				self.table_state.get_mut().select(Some(other));
+			}
+		}
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(
+				self.current_height.get().saturating_sub(1),
+			),
+			ScrollType::PageDown => old_selection
+				.saturating_add(
+					self.current_height.get().saturating_sub(1),
+				)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_rows(&self) -> Vec<Row> {
+		self.entries
+			.iter()
+			.map(|entry| self.get_row(entry))
+			.collect()
+	}
+
+	fn get_row(&self, entry: &TodoEntry) -> Row {
+		let op = entry.commit.op();
+		let dropped = op == InteractiveOperation::Drop;
+
+		let cells: Vec<Cell> = vec![
+			Cell::from(op.to_string())
+				.style(self.theme.text(!dropped, false)),
+			Cell::from(
+				entry
+					.commit
+					.id()
+					.map(|id| id.get_short_string())
+					.unwrap_or_default(),
+			)
+			.style(self.theme.commit_hash(false)),
+			Cell::from(entry.summary.clone())
+				.style(self.theme.text(!dropped, false)),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_index(&self) -> Option<usize> {
+		let table_state = self.table_state.take();
+		let selected = table_state
+			.selected()
+			.filter(|idx| *idx < self.entries.len());
+		self.table_state.set(table_state);
+
+		selected
+	}
+}
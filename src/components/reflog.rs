@@ -0,0 +1,364 @@
+use super::{
+	utils, visibility_blocking, CommandBlocking, CommandInfo,
+	Component, DrawableComponent, EventState, InspectCommitOpen,
+};
+use crate::{
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, ReflogEntry, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	backend::Backend,
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use std::convert::TryInto;
+use ui::style::SharedTheme;
+
+/// reflog of `HEAD` or a local branch, to find commits lost in a
+/// rebase or reset and get back to them
+pub struct ReflogComponent {
+	repo: RepoPathRef,
+	theme: SharedTheme,
+	queue: Queue,
+	/// `HEAD` or a full branch ref
+	reference: String,
+	entries: Vec<ReflogEntry>,
+	visible: bool,
+	table_state: std::cell::Cell<TableState>,
+	current_height: std::cell::Cell<usize>,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReflogComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 50);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.size(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.size().into(), area);
+			let area = area.intersection(rect);
+
+			let selector_width = self
+				.entries
+				.iter()
+				.fold(0, |acc, entry| acc.max(entry.selector.len()));
+			let action_width = self
+				.entries
+				.iter()
+				.fold(0, |acc, entry| acc.max(entry.action.len()));
+
+			let constraints = [
+				// selector (`HEAD@{n}`)
+				Constraint::Length(selector_width.try_into()?),
+				// commit id
+				Constraint::Length(7),
+				// date
+				Constraint::Length(19),
+				// action
+				Constraint::Length(action_width.try_into()?),
+				// message
+				Constraint::Percentage(100),
+			];
+
+			let rows = self.get_rows();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows)
+				.widths(&constraints)
+				.column_spacing(1)
+				.highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_reflog(&self.reference),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ReflogComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_details_open(
+					&self.key_config,
+				),
+				self.selected_entry().is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_reset_commit(&self.key_config),
+				self.selected_entry().is_some(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.move_up)
+				{
+					self.move_selection(ScrollType::Up);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_up,
+				) || key_match(
+					key,
+					self.key_config.keys.home,
+				) {
+					self.move_selection(ScrollType::Home);
+				} else if key_match(
+					key,
+					self.key_config.keys.shift_down,
+				) || key_match(
+					key,
+					self.key_config.keys.end,
+				) {
+					self.move_selection(ScrollType::End);
+				} else if key_match(
+					key,
+					self.key_config.keys.page_down,
+				) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, self.key_config.keys.page_up)
+				{
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(
+					key,
+					self.key_config.keys.move_right,
+				) || key_match(
+					key,
+					self.key_config.keys.enter,
+				) {
+					self.open_selected(|id| {
+						InternalEvent::OpenPopup(
+							StackablePopupOpen::InspectCommit(
+								InspectCommitOpen::new(id),
+							),
+						)
+					});
+				} else if key_match(
+					key,
+					self.key_config.keys.log_reset_comit,
+				) {
+					self.open_selected(InternalEvent::OpenResetPopup);
+				} else if key_match(
+					key,
+					self.key_config.keys.cmd_bar_toggle,
+				) {
+					//do not consume if its the more key
+					return Ok(EventState::NotConsumed);
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl ReflogComponent {
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			theme,
+			queue: queue.clone(),
+			reference: String::from("HEAD"),
+			entries: Vec::new(),
+			visible: false,
+			table_state: std::cell::Cell::new(TableState::default()),
+			current_height: std::cell::Cell::new(0),
+			key_config,
+			repo,
+		}
+	}
+
+	/// `reference` is `HEAD` or a full ref of a local branch
+	pub fn open(&mut self, reference: String) -> Result<()> {
+		self.entries =
+			sync::get_reflog(&self.repo.borrow(), &reference)?;
+		self.reference = reference;
+		self.table_state.get_mut().select(Some(0));
+
+		self.show()
+	}
+
+	/// closes the reflog and hands the selected commit on, the
+	/// popups opened from here sit below it otherwise
+	fn open_selected<F: FnOnce(sync::CommitId) -> InternalEvent>(
+		&mut self,
+		event: F,
+	) {
+		if let Some(id) = self.selected_entry().map(|entry| entry.id)
+		{
+			self.hide();
+			self.queue.push(event(id));
+		}
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) -> bool {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(
+				self.current_height.get().saturating_sub(1),
+			),
+			ScrollType::PageDown => old_selection
+				.saturating_add(
+					self.current_height.get().saturating_sub(1),
+				)
+				.min(max_selection),
+		};
+
+		let needs_update = new_selection != old_selection;
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+
+		needs_update
+	}
+
+	fn get_rows(&self) -> Vec<Row> {
+		self.entries
+			.iter()
+			.map(|entry| self.get_row(entry))
+			.collect()
+	}
+
+	fn get_row(&self, entry: &ReflogEntry) -> Row {
+		let cells: Vec<Cell> = vec![
+			Cell::from(entry.selector.clone())
+				.style(self.theme.text(true, false)),
+			Cell::from(entry.id.get_short_string())
+				.style(self.theme.commit_hash(false)),
+			Cell::from(utils::time_to_string(entry.time, false))
+				.style(self.theme.commit_time(false)),
+			Cell::from(entry.action.clone())
+				.style(self.theme.commit_author(false)),
+			Cell::from(entry.message.clone())
+				.style(self.theme.text(true, false)),
+		];
+
+		Row::new(cells)
+	}
+
+	fn selected_entry(&self) -> Option<&ReflogEntry> {
+		let table_state = self.table_state.take();
+
+		let entry = table_state
+			.selected()
+			.and_then(|selected| self.entries.get(selected));
+
+		self.table_state.set(table_state);
+
+		entry
+	}
+}
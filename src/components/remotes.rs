@@ -0,0 +1,473 @@
+use super::{
+	textinput::TextInputComponent,
+	utils::{
+		list_selection::{
+			clamp_list_selection, list_scroll_type,
+			move_list_selection,
+		},
+		scroll_vertical::VerticalScroll,
+	},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, ScrollType,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RemoteInfo, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	backend::Backend,
+	layout::{Alignment, Margin, Rect},
+	text::{Span, Spans, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::{cell::Cell, convert::TryInto};
+use ui::style::SharedTheme;
+use unicode_truncate::UnicodeTruncateStr;
+
+/// what the text input is currently asked for
+enum RemoteInput {
+	/// name of a new remote
+	AddName,
+	/// url of the new remote `name`
+	AddUrl(String),
+	/// new name for the remote
+	Rename(String),
+	/// new url for the remote
+	SetUrl(String),
+}
+
+///
+pub struct RemotesListComponent {
+	repo: RepoPathRef,
+	queue: Queue,
+	remotes: Vec<RemoteInfo>,
+	input: TextInputComponent,
+	input_mode: Option<RemoteInput>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: u16,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RemotesListComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(60, 40);
+			const MIN_SIZE: Size = Size::new(50, 10);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::POPUP_TITLE_REMOTES)
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			self.draw_list(f, area)?;
+
+			self.input.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RemotesListComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			if self.input.is_visible() {
+				self.input.commands(out, force_all);
+				return visibility_blocking(self);
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::remote_add(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::remote_rename(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::remote_edit_url(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::remote_delete(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if self.input.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.submit_input()?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if let Some(scroll) =
+				list_scroll_type(e, &self.key_config)
+			{
+				return self.move_selection(scroll).map(Into::into);
+			} else if key_match(e, self.key_config.keys.remote_add) {
+				self.open_input(RemoteInput::AddName, String::new())?;
+			} else if key_match(e, self.key_config.keys.remote_rename)
+			{
+				if let Some(remote) = self.selected_entry() {
+					let name = remote.name.clone();
+					self.open_input(
+						RemoteInput::Rename(name.clone()),
+						name,
+					)?;
+				}
+			} else if key_match(
+				e,
+				self.key_config.keys.remote_edit_url,
+			) {
+				if let Some(remote) = self.selected_entry() {
+					let url = remote.url.clone().unwrap_or_default();
+					self.open_input(
+						RemoteInput::SetUrl(remote.name.clone()),
+						url,
+					)?;
+				}
+			} else if key_match(e, self.key_config.keys.remote_delete)
+			{
+				if let Some(remote) = self.selected_entry() {
+					self.queue.push(InternalEvent::ConfirmAction(
+						Action::DeleteRemote(remote.name.clone()),
+					));
+				}
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl RemotesListComponent {
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			remotes: Vec::new(),
+			input: TextInputComponent::new(
+				theme.clone(),
+				key_config.clone(),
+				"",
+				"",
+				false,
+			)
+			.with_input_type(InputType::Singleline),
+			input_mode: None,
+			scroll: VerticalScroll::new(),
+			queue: queue.clone(),
+			selection: 0,
+			visible: false,
+			theme,
+			key_config,
+			current_height: Cell::new(0),
+			repo,
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.show()?;
+		self.update_remotes()?;
+
+		Ok(())
+	}
+
+	///
+	pub fn update_remotes(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.remotes =
+				sync::get_remotes_info(&self.repo.borrow())?;
+
+			self.set_selection(self.selection)?;
+		}
+		Ok(())
+	}
+
+	fn selected_entry(&self) -> Option<&RemoteInfo> {
+		self.remotes.get(self.selection as usize)
+	}
+
+	fn is_valid_selection(&self) -> bool {
+		self.selected_entry().is_some()
+	}
+
+	fn open_input(
+		&mut self,
+		mode: RemoteInput,
+		text: String,
+	) -> Result<()> {
+		let (title, msg) = match &mode {
+			RemoteInput::AddName => (
+				strings::remote_name_popup_title(false),
+				strings::remote_name_popup_msg(),
+			),
+			RemoteInput::Rename(_) => (
+				strings::remote_name_popup_title(true),
+				strings::remote_name_popup_msg(),
+			),
+			RemoteInput::AddUrl(name) | RemoteInput::SetUrl(name) => {
+				(
+					strings::remote_url_popup_title(name),
+					strings::remote_url_popup_msg(),
+				)
+			}
+		};
+
+		self.input.set_title(title);
+		self.input.set_default_msg(msg);
+		self.input.set_text_cursor_end(text);
+		self.input_mode = Some(mode);
+		self.input.show()
+	}
+
+	/// validates the input and either asks for the url next (when
+	/// adding) or applies the change right away
+	fn submit_input(&mut self) -> Result<()> {
+		let text = self.input.get_text().trim().to_string();
+
+		let res = match self.input_mode.take() {
+			Some(RemoteInput::AddName) => {
+				match sync::validate_remote_name(&text) {
+					Ok(()) => {
+						return self.open_input(
+							RemoteInput::AddUrl(text),
+							String::new(),
+						);
+					}
+					Err(e) => Err(e),
+				}
+			}
+			Some(RemoteInput::AddUrl(name)) => {
+				sync::add_remote(&self.repo.borrow(), &name, &text)
+			}
+			Some(RemoteInput::Rename(name)) => {
+				sync::rename_remote(&self.repo.borrow(), &name, &text)
+			}
+			Some(RemoteInput::SetUrl(name)) => sync::set_remote_url(
+				&self.repo.borrow(),
+				&name,
+				&text,
+			),
+			None => Ok(()),
+		};
+
+		self.input.hide();
+		self.input.clear();
+
+		try_or_popup!(self, "remote:", res);
+
+		self.update_remotes()?;
+		self.queue.push(InternalEvent::Update(
+			NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+		));
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
+		self.selection = move_list_selection(
+			self.selection,
+			scroll,
+			self.current_height.get(),
+			self.remotes.len(),
+		)?;
+
+		Ok(true)
+	}
+
+	fn set_selection(&mut self, selection: u16) -> Result<()> {
+		self.selection =
+			clamp_list_selection(selection, self.remotes.len())?;
+
+		Ok(())
+	}
+
+	fn get_text(&self, width_available: u16, height: usize) -> Text {
+		const THREE_DOTS: &str = "...";
+
+		let name_length = self
+			.remotes
+			.iter()
+			.map(|r| r.name.len())
+			.max()
+			.unwrap_or_default()
+			.min(usize::from(width_available / 3));
+		let url_length = usize::from(width_available)
+			.saturating_sub(name_length)
+			.saturating_sub(1);
+
+		let mut txt = Vec::with_capacity(height);
+
+		for (i, remote) in self
+			.remotes
+			.iter()
+			.skip(self.scroll.get_top())
+			.take(height)
+			.enumerate()
+		{
+			let mut name = remote.name.clone();
+			if name.len() > name_length {
+				name.unicode_truncate(
+					name_length.saturating_sub(THREE_DOTS.len()),
+				);
+				name += THREE_DOTS;
+			}
+
+			let mut url = remote.url.clone().unwrap_or_default();
+			if url.len() > url_length {
+				url.unicode_truncate(
+					url_length.saturating_sub(THREE_DOTS.len()),
+				);
+				url += THREE_DOTS;
+			}
+
+			let selected = (self.selection as usize
+				- self.scroll.get_top())
+				== i;
+
+			txt.push(Spans::from(vec![
+				Span::styled(
+					format!("{name:name_length$} "),
+					self.theme.text(true, selected),
+				),
+				Span::styled(
+					format!("{url:url_length$}"),
+					self.theme.text(false, selected),
+				),
+			]));
+		}
+
+		Text::from(txt)
+	}
+
+	fn draw_list<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		r: Rect,
+	) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection as usize,
+			self.remotes.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.get_text(r.width, height_in_lines))
+				.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+}
@@ -1,9 +1,11 @@
 use crate::{
 	components::{
-		popup_paragraph, visibility_blocking, CommandBlocking,
-		CommandInfo, Component, DrawableComponent, EventState,
+		popup_paragraph, utils::scroll_vertical::VerticalScroll,
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{
 		Action, CustomConfirmData, InternalEvent, LocalEvent,
 		NeedsUpdate, Queue,
@@ -11,11 +13,14 @@ use crate::{
 	strings, ui,
 };
 use anyhow::Result;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use ratatui::{
-	backend::Backend, layout::Rect, text::Text, widgets::Clear, Frame,
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::Clear,
+	Frame,
 };
-use std::borrow::Cow;
 use ui::style::SharedTheme;
 
 ///
@@ -25,6 +30,11 @@ pub struct ConfirmComponent {
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	scroll: VerticalScroll,
+	options: SharedOptions,
+	/// has to be typed before confirming, see `StrictConfirm`
+	expected: Option<String>,
+	typed: String,
 
 	custom: Option<CustomConfirmData>,
 }
@@ -36,19 +46,42 @@ impl DrawableComponent for ConfirmComponent {
 		_rect: Rect,
 	) -> Result<()> {
 		if self.visible {
-			let (title, msg) = self.get_text();
+			let (title, mut msg) = self.get_text();
 
-			let txt = Text::styled(
-				Cow::from(msg),
-				self.theme.text_danger(),
-			);
+			if let Some(expected) = &self.expected {
+				msg = format!(
+					"{msg}\n\n{}\n> {}",
+					strings::confirm_type_to_confirm(expected),
+					self.typed
+				);
+			}
 
 			let area = ui::centered_rect(50, 20, f.size());
+			let width = usize::from(area.width.saturating_sub(2));
+			let height = usize::from(area.height.saturating_sub(2));
+
+			// wrap ourselves to know the line count for scrolling
+			let lines = textwrap::wrap(&msg, width.max(1));
+			self.scroll.update_no_selection(lines.len(), height);
+
+			let txt: Vec<Spans> = lines
+				.into_iter()
+				.skip(self.scroll.get_top())
+				.take(height)
+				.map(|line| {
+					Spans::from(Span::styled(
+						line,
+						self.theme.text_danger(),
+					))
+				})
+				.collect();
+
 			f.render_widget(Clear, area);
 			f.render_widget(
 				popup_paragraph(&title, txt, &self.theme, true, true),
 				area,
 			);
+			self.scroll.draw(f, area, &self.theme);
 		}
 
 		Ok(())
@@ -63,7 +96,7 @@ impl Component for ConfirmComponent {
 	) -> CommandBlocking {
 		out.push(CommandInfo::new(
 			strings::commands::confirm_action(&self.key_config),
-			true,
+			self.can_confirm(),
 			self.visible,
 		));
 		out.push(CommandInfo::new(
@@ -71,6 +104,11 @@ impl Component for ConfirmComponent {
 			true,
 			self.visible,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::scroll(&self.key_config),
+			true,
+			self.visible,
+		));
 
 		visibility_blocking(self)
 	}
@@ -78,10 +116,27 @@ impl Component for ConfirmComponent {
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
 			if let Event::Key(e) = ev {
+				if self.expected.is_some()
+					&& self.type_input(e.code, e.modifiers)
+				{
+					return Ok(EventState::Consumed);
+				}
+
 				if key_match(e, self.key_config.keys.exit_popup) {
 					self.hide();
 				} else if key_match(e, self.key_config.keys.enter) {
-					self.confirm();
+					if self.can_confirm() {
+						self.confirm();
+					}
+				} else if key_match(e, self.key_config.keys.move_up) {
+					self.scroll.move_top(ScrollType::Up);
+				} else if key_match(e, self.key_config.keys.move_down)
+				{
+					self.scroll.move_top(ScrollType::Down);
+				} else if key_match(e, self.key_config.keys.home) {
+					self.scroll.move_top(ScrollType::Home);
+				} else if key_match(e, self.key_config.keys.end) {
+					self.scroll.move_top(ScrollType::End);
 				}
 
 				return Ok(EventState::Consumed);
@@ -101,6 +156,7 @@ impl Component for ConfirmComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.scroll.reset();
 
 		Ok(())
 	}
@@ -112,6 +168,7 @@ impl ConfirmComponent {
 		queue: Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			target: None,
@@ -119,24 +176,36 @@ impl ConfirmComponent {
 			queue,
 			theme,
 			key_config,
+			scroll: VerticalScroll::new(),
+			options,
+			expected: None,
+			typed: String::new(),
 
 			custom: None,
 		}
 	}
 	///
 	pub fn open(&mut self, a: Action) -> Result<()> {
+		self.expected = self.expected_input(&a);
+		self.typed.clear();
 		self.target = Some(a);
 		self.custom = None;
 		self.show()?;
 
 		Ok(())
 	}
+	/// true while asking whether to quit despite pending work
+	pub fn is_confirming_quit(&self) -> bool {
+		self.visible
+			&& matches!(self.target, Some(Action::QuitPending(_)))
+	}
 	///
 	pub fn open_custom(
 		&mut self,
 		c: CustomConfirmData,
 	) -> Result<()> {
 		self.target = None;
+		self.expected = None;
 		self.custom = Some(c);
 		self.show()?;
 
@@ -155,6 +224,56 @@ impl ConfirmComponent {
 		self.hide();
 	}
 
+	/// what has to be typed to confirm `action`, if the user
+	/// asked for strict confirmation of it
+	fn expected_input(&self, action: &Action) -> Option<String> {
+		let strict = self.options.borrow().strict_confirm();
+
+		match action {
+			Action::ForcePush(branch, _) if strict.force_push => {
+				Some(branch.clone())
+			}
+			Action::Reset(_)
+			| Action::ResetHunk(_, _)
+			| Action::ResetLines(_, _)
+			| Action::ResetHard(_)
+			| Action::RestoreFiles(_, _)
+			| Action::DeleteUntracked(_)
+				if strict.reset =>
+			{
+				Some(String::from("yes"))
+			}
+			_ => None,
+		}
+	}
+
+	fn can_confirm(&self) -> bool {
+		self.expected
+			.as_ref()
+			.map_or(true, |expected| *expected == self.typed)
+	}
+
+	/// returns true if the key went into the typed text
+	fn type_input(
+		&mut self,
+		code: KeyCode,
+		modifiers: KeyModifiers,
+	) -> bool {
+		match code {
+			KeyCode::Char(c)
+				if (modifiers - KeyModifiers::SHIFT).is_empty() =>
+			{
+				self.typed.push(c);
+				true
+			}
+			KeyCode::Backspace => {
+				self.typed.pop();
+				true
+			}
+			_ => false,
+		}
+	}
+
 	fn get_text(&self) -> (String, String) {
 		if let Some(c) = self.custom.as_ref() {
 			return (c.title.clone(), c.msg.clone());
@@ -182,6 +301,23 @@ impl ConfirmComponent {
                 Action::ResetLines(_, lines) => (
                     strings::confirm_title_reset(),
                     strings::confirm_msg_reset_lines(lines.len()),
+                ),
+				Action::ResetHard(id) => (
+					strings::confirm_title_reset(),
+					strings::confirm_msg_reset_hard(
+						&id.get_short_string(),
+					),
+				),
+                Action::RestoreFiles(tracked, untracked) => (
+                    strings::confirm_title_restore(),
+                    strings::confirm_msg_restore(
+                        tracked.len(),
+                        untracked.len(),
+                    ),
+                ),
+                Action::DeleteUntracked(paths) => (
+                    strings::confirm_title_delete_untracked(),
+                    strings::confirm_msg_delete_untracked(paths.len()),
                 ),
                 Action::DeleteLocalBranch(branch_ref) => (
                     strings::confirm_title_delete_branch(
@@ -239,9 +375,126 @@ impl ConfirmComponent {
                     strings::confirm_title_abortrevert(),
                     strings::confirm_msg_revertchanges(),
                 ),
+				Action::CheckoutPullRequest(branch) => (
+					strings::confirm_title_checkout_pull_request(),
+					strings::confirm_msg_checkout_pull_request(branch),
+				),
+				Action::InitSubmodule(path) => (
+					strings::confirm_title_init_submodule(),
+					strings::confirm_msg_init_submodule(
+						&path.to_string_lossy(),
+					),
+				),
+				Action::DeleteRemote(name) => (
+					strings::confirm_title_delete_remote(),
+					strings::confirm_msg_delete_remote(name),
+				),
+				Action::QuitPending(operations) => (
+					strings::confirm_title_quit_pending(),
+					strings::confirm_msg_quit_pending(
+						&self.key_config,
+						operations,
+					),
+				),
             };
 		}
 
 		(String::new(), String::new())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::ConfirmComponent;
+	use crate::{
+		components::Component,
+		keys::SharedKeyConfig,
+		options::{Options, StrictConfirm},
+		queue::{Action, InternalEvent, Queue},
+		ui::style::SharedTheme,
+	};
+	use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+	fn confirm_popup(
+		strict: StrictConfirm,
+	) -> (ConfirmComponent, Queue) {
+		let options = Options::in_memory();
+		options.borrow_mut().set_strict_confirm(strict);
+		let queue = Queue::new();
+		let popup = ConfirmComponent::new(
+			queue.clone(),
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			options,
+		);
+		(popup, queue)
+	}
+
+	fn press(popup: &mut ConfirmComponent, code: KeyCode) {
+		popup
+			.event(&Event::Key(KeyEvent::new(
+				code,
+				KeyModifiers::empty(),
+			)))
+			.unwrap();
+	}
+
+	fn type_text(popup: &mut ConfirmComponent, text: &str) {
+		for c in text.chars() {
+			press(popup, KeyCode::Char(c));
+		}
+	}
+
+	#[test]
+	fn test_force_push_needs_branch_name() {
+		let (mut popup, queue) = confirm_popup(StrictConfirm {
+			force_push: true,
+			reset: false,
+		});
+		popup
+			.open(Action::ForcePush(String::from("main"), true))
+			.unwrap();
+
+		press(&mut popup, KeyCode::Enter);
+		assert!(popup.is_visible());
+
+		type_text(&mut popup, "mai");
+		press(&mut popup, KeyCode::Enter);
+		assert!(popup.is_visible());
+		assert!(queue.pop().is_none());
+
+		type_text(&mut popup, "x");
+		press(&mut popup, KeyCode::Backspace);
+		type_text(&mut popup, "n");
+		press(&mut popup, KeyCode::Enter);
+		assert!(!popup.is_visible());
+		assert!(matches!(
+			queue.pop(),
+			Some(InternalEvent::ConfirmedAction(Action::ForcePush(
+				branch,
+				true
+			))) if branch == "main"
+		));
+	}
+
+	#[test]
+	fn test_enter_confirms_unless_strict() {
+		let (mut popup, queue) = confirm_popup(StrictConfirm {
+			force_push: false,
+			reset: true,
+		});
+		popup
+			.open(Action::ForcePush(String::from("main"), true))
+			.unwrap();
+
+		press(&mut popup, KeyCode::Enter);
+		assert!(!popup.is_visible());
+		assert!(matches!(
+			queue.pop(),
+			Some(InternalEvent::ConfirmedAction(Action::ForcePush(
+				_,
+				true
+			)))
+		));
+	}
+}
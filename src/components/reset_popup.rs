@@ -4,7 +4,8 @@ use super::{
 };
 use crate::{
 	keys::{key_match, SharedKeyConfig},
-	queue::Queue,
+	options::SharedOptions,
+	queue::{Action, InternalEvent, Queue},
 	strings, try_or_popup,
 	ui::{self, style::SharedTheme},
 };
@@ -48,6 +49,7 @@ pub struct ResetPopupComponent {
 	visible: bool,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
+	options: SharedOptions,
 }
 
 impl ResetPopupComponent {
@@ -57,6 +59,7 @@ impl ResetPopupComponent {
 		repo: &RepoPathRef,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
 		Self {
 			queue: queue.clone(),
@@ -67,6 +70,7 @@ impl ResetPopupComponent {
 			visible: false,
 			key_config,
 			theme,
+			options,
 		}
 	}
 
@@ -130,6 +134,16 @@ impl ResetPopupComponent {
 
 	fn reset(&mut self) {
 		if let Some(id) = self.commit {
+			if matches!(self.kind, ResetType::Hard)
+				&& self.options.borrow().strict_confirm().reset
+			{
+				self.queue.push(InternalEvent::ConfirmAction(
+					Action::ResetHard(id),
+				));
+				self.hide();
+				return;
+			}
+
 			try_or_popup!(
 				self,
 				"reset:",
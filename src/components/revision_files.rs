@@ -464,6 +464,29 @@ impl Component for RevisionFilesComponent {
 				)
 				.order(order::RARE_ACTION),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::copy_abs_path(
+						&self.key_config,
+					),
+					self.tree.selected_file().is_some(),
+					true,
+				)
+				.order(order::RARE_ACTION),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::reveal_file(&self.key_config),
+					self.tree.selected_file().is_some(),
+					true,
+				)
+				.order(order::RARE_ACTION),
+			);
+			out.push(CommandInfo::new(
+				strings::commands::grep_files(&self.key_config),
+				self.revision.is_some(),
+				true,
+			));
 			tree_nav_cmds(&self.tree, &self.key_config, out);
 		} else {
 			self.current_file.commands(out, force_all);
@@ -523,6 +546,12 @@ impl Component for RevisionFilesComponent {
 					self.open_finder();
 					return Ok(EventState::Consumed);
 				}
+			} else if key_match(key, self.key_config.keys.grep_files) {
+				if let Some(rev) = &self.revision {
+					self.queue
+						.push(InternalEvent::OpenGrep(Some(rev.id)));
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.edit_file) {
 				if let Some(file) =
 					self.selected_file_path_with_prefix()
@@ -544,6 +573,21 @@ impl Component for RevisionFilesComponent {
 					);
 				}
 				return Ok(EventState::Consumed);
+			} else if key_match(
+				key,
+				self.key_config.keys.copy_abs_path,
+			) {
+				if let Some(file) = self.selected_file_path() {
+					self.queue
+						.push(InternalEvent::CopyAbsolutePath(file));
+				}
+				return Ok(EventState::Consumed);
+			} else if key_match(key, self.key_config.keys.reveal_file)
+			{
+				if let Some(file) = self.selected_file_path() {
+					self.queue.push(InternalEvent::RevealFile(file));
+				}
+				return Ok(EventState::Consumed);
 			} else if !is_tree_focused {
 				return self.current_file.event(event);
 			}
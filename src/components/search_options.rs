@@ -5,6 +5,7 @@ use super::{
 use crate::{
 	//components::utils::string_width_align,
 	keys::{key_match, SharedKeyConfig},
+	options::SearchScope,
 	strings::{self},
 	ui::{self, style::SharedTheme},
 };
@@ -109,10 +110,20 @@ impl SearchOptionsPopupComponent {
 		}
 	}
 
-	pub fn enable_all(&mut self) {
-		self.author = true;
-		self.message = true;
-		self.sha = true;
+	///
+	pub const fn scope(&self) -> SearchScope {
+		SearchScope {
+			author: self.author,
+			message: self.message,
+			sha: self.sha,
+		}
+	}
+
+	///
+	pub fn set_scope(&mut self, scope: SearchScope) {
+		self.author = scope.author;
+		self.message = scope.message;
+		self.sha = scope.sha;
 		self.update_title();
 	}
 
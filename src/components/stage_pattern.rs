@@ -0,0 +1,164 @@
+use super::{
+	textinput::TextInputComponent, visibility_blocking,
+	CommandBlocking, CommandInfo, Component, DrawableComponent,
+	EventState,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{backend::Backend, layout::Rect, Frame};
+
+/// prompts for a pathspec (e.g. `*.rs`) and stages or unstages
+/// every file matching it
+pub struct StagePatternComponent {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	stage: bool,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+	options: SharedOptions,
+}
+
+impl DrawableComponent for StagePatternComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for StagePatternComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::stage_pattern_confirm_msg(
+					&self.key_config,
+					self.stage,
+				),
+				!self.input.get_text().is_empty(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter)
+					&& !self.input.get_text().is_empty()
+				{
+					self.apply();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl StagePatternComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		queue: Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+		options: SharedOptions,
+	) -> Self {
+		Self {
+			repo,
+			queue,
+			input: TextInputComponent::new(
+				theme,
+				key_config.clone(),
+				&strings::stage_pattern_popup_title(true),
+				&strings::stage_pattern_popup_msg(&key_config),
+				true,
+			),
+			stage: true,
+			key_config,
+			options,
+		}
+	}
+
+	/// `stage` selects between staging and unstaging matches
+	pub fn open(&mut self, stage: bool) -> Result<()> {
+		self.stage = stage;
+		self.input
+			.set_title(strings::stage_pattern_popup_title(stage));
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn apply(&mut self) {
+		let pattern = self.input.get_text();
+		let res = if self.stage {
+			sync::stage_add_all(
+				&self.repo.borrow(),
+				pattern,
+				self.options.borrow().status_show_untracked(),
+			)
+		} else {
+			sync::reset_stage(&self.repo.borrow(), pattern)
+		};
+
+		match res {
+			Ok(count) => {
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::stage_pattern_result(
+						pattern, count, self.stage,
+					),
+				));
+				self.hide();
+				self.input.clear();
+			}
+			Err(e) => {
+				log::error!("stage pattern: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("stage pattern error:\n{e}"),
+				));
+			}
+		}
+	}
+}
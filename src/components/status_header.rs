@@ -0,0 +1,212 @@
+use super::DrawableComponent;
+use crate::{
+	options::SharedOptions, strings, ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	cached,
+	sync::{self, BranchCompare, RepoPathRef, RepoState},
+	AsyncGitNotification, AsyncUpstreamJob,
+};
+use crossbeam_channel::Sender;
+use ratatui::{
+	backend::Backend,
+	layout::Rect,
+	text::{Span, Spans},
+	widgets::Paragraph,
+	Frame,
+};
+
+/// upstream of a branch, looked up in the background
+struct UpstreamInfo {
+	branch: String,
+	upstream: Option<String>,
+	compare: Option<BranchCompare>,
+}
+
+/// what the header shows, gathered on update
+struct HeaderInfo {
+	/// branch name or detached description of `HEAD`
+	head: String,
+	/// branch of `HEAD`, `None` if detached
+	branch: Option<String>,
+	state: RepoState,
+	/// `(current, steps)` of a pending rebase
+	rebase_step: Option<(usize, usize)>,
+}
+
+/// one line above the tabs with the current branch, its upstream
+/// and whether a merge or rebase is pending
+pub struct StatusHeaderComponent {
+	repo: RepoPathRef,
+	git_branch_name: cached::BranchName,
+	info: Option<HeaderInfo>,
+	upstream: Option<UpstreamInfo>,
+	git_upstream: AsyncSingleJob<AsyncUpstreamJob>,
+	theme: SharedTheme,
+	options: SharedOptions,
+}
+
+impl StatusHeaderComponent {
+	///
+	pub fn new(
+		repo: RepoPathRef,
+		sender: &Sender<AsyncGitNotification>,
+		theme: SharedTheme,
+		options: SharedOptions,
+	) -> Self {
+		Self {
+			git_branch_name: cached::BranchName::new(repo.clone()),
+			repo,
+			info: None,
+			upstream: None,
+			git_upstream: AsyncSingleJob::new(sender.clone()),
+			theme,
+			options,
+		}
+	}
+
+	/// lines to reserve for the header
+	pub fn height(&self) -> u16 {
+		u16::from(self.is_visible())
+	}
+
+	pub fn is_visible(&self) -> bool {
+		self.options.borrow().status_header()
+	}
+
+	pub fn toggle(&mut self) {
+		self.options.borrow_mut().toggle_status_header();
+		self.update();
+	}
+
+	/// upstream and ahead/behind counts walk the history and
+	/// follow in the background
+	pub fn update(&mut self) {
+		if !self.is_visible() {
+			self.info = None;
+			self.upstream = None;
+			return;
+		}
+
+		let branch = self.git_branch_name.lookup().ok();
+		let repo = self.repo.borrow();
+
+		// an unborn `HEAD` (fresh `git init`, orphan branch) has
+		// neither a state nor a name to show, which is no reason to
+		// fail the whole app
+		let state =
+			sync::repo_state(&repo).unwrap_or(RepoState::Clean);
+		let head = branch.clone().unwrap_or_else(|| {
+			sync::utils::get_head_name(&repo).unwrap_or_else(|_| {
+				strings::HEADER_UNBORN_HEAD.to_string()
+			})
+		});
+
+		if let Some(branch) = &branch {
+			self.git_upstream.spawn(AsyncUpstreamJob::new(
+				repo.clone(),
+				branch.clone(),
+			));
+		}
+
+		let rebase_step = if state == RepoState::Rebase {
+			sync::rebase_progress(&repo)
+				.ok()
+				.map(|p| (p.current + 1, p.steps))
+		} else {
+			None
+		};
+
+		self.info = Some(HeaderInfo {
+			head,
+			branch,
+			state,
+			rebase_step,
+		});
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::Upstream {
+			return;
+		}
+
+		if let Some(job) = self.git_upstream.take_last() {
+			if let Some(Ok((upstream, compare))) = job.result() {
+				self.upstream = Some(UpstreamInfo {
+					branch: job.branch().to_string(),
+					upstream,
+					compare,
+				});
+			}
+		}
+	}
+
+	fn get_text(&self, info: &HeaderInfo) -> Spans {
+		let mut txt = vec![Span::styled(
+			info.head.clone(),
+			self.theme.branch(false, true),
+		)];
+
+		// a lookup for the branch checked out before is outdated
+		let upstream = self.upstream.as_ref().filter(|upstream| {
+			info.branch.as_ref() == Some(&upstream.branch)
+		});
+
+		if let Some(name) =
+			upstream.and_then(|upstream| upstream.upstream.as_ref())
+		{
+			txt.push(Span::styled(
+				format!(" \u{2192} {name}"),
+				self.theme.branch(false, false),
+			));
+		}
+
+		if let Some(compare) =
+			upstream.and_then(|upstream| upstream.compare.as_ref())
+		{
+			txt.push(Span::styled(
+				format!(
+					" \u{2191}{} \u{2193}{}",
+					compare.ahead, compare.behind
+				),
+				self.theme.text(true, false),
+			));
+		}
+
+		txt.push(Span::styled(" | ", self.theme.text(false, false)));
+
+		let state = strings::header_repo_state(&info.state);
+		txt.push(if info.state == RepoState::Clean {
+			Span::styled(state, self.theme.text(false, false))
+		} else if let Some((current, steps)) = info.rebase_step {
+			Span::styled(
+				format!("{state} {current}/{steps}"),
+				self.theme.text_danger(),
+			)
+		} else {
+			Span::styled(state, self.theme.text_danger())
+		});
+
+		Spans::from(txt)
+	}
+}
+
+impl DrawableComponent for StatusHeaderComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if let Some(info) = &self.info {
+			f.render_widget(
+				Paragraph::new(self.get_text(info)),
+				rect,
+			);
+		}
+
+		Ok(())
+	}
+}
@@ -7,10 +7,10 @@ use super::{
 };
 use crate::{
 	components::{CommandInfo, Component, EventState},
-	is_among_tracked_lfs_files,
 	keys::{key_match, SharedKeyConfig},
+	lfs::is_among_tracked_lfs_files,
 	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
-	strings::{self, order},
+	strings::{self, order, symbol},
 	ui,
 	ui::style::SharedTheme,
 };
@@ -18,7 +18,10 @@ use anyhow::Result;
 use asyncgit::{hash, sync::CommitId, StatusItem, StatusItemType};
 use crossterm::event::Event;
 use ratatui::{backend::Backend, layout::Rect, text::Span, Frame};
-use std::{borrow::Cow, cell::Cell, convert::From, path::Path};
+use std::{
+	borrow::Cow, cell::Cell, collections::BTreeSet, convert::From,
+	path::Path,
+};
 
 //TODO: use new `filetreelist` crate
 
@@ -37,6 +40,8 @@ pub struct StatusTreeComponent {
 	scroll_top: Cell<usize>,
 	visible: bool,
 	revision: Option<CommitId>,
+	/// paths of marked files
+	marked: BTreeSet<String>,
 }
 
 impl StatusTreeComponent {
@@ -61,6 +66,7 @@ impl StatusTreeComponent {
 			pending: true,
 			visible: false,
 			revision: None,
+			marked: BTreeSet::new(),
 		}
 	}
 
@@ -80,6 +86,8 @@ impl StatusTreeComponent {
 		if self.current_hash != new_hash {
 			self.tree.update(list)?;
 			self.current_hash = new_hash;
+			self.marked
+				.retain(|path| list.iter().any(|i| &i.path == path));
 		}
 
 		Ok(())
@@ -125,9 +133,28 @@ impl StatusTreeComponent {
 	pub fn clear(&mut self) -> Result<()> {
 		self.current_hash = 0;
 		self.pending = true;
+		self.marked.clear();
 		self.tree.update(&[])
 	}
 
+	/// (un)marks the selected file, returns false if no file is
+	/// selected
+	pub fn toggle_mark_selected(&mut self) -> bool {
+		if let Some(item) = self.selection_file() {
+			if !self.marked.remove(&item.path) {
+				self.marked.insert(item.path);
+			}
+			true
+		} else {
+			false
+		}
+	}
+
+	/// paths of the marked files, sorted
+	pub fn marked_paths(&self) -> Vec<String> {
+		self.marked.iter().cloned().collect()
+	}
+
 	///
 	pub fn is_file_seleted(&self) -> bool {
 		self.tree.selected_item().map_or(false, |item| {
@@ -162,22 +189,33 @@ impl StatusTreeComponent {
 	}
 
 	fn item_to_text<'b>(
-		string: &str,
-		indent: usize,
-		visible: bool,
-		file_item_kind: &FileTreeItemKind,
-		lfs_tracked: bool,
+		info: &TextDrawInfo,
+		// `None` unless any file is marked
+		marked: Option<bool>,
 		width: u16,
 		selected: bool,
 		theme: &'b SharedTheme,
 	) -> Option<Span<'b>> {
+		let string = &info.name;
+		let indent = usize::from(info.indent);
+		let file_item_kind = info.item_kind;
+		let lfs_tracked = info.lfs_tracked;
+
+		let mark_column = marked.map_or_else(String::new, |marked| {
+			let symbol = if marked {
+				symbol::CHECKMARK
+			} else {
+				symbol::EMPTY_SPACE
+			};
+			format!("{symbol} ")
+		});
 		let indent_str = if indent == 0 {
-			String::new()
+			mark_column
 		} else {
-			format!("{:w$}", " ", w = indent * 2)
+			format!("{mark_column}{:w$}", " ", w = indent * 2)
 		};
 
-		if !visible {
+		if !info.visible {
 			return None;
 		}
 
@@ -266,7 +304,8 @@ impl StatusTreeComponent {
 				item_kind: &item.kind,
 				lfs_tracked: is_among_tracked_lfs_files(
 					&item.info.full_path,
-				),
+				)
+				.unwrap_or_default(),
 			});
 
 			let mut idx_temp = index;
@@ -384,16 +423,23 @@ impl DrawableComponent for StatusTreeComponent {
 				select.saturating_sub(selection_offset_visible),
 			));
 
+			let any_marked = !self.marked.is_empty();
+
 			let items = vec_draw_text_info
 				.iter()
 				.enumerate()
 				.filter_map(|(index, draw_text_info)| {
+					let marked = any_marked.then(|| {
+						matches!(
+							draw_text_info.item_kind,
+							FileTreeItemKind::File(f)
+								if self.marked.contains(&f.path)
+						)
+					});
+
 					Self::item_to_text(
-						&draw_text_info.name,
-						draw_text_info.indent as usize,
-						draw_text_info.visible,
-						draw_text_info.item_kind,
-						draw_text_info.lfs_tracked,
+						draw_text_info,
+						marked,
 						r.width,
 						self.show_selection && select == index,
 						&self.theme,
@@ -468,6 +514,24 @@ impl Component for StatusTreeComponent {
 			.order(order::RARE_ACTION),
 		);
 
+		out.push(
+			CommandInfo::new(
+				strings::commands::copy_abs_path(&self.key_config),
+				self.selection_file().is_some(),
+				self.focused || force_all,
+			)
+			.order(order::RARE_ACTION),
+		);
+
+		out.push(
+			CommandInfo::new(
+				strings::commands::reveal_file(&self.key_config),
+				self.selection_file().is_some(),
+				self.focused || force_all,
+			)
+			.order(order::RARE_ACTION),
+		);
+
 		CommandBlocking::PassingOn
 	}
 
@@ -522,6 +586,28 @@ impl Component for StatusTreeComponent {
 				} else if key_match(e, self.key_config.keys.copy) {
 					self.copy_file_path();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.copy_abs_path,
+				) {
+					if let Some(status_item) = self.selection_file() {
+						if let Some(queue) = &self.queue {
+							queue.push(InternalEvent::CopyAbsolutePath(
+								status_item.path,
+							));
+						}
+					}
+					Ok(EventState::Consumed)
+				} else if key_match(e, self.key_config.keys.reveal_file)
+				{
+					if let Some(status_item) = self.selection_file() {
+						if let Some(queue) = &self.queue {
+							queue.push(InternalEvent::RevealFile(
+								status_item.path,
+							));
+						}
+					}
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.move_down)
 				{
 					Ok(self
@@ -4,16 +4,20 @@ use super::{
 	EventState, ScrollType,
 };
 use crate::{
+	async_jobs::{
+		AsyncDynJob, AsyncJobFeedback, BoxFeedback, JobFeedbackSender,
+		JobSender,
+	},
 	keys::{key_match, SharedKeyConfig},
-	queue::{InternalEvent, NeedsUpdate, Queue},
+	queue::{Action, InternalEvent, NeedsUpdate, Queue},
 	strings, try_or_popup,
 	ui::{self, Size},
 };
 use anyhow::Result;
 use asyncgit::sync::{
-	get_submodules, repo_dir, submodule_parent_info,
-	update_submodule, RepoPathRef, SubmoduleInfo,
-	SubmoduleParentInfo,
+	get_submodules, init_submodule_cmd, repo_dir,
+	submodule_parent_info, update_submodule, RepoPath, RepoPathRef,
+	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -25,10 +29,49 @@ use ratatui::{
 	widgets::{Block, Borders, Clear, Paragraph},
 	Frame,
 };
-use std::{cell::Cell, convert::TryInto};
+use std::{
+	cell::Cell,
+	convert::TryInto,
+	path::{Path, PathBuf},
+};
 use ui::style::SharedTheme;
 use unicode_truncate::UnicodeTruncateStr;
 
+/// `git submodule update --init` for one submodule
+struct AsyncJobInitSubmodule {
+	repo: RepoPath,
+	path: PathBuf,
+}
+
+impl AsyncDynJob for AsyncJobInitSubmodule {
+	fn run(
+		&mut self,
+		_sender: JobFeedbackSender,
+	) -> Option<BoxFeedback> {
+		Some(Box::new(AsyncJobInitSubmoduleFeedback {
+			res: init_submodule_cmd(&self.repo, &self.path)
+				.map_err(|e| e.to_string()),
+			path: self.path.clone(),
+		}))
+	}
+
+	fn should_stop(&self) -> bool {
+		false
+	}
+}
+
+struct AsyncJobInitSubmoduleFeedback {
+	path: PathBuf,
+	res: Result<(), String>,
+}
+
+impl AsyncJobFeedback for AsyncJobInitSubmoduleFeedback {
+	fn visit(&mut self, app: &mut crate::app::App) {
+		app.submodule_popup
+			.finish_init_submodule(self.path.clone(), &self.res);
+	}
+}
+
 ///
 pub struct SubmodulesListComponent {
 	repo: RepoPathRef,
@@ -42,6 +85,8 @@ pub struct SubmodulesListComponent {
 	scroll: VerticalScroll,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
+	async_job_sender: JobSender,
+	init_pending: bool,
 }
 
 impl DrawableComponent for SubmodulesListComponent {
@@ -182,7 +227,15 @@ impl Component for SubmodulesListComponent {
 					.map(Into::into);
 			} else if key_match(e, self.key_config.keys.enter) {
 				if let Some(submodule) = self.selected_entry() {
-					if submodule.status.is_in_wd() {
+					if Self::is_uninitialized(submodule) {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::InitSubmodule(
+									submodule.path.clone(),
+								),
+							),
+						);
+					} else if submodule.status.is_in_wd() {
 						self.queue.push(InternalEvent::OpenRepo {
 							path: submodule.path.clone(),
 						});
@@ -213,7 +266,7 @@ impl Component for SubmodulesListComponent {
 				self.key_config.keys.view_submodule_parent,
 			) {
 				if let Some(parent) = &self.submodule_parent {
-					self.queue.push(InternalEvent::OpenRepo {
+					self.queue.push(InternalEvent::OpenParentRepo {
 						path: parent.parent_gitpath.clone(),
 					});
 				}
@@ -250,6 +303,7 @@ impl SubmodulesListComponent {
 		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		async_job_sender: JobSender,
 	) -> Self {
 		Self {
 			submodules: Vec::new(),
@@ -263,9 +317,54 @@ impl SubmodulesListComponent {
 			current_height: Cell::new(0),
 			repo,
 			repo_path: String::new(),
+			async_job_sender,
+			init_pending: false,
 		}
 	}
 
+	pub const fn any_work_pending(&self) -> bool {
+		self.init_pending
+	}
+
+	/// initializes the submodule at `path` in the background and
+	/// opens it once done
+	pub fn init_submodule(&mut self, path: PathBuf) {
+		self.init_pending = true;
+		if self
+			.async_job_sender
+			.send(Box::new(AsyncJobInitSubmodule {
+				repo: self.repo.borrow().clone(),
+				path: path.clone(),
+			}))
+			.is_err()
+		{
+			self.finish_init_submodule(
+				path,
+				&Err(std::io::Error::from(std::io::ErrorKind::Other)
+					.to_string()),
+			);
+		}
+	}
+
+	pub fn finish_init_submodule(
+		&mut self,
+		path: PathBuf,
+		res: &Result<(), String>,
+	) {
+		self.init_pending = false;
+		match res {
+			Ok(()) => {
+				self.queue.push(InternalEvent::OpenRepo { path });
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("submodule init failed:\n{e}"),
+				));
+			}
+		}
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
 	///
 	pub fn open(&mut self) -> Result<()> {
 		self.show()?;
@@ -274,6 +373,19 @@ impl SubmodulesListComponent {
 		Ok(())
 	}
 
+	/// opens the popup with the submodule at `path` selected
+	pub fn open_at(&mut self, path: &Path) -> Result<()> {
+		self.open()?;
+
+		if let Some(idx) =
+			self.submodules.iter().position(|s| s.path == path)
+		{
+			self.set_selection(idx.try_into()?)?;
+		}
+
+		Ok(())
+	}
+
 	///
 	pub fn update_submodules(&mut self) -> Result<()> {
 		if self.is_visible() {
@@ -301,10 +413,16 @@ impl SubmodulesListComponent {
 
 	fn can_open_submodule(&self) -> bool {
 		self.selected_entry()
-			.map(|s| s.status.is_in_wd())
+			.map(|s| s.status.is_in_wd() || Self::is_uninitialized(s))
 			.unwrap_or_default()
 	}
 
+	/// the (empty) folder of an uninitialized submodule would open
+	/// the parent repo again, it needs an init first
+	fn is_uninitialized(submodule: &SubmoduleInfo) -> bool {
+		submodule.status.contains(SubmoduleStatus::WD_UNINITIALIZED)
+	}
+
 	//TODO: dedup this almost identical with BranchListComponent
 	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
 		let new_selection = match scroll {
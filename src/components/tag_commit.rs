@@ -114,7 +114,10 @@ impl Component for TagCommitComponent {
 
 	fn show(&mut self) -> Result<()> {
 		self.mode = Mode::Name;
-		self.input.set_title(strings::tag_popup_name_title());
+		self.input.set_title(self.commit_id.as_ref().map_or_else(
+			strings::tag_popup_name_title,
+			strings::tag_popup_name_title_at,
+		));
 		self.input.set_default_msg(strings::tag_popup_name_msg());
 		self.input.show()?;
 
@@ -184,7 +187,7 @@ impl TagCommitComponent {
 					self.hide();
 
 					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
+						NeedsUpdate::ALL | NeedsUpdate::TAGS,
 					));
 				}
 				Err(e) => {
@@ -225,6 +225,12 @@ impl TextInputComponent {
 		self.cursor_position = 0;
 	}
 
+	/// Set the `msg` and place the cursor behind it.
+	pub fn set_text_cursor_end(&mut self, msg: String) {
+		self.cursor_position = msg.len();
+		self.msg = msg;
+	}
+
 	/// Set the `title`.
 	pub fn set_title(&mut self, t: String) {
 		self.title = t;
@@ -588,6 +594,21 @@ mod tests {
 		assert_eq!(comp.cursor_position, 0);
 	}
 
+	#[test]
+	fn test_set_text_cursor_end() {
+		let mut comp = TextInputComponent::new(
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			"",
+			"",
+			false,
+		);
+
+		comp.set_text_cursor_end(String::from("a\u{2764}"));
+		assert_eq!(comp.cursor_position, 4);
+		assert_eq!(comp.next_char_position(), None);
+	}
+
 	#[test]
 	fn text_cursor_initial_position() {
 		let mut comp = TextInputComponent::new(
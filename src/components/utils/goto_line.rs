@@ -0,0 +1,76 @@
+/// digits typed ahead of a movement key, used as a line number to
+/// jump to (or a count of lines to move by)
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct GotoLine {
+	digits: String,
+}
+
+impl GotoLine {
+	/// buffers `c`, returns false (and buffers nothing) for
+	/// anything but a digit
+	pub fn push(&mut self, c: char) -> bool {
+		if c.is_ascii_digit() {
+			self.digits.push(c);
+			true
+		} else {
+			false
+		}
+	}
+
+	///
+	pub fn is_empty(&self) -> bool {
+		self.digits.is_empty()
+	}
+
+	/// the digits typed so far
+	pub fn as_str(&self) -> &str {
+		&self.digits
+	}
+
+	///
+	pub fn clear(&mut self) {
+		self.digits.clear();
+	}
+
+	/// parses and clears the buffer, `None` if it was empty or the
+	/// number does not fit into a `usize`
+	pub fn take(&mut self) -> Option<usize> {
+		std::mem::take(&mut self.digits).parse().ok()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::GotoLine;
+
+	#[test]
+	fn test_buffers_digits_only() {
+		let mut goto = GotoLine::default();
+		assert!(goto.push('4'));
+		assert!(goto.push('2'));
+		assert!(!goto.push('G'));
+		assert_eq!(goto.as_str(), "42");
+	}
+
+	#[test]
+	fn test_take_parses_and_clears() {
+		let mut goto = GotoLine::default();
+		assert_eq!(goto.take(), None);
+
+		goto.push('0');
+		goto.push('7');
+		assert_eq!(goto.take(), Some(7));
+		assert!(goto.is_empty());
+		assert_eq!(goto.take(), None);
+	}
+
+	#[test]
+	fn test_take_overflow() {
+		let mut goto = GotoLine::default();
+		for _ in 0..30 {
+			goto.push('9');
+		}
+		assert_eq!(goto.take(), None);
+		assert!(goto.is_empty());
+	}
+}
@@ -0,0 +1,96 @@
+//! selection handling shared by the simple popup lists
+
+use crate::{
+	components::ScrollType,
+	keys::{key_match, SharedKeyConfig},
+};
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use std::convert::TryFrom;
+
+/// maps the list navigation keys to the scroll they request
+pub fn list_scroll_type(
+	key: &KeyEvent,
+	key_config: &SharedKeyConfig,
+) -> Option<ScrollType> {
+	if key_match(key, key_config.keys.move_down) {
+		Some(ScrollType::Down)
+	} else if key_match(key, key_config.keys.move_up) {
+		Some(ScrollType::Up)
+	} else if key_match(key, key_config.keys.page_down) {
+		Some(ScrollType::PageDown)
+	} else if key_match(key, key_config.keys.page_up) {
+		Some(ScrollType::PageUp)
+	} else if key_match(key, key_config.keys.home) {
+		Some(ScrollType::Home)
+	} else if key_match(key, key_config.keys.end) {
+		Some(ScrollType::End)
+	} else {
+		None
+	}
+}
+
+/// moves `selection` in a list of `count` entries showing `page`
+/// entries at once
+pub fn move_list_selection(
+	selection: u16,
+	scroll: ScrollType,
+	page: u16,
+	count: usize,
+) -> Result<u16> {
+	let new_selection = match scroll {
+		ScrollType::Down => selection.saturating_add(1),
+		ScrollType::Up => selection.saturating_sub(1),
+		ScrollType::PageDown => selection.saturating_add(page),
+		ScrollType::PageUp => selection.saturating_sub(page),
+		ScrollType::Home => 0,
+		ScrollType::End => u16::MAX,
+	};
+
+	clamp_list_selection(new_selection, count)
+}
+
+/// keeps `selection` inside a list of `count` entries
+pub fn clamp_list_selection(
+	selection: u16,
+	count: usize,
+) -> Result<u16> {
+	let count = u16::try_from(count)?;
+
+	Ok(selection.min(count.saturating_sub(1)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_move_list_selection() {
+		assert_eq!(
+			move_list_selection(1, ScrollType::Down, 5, 3).unwrap(),
+			2
+		);
+		assert_eq!(
+			move_list_selection(2, ScrollType::Down, 5, 3).unwrap(),
+			2
+		);
+		assert_eq!(
+			move_list_selection(1, ScrollType::Up, 5, 3).unwrap(),
+			0
+		);
+		assert_eq!(
+			move_list_selection(0, ScrollType::PageDown, 5, 3)
+				.unwrap(),
+			2
+		);
+		assert_eq!(
+			move_list_selection(0, ScrollType::End, 5, 3).unwrap(),
+			2
+		);
+		assert_eq!(
+			move_list_selection(0, ScrollType::End, 5, 0).unwrap(),
+			0
+		);
+	}
+}
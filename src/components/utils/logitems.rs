@@ -14,6 +14,7 @@ pub struct LogEntry {
 	pub time: DateTime<Local>,
 	//TODO: use tinyvec here
 	pub author: BoxStr,
+	pub committer: Option<BoxStr>,
 	pub msg: BoxStr,
 	//TODO: use tinyvec here
 	pub hash_short: BoxStr,
@@ -47,6 +48,7 @@ impl From<CommitInfo> for LogEntry {
 
 		Self {
 			author: author.into(),
+			committer: c.committer.map(Into::into),
 			msg: msg.into(),
 			time,
 			hash_short,
@@ -82,7 +84,8 @@ pub struct ItemBatch {
 }
 
 impl ItemBatch {
-	fn last_idx(&self) -> usize {
+	/// absolute index one past the last loaded item
+	pub fn last_idx(&self) -> usize {
 		self.index_offset + self.items.len()
 	}
 
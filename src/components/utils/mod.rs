@@ -4,9 +4,12 @@ use unicode_width::UnicodeWidthStr;
 #[cfg(feature = "ghemoji")]
 pub mod emoji;
 pub mod filetree;
+pub mod goto_line;
+pub mod list_selection;
 pub mod logitems;
 pub mod scroll_horizontal;
 pub mod scroll_vertical;
+pub mod search_pattern;
 pub mod statustree;
 
 /// macro to simplify running code that might return Err.
@@ -42,6 +45,36 @@ pub fn time_to_string(secs: i64, short: bool) -> String {
 	.to_string()
 }
 
+/// compact age of unix time `secs` relative to `now`, like `3d ago`
+pub fn time_to_age_string(secs: i64, now: i64) -> String {
+	const MINUTE: i64 = 60;
+	const HOUR: i64 = 60 * MINUTE;
+	const DAY: i64 = 24 * HOUR;
+	const WEEK: i64 = 7 * DAY;
+	const MONTH: i64 = 30 * DAY;
+	const YEAR: i64 = 365 * DAY;
+
+	let age = now.saturating_sub(secs).max(0);
+
+	let (value, unit) = if age < MINUTE {
+		return String::from("now");
+	} else if age < HOUR {
+		(age / MINUTE, "m")
+	} else if age < DAY {
+		(age / HOUR, "h")
+	} else if age < WEEK {
+		(age / DAY, "d")
+	} else if age < MONTH {
+		(age / WEEK, "w")
+	} else if age < YEAR {
+		(age / MONTH, "mo")
+	} else {
+		(age / YEAR, "y")
+	};
+
+	format!("{value}{unit} ago")
+}
+
 #[inline]
 pub fn string_width_align(s: &str, width: usize) -> String {
 	static POSTFIX: &str = "..";
@@ -60,7 +93,51 @@ pub fn string_width_align(s: &str, width: usize) -> String {
 	}
 }
 
+/// `0` has no digits
+pub const fn number_of_digits(number: usize) -> usize {
+	let mut rest = number;
+	let mut result = 0;
+
+	while rest > 0 {
+		rest /= 10;
+		result += 1;
+	}
+
+	result
+}
+
 #[inline]
 fn find_truncate_point(s: &str, chars: usize) -> usize {
 	s.chars().take(chars).map(char::len_utf8).sum()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{number_of_digits, time_to_age_string};
+
+	#[test]
+	fn test_time_to_age_string() {
+		const NOW: i64 = 1_700_000_000;
+		let age = |secs: i64| time_to_age_string(NOW - secs, NOW);
+
+		assert_eq!(age(59), "now");
+		assert_eq!(age(60), "1m ago");
+		assert_eq!(age(3 * 3600 + 59), "3h ago");
+		assert_eq!(age(6 * 86400), "6d ago");
+		assert_eq!(age(7 * 86400), "1w ago");
+		assert_eq!(age(60 * 86400), "2mo ago");
+		assert_eq!(age(2 * 365 * 86400), "2y ago");
+
+		// commits dated in the future are just new
+		assert_eq!(age(-3600), "now");
+	}
+
+	#[test]
+	fn test_number_of_digits() {
+		assert_eq!(number_of_digits(0), 0);
+		assert_eq!(number_of_digits(9), 1);
+		assert_eq!(number_of_digits(10), 2);
+		assert_eq!(number_of_digits(999), 3);
+		assert_eq!(number_of_digits(1000), 4);
+	}
+}
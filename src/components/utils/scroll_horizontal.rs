@@ -28,6 +28,26 @@ impl HorizontalScroll {
 		self.right.set(0);
 	}
 
+	pub fn set_right(&self, right: usize) {
+		self.right.set(right);
+	}
+
+	/// scrolls just enough to make columns `start..end` visible,
+	/// preferring `start` if the range does not fit
+	pub fn scroll_into_view(
+		&self,
+		start: usize,
+		end: usize,
+		visual_width: usize,
+	) {
+		self.right.set(calc_scroll_into_view(
+			self.get_right(),
+			visual_width,
+			start,
+			end,
+		));
+	}
+
 	pub fn move_right(
 		&self,
 		move_type: HorizontalScrollType,
@@ -123,6 +143,21 @@ const fn calc_scroll_right(
 	}
 }
 
+const fn calc_scroll_into_view(
+	current_right: usize,
+	width: usize,
+	start: usize,
+	end: usize,
+) -> usize {
+	if start < current_right || end.saturating_sub(start) > width {
+		start
+	} else if end > current_right + width {
+		end - width
+	} else {
+		current_right
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -137,4 +172,16 @@ mod tests {
 	fn test_scroll_zero_width() {
 		assert_eq!(calc_scroll_right(4, 0, 4, 3), 0);
 	}
+
+	#[test]
+	fn test_scroll_into_view() {
+		// already visible
+		assert_eq!(calc_scroll_into_view(0, 10, 2, 5), 0);
+		// right of view
+		assert_eq!(calc_scroll_into_view(0, 10, 12, 15), 5);
+		// left of view
+		assert_eq!(calc_scroll_into_view(20, 10, 2, 5), 2);
+		// wider than view
+		assert_eq!(calc_scroll_into_view(0, 4, 12, 20), 12);
+	}
 }
@@ -28,6 +28,11 @@ impl VerticalScroll {
 		self.top.set(0);
 	}
 
+	/// clamped into view by the next `update`
+	pub fn set_top(&self, top: usize) {
+		self.top.set(top);
+	}
+
 	pub fn move_top(&self, move_type: ScrollType) -> bool {
 		let old = self.top.get();
 		let max = self.max_top.get();
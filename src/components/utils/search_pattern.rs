@@ -0,0 +1,92 @@
+use regex::{Regex, RegexBuilder};
+
+/// needle of a text search, compiled once to match many lines.
+/// smart case ignores case unless the needle has an uppercase char
+#[derive(Debug, Clone)]
+pub enum SearchPattern {
+	Text { needle: String, ignore_case: bool },
+	Regex(Regex),
+}
+
+impl SearchPattern {
+	/// fails on an invalid regex in `regex` mode
+	pub fn new(
+		needle: &str,
+		smart_case: bool,
+		regex: bool,
+	) -> Result<Self, regex::Error> {
+		let ignore_case =
+			smart_case && !needle.chars().any(char::is_uppercase);
+
+		Ok(if regex {
+			Self::Regex(
+				RegexBuilder::new(needle)
+					.case_insensitive(ignore_case)
+					.build()?,
+			)
+		} else if ignore_case {
+			Self::Text {
+				needle: needle.to_lowercase(),
+				ignore_case,
+			}
+		} else {
+			Self::Text {
+				needle: needle.to_string(),
+				ignore_case,
+			}
+		})
+	}
+
+	/// byte range of the first match in `line`
+	pub fn find(&self, line: &str) -> Option<(usize, usize)> {
+		match self {
+			Self::Regex(regex) => {
+				regex.find(line).map(|m| (m.start(), m.end()))
+			}
+			Self::Text {
+				needle,
+				ignore_case: true,
+			} => line
+				.to_lowercase()
+				.find(needle.as_str())
+				.map(|idx| (idx, idx + needle.len())),
+			Self::Text { needle, .. } => line
+				.find(needle.as_str())
+				.map(|idx| (idx, idx + needle.len())),
+		}
+	}
+
+	///
+	pub fn is_match(&self, line: &str) -> bool {
+		self.find(line).is_some()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_smart_case() {
+		let pattern = SearchPattern::new("foo", true, false).unwrap();
+		assert_eq!(pattern.find("a Foo"), Some((2, 5)));
+
+		let pattern = SearchPattern::new("Foo", true, false).unwrap();
+		assert!(!pattern.is_match("a foo"));
+
+		let pattern =
+			SearchPattern::new("foo", false, false).unwrap();
+		assert!(!pattern.is_match("a Foo"));
+	}
+
+	#[test]
+	fn test_regex() {
+		let pattern = SearchPattern::new("f.o", true, true).unwrap();
+		assert_eq!(pattern.find("a FOO"), Some((2, 5)));
+
+		let pattern = SearchPattern::new("F.o", true, true).unwrap();
+		assert!(!pattern.is_match("a foo"));
+
+		assert!(SearchPattern::new("f(o", true, true).is_err());
+	}
+}
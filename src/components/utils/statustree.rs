@@ -3,7 +3,7 @@ use super::filetree::{
 };
 use anyhow::Result;
 use asyncgit::StatusItem;
-use std::{cmp, collections::BTreeSet};
+use std::{cmp, collections::BTreeSet, path::Path};
 
 //TODO: use new `filetreelist` crate
 
@@ -400,6 +400,23 @@ impl StatusTree {
 			.any(cb)
 	}
 
+	/// all files at `path`, which is either a file or a folder
+	pub fn files_at_path(&self, path: &str) -> Vec<StatusItem> {
+		self.tree
+			.items()
+			.iter()
+			.filter_map(|i| match &i.kind {
+				FileTreeItemKind::File(item)
+					if Path::new(&i.info.full_path)
+						.starts_with(path) =>
+				{
+					Some(item.clone())
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
 	fn update_visibility(
 		&mut self,
 		prefix: Option<&str>,
@@ -919,4 +936,28 @@ mod tests {
 		assert!(res.move_selection(MoveSelection::Left)); // jump to 0
 		assert_eq!(res.selection, Some(0));
 	}
+
+	#[test]
+	fn test_files_at_path() {
+		let mut res = StatusTree::default();
+		res.update(&string_vec_to_status(&[
+			"a/b/c", //
+			"a/b/d", //
+			"a/bb",  //
+			"e",     //
+		]))
+		.unwrap();
+
+		let paths = |path: &str| {
+			res.files_at_path(path)
+				.into_iter()
+				.map(|item| item.path)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(paths("a/b"), vec!["a/b/c", "a/b/d"]);
+		assert_eq!(paths("a"), vec!["a/b/c", "a/b/d", "a/bb"]);
+		assert_eq!(paths("e"), vec!["e"]);
+		assert!(paths("x").is_empty());
+	}
 }
@@ -0,0 +1,351 @@
+use super::{
+	utils::{
+		list_selection::{
+			clamp_list_selection, list_scroll_type,
+			move_list_selection,
+		},
+		scroll_vertical::VerticalScroll,
+	},
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ScrollType,
+};
+use crate::{
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef, WorktreeInfo};
+use crossterm::event::Event;
+use ratatui::{
+	backend::Backend,
+	layout::{Alignment, Margin, Rect},
+	text::{Span, Spans, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::{cell::Cell, convert::TryInto};
+use ui::style::SharedTheme;
+use unicode_truncate::UnicodeTruncateStr;
+
+///
+pub struct WorktreesListComponent {
+	repo: RepoPathRef,
+	queue: Queue,
+	worktrees: Vec<WorktreeInfo>,
+	visible: bool,
+	current_height: Cell<u16>,
+	selection: u16,
+	scroll: VerticalScroll,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for WorktreesListComponent {
+	fn draw<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		rect: Rect,
+	) -> Result<()> {
+		if self.is_visible() {
+			const PERCENT_SIZE: Size = Size::new(60, 40);
+			const MIN_SIZE: Size = Size::new(50, 10);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				rect,
+			);
+			let area = ui::rect_inside(MIN_SIZE, rect.into(), area);
+			let area = area.intersection(rect);
+
+			f.render_widget(Clear, area);
+
+			f.render_widget(
+				Block::default()
+					.title(strings::POPUP_TITLE_WORKTREES)
+					.border_type(BorderType::Thick)
+					.borders(Borders::ALL),
+				area,
+			);
+
+			let area = area.inner(&Margin {
+				vertical: 1,
+				horizontal: 1,
+			});
+
+			self.draw_list(f, area)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for WorktreesListComponent {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_worktree(&self.key_config),
+				self.can_open_selected(),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if !self.visible {
+			return Ok(EventState::NotConsumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide();
+			} else if let Some(scroll) =
+				list_scroll_type(e, &self.key_config)
+			{
+				return self.move_selection(scroll).map(Into::into);
+			} else if key_match(e, self.key_config.keys.enter) {
+				if self.can_open_selected() {
+					if let Some(worktree) = self.selected_entry() {
+						self.queue.push(
+							InternalEvent::OpenWorktree {
+								path: worktree.path.clone(),
+							},
+						);
+					}
+				}
+			} else if key_match(
+				e,
+				self.key_config.keys.cmd_bar_toggle,
+			) {
+				//do not consume if its the more key
+				return Ok(EventState::NotConsumed);
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+impl WorktreesListComponent {
+	pub fn new(
+		repo: RepoPathRef,
+		queue: &Queue,
+		theme: SharedTheme,
+		key_config: SharedKeyConfig,
+	) -> Self {
+		Self {
+			worktrees: Vec::new(),
+			scroll: VerticalScroll::new(),
+			queue: queue.clone(),
+			selection: 0,
+			visible: false,
+			theme,
+			key_config,
+			current_height: Cell::new(0),
+			repo,
+		}
+	}
+
+	/// opens the popup with the current worktree selected
+	pub fn open(&mut self) -> Result<()> {
+		self.show()?;
+		self.worktrees = sync::get_worktrees(&self.repo.borrow())?;
+
+		let current = self
+			.worktrees
+			.iter()
+			.position(|w| w.is_current)
+			.unwrap_or_default();
+		self.set_selection(current.try_into()?)?;
+
+		Ok(())
+	}
+
+	fn selected_entry(&self) -> Option<&WorktreeInfo> {
+		self.worktrees.get(self.selection as usize)
+	}
+
+	fn can_open_selected(&self) -> bool {
+		self.selected_entry()
+			.map_or(false, |w| w.can_open() && !w.is_current)
+	}
+
+	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
+		self.selection = move_list_selection(
+			self.selection,
+			scroll,
+			self.current_height.get(),
+			self.worktrees.len(),
+		)?;
+
+		Ok(true)
+	}
+
+	fn set_selection(&mut self, selection: u16) -> Result<()> {
+		self.selection =
+			clamp_list_selection(selection, self.worktrees.len())?;
+
+		Ok(())
+	}
+
+	/// branch name, or what is checked out instead
+	fn worktree_name(worktree: &WorktreeInfo) -> String {
+		if worktree.is_bare {
+			String::from("(bare)")
+		} else if let Some(branch) = &worktree.branch {
+			branch.clone()
+		} else {
+			format!(
+				"({})",
+				worktree.head.unwrap_or_default().get_short_string()
+			)
+		}
+	}
+
+	fn get_text(&self, width_available: u16, height: usize) -> Text {
+		const THREE_DOTS: &str = "...";
+		const CURRENT_MARKER: &str = "* ";
+
+		let names = self
+			.worktrees
+			.iter()
+			.map(Self::worktree_name)
+			.collect::<Vec<_>>();
+
+		let name_length = names
+			.iter()
+			.map(String::len)
+			.max()
+			.unwrap_or_default()
+			.min(usize::from(width_available / 3));
+		let path_length = usize::from(width_available)
+			.saturating_sub(CURRENT_MARKER.len())
+			.saturating_sub(name_length)
+			.saturating_sub(1);
+
+		let mut txt = Vec::with_capacity(height);
+
+		for (i, (worktree, name)) in self
+			.worktrees
+			.iter()
+			.zip(names)
+			.skip(self.scroll.get_top())
+			.take(height)
+			.enumerate()
+		{
+			let mut name = name;
+			if name.len() > name_length {
+				name.unicode_truncate(
+					name_length.saturating_sub(THREE_DOTS.len()),
+				);
+				name += THREE_DOTS;
+			}
+
+			let mut path =
+				worktree.path.to_string_lossy().to_string();
+			if worktree.locked {
+				path += strings::WORKTREE_LOCKED;
+			}
+			if worktree.prunable {
+				path += strings::WORKTREE_PRUNABLE;
+			}
+			if path.len() > path_length {
+				path.unicode_truncate(
+					path_length.saturating_sub(THREE_DOTS.len()),
+				);
+				path += THREE_DOTS;
+			}
+
+			let selected = (self.selection as usize
+				- self.scroll.get_top())
+				== i;
+
+			txt.push(Spans::from(vec![
+				Span::styled(
+					if worktree.is_current {
+						CURRENT_MARKER
+					} else {
+						"  "
+					},
+					self.theme.text(true, selected),
+				),
+				Span::styled(
+					format!("{name:name_length$} "),
+					self.theme.text(worktree.can_open(), selected),
+				),
+				Span::styled(
+					format!("{path:path_length$}"),
+					self.theme.text(false, selected),
+				),
+			]));
+		}
+
+		Text::from(txt)
+	}
+
+	fn draw_list<B: Backend>(
+		&self,
+		f: &mut Frame<B>,
+		r: Rect,
+	) -> Result<()> {
+		let height_in_lines = r.height as usize;
+		self.current_height.set(height_in_lines.try_into()?);
+
+		self.scroll.update(
+			self.selection as usize,
+			self.worktrees.len(),
+			height_in_lines,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.get_text(r.width, height_in_lines))
+				.alignment(Alignment::Left),
+			r,
+		);
+
+		let mut r = r;
+		r.height += 2;
+		r.y = r.y.saturating_sub(1);
+
+		self.scroll.draw(f, r, &self.theme);
+
+		Ok(())
+	}
+}
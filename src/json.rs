@@ -0,0 +1,305 @@
+//! `gitui json`: answers a few queries on stdout so scripts do not
+//! have to drive the UI
+
+use crate::args::JsonQuery;
+use anyhow::Result;
+use asyncgit::{
+	filter_compose_and,
+	sync::{
+		self, filter_by_path,
+		status::{get_status, StatusType},
+		BranchDetails, BranchInfo, Commit, CommitId, CommitInfo,
+		LogWalker, LogWalkerFilter, RepoPath, Repository,
+	},
+	StatusItem, StatusItemType,
+};
+use serde::Serialize;
+use std::{
+	io::{self, Write},
+	sync::Arc,
+};
+
+#[derive(Serialize)]
+struct JsonBranch {
+	name: String,
+	reference: String,
+	commit: String,
+	summary: String,
+	author: String,
+	time: i64,
+	/// checked out, always `false` for remote branches
+	head: bool,
+	upstream: Option<String>,
+}
+
+impl From<BranchInfo> for JsonBranch {
+	fn from(b: BranchInfo) -> Self {
+		let (head, upstream) = match b.details {
+			BranchDetails::Local(l) => {
+				(l.is_head, l.upstream.map(|u| u.reference))
+			}
+			BranchDetails::Remote(_) => (false, None),
+		};
+
+		Self {
+			name: b.name,
+			reference: b.reference,
+			commit: b.top_commit.to_string(),
+			summary: b.top_commit_message,
+			author: b.top_commit_author,
+			time: b.top_commit_time,
+			head,
+			upstream,
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct JsonCommit {
+	id: String,
+	author: String,
+	email: String,
+	committer: Option<String>,
+	time: i64,
+	summary: String,
+}
+
+impl From<CommitInfo> for JsonCommit {
+	fn from(c: CommitInfo) -> Self {
+		Self {
+			id: c.id.to_string(),
+			author: c.author,
+			email: c.email,
+			committer: c.committer,
+			time: c.time,
+			summary: c.message,
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct JsonFile {
+	path: String,
+	status: &'static str,
+}
+
+impl From<StatusItem> for JsonFile {
+	fn from(item: StatusItem) -> Self {
+		Self {
+			path: item.path,
+			status: status_name(item.status),
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct JsonStatus {
+	workdir: Vec<JsonFile>,
+	stage: Vec<JsonFile>,
+}
+
+const fn status_name(status: StatusItemType) -> &'static str {
+	match status {
+		StatusItemType::New => "new",
+		StatusItemType::Modified => "modified",
+		StatusItemType::Deleted => "deleted",
+		StatusItemType::Renamed => "renamed",
+		StatusItemType::Typechange => "typechange",
+		StatusItemType::Conflicted => "conflicted",
+	}
+}
+
+/// prints the answer to `query` as pretty printed JSON
+pub fn run(repo_path: &RepoPath, query: &JsonQuery) -> Result<()> {
+	let mut out = io::stdout().lock();
+
+	match query {
+		JsonQuery::Branches { remote } => {
+			let branches: Vec<JsonBranch> =
+				sync::get_branches_info(repo_path, !remote)?
+					.into_iter()
+					.map(JsonBranch::from)
+					.collect();
+			serde_json::to_writer_pretty(&mut out, &branches)?;
+		}
+		JsonQuery::Log {
+			max_count,
+			path,
+			author,
+			grep,
+		} => {
+			let filter = log_filter(
+				path.clone(),
+				author.clone(),
+				grep.clone(),
+			);
+			let commits = log_commits(repo_path, filter, *max_count)?;
+			serde_json::to_writer_pretty(&mut out, &commits)?;
+		}
+		JsonQuery::Status => {
+			let files = |status_type| -> Result<Vec<JsonFile>> {
+				Ok(get_status(repo_path, status_type, None)?
+					.into_iter()
+					.map(JsonFile::from)
+					.collect())
+			};
+			let status = JsonStatus {
+				workdir: files(StatusType::WorkingDir)?,
+				stage: files(StatusType::Stage)?,
+			};
+			serde_json::to_writer_pretty(&mut out, &status)?;
+		}
+	}
+
+	writeln!(out)?;
+
+	Ok(())
+}
+
+/// up to `max_count` commits reachable from HEAD passing `filter`,
+/// `0` lists all
+fn log_commits(
+	repo_path: &RepoPath,
+	filter: Option<LogWalkerFilter>,
+	max_count: usize,
+) -> Result<Vec<JsonCommit>> {
+	let ids = LogWalker::new_with_start_by_path(
+		repo_path,
+		None,
+		|walker| walker.filter_with_limit(filter, max_count),
+	)?;
+
+	Ok(sync::get_commits_info(repo_path, &ids, usize::MAX)?
+		.into_iter()
+		.map(JsonCommit::from)
+		.collect())
+}
+
+fn log_filter(
+	path: Option<String>,
+	author: Option<String>,
+	grep: Option<String>,
+) -> Option<LogWalkerFilter> {
+	let path = path.map(|p| filter_by_path(p, false));
+	let author = author.map(|a| {
+		text_filter(&a, |c| {
+			c.author().name().unwrap_or_default().to_string()
+		})
+	});
+	let grep = grep.map(|g| {
+		text_filter(&g, |c| c.message().unwrap_or_default().into())
+	});
+
+	if path.is_none() && author.is_none() && grep.is_none() {
+		None
+	} else {
+		Some(filter_compose_and!(path, author, grep))
+	}
+}
+
+/// matches commits where `field` contains `needle`, ignoring case
+fn text_filter(
+	needle: &str,
+	field: fn(&Commit) -> String,
+) -> LogWalkerFilter {
+	let needle = needle.to_lowercase();
+	Arc::new(Box::new(
+		move |_repo: &Repository,
+		      _commit_id: &CommitId,
+		      commit: &Commit|
+		      -> asyncgit::Result<bool> {
+			Ok(field(commit).to_lowercase().contains(&needle))
+		},
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{log_commits, log_filter, JsonFile, JsonStatus};
+	use crate::test_utils::{git, temp_repo};
+	use asyncgit::{sync::RepoPath, StatusItem, StatusItemType};
+
+	/// summaries of the commits `gitui json log` lists
+	fn log(
+		repo_path: &RepoPath,
+		author: Option<&str>,
+		grep: Option<&str>,
+		max_count: usize,
+	) -> Vec<String> {
+		let filter = log_filter(
+			None,
+			author.map(String::from),
+			grep.map(String::from),
+		);
+		log_commits(repo_path, filter, max_count)
+			.unwrap()
+			.into_iter()
+			.map(|c| c.summary)
+			.collect()
+	}
+
+	#[test]
+	fn test_log_filters() {
+		let (td, repo_path) = temp_repo();
+		for (author, msg) in [
+			("Alice <alice@example.com>", "fix parser"),
+			("Bob <bob@example.com>", "Fix typo"),
+			("alice two <two@example.com>", "add feature"),
+		] {
+			git(
+				td.path(),
+				&[
+					"commit",
+					"-q",
+					"--allow-empty",
+					"--author",
+					author,
+					"-m",
+					msg,
+				],
+			);
+		}
+
+		assert_eq!(
+			log(&repo_path, None, None, 0),
+			["add feature", "Fix typo", "fix parser"]
+		);
+		assert_eq!(
+			log(&repo_path, Some("ALICE"), None, 0),
+			["add feature", "fix parser"]
+		);
+		assert_eq!(
+			log(&repo_path, None, Some("fix"), 0),
+			["Fix typo", "fix parser"]
+		);
+		// both have to match
+		assert_eq!(
+			log(&repo_path, Some("alice"), Some("fix"), 0),
+			["fix parser"]
+		);
+		assert!(log(&repo_path, Some("carol"), None, 0).is_empty());
+
+		// the limit counts matching commits only
+		assert_eq!(log(&repo_path, None, None, 1), ["add feature"]);
+		assert_eq!(
+			log(&repo_path, None, Some("fix"), 1),
+			["Fix typo"]
+		);
+	}
+
+	#[test]
+	fn test_status_json() {
+		let status = JsonStatus {
+			workdir: vec![JsonFile::from(StatusItem {
+				path: String::from("a.rs"),
+				status: StatusItemType::New,
+			})],
+			stage: Vec::new(),
+		};
+
+		assert_eq!(
+			serde_json::to_string(&status).unwrap(),
+			r#"{"workdir":[{"path":"a.rs","status":"new"}],"stage":[]}"#
+		);
+	}
+}
@@ -56,7 +56,24 @@ impl KeyConfig {
 	}
 
 	pub fn get_hint(&self, ev: GituiKeyEvent) -> String {
-		match ev.code {
+		let hint = self.get_key_hint(ev.code, ev.modifiers);
+		match ev.then {
+			Some(then) => {
+				format!(
+					"{hint}{}",
+					self.get_key_hint(then.code, then.modifiers)
+				)
+			}
+			None => hint,
+		}
+	}
+
+	fn get_key_hint(
+		&self,
+		code: KeyCode,
+		modifiers: KeyModifiers,
+	) -> String {
+		match code {
 			KeyCode::Down
 			| KeyCode::Up
 			| KeyCode::Right
@@ -74,28 +91,18 @@ impl KeyConfig {
 			| KeyCode::Esc => {
 				format!(
 					"{}{}",
-					self.get_modifier_hint(ev.modifiers),
-					self.get_key_symbol(ev.code)
+					self.get_modifier_hint(modifiers),
+					self.get_key_symbol(code)
 				)
 			}
 			KeyCode::Char(' ') => String::from(symbol::SPACE),
 			KeyCode::Char(c) => {
-				format!(
-					"{}{}",
-					self.get_modifier_hint(ev.modifiers),
-					c
-				)
+				format!("{}{}", self.get_modifier_hint(modifiers), c)
 			}
 			KeyCode::F(u) => {
-				format!(
-					"{}F{}",
-					self.get_modifier_hint(ev.modifiers),
-					u
-				)
-			}
-			KeyCode::Null => {
-				self.get_modifier_hint(ev.modifiers).into()
+				format!("{}F{}", self.get_modifier_hint(modifiers), u)
 			}
+			KeyCode::Null => self.get_modifier_hint(modifiers).into(),
 			_ => String::new(),
 		}
 	}
@@ -124,4 +131,17 @@ mod tests {
 		));
 		assert_eq!(h, "^c");
 	}
+
+	#[test]
+	fn test_get_hint_chord() {
+		let config = KeyConfig::default();
+		let h = config.get_hint(
+			GituiKeyEvent::new(
+				KeyCode::Char('g'),
+				KeyModifiers::empty(),
+			)
+			.followed_by(KeyCode::Char('g'), KeyModifiers::empty()),
+		);
+		assert_eq!(h, "gg");
+	}
 }
@@ -1,30 +1,143 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+	KeyCode, KeyEvent, KeyEventState, KeyModifiers,
+};
 use serde::{Deserialize, Serialize};
-use std::{fs::File, path::PathBuf};
+use std::{
+	fs::File,
+	path::PathBuf,
+	time::{Duration, Instant},
+};
 use struct_patch::traits::Patch as PatchTrait;
 use struct_patch::Patch;
 
+/// how long the second key of a chord may take to follow the first
+const CHORD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// set by `PendingChord` on the key completing a chord. terminals
+/// only report a key state once keyboard enhancements are pushed,
+/// which gitui never does
+const CHORD_COMPLETED: KeyEventState = KeyEventState::KEYPAD;
+
+/// second key of a chord binding
+#[derive(
+	Debug,
+	PartialOrd,
+	PartialEq,
+	Eq,
+	Clone,
+	Copy,
+	Serialize,
+	Deserialize,
+)]
+pub struct ChordKey {
+	pub code: KeyCode,
+	pub modifiers: KeyModifiers,
+}
+
+impl From<&KeyEvent> for ChordKey {
+	fn from(other: &KeyEvent) -> Self {
+		Self {
+			code: other.code,
+			modifiers: other.modifiers,
+		}
+	}
+}
+
 #[derive(Debug, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub struct GituiKeyEvent {
 	pub code: KeyCode,
 	pub modifiers: KeyModifiers,
+	/// turns the binding into a two key chord (like vim's `gg`)
+	/// completed by this key
+	#[serde(default)]
+	pub then: Option<ChordKey>,
 }
 
 impl GituiKeyEvent {
 	pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
-		Self { code, modifiers }
+		Self {
+			code,
+			modifiers,
+			then: None,
+		}
+	}
+
+	const fn first(self) -> ChordKey {
+		ChordKey {
+			code: self.code,
+			modifiers: self.modifiers,
+		}
+	}
+
+	/// chord of this key followed by `code` + `modifiers`
+	pub const fn followed_by(
+		self,
+		code: KeyCode,
+		modifiers: KeyModifiers,
+	) -> Self {
+		Self {
+			then: Some(ChordKey { code, modifiers }),
+			..self
+		}
 	}
 }
 
+/// a chord matches its second key once `PendingChord` marked it as
+/// completed. single key bindings match right away, a chord never
+/// delays them.
 pub fn key_match(ev: &KeyEvent, binding: GituiKeyEvent) -> bool {
-	ev.code == binding.code && ev.modifiers == binding.modifiers
+	let key = ChordKey::from(ev);
+	binding.then.map_or_else(
+		|| key == binding.first(),
+		|then| ev.state.contains(CHORD_COMPLETED) && key == then,
+	)
+}
+
+/// first key of a chord waiting for its second one
+pub struct PendingChord {
+	chords: Vec<GituiKeyEvent>,
+	pending: Option<(ChordKey, Instant)>,
+}
+
+impl PendingChord {
+	///
+	pub fn new(keys: &KeysList) -> Self {
+		Self {
+			chords: keys.chords(),
+			pending: None,
+		}
+	}
+
+	/// marks `ev` if it completes a chord within `CHORD_TIMEOUT`,
+	/// otherwise remembers it as the possible start of one
+	pub fn resolve(&mut self, ev: KeyEvent) -> KeyEvent {
+		let key = ChordKey::from(&ev);
+		let completes =
+			self.pending.take().map_or(false, |(first, at)| {
+				at.elapsed() <= CHORD_TIMEOUT
+					&& self.chords.iter().any(|chord| {
+						chord.first() == first
+							&& chord.then == Some(key)
+					})
+			});
+
+		if completes {
+			KeyEvent {
+				state: ev.state | CHORD_COMPLETED,
+				..ev
+			}
+		} else {
+			self.pending = Some((key, Instant::now()));
+			ev
+		}
+	}
 }
 
 impl PartialEq for GituiKeyEvent {
 	fn eq(&self, other: &Self) -> bool {
 		let ev: KeyEvent = self.into();
-		let other: KeyEvent = other.into();
-		ev == other
+		let other_ev: KeyEvent = other.into();
+		ev == other_ev && self.then == other.then
 	}
 }
 
@@ -40,7 +153,7 @@ impl From<&KeyEvent> for GituiKeyEvent {
 	}
 }
 
-#[derive(Clone, Patch)]
+#[derive(Clone, Serialize, Patch)]
 #[patch_derive(Deserialize)]
 pub struct KeysList {
 	pub tab_status: GituiKeyEvent,
@@ -59,6 +172,7 @@ pub struct KeysList {
 	pub open_commit_editor: GituiKeyEvent,
 	pub open_help: GituiKeyEvent,
 	pub open_options: GituiKeyEvent,
+	pub toggle_status_header: GituiKeyEvent,
 	pub move_left: GituiKeyEvent,
 	pub move_right: GituiKeyEvent,
 	pub move_up: GituiKeyEvent,
@@ -75,13 +189,31 @@ pub struct KeysList {
 	pub shift_down: GituiKeyEvent,
 	pub enter: GituiKeyEvent,
 	pub blame: GituiKeyEvent,
+	pub blame_toggle_author: GituiKeyEvent,
 	pub file_history: GituiKeyEvent,
 	pub edit_file: GituiKeyEvent,
+	pub reveal_file: GituiKeyEvent,
+	pub copy_abs_path: GituiKeyEvent,
 	pub status_stage_all: GituiKeyEvent,
+	pub status_stage_pattern: GituiKeyEvent,
+	pub status_mark_file: GituiKeyEvent,
+	pub status_toggle_untracked: GituiKeyEvent,
 	pub status_reset_item: GituiKeyEvent,
+	pub status_restore_item: GituiKeyEvent,
 	pub status_ignore_file: GituiKeyEvent,
 	pub diff_stage_lines: GituiKeyEvent,
+	pub diff_stage_all_hunks: GituiKeyEvent,
 	pub diff_reset_lines: GituiKeyEvent,
+	pub diff_scroll_reset: GituiKeyEvent,
+	pub diff_conflict_next: GituiKeyEvent,
+	pub diff_conflict_prev: GituiKeyEvent,
+	pub diff_toggle_whitespace: GituiKeyEvent,
+	pub diff_toggle_line_numbers: GituiKeyEvent,
+	pub diff_toggle_show_whitespace: GituiKeyEvent,
+	pub diff_toggle_wrap: GituiKeyEvent,
+	pub diff_search_scope: GituiKeyEvent,
+	pub diff_search_regex: GituiKeyEvent,
+	pub diff_save_patch: GituiKeyEvent,
 	pub stashing_save: GituiKeyEvent,
 	pub stashing_toggle_untracked: GituiKeyEvent,
 	pub stashing_toggle_index: GituiKeyEvent,
@@ -90,15 +222,28 @@ pub struct KeysList {
 	pub stash_drop: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
+	pub log_edit_note: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
 	pub log_checkout_commit: GituiKeyEvent,
 	pub log_reset_comit: GituiKeyEvent,
 	pub log_reword_comit: GituiKeyEvent,
+	pub log_toggle_committer: GituiKeyEvent,
+	pub log_toggle_signature: GituiKeyEvent,
+	pub log_toggle_file_count: GituiKeyEvent,
+	pub log_toggle_local_branches: GituiKeyEvent,
+	pub log_toggle_remote_branches: GituiKeyEvent,
+	pub log_toggle_tags: GituiKeyEvent,
+	pub log_exclude_branch: GituiKeyEvent,
+	pub log_mark_range_start: GituiKeyEvent,
+	pub log_mark_range: GituiKeyEvent,
+	pub log_path_next: GituiKeyEvent,
+	pub log_path_prev: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
 	pub toggle_verify: GituiKeyEvent,
 	pub copy: GituiKeyEvent,
 	pub copy_hunk: GituiKeyEvent,
 	pub create_branch: GituiKeyEvent,
+	pub create_branch_from_selected: GituiKeyEvent,
 	pub rename_branch: GituiKeyEvent,
 	pub select_branch: GituiKeyEvent,
 	pub delete_branch: GituiKeyEvent,
@@ -106,9 +251,20 @@ pub struct KeysList {
 	pub rebase_branch: GituiKeyEvent,
 	pub rebase_abort: GituiKeyEvent,
 	pub rebase_interactive: GituiKeyEvent,
+	pub rebase_interactive_onto_marked: GituiKeyEvent,
 	pub rebase_skip: GituiKeyEvent,
 	pub rebase_continue: GituiKeyEvent,
 	pub rebase_fixup_marked: GituiKeyEvent,
+	pub rebase_autosquash_marked: GituiKeyEvent,
+	pub rebase_todo_pick: GituiKeyEvent,
+	pub rebase_todo_reword: GituiKeyEvent,
+	pub rebase_todo_edit: GituiKeyEvent,
+	pub rebase_todo_squash: GituiKeyEvent,
+	pub rebase_todo_fixup: GituiKeyEvent,
+	pub rebase_todo_drop: GituiKeyEvent,
+	pub rebase_todo_move_up: GituiKeyEvent,
+	pub rebase_todo_move_down: GituiKeyEvent,
+	pub commit_fixup: GituiKeyEvent,
 	pub compare_commits: GituiKeyEvent,
 	pub tags: GituiKeyEvent,
 	pub delete_tag: GituiKeyEvent,
@@ -118,6 +274,7 @@ pub struct KeysList {
 	pub open_file_tree: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
 	pub fetch: GituiKeyEvent,
+	pub fetch_pull_request: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
 	pub abort_merge: GituiKeyEvent,
 	pub undo_commit: GituiKeyEvent,
@@ -126,13 +283,28 @@ pub struct KeysList {
 	pub view_submodules: GituiKeyEvent,
 	pub view_submodule_parent: GituiKeyEvent,
 	pub update_submodule: GituiKeyEvent,
+	pub view_remotes: GituiKeyEvent,
+	pub view_worktrees: GituiKeyEvent,
+	pub view_reflog: GituiKeyEvent,
+	pub branch_toggle_details: GituiKeyEvent,
+	pub copy_permalink: GituiKeyEvent,
+	pub remote_add: GituiKeyEvent,
+	pub remote_rename: GituiKeyEvent,
+	pub remote_edit_url: GituiKeyEvent,
+	pub remote_delete: GituiKeyEvent,
 	pub commit_history_next: GituiKeyEvent,
+	pub inspect_child_commit: GituiKeyEvent,
+	pub inspect_diff_parent: GituiKeyEvent,
 	pub copy_clipboard_sha: GituiKeyEvent,
+	pub branch_copy_short_name: GituiKeyEvent,
 	pub copy_clipboard_email: GituiKeyEvent,
 	pub copy_clipboard_author: GituiKeyEvent,
+	pub copy_clipboard_date: GituiKeyEvent,
 	pub copy_clipboard_message: GituiKeyEvent,
 	pub copy_clipboard_summary: GituiKeyEvent,
+	pub copy_clipboard_changelog: GituiKeyEvent,
 	pub run_external_command: GituiKeyEvent,
+	pub run_external_command_marked: GituiKeyEvent,
 	pub start_search_forward_init: GituiKeyEvent,
 	pub start_search_backward_init: GituiKeyEvent,
 	pub search_filter_author: GituiKeyEvent,
@@ -142,6 +314,7 @@ pub struct KeysList {
 	pub search_prev: GituiKeyEvent,
 	pub open_suboptions: GituiKeyEvent,
 	pub filter_commits_init: GituiKeyEvent,
+	pub filter_branches_init: GituiKeyEvent,
 	pub assign_shortcut: GituiKeyEvent,
 	pub clear_shortcut: GituiKeyEvent,
 	pub clear_all_shortcut: GituiKeyEvent,
@@ -149,6 +322,8 @@ pub struct KeysList {
 	pub toggle_split: GituiKeyEvent,
 	pub cherrypick: GituiKeyEvent,
 	pub fuzzy_find: GituiKeyEvent,
+	pub grep_files: GituiKeyEvent,
+	pub file_find_preview: GituiKeyEvent,
 	pub generic_push: GituiKeyEvent,
 	pub generic_pop: GituiKeyEvent,
 }
@@ -173,6 +348,7 @@ impl Default for KeysList {
 			open_commit_editor: GituiKeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
 			open_help: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
 			open_options: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			toggle_status_header: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::ALT),
 			move_left: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::empty()),
 			move_right: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			tree_collapse_recursive: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::SHIFT),
@@ -189,13 +365,31 @@ impl Default for KeysList {
 			shift_down: GituiKeyEvent::new(KeyCode::Down,  KeyModifiers::SHIFT),
 			enter: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			blame: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			blame_toggle_author: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			file_history: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
 			edit_file: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			reveal_file: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::ALT),
+			copy_abs_path: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::ALT),
 			status_stage_all: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			status_stage_pattern: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::ALT),
+			status_mark_file: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
+			status_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			status_reset_item: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			status_restore_item: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
 			diff_reset_lines: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			diff_scroll_reset: GituiKeyEvent::new(KeyCode::Char('0'),  KeyModifiers::empty()),
+			diff_conflict_next: GituiKeyEvent::new(KeyCode::Char(']'),  KeyModifiers::empty()),
+			diff_conflict_prev: GituiKeyEvent::new(KeyCode::Char('['),  KeyModifiers::empty()),
+			diff_toggle_whitespace: GituiKeyEvent::new(KeyCode::Char('W'),  KeyModifiers::SHIFT),
+			diff_toggle_line_numbers: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			diff_toggle_show_whitespace: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::ALT),
+			diff_toggle_wrap: GituiKeyEvent::new(KeyCode::Char('z'),  KeyModifiers::ALT),
+			diff_search_scope: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::ALT),
+			diff_search_regex: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::ALT),
+			diff_save_patch: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::ALT),
 			status_ignore_file: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
 			diff_stage_lines: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			diff_stage_all_hunks: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			stashing_toggle_index: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
@@ -204,25 +398,49 @@ impl Default for KeysList {
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			log_edit_note: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::ALT),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
-			log_checkout_commit: GituiKeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT },
-			log_reset_comit: GituiKeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT },
-			log_reword_comit: GituiKeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty() },
+			log_checkout_commit: GituiKeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
+			log_reset_comit: GituiKeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT),
+			log_reword_comit: GituiKeyEvent::new(KeyCode::Char('r'), KeyModifiers::empty()),
+			log_toggle_committer: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			log_toggle_signature: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::ALT),
+			log_toggle_file_count: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::ALT),
+			log_toggle_local_branches: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::ALT),
+			log_toggle_remote_branches: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::ALT),
+			log_toggle_tags: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::ALT),
+			log_exclude_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::ALT),
+			log_mark_range_start: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
+			log_mark_range: GituiKeyEvent::new(KeyCode::Char('V'),  KeyModifiers::SHIFT),
+			log_path_next: GituiKeyEvent::new(KeyCode::Char(']'),  KeyModifiers::empty()),
+			log_path_prev: GituiKeyEvent::new(KeyCode::Char('['),  KeyModifiers::empty()),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
 			toggle_verify: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
 			copy: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::empty()),
 			copy_hunk: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
 			create_branch: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			create_branch_from_selected: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
 			rename_branch: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
 			select_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			delete_branch: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			merge_branch: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			rebase_branch: GituiKeyEvent::new(KeyCode::Char('R'),  KeyModifiers::SHIFT),
 			rebase_interactive: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
+			rebase_interactive_onto_marked: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
 			rebase_skip: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
 			rebase_continue: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
 			rebase_abort: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
 			rebase_fixup_marked: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
+			rebase_autosquash_marked: GituiKeyEvent::new(KeyCode::Char('Q'),  KeyModifiers::SHIFT),
+			rebase_todo_pick: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			rebase_todo_reword: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			rebase_todo_edit: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			rebase_todo_squash: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			rebase_todo_fixup: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			rebase_todo_drop: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			rebase_todo_move_up: GituiKeyEvent::new(KeyCode::Up,  KeyModifiers::ALT),
+			rebase_todo_move_down: GituiKeyEvent::new(KeyCode::Down,  KeyModifiers::ALT),
+			commit_fixup: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::CONTROL),
 			compare_commits: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
 			delete_tag: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
@@ -232,6 +450,7 @@ impl Default for KeysList {
 			force_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
 			undo_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
 			fetch: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
+			fetch_pull_request: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::CONTROL),
 			pull: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			abort_merge: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
 			open_file_tree: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
@@ -240,13 +459,28 @@ impl Default for KeysList {
 			view_submodules: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
 			view_submodule_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			update_submodule: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			view_remotes: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
+			view_worktrees: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::CONTROL),
+			view_reflog: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::CONTROL),
+			branch_toggle_details: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			copy_permalink: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			remote_add: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			remote_rename: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			remote_edit_url: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			remote_delete: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			commit_history_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
+			inspect_child_commit: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::CONTROL),
+			inspect_diff_parent: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::CONTROL),
 			copy_clipboard_sha: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			branch_copy_short_name: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
 			copy_clipboard_email: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			copy_clipboard_author: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			copy_clipboard_date: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
 			copy_clipboard_message: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			copy_clipboard_summary: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
+			copy_clipboard_changelog: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			run_external_command: GituiKeyEvent::new(KeyCode::Char(':'),  KeyModifiers::SHIFT),
+			run_external_command_marked: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::CONTROL),
 			start_search_forward_init: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::empty()),
 			start_search_backward_init: GituiKeyEvent::new(KeyCode::Char('?'),  KeyModifiers::empty()),
 			search_filter_author: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
@@ -256,6 +490,7 @@ impl Default for KeysList {
 			search_prev: GituiKeyEvent::new(KeyCode::Char('N'),  KeyModifiers::SHIFT),
 			open_suboptions: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::CONTROL),
 			filter_commits_init: GituiKeyEvent::new(KeyCode::Char('='),  KeyModifiers::empty()),
+			filter_branches_init: GituiKeyEvent::new(KeyCode::Char('/'),  KeyModifiers::ALT),
 			assign_shortcut: GituiKeyEvent::new(KeyCode::Char('='),  KeyModifiers::empty()),
 			clear_shortcut: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
 			clear_all_shortcut: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
@@ -263,6 +498,8 @@ impl Default for KeysList {
             toggle_split: GituiKeyEvent::new(KeyCode::Char('|'),  KeyModifiers::empty()),
             cherrypick: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			fuzzy_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			grep_files: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::ALT),
+			file_find_preview: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::ALT),
 			generic_push: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			generic_pop: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
 		}
@@ -279,6 +516,20 @@ impl KeysList {
 		}
 		keys_list
 	}
+
+	/// every binding configured as a chord
+	fn chords(&self) -> Vec<GituiKeyEvent> {
+		match serde_json::to_value(self) {
+			Ok(serde_json::Value::Object(keys)) => keys
+				.into_iter()
+				.filter_map(|(_, key)| {
+					serde_json::from_value::<GituiKeyEvent>(key).ok()
+				})
+				.filter(|key| key.then.is_some())
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
 }
 
 #[cfg(test)]
@@ -296,6 +547,20 @@ mod tests {
 		let patch = ron::de::from_reader(f)
 			.expect("vim style config format incorrect");
 		keys_list.apply(patch);
+
+		// the commit list checks scrolling first
+		for scroll in [
+			keys_list.page_up,
+			keys_list.page_down,
+			keys_list.home,
+			keys_list.end,
+		] {
+			assert_ne!(
+				keys_list.rebase_interactive_onto_marked,
+				scroll
+			);
+			assert_ne!(keys_list.log_toggle_signature, scroll);
+		}
 	}
 
 	#[test]
@@ -323,4 +588,73 @@ mod tests {
 			)
 		);
 	}
+
+	#[test]
+	fn test_chord() {
+		let g =
+			KeyEvent::new(KeyCode::Char('g'), KeyModifiers::empty());
+		let x =
+			KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty());
+		let gg = GituiKeyEvent::from(&g)
+			.followed_by(KeyCode::Char('g'), KeyModifiers::empty());
+
+		let mut chord = PendingChord::new(&KeysList {
+			home: gg,
+			..KeysList::default()
+		});
+
+		chord.resolve(x);
+		let ev = chord.resolve(g);
+		assert!(!key_match(&ev, gg));
+		// the single key binding is not held back
+		assert!(key_match(&ev, GituiKeyEvent::from(&g)));
+
+		let ev = chord.resolve(g);
+		assert!(key_match(&ev, gg));
+		assert!(key_match(&ev, GituiKeyEvent::from(&g)));
+
+		// a third `g` starts over
+		let ev = chord.resolve(g);
+		assert!(!key_match(&ev, gg));
+		let ev = chord.resolve(g);
+		assert!(key_match(&ev, gg));
+
+		// only configured chords complete
+		chord.resolve(x);
+		let ev = chord.resolve(x);
+		assert!(!key_match(
+			&ev,
+			GituiKeyEvent::from(&x).followed_by(
+				KeyCode::Char('x'),
+				KeyModifiers::empty()
+			)
+		));
+	}
+
+	#[test]
+	fn test_chord_from_file() {
+		let mut file = NamedTempFile::new().unwrap();
+
+		writeln!(
+			file,
+			r"
+(
+	home: Some(( code: Char('g'), modifiers: ( bits: 0,), then: Some(( code: Char('g'), modifiers: ( bits: 0,),)),)),
+)
+"
+		)
+		.unwrap();
+
+		let keys = KeysList::init(file.path().to_path_buf());
+
+		assert_eq!(
+			keys.home,
+			GituiKeyEvent::new(
+				KeyCode::Char('g'),
+				KeyModifiers::empty()
+			)
+			.followed_by(KeyCode::Char('g'), KeyModifiers::empty())
+		);
+		assert_eq!(keys.end, KeysList::default().end);
+	}
 }
@@ -5,3 +5,4 @@ mod symbols;
 pub use key_config::{KeyConfig, SharedKeyConfig};
 pub use key_list::key_match;
 pub use key_list::GituiKeyEvent;
+pub use key_list::PendingChord;
@@ -0,0 +1,107 @@
+use crate::async_jobs::{
+	AsyncDynJob, AsyncJobFeedback, BoxFeedback, JobFeedbackSender,
+};
+use std::{path::PathBuf, process::Command, sync::Mutex};
+
+/// files tracked by git lfs in the current repo, `None` until
+/// `git lfs ls-files` finished in the background
+static LFS_FILES: Mutex<Option<Vec<PathBuf>>> = Mutex::new(None);
+
+/// whether `p` is a lfs tracked file or a directory containing one,
+/// `None` while the list of tracked files is still loading
+pub fn is_among_tracked_lfs_files(p: &str) -> Option<bool> {
+	LFS_FILES
+		.lock()
+		.ok()?
+		.as_ref()
+		.map(|files| files.iter().any(|i| i.starts_with(p)))
+}
+
+/// forgets the tracked files, used when (re)opening a repo
+pub fn reset_tracked_lfs_files() {
+	set_tracked_lfs_files(None);
+}
+
+fn set_tracked_lfs_files(files: Option<Vec<PathBuf>>) {
+	if let Ok(mut lfs_files) = LFS_FILES.lock() {
+		*lfs_files = files;
+	}
+}
+
+/// lists the lfs tracked files of `workdir` via `git lfs ls-files`,
+/// without git lfs installed this simply finds none
+pub struct AsyncLfsFilesJob {
+	workdir: PathBuf,
+}
+
+impl AsyncLfsFilesJob {
+	pub fn new(workdir: &str) -> Self {
+		Self {
+			workdir: PathBuf::from(workdir),
+		}
+	}
+
+	/// lines look like `<oid> <*|-> <path>`, the path may contain
+	/// spaces
+	fn parse_ls_files(out: &str) -> Vec<PathBuf> {
+		out.lines()
+			.filter_map(|line| line.splitn(3, ' ').nth(2))
+			.map(PathBuf::from)
+			.collect()
+	}
+}
+
+impl AsyncDynJob for AsyncLfsFilesJob {
+	fn run(
+		&mut self,
+		_sender: JobFeedbackSender,
+	) -> Option<BoxFeedback> {
+		let files = Command::new("git")
+			.args(["lfs", "ls-files"])
+			.current_dir(&self.workdir)
+			.output()
+			.map_or_else(
+				|_| Vec::new(),
+				|o| {
+					Self::parse_ls_files(
+						std::str::from_utf8(o.stdout.as_slice())
+							.unwrap_or_default(),
+					)
+				},
+			);
+
+		Some(Box::new(AsyncLfsFilesFeedback(Some(files))))
+	}
+
+	fn should_stop(&self) -> bool {
+		false
+	}
+}
+
+struct AsyncLfsFilesFeedback(Option<Vec<PathBuf>>);
+
+impl AsyncJobFeedback for AsyncLfsFilesFeedback {
+	fn visit(&mut self, _app: &mut crate::app::App) {
+		set_tracked_lfs_files(self.0.take());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_parse_ls_files() {
+		let out = "0123456789 * assets/logo.png\n\
+			abcdef0123 - data/big file.bin\n\n";
+
+		assert_eq!(
+			AsyncLfsFilesJob::parse_ls_files(out),
+			vec![
+				PathBuf::from("assets/logo.png"),
+				PathBuf::from("data/big file.bin"),
+			]
+		);
+	}
+}
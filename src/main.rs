@@ -34,7 +34,9 @@ mod clipboard;
 mod cmdbar;
 mod components;
 mod input;
+mod json;
 mod keys;
+mod lfs;
 mod notify_mutex;
 mod options;
 mod popup_stack;
@@ -44,15 +46,24 @@ mod spinner;
 mod string_utils;
 mod strings;
 mod tabs;
+#[cfg(test)]
+mod test_utils;
 mod ui;
 mod version;
 mod watcher;
 
-use crate::{app::App, args::process_cmdline};
+use crate::{
+	app::App,
+	args::{process_cmdline, RebaseArgs},
+};
 use anyhow::{bail, Result};
-use app::QuitState;
+use app::{QuitState, SubmoduleBreadcrumb};
 use asyncgit::{
-	sync::{extern_git::IPCEvents, utils::repo_work_dir, RepoPath},
+	sync::{
+		extern_git::{self, IPCEvents},
+		utils::repo_work_dir,
+		RepoPath,
+	},
 	AsyncGitNotification,
 };
 use backtrace::Backtrace;
@@ -66,6 +77,7 @@ use crossterm::{
 };
 use input::{Input, InputEvent, InputState};
 use keys::KeyConfig;
+use options::UpdateIntervals;
 use profiler::Profiler;
 use ratatui::{
 	backend::{Backend, CrosstermBackend},
@@ -78,18 +90,12 @@ use std::{
 	cell::RefCell,
 	io::{self, Write},
 	panic,
-	path::PathBuf,
 	process,
 	time::{Duration, Instant},
 };
 use ui::style::Theme;
 use watcher::RepoWatcher;
 
-static TICK_INTERVAL_INT: u64  = 120;//ms
-static UPDATE_INTERVAL_INT: u64 = 5000;//ms
-static TICK_INTERVAL: Duration = Duration::from_millis(TICK_INTERVAL_INT);
-static SPINNER_INTERVAL: Duration = Duration::from_millis(80);
-
 ///
 #[derive(Clone)]
 pub enum QueueEvent {
@@ -122,7 +128,7 @@ pub enum AsyncNotification {
 	Git(AsyncGitNotification),
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 enum Updater {
 	Ticker,
 	NotifyWatcher,
@@ -187,6 +193,23 @@ fn dummy_mode() -> Result<()> {
 	Ok(())
 }
 
+/// `gitui rebase`, fails (exit code 1) if the rebase did not go
+/// through
+fn rebase_mode(
+	repo_path: &RepoPath,
+	args: &RebaseArgs,
+) -> Result<()> {
+	if args.transforms.is_empty() {
+		bail!("nothing to do, pass --drop, --fixup or --reword");
+	}
+
+	extern_git::rebase_transform_commits(
+		&repo_work_dir(repo_path)?,
+		&args.base,
+		&args.transforms,
+	)
+}
+
 fn main() -> Result<()> {
 	if let Some(_) = std::env::args().find(|i| i == "--event_id") {
 		/*std::fs::write(
@@ -213,6 +236,14 @@ fn main() -> Result<()> {
 		bail!("invalid path\nplease run gitui inside of a non-bare git repository");
 	}
 
+	if let Some(rebase) = cliargs.rebase {
+		return rebase_mode(&cliargs.repo_path, &rebase);
+	}
+
+	if let Some(query) = cliargs.json {
+		return json::run(&cliargs.repo_path, &query);
+	}
+
 	let key_config = KeyConfig::init()
 		.map_err(|e| eprintln!("KeyConfig loading error: {e}"))
 		.unwrap_or_default();
@@ -237,10 +268,15 @@ fn main() -> Result<()> {
 		Updater::Ticker
 	};
 
+	let mut breadcrumbs: Vec<SubmoduleBreadcrumb> = Vec::new();
+	let mut restore = None;
+
 	loop {
+		let restored = restore.take();
 		let quit_state = run_app(
 			app_start,
 			repo_path.clone(),
+			restored.as_ref(),
 			theme,
 			key_config.clone(),
 			&input,
@@ -249,7 +285,16 @@ fn main() -> Result<()> {
 		)?;
 
 		match quit_state {
-			QuitState::OpenSubmodule(p) => {
+			QuitState::OpenSubmodule(p, breadcrumb) => {
+				breadcrumbs.push(breadcrumb);
+				repo_path = p;
+			}
+			QuitState::OpenParentRepo(p) => {
+				restore = parent_breadcrumb(&mut breadcrumbs, &p);
+				repo_path = p;
+			}
+			QuitState::OpenWorktree(p) => {
+				breadcrumbs.clear();
 				repo_path = p;
 			}
 			_ => break,
@@ -259,57 +304,44 @@ fn main() -> Result<()> {
 	Ok(())
 }
 
-static mut LFS_FILES: Vec<PathBuf> = vec![];
-pub fn is_among_tracked_lfs_files(p: &str) -> bool {
-	let files = unsafe { &LFS_FILES };
-	files.iter().find(|i| i.starts_with(p)).is_some()
+/// the breadcrumb left when entering the submodule we now leave for
+/// `parent`, the trail is dropped if we end up somewhere else
+fn parent_breadcrumb(
+	breadcrumbs: &mut Vec<SubmoduleBreadcrumb>,
+	parent: &RepoPath,
+) -> Option<SubmoduleBreadcrumb> {
+	let breadcrumb = breadcrumbs.pop()?;
+
+	if repo_work_dir(&breadcrumb.parent).ok()
+		== repo_work_dir(parent).ok()
+	{
+		Some(breadcrumb)
+	} else {
+		breadcrumbs.clear();
+		None
+	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_app(
 	app_start: Instant,
 	repo: RepoPath,
+	breadcrumb: Option<&SubmoduleBreadcrumb>,
 	theme: Theme,
 	key_config: KeyConfig,
 	input: &Input,
-	updater: Updater,
+	default_updater: Updater,
 	terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) -> Result<QuitState, anyhow::Error> {
-	unsafe {
-		LFS_FILES = process::Command::new("git")
-			.args(["lfs", "ls-files"])
-			.output()
-			.map_or(Vec::new(), |o| {
-				std::str::from_utf8(o.stdout.as_slice())
-					.unwrap_or_default()
-					.split('\n')
-					.skip_while(|i| i.len() == 0)
-					.map(|i| {
-						i.split_ascii_whitespace()
-							.nth(2)
-							.unwrap_or_default()
-					})
-					.skip_while(|i| i.len() == 0)
-					.map(PathBuf::from)
-					.collect()
-			});
-	}
+	lfs::reset_tracked_lfs_files();
 
 	let (tx_git, rx_git) = unbounded();
 	let (tx_app, rx_app) = unbounded();
 
 	let rx_input = input.receiver();
 
-	let (rx_ticker, rx_watcher) = match updater {
-		Updater::NotifyWatcher => {
-			let repo_watcher =
-				RepoWatcher::new(repo_work_dir(&repo)?.as_str());
-
-			(never(), repo_watcher.receiver())
-		}
-		Updater::Ticker => (tick(TICK_INTERVAL), never()),
-	};
+	let workdir = repo_work_dir(&repo)?;
 
-	let spinner_ticker = tick(SPINNER_INTERVAL);
 	let (dyn_jobs_thread, dyn_jobs_send, dyn_jobs_feedback) =
 		async_jobs::AsyncJobList::new(tx_app.clone());
 
@@ -323,7 +355,34 @@ fn run_app(
 		dyn_jobs_send.clone(),
 	)?;
 
+	match breadcrumb {
+		Some(breadcrumb) => {
+			// the submodule may be gone by now, that is no reason
+			// not to open its parent
+			if let Err(e) = app.restore_breadcrumb(breadcrumb) {
+				log::error!("restore submodule selection: {}", e);
+			}
+		}
+		None => app.note_detached_submodule(),
+	}
+
+	// listing lfs files can take a while in big repos, don't block
+	// the first draw on it
+	dyn_jobs_send
+		.send(Box::new(lfs::AsyncLfsFilesJob::new(&workdir)))?;
+
+	let intervals = app.update_intervals();
+	log::trace!("update intervals: {:?}", intervals);
+	let debounce = app.watcher_debounce();
+	let spinner_interval = app.spinner_interval();
+
+	let mut updater = repo_updater(&app, default_updater);
+	let (mut rx_ticker, mut rx_watcher, mut repo_watcher) =
+		updater_receivers(updater, &workdir, intervals, debounce);
+
 	let mut spinner = Spinner::default();
+	let mut spinner_ticker = never();
+	let mut spinning = false;
 	let mut first_update = true;
 
 	log::trace!("app start: {} ms", app_start.elapsed().as_millis());
@@ -373,9 +432,9 @@ fn run_app(
 					app.event(ev)?;
 				}
 				QueueEvent::Tick | QueueEvent::Notify => {
-					update_ticker += TICK_INTERVAL_INT;
-					if update_ticker > UPDATE_INTERVAL_INT {
-						update_ticker %= UPDATE_INTERVAL_INT;
+					update_ticker += intervals.tick_ms;
+					if update_ticker >= intervals.update_ms {
+						update_ticker %= intervals.update_ms;
 						app.update()?;
 					}
 					app.on_tick()?;
@@ -393,9 +452,27 @@ fn run_app(
 				QueueEvent::SpinnerUpdate => unreachable!(),
 			}
 
+			let new_updater = repo_updater(&app, default_updater);
+			if new_updater != updater {
+				log::trace!("switching updater: {:?}", new_updater);
+				updater = new_updater;
+				// drop the old watcher before starting a new one
+				drop(repo_watcher.take());
+				(rx_ticker, rx_watcher, repo_watcher) =
+					updater_receivers(
+						updater, &workdir, intervals, debounce,
+					);
+			}
+
 			draw(terminal, &app)?;
 
-			spinner.set_state(app.any_work_pending());
+			let pending = app.any_work_pending();
+			if pending != spinning {
+				spinning = pending;
+				spinner_ticker =
+					spinner_receiver(pending, spinner_interval);
+			}
+			spinner.set_state(pending);
 			spinner.draw(terminal)?;
 
 			if app.is_quit() {
@@ -408,9 +485,48 @@ fn run_app(
 			as async_jobs::BoxJob)?;
 	if let Err(_) = dyn_jobs_thread.join() {}
 
+	app.save_view_state();
+
 	Ok(app.quit_state())
 }
 
+/// per repo option wins over the cli arg
+fn repo_updater(app: &App, default: Updater) -> Updater {
+	match app.notify_watcher() {
+		Some(true) => Updater::NotifyWatcher,
+		Some(false) => Updater::Ticker,
+		None => default,
+	}
+}
+
+/// the spinner ticks only while work is pending, so an idle gitui
+/// does not wake up for it
+fn spinner_receiver(
+	pending: bool,
+	interval: Option<Duration>,
+) -> Receiver<Instant> {
+	match interval {
+		Some(interval) if pending => tick(interval),
+		_ => never(),
+	}
+}
+
+fn updater_receivers(
+	updater: Updater,
+	workdir: &str,
+	intervals: UpdateIntervals,
+	debounce: Duration,
+) -> (Receiver<Instant>, Receiver<()>, Option<RepoWatcher>) {
+	match updater {
+		Updater::NotifyWatcher => {
+			let repo_watcher = RepoWatcher::new(workdir, debounce);
+
+			(never(), repo_watcher.receiver(), Some(repo_watcher))
+		}
+		Updater::Ticker => (tick(intervals.tick()), never(), None),
+	}
+}
+
 fn setup_terminal() -> Result<()> {
 	enable_raw_mode()?;
 	io::stdout().execute(EnterAlternateScreen)?;
@@ -0,0 +1,302 @@
+use asyncgit::sync::{diff::DiffOptions, GitExternCommands};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::ExternCmdList;
+
+/// width of the author column in log and blame:
+/// `(width - 19) / divisor` clamped to `min..=max`
+#[derive(
+	Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct AuthorWidth {
+	pub min: usize,
+	pub max: usize,
+	pub divisor: usize,
+}
+
+impl Default for AuthorWidth {
+	fn default() -> Self {
+		Self {
+			min: 3,
+			max: 20,
+			divisor: 3,
+		}
+	}
+}
+
+impl AuthorWidth {
+	/// author column width for a view `width` columns wide
+	pub fn columns(self, width: usize) -> usize {
+		self.scaled_columns(width, 1)
+	}
+
+	/// like `columns` but with share and bounds multiplied by
+	/// `factor`, never taking more than half of the view
+	pub fn scaled_columns(
+		self,
+		width: usize,
+		factor: usize,
+	) -> usize {
+		if factor <= 1 {
+			return (width.saturating_sub(19) / self.divisor.max(1))
+				.clamp(self.min, self.max.max(self.min));
+		}
+
+		let min = self.min.saturating_mul(factor);
+		let max = self.max.max(self.min).saturating_mul(factor);
+		(width.saturating_sub(19).saturating_mul(factor)
+			/ self.divisor.max(1))
+		.clamp(min, max)
+		.min((width / 2).max(self.columns(width)))
+	}
+}
+
+/// accelerating scroll through the log: a step within `repeat_ms`
+/// of the previous one moves `multiplier` times as many lines,
+/// starting at `start` and capped at `max`. `multiplier: 1.0`
+/// turns the acceleration off
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrollSpeed {
+	pub start: f32,
+	pub max: f32,
+	pub multiplier: f32,
+	pub repeat_ms: u64,
+}
+
+impl Default for ScrollSpeed {
+	fn default() -> Self {
+		Self {
+			start: 0.1,
+			max: 10.0,
+			multiplier: 1.05,
+			repeat_ms: 300,
+		}
+	}
+}
+
+impl ScrollSpeed {
+	const MAX_LINES: f32 = 1000.0;
+	const MULTIPLIER_MAX: f32 = 2.0;
+	const REPEAT_MAX_MS: u64 = 2000;
+
+	/// speed of the step `since_last` after one at `speed`, a
+	/// nonsense config falls back to steps of a single line
+	pub fn next(self, speed: f32, since_last: Duration) -> f32 {
+		let clamp = |value: f32, min: f32, max: f32| {
+			if value.is_finite() {
+				value.clamp(min, max)
+			} else {
+				min
+			}
+		};
+
+		let max = clamp(self.max, 1.0, Self::MAX_LINES);
+		let start = clamp(self.start, 0.0, max);
+		let multiplier =
+			clamp(self.multiplier, 1.0, Self::MULTIPLIER_MAX);
+		let repeat = Duration::from_millis(
+			self.repeat_ms.min(Self::REPEAT_MAX_MS),
+		);
+
+		let speed = if since_last < repeat {
+			speed * multiplier
+		} else {
+			start
+		};
+
+		speed.min(max)
+	}
+}
+
+/// "type to confirm" instead of just hitting enter for actions
+/// that easily lose work
+#[derive(
+	Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct StrictConfirm {
+	/// type the branch name to force push it
+	#[serde(default)]
+	pub force_push: bool,
+	/// type `yes` to reset hard or to discard changes
+	#[serde(default)]
+	pub reset: bool,
+}
+
+/// settings shared by all repositories
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct GlobalOptionsData {
+	#[serde(default)]
+	pub diff: DiffOptions,
+	#[serde(default)]
+	pub extern_cmds: ExternCmdList,
+	#[serde(default)]
+	pub git_extern_cmds: GitExternCommands,
+	/// takes precedence over `$VISUAL`/`$EDITOR` and `core.editor`
+	#[serde(default)]
+	pub editor: Option<String>,
+	/// line per commit when copying a changelog
+	#[serde(default)]
+	pub changelog_template: Option<String>,
+	/// web link to a line, for forges not laid out like github
+	#[serde(default)]
+	pub permalink_template: Option<String>,
+	#[serde(default)]
+	pub author_width: AuthorWidth,
+	/// credential prompts after a rejected push, pull or fetch
+	#[serde(default)]
+	pub auth_retries: Option<usize>,
+	/// ms per spinner frame, `0` keeps it still
+	#[serde(default)]
+	pub spinner_interval_ms: Option<u64>,
+	#[serde(default)]
+	pub strict_confirm: StrictConfirm,
+	#[serde(default)]
+	pub scroll_speed: ScrollSpeed,
+	/// commits the log loads per batch, `0` loads all at once
+	#[serde(default)]
+	pub log_batch_size: Option<usize>,
+	/// external command results start with the command and its
+	/// exit code
+	#[serde(default)]
+	pub show_cmd_status: Option<bool>,
+}
+
+const SPINNER_INTERVAL_DEFAULT_MS: u64 = 80;
+const SPINNER_INTERVAL_MIN_MS: u64 = 40;
+const SPINNER_INTERVAL_MAX_MS: u64 = 5000;
+const LOG_BATCH_SIZE_MIN: usize = 500;
+
+pub const AUTH_RETRIES_DEFAULT: usize = 3;
+
+pub const CHANGELOG_TEMPLATE_DEFAULT: &str =
+	"- {short_hash} {summary} ({author})";
+
+/// github and gitlab both understand this
+pub const PERMALINK_TEMPLATE_DEFAULT: &str =
+	"{remote}/blob/{commit}/{path}#L{line}";
+
+/// tiny batches would load more on every step
+pub fn log_batch_size(size: Option<usize>) -> usize {
+	match size.unwrap_or_default() {
+		0 => 0,
+		size => size.max(LOG_BATCH_SIZE_MIN),
+	}
+}
+
+/// `None` if the spinner should not animate at all
+pub fn spinner_interval(
+	interval_ms: Option<u64>,
+) -> Option<Duration> {
+	match interval_ms.unwrap_or(SPINNER_INTERVAL_DEFAULT_MS) {
+		0 => None,
+		ms => Some(Duration::from_millis(ms.clamp(
+			SPINNER_INTERVAL_MIN_MS,
+			SPINNER_INTERVAL_MAX_MS,
+		))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ron::de::from_bytes;
+
+	#[test]
+	fn test_author_width() {
+		let width = AuthorWidth::default();
+		assert_eq!(width.columns(0), 3);
+		assert_eq!(width.columns(49), 10);
+		assert_eq!(width.columns(200), 20);
+
+		let width = AuthorWidth {
+			min: 10,
+			max: 40,
+			divisor: 2,
+		};
+		assert_eq!(width.columns(49), 15);
+		assert_eq!(width.columns(200), 40);
+
+		// nonsense config must not panic
+		let width = AuthorWidth {
+			min: 10,
+			max: 5,
+			divisor: 0,
+		};
+		assert_eq!(width.columns(200), 10);
+
+		// name and email together get a wider column
+		let width = AuthorWidth::default();
+		assert_eq!(width.scaled_columns(200, 1), 20);
+		assert_eq!(width.scaled_columns(200, 2), 40);
+		assert_eq!(width.scaled_columns(49, 2), 20);
+		assert_eq!(width.scaled_columns(0, 2), 3);
+
+		// fields left out keep their default
+		let data: GlobalOptionsData =
+			from_bytes(b"(author_width: (max: 30))").unwrap();
+		assert_eq!(
+			data.author_width,
+			AuthorWidth {
+				max: 30,
+				..AuthorWidth::default()
+			}
+		);
+	}
+
+	#[test]
+	fn test_scroll_speed() {
+		let fast = Duration::from_millis(10);
+		let slow = Duration::from_secs(1);
+
+		let speed = ScrollSpeed::default();
+		assert!((speed.next(2.0, fast) - 2.1).abs() < 0.001);
+		assert!((speed.next(9.9, fast) - 10.0).abs() < 0.001);
+		assert!((speed.next(5.0, slow) - 0.1).abs() < 0.001);
+
+		// no acceleration
+		let speed = ScrollSpeed {
+			start: 3.0,
+			multiplier: 1.0,
+			..ScrollSpeed::default()
+		};
+		assert!((speed.next(3.0, fast) - 3.0).abs() < 0.001);
+
+		// nonsense config must not panic
+		let speed = ScrollSpeed {
+			start: f32::NAN,
+			max: -1.0,
+			multiplier: 100.0,
+			repeat_ms: u64::MAX,
+		};
+		assert!((speed.next(0.0, slow) - 0.0).abs() < 0.001);
+		assert!((speed.next(0.8, fast) - 1.0).abs() < 0.001);
+	}
+
+	#[test]
+	fn test_log_batch_size() {
+		assert_eq!(log_batch_size(None), 0);
+		assert_eq!(log_batch_size(Some(0)), 0);
+		assert_eq!(log_batch_size(Some(1)), LOG_BATCH_SIZE_MIN);
+		assert_eq!(log_batch_size(Some(20_000)), 20_000);
+	}
+
+	#[test]
+	fn test_spinner_interval() {
+		assert_eq!(
+			spinner_interval(None),
+			Some(Duration::from_millis(SPINNER_INTERVAL_DEFAULT_MS))
+		);
+		assert_eq!(spinner_interval(Some(0)), None);
+		assert_eq!(
+			spinner_interval(Some(1)),
+			Some(Duration::from_millis(SPINNER_INTERVAL_MIN_MS))
+		);
+		assert_eq!(
+			spinner_interval(Some(u64::MAX)),
+			Some(Duration::from_millis(SPINNER_INTERVAL_MAX_MS))
+		);
+	}
+}
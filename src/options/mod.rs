@@ -0,0 +1,1045 @@
+mod global;
+mod repo;
+
+use anyhow::Result;
+use asyncgit::sync::{
+	diff::DiffOptions, repo_dir, GitExternCommands, RepoPathRef,
+	ShowUntrackedFilesConfig,
+};
+use crossterm::event::KeyEvent;
+use fuzzy_matcher::FuzzyMatcher;
+use ron::{
+	de::from_bytes,
+	extensions::Extensions,
+	ser::{to_string_pretty, PrettyConfig},
+};
+use std::{
+	cell::RefCell,
+	fs::File,
+	io::{Read, Write},
+	path::{Path, PathBuf},
+	rc::Rc,
+	time::Duration,
+};
+
+use crate::args::get_app_config_path;
+use crate::keys::key_match;
+use crate::keys::GituiKeyEvent;
+use global::{
+	log_batch_size, spinner_interval, GlobalOptionsData,
+	AUTH_RETRIES_DEFAULT, CHANGELOG_TEMPLATE_DEFAULT,
+	PERMALINK_TEMPLATE_DEFAULT,
+};
+pub use global::{AuthorWidth, ScrollSpeed, StrictConfirm};
+use repo::{watcher_debounce, OptionsData, PULL_REQUEST_REF_DEFAULT};
+pub use repo::{
+	BlameAuthor, BranchListState, ListState, LogRefs, SearchScope,
+	UpdateIntervals, ViewState,
+};
+
+type ExternCmdList = Vec<(String, Option<GituiKeyEvent>)>;
+
+const COMMIT_MSG_HISTRY_LENGTH: usize = 20;
+
+/// repo files before version 1 carry every global setting
+const OPTIONS_VERSION: u32 = 1;
+
+/// upper bound for diff context and inter hunk lines
+pub const DIFF_LINES_MAX: u32 = 1000;
+
+/// Settings are layered: `GlobalOptionsData` lives in the gitui
+/// config dir and holds diff defaults, (git) extern commands and
+/// the editor, everything else is per repo. A per repo file may still carry
+/// any of the global settings (older files always do), which then
+/// takes precedence and is also where changes to it get saved.
+#[derive(Clone)]
+pub struct Options {
+	repo: RepoPathRef,
+	data: OptionsData,
+	global: GlobalOptionsData,
+	/// holds the global `options.ron`, `None` keeps global changes
+	/// in memory
+	config_dir: Option<PathBuf>,
+	/// `false` never touches the disk
+	persist: bool,
+}
+
+pub type SharedOptions = Rc<RefCell<Options>>;
+
+impl Options {
+	pub fn new(repo: RepoPathRef) -> SharedOptions {
+		Rc::new(RefCell::new(Self::load(
+			repo,
+			get_app_config_path().ok(),
+		)))
+	}
+
+	/// reads the settings of `repo` layered over the global ones
+	/// in `config_dir`
+	fn load(repo: RepoPathRef, config_dir: Option<PathBuf>) -> Self {
+		let global = config_dir
+			.as_deref()
+			.and_then(|dir| Self::read_global(dir).ok())
+			.unwrap_or_default();
+		let mut data = Self::read(&repo).unwrap_or_default();
+		Self::migrate(&mut data);
+
+		Self {
+			repo,
+			data,
+			global,
+			config_dir,
+			persist: true,
+		}
+	}
+
+	/// defaults only, nothing is read or saved
+	#[cfg(test)]
+	pub fn in_memory() -> SharedOptions {
+		Rc::new(RefCell::new(Self {
+			repo: RefCell::new(asyncgit::sync::RepoPath::from("")),
+			data: OptionsData::default(),
+			global: GlobalOptionsData::default(),
+			config_dir: None,
+			persist: false,
+		}))
+	}
+
+	#[cfg(test)]
+	pub fn set_strict_confirm(&mut self, strict: StrictConfirm) {
+		self.global.strict_confirm = strict;
+	}
+
+	/// older versions wrote the global settings into every repo
+	/// file, drop those still at their default so that the global
+	/// file takes effect
+	fn migrate(data: &mut OptionsData) {
+		if data.version >= OPTIONS_VERSION {
+			return;
+		}
+
+		if data.diff == Some(DiffOptions::default()) {
+			data.diff = None;
+		}
+		if data.extern_cmds.as_ref().map_or(false, Vec::is_empty) {
+			data.extern_cmds = None;
+		}
+		if data.git_extern_cmds == Some(GitExternCommands::default())
+		{
+			data.git_extern_cmds = None;
+		}
+		data.version = OPTIONS_VERSION;
+	}
+
+	/// what goes into the repo file: overrides matching the global
+	/// setting are left out so they don't pin it
+	fn repo_file_data(&self) -> OptionsData {
+		let mut data = self.data.clone();
+		if data.diff == Some(self.global.diff) {
+			data.diff = None;
+		}
+		if data.extern_cmds.as_ref() == Some(&self.global.extern_cmds)
+		{
+			data.extern_cmds = None;
+		}
+		if data.git_extern_cmds.as_ref()
+			== Some(&self.global.git_extern_cmds)
+		{
+			data.git_extern_cmds = None;
+		}
+		data.version = OPTIONS_VERSION;
+		data
+	}
+
+	pub fn set_current_tab(&mut self, tab: usize) {
+		self.data.tab = tab;
+		self.save();
+	}
+
+	pub const fn current_tab(&self) -> usize {
+		self.data.tab
+	}
+
+	pub fn update_intervals(&self) -> UpdateIntervals {
+		UpdateIntervals::new(
+			self.data.tick_interval_ms,
+			self.data.update_interval_ms,
+		)
+	}
+
+	/// debounce window of the `notify` based watcher
+	pub fn watcher_debounce(&self) -> Duration {
+		watcher_debounce(self.data.watcher_debounce_ms)
+	}
+
+	/// `None` means: fall back to the `--watcher` cli arg
+	pub const fn notify_watcher(&self) -> Option<bool> {
+		self.data.notify_watcher
+	}
+
+	pub fn set_notify_watcher(&mut self, value: Option<bool>) {
+		self.data.notify_watcher = value;
+		self.save();
+	}
+
+	/// remote ref to fetch for pull request `number`
+	pub fn pull_request_ref(&self, number: u64) -> String {
+		self.data
+			.pull_request_ref
+			.as_deref()
+			.unwrap_or(PULL_REQUEST_REF_DEFAULT)
+			.replace("{}", &number.to_string())
+	}
+
+	/// last submitted commit search, empty if none
+	pub fn last_search(&self) -> &str {
+		&self.data.last_search
+	}
+
+	pub fn set_last_search(&mut self, search: &str) {
+		if self.data.last_search != search {
+			self.data.last_search = search.to_string();
+			self.save();
+		}
+	}
+
+	/// last submitted commit filter, empty if none
+	pub fn last_filter(&self) -> &str {
+		&self.data.last_filter
+	}
+
+	pub fn set_last_filter(&mut self, filter: &str) {
+		if self.data.last_filter != filter {
+			self.data.last_filter = filter.to_string();
+			self.save();
+		}
+	}
+
+	pub const fn log_refs(&self) -> LogRefs {
+		self.data.log_refs
+	}
+
+	pub fn set_log_refs(&mut self, refs: LogRefs) {
+		if self.data.log_refs != refs {
+			self.data.log_refs = refs;
+			self.save();
+		}
+	}
+
+	/// the scope the last search was configured with
+	pub const fn search_scope(&self) -> SearchScope {
+		self.data.search_scope
+	}
+
+	pub fn set_search_scope(&mut self, scope: SearchScope) {
+		if self.data.search_scope != scope {
+			self.data.search_scope = scope;
+			self.save();
+		}
+	}
+
+	pub const fn rebase_autostash(&self) -> bool {
+		self.data.rebase_autostash
+	}
+
+	pub fn toggle_rebase_autostash(&mut self) {
+		self.data.rebase_autostash = !self.data.rebase_autostash;
+		self.save();
+	}
+
+	pub const fn branch_details(&self) -> bool {
+		self.data.branch_details
+	}
+
+	pub fn toggle_branch_details(&mut self) {
+		self.data.branch_details = !self.data.branch_details;
+		self.save();
+	}
+
+	pub const fn diff_wrap_lines(&self) -> bool {
+		self.data.diff_view.wrap_lines
+	}
+
+	pub fn toggle_diff_wrap_lines(&mut self) {
+		self.data.diff_view.wrap_lines =
+			!self.data.diff_view.wrap_lines;
+		self.save();
+	}
+
+	pub const fn diff_word_highlight(&self) -> bool {
+		!self.data.diff_view.plain_lines
+	}
+
+	pub fn toggle_diff_word_highlight(&mut self) {
+		self.data.diff_view.plain_lines =
+			!self.data.diff_view.plain_lines;
+		self.save();
+	}
+
+	pub const fn diff_line_numbers_absolute(&self) -> bool {
+		self.data.diff_view.line_numbers_absolute
+	}
+
+	pub fn toggle_diff_line_numbers_absolute(&mut self) {
+		self.data.diff_view.line_numbers_absolute =
+			!self.data.diff_view.line_numbers_absolute;
+		self.save();
+	}
+
+	pub const fn status_header(&self) -> bool {
+		self.data.status_header
+	}
+
+	pub fn toggle_status_header(&mut self) {
+		self.data.status_header = !self.data.status_header;
+		self.save();
+	}
+
+	pub const fn view_state(&self) -> &ViewState {
+		&self.data.view_state
+	}
+
+	pub fn set_view_state(&mut self, state: ViewState) {
+		if self.data.view_state != state {
+			self.data.view_state = state;
+			self.save();
+		}
+	}
+
+	pub const fn blame_author(&self) -> BlameAuthor {
+		self.data.blame_author
+	}
+
+	pub fn cycle_blame_author(&mut self) {
+		self.data.blame_author = self.data.blame_author.next();
+		self.save();
+	}
+
+	pub fn diff_options(&self) -> DiffOptions {
+		self.data.diff.unwrap_or(self.global.diff)
+	}
+
+	/// `diff_options` with the whitespace override of `path` applied
+	pub fn diff_options_for(&self, path: &str) -> DiffOptions {
+		let mut diff = self.diff_options();
+		if let Some(ignore) =
+			self.data.diff_whitespace_overrides.get(path)
+		{
+			diff.ignore_whitespace = *ignore;
+		}
+		diff
+	}
+
+	/// flips whitespace handling for `path` only, an override
+	/// matching the general setting again is dropped
+	pub fn diff_toggle_whitespace_for(&mut self, path: &str) {
+		let ignore = !self.diff_options_for(path).ignore_whitespace;
+		if ignore == self.diff_options().ignore_whitespace {
+			self.data.diff_whitespace_overrides.remove(path);
+		} else {
+			self.data
+				.diff_whitespace_overrides
+				.insert(path.to_string(), ignore);
+		}
+		self.save();
+	}
+
+	pub const fn status_show_untracked(
+		&self,
+	) -> Option<ShowUntrackedFilesConfig> {
+		self.data.status_show_untracked
+	}
+
+	pub fn set_status_show_untracked(
+		&mut self,
+		value: Option<ShowUntrackedFilesConfig>,
+	) {
+		self.data.status_show_untracked = value;
+		self.save();
+	}
+
+	pub fn diff_context_change(&mut self, increase: bool) {
+		self.update_diff(|diff| {
+			diff.context = if increase {
+				diff.context.saturating_add(1).min(DIFF_LINES_MAX)
+			} else {
+				diff.context.saturating_sub(1)
+			};
+		});
+	}
+
+	/// clamped to `DIFF_LINES_MAX`
+	pub fn set_diff_context(&mut self, context: u32) {
+		self.update_diff(|diff| {
+			diff.context = context.min(DIFF_LINES_MAX);
+		});
+	}
+
+	pub fn diff_hunk_lines_change(&mut self, increase: bool) {
+		self.update_diff(|diff| {
+			diff.interhunk_lines = if increase {
+				diff.interhunk_lines
+					.saturating_add(1)
+					.min(DIFF_LINES_MAX)
+			} else {
+				diff.interhunk_lines.saturating_sub(1)
+			};
+		});
+	}
+
+	/// clamped to `DIFF_LINES_MAX`
+	pub fn set_diff_hunk_lines(&mut self, lines: u32) {
+		self.update_diff(|diff| {
+			diff.interhunk_lines = lines.min(DIFF_LINES_MAX);
+		});
+	}
+
+	pub fn diff_toggle_whitespace(&mut self) {
+		self.update_diff(|diff| {
+			diff.ignore_whitespace = !diff.ignore_whitespace;
+		});
+	}
+
+	pub fn diff_toggle_blank_lines(&mut self) {
+		self.update_diff(|diff| {
+			diff.ignore_blank_lines = !diff.ignore_blank_lines;
+		});
+	}
+
+	pub fn git_extern_commands(&self) -> &GitExternCommands {
+		self.data
+			.git_extern_cmds
+			.as_ref()
+			.unwrap_or(&self.global.git_extern_cmds)
+	}
+
+	/// editor command from the config, if set
+	pub fn editor(&self) -> Option<&str> {
+		self.global
+			.editor
+			.as_deref()
+			.filter(|editor| !editor.trim().is_empty())
+	}
+
+	pub const fn author_width(&self) -> AuthorWidth {
+		self.global.author_width
+	}
+
+	pub const fn strict_confirm(&self) -> StrictConfirm {
+		self.global.strict_confirm
+	}
+
+	pub const fn scroll_speed(&self) -> ScrollSpeed {
+		self.global.scroll_speed
+	}
+
+	pub fn show_cmd_status(&self) -> bool {
+		self.global.show_cmd_status.unwrap_or(true)
+	}
+
+	/// line template for the changelog copied from the log
+	pub fn changelog_template(&self) -> &str {
+		self.global
+			.changelog_template
+			.as_deref()
+			.filter(|template| !template.trim().is_empty())
+			.unwrap_or(CHANGELOG_TEMPLATE_DEFAULT)
+	}
+
+	/// template of the web link copied for a blame or diff line
+	pub fn permalink_template(&self) -> &str {
+		self.global
+			.permalink_template
+			.as_deref()
+			.filter(|template| !template.trim().is_empty())
+			.unwrap_or(PERMALINK_TEMPLATE_DEFAULT)
+	}
+
+	/// how often to ask for the password again when a push, pull
+	/// or fetch got rejected because of the credentials
+	pub fn auth_retries(&self) -> usize {
+		self.global.auth_retries.unwrap_or(AUTH_RETRIES_DEFAULT)
+	}
+
+	/// frame time of the spinner shown while work is pending
+	pub fn spinner_interval(&self) -> Option<Duration> {
+		spinner_interval(self.global.spinner_interval_ms)
+	}
+
+	/// commits the log loads before the selection comes close to
+	/// the end of them, `0` (default) loads the whole history
+	pub fn log_batch_size(&self) -> usize {
+		log_batch_size(self.global.log_batch_size)
+	}
+
+	pub fn set_git_extern_push(&mut self, cmd: Option<String>) {
+		self.update_git_extern_cmds(|cmds| cmds.push_base = cmd);
+	}
+
+	pub fn set_git_extern_fetch(&mut self, cmd: Option<String>) {
+		self.update_git_extern_cmds(|cmds| cmds.fetch_base = cmd);
+	}
+
+	pub fn set_git_extern_pull(&mut self, cmd: Option<String>) {
+		self.update_git_extern_cmds(|cmds| cmds.pull_base = cmd);
+	}
+
+	pub fn set_git_extern_checkout(&mut self, cmd: Option<String>) {
+		self.update_git_extern_cmds(|cmds| cmds.checkout_base = cmd);
+	}
+
+	pub fn extern_commands(&self) -> &ExternCmdList {
+		self.data
+			.extern_cmds
+			.as_ref()
+			.unwrap_or(&self.global.extern_cmds)
+	}
+
+	pub fn remove_extern_command(&mut self, idx: usize) -> usize {
+		if idx < self.extern_commands().len() {
+			self.update_extern_cmds(|cmds| {
+				cmds.remove(idx);
+				if idx == cmds.len() {
+					idx.saturating_sub(1)
+				} else {
+					idx
+				}
+			})
+		} else {
+			0
+		}
+	}
+
+	pub fn add_extern_command(&mut self, cmd: &str) {
+		let existing = self
+			.extern_commands()
+			.iter()
+			.enumerate()
+			.find(|i| i.1 .0 == cmd);
+		if existing.is_none() {
+			//add new
+			self.update_extern_cmds(|cmds| {
+				cmds.insert(0, (cmd.to_string(), None));
+			});
+		}
+	}
+
+	pub fn assign_shortcut_for_extern_command(
+		&mut self,
+		idx: usize,
+		shortcut: Option<GituiKeyEvent>,
+	) {
+		self.update_extern_cmds(|cmds| cmds[idx].1 = shortcut);
+	}
+
+	pub fn clear_all_shortcuts_for_extern_commands(&mut self) {
+		self.update_extern_cmds(|cmds| {
+			for i in cmds.iter_mut() {
+				i.1 = None;
+			}
+		});
+	}
+
+	pub fn find_extern_cmd_for_shortcut(
+		&self,
+		e: GituiKeyEvent,
+	) -> Option<String> {
+		self.extern_commands()
+			.iter()
+			.find(|i| i.1 == Some(e))
+			.map(|i| i.0.clone())
+	}
+
+	pub fn assign_shortcut_for_branch(
+		&mut self,
+		branch: &str,
+		e: &KeyEvent,
+	) {
+		let shortcut = GituiKeyEvent::new(e.code, e.modifiers);
+		let existing = self
+			.data
+			.branch_shortcuts
+			.iter_mut()
+			.find(|i| i.0 == branch);
+		if let Some(i) = existing {
+			i.1 = shortcut;
+		} else {
+			self.data
+				.branch_shortcuts
+				.push((branch.to_string(), shortcut));
+		}
+		self.save();
+	}
+
+	pub fn remove_shortcut_for_branch(&mut self, branch: &str) {
+		self.data.branch_shortcuts.retain(|i| i.0 != branch);
+		self.save();
+	}
+
+	pub fn clear_all_branch_shortcuts(&mut self) {
+		self.data.branch_shortcuts.clear();
+		self.save();
+	}
+
+	pub fn find_branch_by_key_event(
+		&self,
+		e: &KeyEvent,
+	) -> Option<&str> {
+		self.data
+			.branch_shortcuts
+			.iter()
+			.find(|i| key_match(e, i.1))
+			.map(|i| i.0.as_str())
+	}
+
+	pub fn has_any_branch_shortcuts(&self) -> bool {
+		!self.data.branch_shortcuts.is_empty()
+	}
+
+	pub fn find_branch_shortcut_by_branch(
+		&self,
+		branch: &str,
+	) -> Option<&GituiKeyEvent> {
+		self.data
+			.branch_shortcuts
+			.iter()
+			.find(|i| i.0 == branch)
+			.map(|i| &i.1)
+	}
+
+	/// an identical older message is moved to the most recent spot
+	pub fn add_commit_msg(&mut self, msg: &str) {
+		self.data.commit_msgs.retain(|m| m != msg);
+		self.data.commit_msgs.push(msg.to_owned());
+		while self.data.commit_msgs.len() > COMMIT_MSG_HISTRY_LENGTH {
+			self.data.commit_msgs.remove(0);
+		}
+		self.save();
+	}
+
+	pub fn has_commit_msg_history(&self) -> bool {
+		!self.data.commit_msgs.is_empty()
+	}
+
+	pub fn commit_msg(&self, idx: usize) -> Option<String> {
+		if self.data.commit_msgs.is_empty() {
+			None
+		} else {
+			let entries = self.data.commit_msgs.len();
+			let mut index = idx;
+
+			while index >= entries {
+				index -= entries;
+			}
+
+			index = entries.saturating_sub(1) - index;
+
+			Some(self.data.commit_msgs[index].to_string())
+		}
+	}
+
+	/// like `commit_msg` but only cycles through messages fuzzy
+	/// matching `query`, best match first
+	pub fn commit_msg_matching(
+		&self,
+		query: &str,
+		idx: usize,
+	) -> Option<String> {
+		if query.is_empty() {
+			return self.commit_msg(idx);
+		}
+
+		let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+		let mut scored = self
+			.data
+			.commit_msgs
+			.iter()
+			.rev()
+			.filter_map(|msg| {
+				matcher
+					.fuzzy_match(msg, query)
+					.map(|score| (score, msg))
+			})
+			.collect::<Vec<_>>();
+
+		if scored.is_empty() {
+			return None;
+		}
+
+		// stable, so equal scores stay most recent first
+		scored.sort_by(|(score1, _), (score2, _)| score2.cmp(score1));
+
+		Some(scored[idx % scored.len()].1.clone())
+	}
+
+	/// changes the diff options in whichever layer they come from
+	fn update_diff(&mut self, f: impl FnOnce(&mut DiffOptions)) {
+		if let Some(diff) = self.data.diff.as_mut() {
+			f(diff);
+			self.save();
+		} else {
+			f(&mut self.global.diff);
+			self.save_global();
+		}
+	}
+
+	fn update_extern_cmds<R>(
+		&mut self,
+		f: impl FnOnce(&mut ExternCmdList) -> R,
+	) -> R {
+		if let Some(cmds) = self.data.extern_cmds.as_mut() {
+			let res = f(cmds);
+			self.save();
+			res
+		} else {
+			let res = f(&mut self.global.extern_cmds);
+			self.save_global();
+			res
+		}
+	}
+
+	fn update_git_extern_cmds(
+		&mut self,
+		f: impl FnOnce(&mut GitExternCommands),
+	) {
+		if let Some(cmds) = self.data.git_extern_cmds.as_mut() {
+			f(cmds);
+			self.save();
+		} else {
+			f(&mut self.global.git_extern_cmds);
+			self.save_global();
+		}
+	}
+
+	fn save(&self) {
+		if let Err(e) = self.save_failable() {
+			log::error!("options save error: {}", e);
+		}
+	}
+
+	fn save_global(&self) {
+		if let Err(e) = self.save_global_failable() {
+			log::error!("global options save error: {}", e);
+		}
+	}
+
+	fn read(repo: &RepoPathRef) -> Result<OptionsData> {
+		let dir = Self::options_file(repo)?;
+
+		let mut f = File::open(dir)?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Self::parse(&buffer)
+	}
+
+	/// older files store the now optional settings without `Some(..)`
+	fn parse(buffer: &[u8]) -> Result<OptionsData> {
+		Ok(ron::Options::default()
+			.with_default_extension(Extensions::IMPLICIT_SOME)
+			.from_bytes(buffer)?)
+	}
+
+	fn read_global(config_dir: &Path) -> Result<GlobalOptionsData> {
+		let mut f =
+			File::open(Self::global_options_file(config_dir))?;
+		let mut buffer = Vec::new();
+		f.read_to_end(&mut buffer)?;
+		Ok(from_bytes(&buffer)?)
+	}
+
+	fn save_global_failable(&self) -> Result<()> {
+		let config_dir = match &self.config_dir {
+			Some(dir) if self.persist => dir,
+			_ => return Ok(()),
+		};
+
+		let mut file =
+			File::create(Self::global_options_file(config_dir))?;
+		let data =
+			to_string_pretty(&self.global, PrettyConfig::default())?;
+		file.write_all(data.as_bytes())?;
+
+		Ok(())
+	}
+
+	fn global_options_file(config_dir: &Path) -> PathBuf {
+		config_dir.join("options.ron")
+	}
+
+	//TODO: fix once FP in clippy is fixed
+	#[allow(clippy::needless_borrow)]
+	fn save_failable(&self) -> Result<()> {
+		if !self.persist {
+			return Ok(());
+		}
+
+		let dir = Self::options_file(&self.repo)?;
+
+		let mut file = File::create(&dir)?;
+		let data = to_string_pretty(
+			&self.repo_file_data(),
+			PrettyConfig::default(),
+		)?;
+		file.write_all(data.as_bytes())?;
+
+		Ok(())
+	}
+
+	fn options_file(repo: &RepoPathRef) -> Result<PathBuf> {
+		let dir = repo_dir(&repo.borrow())?;
+		let dir = dir.join("gitui");
+		Ok(dir)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::temp_repo;
+	use repo::DiffView;
+
+	#[test]
+	fn test_parse_legacy_repo_options() {
+		let data = Options::parse(
+			br#"(
+				tab: 1,
+				diff: (ignore_whitespace: true, context: 5, interhunk_lines: 0),
+				status_show_untracked: None,
+				commit_msgs: [],
+				extern_cmds: [("make", None)],
+				git_extern_cmds: (push_base: None, fetch_base: None, checkout_base: None),
+				branch_shortcuts: [],
+			)"#,
+		)
+		.unwrap();
+
+		assert_eq!(data.diff.map(|d| d.context), Some(5));
+		assert_eq!(data.extern_cmds.map(|c| c.len()), Some(1));
+		assert!(data.git_extern_cmds.is_some());
+	}
+
+	#[test]
+	fn test_parse_repo_options_without_overrides() {
+		let data = Options::parse(
+			br"(
+				tab: 0,
+				status_show_untracked: None,
+				commit_msgs: [],
+				branch_shortcuts: [],
+			)",
+		)
+		.unwrap();
+
+		assert!(data.diff.is_none());
+		assert!(data.extern_cmds.is_none());
+		assert!(data.git_extern_cmds.is_none());
+		assert_eq!(data.log_refs, LogRefs::default());
+		assert!(data.log_refs.remote_branches);
+		assert_eq!(data.search_scope, SearchScope::default());
+		assert!(!data.rebase_autostash);
+		assert!(!data.branch_details);
+		assert_eq!(data.diff_view, DiffView::default());
+		assert_eq!(data.view_state, ViewState::default());
+	}
+
+	#[test]
+	fn test_parse_view_state() {
+		let data = Options::parse(
+			br#"(
+				tab: 1,
+				status_show_untracked: None,
+				commit_msgs: [],
+				branch_shortcuts: [],
+				view_state: (log: (commit: "abc", selection: 7)),
+			)"#,
+		)
+		.unwrap();
+
+		assert_eq!(data.view_state.log.commit, "abc");
+		assert_eq!(data.view_state.log.selection, 7);
+		assert_eq!(data.view_state.log.row, 0);
+		assert_eq!(data.view_state.stashes, ListState::default());
+		assert_eq!(
+			data.view_state.branches,
+			BranchListState::default()
+		);
+	}
+
+	#[test]
+	fn test_parse_branch_view_state() {
+		let data = Options::parse(
+			br#"(
+				tab: 0,
+				status_show_untracked: None,
+				commit_msgs: [],
+				branch_shortcuts: [],
+				view_state: (branches: (branch: "origin/main", remote: true)),
+			)"#,
+		)
+		.unwrap();
+
+		assert_eq!(data.view_state.branches.branch, "origin/main");
+		assert!(data.view_state.branches.remote);
+		assert_eq!(data.view_state.branches.selection, 0);
+		assert_eq!(data.view_state.log, ListState::default());
+	}
+
+	#[test]
+	fn test_migrate_legacy_overrides() {
+		let mut data = Options::parse(
+			br"(
+				tab: 1,
+				diff: (ignore_whitespace: false, context: 3, interhunk_lines: 0),
+				status_show_untracked: None,
+				commit_msgs: [],
+				extern_cmds: [],
+				git_extern_cmds: (push_base: None, fetch_base: None, checkout_base: None),
+				branch_shortcuts: [],
+			)",
+		)
+		.unwrap();
+		Options::migrate(&mut data);
+
+		assert!(data.diff.is_none());
+		assert!(data.extern_cmds.is_none());
+		assert!(data.git_extern_cmds.is_none());
+		assert_eq!(data.version, OPTIONS_VERSION);
+
+		// settings actually changed in an older version stay
+		let mut data = Options::parse(
+			br#"(
+				tab: 1,
+				diff: (ignore_whitespace: true, context: 3, interhunk_lines: 0),
+				status_show_untracked: None,
+				commit_msgs: [],
+				extern_cmds: [("make", None)],
+				branch_shortcuts: [],
+			)"#,
+		)
+		.unwrap();
+		Options::migrate(&mut data);
+
+		assert!(data.diff.is_some());
+		assert!(data.extern_cmds.is_some());
+	}
+
+	#[test]
+	fn test_global_layer() {
+		let (_repo_dir, repo) = temp_repo();
+		let repo = RefCell::new(repo);
+		let config_dir = tempfile::TempDir::new().unwrap();
+		let config = Some(config_dir.path().to_path_buf());
+
+		let mut options = Options::load(repo.clone(), config.clone());
+		options.diff_toggle_whitespace();
+		options.set_current_tab(2);
+
+		let options = Options::load(repo.clone(), config);
+		assert!(options.diff_options().ignore_whitespace);
+		assert_eq!(options.current_tab(), 2);
+		assert!(options.data.diff.is_none());
+
+		// the repo file left the diff settings to the global file
+		let options = Options::load(repo, None);
+		assert!(!options.diff_options().ignore_whitespace);
+		assert_eq!(options.current_tab(), 2);
+	}
+
+	/// options of a fresh temp repo, the global layer stays in
+	/// memory
+	fn options_with_msgs(
+		msgs: &[&str],
+	) -> (tempfile::TempDir, Options) {
+		let (td, repo) = temp_repo();
+		let repo = RefCell::new(repo);
+		let mut options = Options::load(repo, None);
+		for msg in msgs {
+			options.add_commit_msg(msg);
+		}
+		(td, options)
+	}
+
+	#[test]
+	fn test_diff_whitespace_override() {
+		let (_td, mut options) = options_with_msgs(&[]);
+		let ignore = options.diff_options().ignore_whitespace;
+
+		options.diff_toggle_whitespace_for("a.rs");
+		assert_eq!(
+			options.diff_options_for("a.rs").ignore_whitespace,
+			!ignore
+		);
+		assert_eq!(
+			options.diff_options_for("b.rs").ignore_whitespace,
+			ignore
+		);
+
+		options.diff_toggle_whitespace_for("a.rs");
+		assert!(options.data.diff_whitespace_overrides.is_empty());
+	}
+
+	#[test]
+	fn test_pull_request_ref() {
+		let (_td, mut options) = options_with_msgs(&[]);
+		assert_eq!(options.pull_request_ref(42), "refs/pull/42/head");
+
+		options.data.pull_request_ref =
+			Some("refs/merge-requests/{}/head".into());
+		assert_eq!(
+			options.pull_request_ref(7),
+			"refs/merge-requests/7/head"
+		);
+	}
+
+	#[test]
+	fn test_commit_msg_dedup() {
+		let (_td, options) = options_with_msgs(&["a", "b", "a"]);
+
+		assert_eq!(options.data.commit_msgs, vec!["b", "a"]);
+		assert_eq!(options.commit_msg(0).as_deref(), Some("a"));
+		assert_eq!(options.commit_msg(1).as_deref(), Some("b"));
+	}
+
+	#[test]
+	fn test_commit_msg_matching() {
+		let (_td, options) = options_with_msgs(&[
+			"fix parser",
+			"add tests",
+			"fix lexer",
+		]);
+
+		let found = (0..2)
+			.filter_map(|idx| options.commit_msg_matching("fix", idx))
+			.collect::<Vec<_>>();
+		assert_eq!(found.len(), 2);
+		assert!(found.iter().all(|msg| msg.starts_with("fix")));
+
+		assert_eq!(
+			options.commit_msg_matching("fix", 2),
+			options.commit_msg_matching("fix", 0)
+		);
+		assert_eq!(options.commit_msg_matching("zzz", 0), None);
+	}
+
+	#[test]
+	fn test_set_diff_lines_clamped() {
+		let (_td, repo) = temp_repo();
+		let repo = RefCell::new(repo);
+		let mut options = Options::load(repo, None);
+		options.data.diff = Some(DiffOptions::default());
+
+		options.set_diff_context(42);
+		options.set_diff_hunk_lines(DIFF_LINES_MAX + 1);
+		assert_eq!(options.diff_options().context, 42);
+		assert_eq!(
+			options.diff_options().interhunk_lines,
+			DIFF_LINES_MAX
+		);
+
+		options.diff_hunk_lines_change(true);
+		assert_eq!(
+			options.diff_options().interhunk_lines,
+			DIFF_LINES_MAX
+		);
+	}
+}
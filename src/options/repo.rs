@@ -0,0 +1,305 @@
+use asyncgit::sync::{
+	diff::DiffOptions, GitExternCommands, ShowUntrackedFilesConfig,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+use super::ExternCmdList;
+use crate::keys::GituiKeyEvent;
+
+/// per repo settings, the `Option` fields override the global ones
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct OptionsData {
+	pub tab: usize,
+	#[serde(default)]
+	pub diff: Option<DiffOptions>,
+	pub status_show_untracked: Option<ShowUntrackedFilesConfig>,
+	pub commit_msgs: Vec<String>,
+	#[serde(default)]
+	pub extern_cmds: Option<ExternCmdList>,
+	#[serde(default)]
+	pub git_extern_cmds: Option<GitExternCommands>,
+	pub branch_shortcuts: Vec<(String, GituiKeyEvent)>,
+	#[serde(default)]
+	pub tick_interval_ms: Option<u64>,
+	#[serde(default)]
+	pub update_interval_ms: Option<u64>,
+	#[serde(default)]
+	pub notify_watcher: Option<bool>,
+	#[serde(default)]
+	pub watcher_debounce_ms: Option<u64>,
+	#[serde(default)]
+	pub last_search: String,
+	#[serde(default)]
+	pub last_filter: String,
+	/// per file `ignore_whitespace` deviating from `diff`
+	#[serde(default)]
+	pub diff_whitespace_overrides: BTreeMap<String, bool>,
+	/// remote ref of a pull request, `{}` is the number
+	#[serde(default)]
+	pub pull_request_ref: Option<String>,
+	#[serde(default)]
+	pub log_refs: LogRefs,
+	#[serde(default)]
+	pub search_scope: SearchScope,
+	/// stash local changes around cherry-pick, drop and fixup
+	#[serde(default)]
+	pub rebase_autostash: bool,
+	/// author and age of the last commit in the branch list
+	#[serde(default)]
+	pub branch_details: bool,
+	/// how the diff view draws its lines
+	#[serde(default)]
+	pub diff_view: DiffView,
+	#[serde(default)]
+	pub blame_author: BlameAuthor,
+	/// branch, upstream and repo state in a line above the tabs
+	#[serde(default)]
+	pub status_header: bool,
+	#[serde(default)]
+	pub view_state: ViewState,
+	/// layout of the file, see `OPTIONS_VERSION`
+	#[serde(default)]
+	pub version: u32,
+}
+
+/// how the diff view draws its lines
+#[derive(
+	Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct DiffView {
+	/// wrap long lines instead of scrolling horizontally
+	pub wrap_lines: bool,
+	/// color changed lines as a whole instead of emphasizing the
+	/// words that differ from the paired line
+	pub plain_lines: bool,
+	/// number lines by their line in the new file instead of the
+	/// distance to the selection
+	pub line_numbers_absolute: bool,
+}
+
+/// refs shown next to the commits in the log
+#[derive(
+	Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct LogRefs {
+	pub local_branches: bool,
+	pub remote_branches: bool,
+	pub tags: bool,
+}
+
+impl Default for LogRefs {
+	fn default() -> Self {
+		Self {
+			local_branches: true,
+			remote_branches: true,
+			tags: true,
+		}
+	}
+}
+
+/// commit fields the log search looks at
+#[derive(
+	Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct SearchScope {
+	pub author: bool,
+	pub message: bool,
+	pub sha: bool,
+}
+
+impl Default for SearchScope {
+	fn default() -> Self {
+		Self {
+			author: true,
+			message: true,
+			sha: true,
+		}
+	}
+}
+
+/// selection and scroll position of a commit list
+#[derive(
+	Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ListState {
+	/// full hash of the selected commit, empty if none
+	pub commit: String,
+	pub selection: usize,
+	/// line of the selection within the view
+	pub row: usize,
+}
+
+/// selection of the branch list
+#[derive(
+	Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct BranchListState {
+	/// name of the selected branch, empty if none
+	pub branch: String,
+	/// the remote branches were listed
+	pub remote: bool,
+	pub selection: usize,
+}
+
+/// where the main views were left, restored on the next launch
+#[derive(
+	Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ViewState {
+	pub log: ListState,
+	pub stashes: ListState,
+	pub branches: BranchListState,
+}
+
+/// what the author column of blame shows
+#[derive(
+	Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum BlameAuthor {
+	#[default]
+	Name,
+	Email,
+	Both,
+}
+
+impl BlameAuthor {
+	pub const fn next(self) -> Self {
+		match self {
+			Self::Name => Self::Email,
+			Self::Email => Self::Both,
+			Self::Both => Self::Name,
+		}
+	}
+
+	pub fn text(self, name: &str, email: &str) -> String {
+		match self {
+			Self::Name => name.to_string(),
+			Self::Email => email.to_string(),
+			Self::Both => format!("{name} <{email}>"),
+		}
+	}
+
+	/// how many author columns' worth of text this shows
+	pub const fn width_factor(self) -> usize {
+		match self {
+			Self::Name | Self::Email => 1,
+			Self::Both => 2,
+		}
+	}
+}
+
+const TICK_INTERVAL_DEFAULT_MS: u64 = 120;
+const TICK_INTERVAL_MIN_MS: u64 = 20;
+const TICK_INTERVAL_MAX_MS: u64 = 10_000;
+const UPDATE_INTERVAL_DEFAULT_MS: u64 = 5000;
+const UPDATE_INTERVAL_MAX_MS: u64 = 600_000;
+const WATCHER_DEBOUNCE_DEFAULT_MS: u64 = 2000;
+const WATCHER_DEBOUNCE_MIN_MS: u64 = 50;
+const WATCHER_DEBOUNCE_MAX_MS: u64 = 60_000;
+/// github style, gitlab uses `refs/merge-requests/{}/head`
+pub const PULL_REQUEST_REF_DEFAULT: &str = "refs/pull/{}/head";
+
+/// window in which filesystem events coalesce into one notify
+pub fn watcher_debounce(debounce_ms: Option<u64>) -> Duration {
+	Duration::from_millis(
+		debounce_ms
+			.unwrap_or(WATCHER_DEBOUNCE_DEFAULT_MS)
+			.clamp(WATCHER_DEBOUNCE_MIN_MS, WATCHER_DEBOUNCE_MAX_MS),
+	)
+}
+
+/// tick and background update cadence of the main loop
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UpdateIntervals {
+	/// ms between two ticks
+	pub tick_ms: u64,
+	/// ms between two full updates, at least one tick. updates run
+	/// on the first tick reaching it, the remainder carries over
+	pub update_ms: u64,
+}
+
+impl UpdateIntervals {
+	/// clamps configured values into sane ranges, a zero tick would
+	/// otherwise busy-loop the event loop
+	pub fn new(tick_ms: Option<u64>, update_ms: Option<u64>) -> Self {
+		let tick_ms = tick_ms
+			.unwrap_or(TICK_INTERVAL_DEFAULT_MS)
+			.clamp(TICK_INTERVAL_MIN_MS, TICK_INTERVAL_MAX_MS);
+		let update_ms = update_ms
+			.unwrap_or(UPDATE_INTERVAL_DEFAULT_MS)
+			.clamp(tick_ms, UPDATE_INTERVAL_MAX_MS.max(tick_ms));
+
+		Self { tick_ms, update_ms }
+	}
+
+	/// tick interval as a `Duration` for the ticker channel
+	pub const fn tick(&self) -> Duration {
+		Duration::from_millis(self.tick_ms)
+	}
+}
+
+impl Default for UpdateIntervals {
+	fn default() -> Self {
+		Self::new(None, None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_blame_author() {
+		let mut shown = BlameAuthor::default();
+		assert_eq!(shown.text("name", "mail"), "name");
+
+		shown = shown.next();
+		assert_eq!(shown.text("name", "mail"), "mail");
+
+		shown = shown.next();
+		assert_eq!(shown.text("name", "mail"), "name <mail>");
+		assert_eq!(shown.next(), BlameAuthor::Name);
+	}
+
+	#[test]
+	fn test_watcher_debounce_clamped() {
+		assert_eq!(
+			watcher_debounce(None),
+			Duration::from_millis(WATCHER_DEBOUNCE_DEFAULT_MS)
+		);
+		assert_eq!(
+			watcher_debounce(Some(0)),
+			Duration::from_millis(WATCHER_DEBOUNCE_MIN_MS)
+		);
+		assert_eq!(
+			watcher_debounce(Some(u64::MAX)),
+			Duration::from_millis(WATCHER_DEBOUNCE_MAX_MS)
+		);
+	}
+
+	#[test]
+	fn test_update_intervals_defaults() {
+		let i = UpdateIntervals::default();
+		assert_eq!(i.tick_ms, TICK_INTERVAL_DEFAULT_MS);
+		assert_eq!(i.update_ms, UPDATE_INTERVAL_DEFAULT_MS);
+	}
+
+	#[test]
+	fn test_update_intervals_clamped() {
+		let i = UpdateIntervals::new(Some(0), Some(0));
+		assert_eq!(i.tick_ms, TICK_INTERVAL_MIN_MS);
+		assert_eq!(i.update_ms, TICK_INTERVAL_MIN_MS);
+
+		let i = UpdateIntervals::new(Some(u64::MAX), Some(1));
+		assert_eq!(i.tick_ms, TICK_INTERVAL_MAX_MS);
+		assert_eq!(i.update_ms, TICK_INTERVAL_MAX_MS);
+
+		let i = UpdateIntervals::new(None, Some(u64::MAX));
+		assert_eq!(i.update_ms, UPDATE_INTERVAL_MAX_MS);
+	}
+}
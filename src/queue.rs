@@ -25,6 +25,10 @@ bitflags! {
 		const COMMANDS = 0b100;
 		/// branches have changed
 		const BRANCHES = 0b1000;
+		/// tags have changed
+		const TAGS = 0b1_0000;
+		/// notes on commits have changed
+		const NOTES = 0b10_0000;
 	}
 }
 
@@ -54,6 +58,12 @@ pub enum Action {
 	Reset(ResetItem),
 	ResetHunk(String, u64),
 	ResetLines(String, Vec<DiffLinePosition>),
+	/// hard reset of the current branch to this commit
+	ResetHard(CommitId),
+	/// restore the tracked files to `HEAD`, dropping staged changes
+	/// too, and delete the untracked ones next to them
+	RestoreFiles(Vec<String>, Vec<String>),
+	DeleteUntracked(Vec<String>),
 	StashDrop(Vec<CommitId>),
 	StashPop(CommitId),
 	DeleteLocalBranch(String),
@@ -65,6 +75,14 @@ pub enum Action {
 	AbortMerge,
 	AbortRebase,
 	AbortRevert,
+	CheckoutPullRequest(String),
+	/// `git submodule update --init` the submodule at this path
+	/// and open it afterwards
+	InitSubmodule(PathBuf),
+	/// remove the remote with this name
+	DeleteRemote(String),
+	/// quit although these operations are still running
+	QuitPending(Vec<&'static str>),
 }
 
 #[derive(Debug)]
@@ -110,8 +128,11 @@ pub enum InternalEvent {
 	OpenCommit,
 	/// open external editor to edit commit msg and commit
 	CommitWithExternalEditor,
-	///
-	RebaseInteractiveWithEditor(CommitId),
+	/// interactive rebase onto the commit, todo edited in app
+	RebaseInteractive(CommitId),
+	/// the rebase todo is written, `true` to edit it in the external
+	/// editor before handing it to git
+	RebaseTodoDone(bool),
 	///
 	RebaseInteractiveContinue,
 	///
@@ -126,10 +147,14 @@ pub enum InternalEvent {
 	SelectCommitInRevlog(CommitId),
 	///
 	TagCommit(CommitId),
+	/// view or edit the git note of a commit
+	EditCommitNote(CommitId),
 	///
 	Tags,
-	///
-	CreateBranch,
+	/// `true` stages, `false` unstages files matching a pattern
+	OpenStagePattern(bool),
+	/// base commit, `None` branches off (and switches to) HEAD
+	CreateBranch(Option<CommitId>),
 	///
 	RenameBranch(String, String),
 	///
@@ -138,6 +163,14 @@ pub enum InternalEvent {
 	PickBranch(SharedLocalQueue),
 	///
 	OpenExternalEditor(Option<String>),
+	/// open a file (relative to the work dir) in the editor at a
+	/// line (1-based)
+	OpenExternalEditorAt(String, usize),
+	/// open the folder of a file (relative to the work dir) in the
+	/// file manager
+	RevealFile(String),
+	/// copy the absolute path of a file relative to the work dir
+	CopyAbsolutePath(String),
 	///
 	Push(String, PushType, bool, bool),
 	///
@@ -146,10 +179,14 @@ pub enum InternalEvent {
 	PushTags,
 	///
 	OptionSwitched(AppOption),
+	/// flip ignoring whitespace in the diff of this path only
+	ToggleDiffWhitespace(String),
 	///
 	OpenFileFinder(Vec<TreeFile>, Option<SharedLocalQueue>),
 	///
 	OpenBranchFinder(Vec<String>),
+	/// search file contents of a commit, the worktree if `None`
+	OpenGrep(Option<CommitId>),
 	///
 	FileFinderChanged(Option<PathBuf>),
 	///
@@ -157,6 +194,8 @@ pub enum InternalEvent {
 	///
 	FetchRemotes,
 	///
+	OpenFetchPullRequest,
+	///
 	OpenPopup(StackablePopupOpen),
 	///
 	PopupStackPop,
@@ -165,11 +204,28 @@ pub enum InternalEvent {
 	///
 	ViewSubmodules,
 	///
+	ViewRemotes,
+	///
+	ViewWorktrees,
+	/// reflog of `HEAD` or the given full branch ref
+	ViewReflog(String),
+	/// copy a web link to a line (1-based) of a file at a commit,
+	/// `HEAD` if `None`
+	CopyPermalink(Option<CommitId>, String, usize),
+	///
 	OpenRepo { path: PathBuf },
+	/// return from a submodule to its parent repo
+	OpenParentRepo { path: PathBuf },
+	/// switch to another worktree of the repo
+	OpenWorktree { path: PathBuf },
 	///
 	OpenResetPopup(CommitId),
 	///
 	RewordCommit(CommitId),
+	/// open commit msg input to amend `HEAD`, prefilled with its message
+	AmendCommit,
+	/// open commit msg input to commit only these staged files
+	OpenCommitMarked(Vec<String>),
 }
 
 /// single threaded simple queue for components to communicate with each other
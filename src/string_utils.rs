@@ -1,3 +1,4 @@
+use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
@@ -24,6 +25,40 @@ pub fn tabs_to_spaces(input: String) -> String {
 	}
 }
 
+/// stands in for a tab, as wide as `tabs_to_spaces` makes it
+const TAB_MARKER: &str = "→ ";
+/// stands in for a trailing space
+const SPACE_MARKER: &str = "·";
+
+/// like `tabs_to_spaces` but split into `(text, is_marker)` parts,
+/// showing tabs as `→` and trailing spaces as `·`
+pub fn mark_whitespace(input: &str) -> Vec<(String, bool)> {
+	let body = input.trim_end_matches([' ', '\t']);
+	let trailing = &input[body.len()..];
+
+	let mut parts = Vec::new();
+	for (idx, text) in body.split('\t').enumerate() {
+		if idx > 0 {
+			parts.push((TAB_MARKER.to_string(), true));
+		}
+		if !text.is_empty() {
+			parts.push((text.to_string(), false));
+		}
+	}
+
+	if !trailing.is_empty() {
+		let markers = trailing
+			.chars()
+			.map(
+				|c| if c == '\t' { TAB_MARKER } else { SPACE_MARKER },
+			)
+			.collect();
+		parts.push((markers, true));
+	}
+
+	parts
+}
+
 /// This function will return a str slice which start at specified offset.
 /// As src is a unicode str, start offset has to be calculated with each character.
 pub fn trim_offset(src: &str, mut offset: usize) -> &str {
@@ -40,15 +75,311 @@ pub fn trim_offset(src: &str, mut offset: usize) -> &str {
 	&src[start..]
 }
 
+/// splits the tagged `parts` of a line into rows at most `width`
+/// columns wide, breaking between graphemes and keeping each part's
+/// tag on the pieces it is split into
+pub fn wrap_parts<T: Copy>(
+	parts: &[(String, T)],
+	width: usize,
+) -> Vec<Vec<(String, T)>> {
+	let mut rows = Vec::new();
+	let mut row = Vec::new();
+	let mut row_width = 0;
+
+	for (text, tag) in parts {
+		let mut piece = String::new();
+		for c in UnicodeSegmentation::graphemes(text.as_str(), true) {
+			let w = c.width();
+			// a row always takes at least one grapheme
+			if row_width > 0 && row_width + w > width {
+				if !piece.is_empty() {
+					row.push((std::mem::take(&mut piece), *tag));
+				}
+				rows.push(std::mem::take(&mut row));
+				row_width = 0;
+			}
+			piece.push_str(c);
+			row_width += w;
+		}
+		if !piece.is_empty() {
+			row.push((piece, *tag));
+		}
+	}
+	rows.push(row);
+
+	rows
+}
+
+/// char ranges of the changed words in a line
+pub type WordRanges = Vec<Range<usize>>;
+
+/// longest lines `word_diff` compares, in tokens
+const WORD_DIFF_MAX_TOKENS: usize = 256;
+
+/// splits `input` into alternating runs of whitespace and words
+fn word_tokens(input: &str) -> Vec<&str> {
+	let mut tokens = Vec::new();
+	let mut start = 0;
+	let mut last_space = None;
+
+	for (idx, c) in input.char_indices() {
+		let space = c.is_whitespace();
+		if matches!(last_space, Some(last) if last != space) {
+			tokens.push(&input[start..idx]);
+			start = idx;
+		}
+		last_space = Some(space);
+	}
+	if start < input.len() {
+		tokens.push(&input[start..]);
+	}
+
+	tokens
+}
+
+/// char ranges of the tokens marked in `changed`, neighbours merged
+fn changed_ranges(tokens: &[&str], changed: &[bool]) -> WordRanges {
+	let mut ranges: WordRanges = Vec::new();
+	let mut pos = 0;
+
+	for (token, changed) in tokens.iter().zip(changed) {
+		let end = pos + token.chars().count();
+		if *changed {
+			match ranges.last_mut() {
+				Some(last) if last.end == pos => last.end = end,
+				_ => ranges.push(pos..end),
+			}
+		}
+		pos = end;
+	}
+
+	ranges
+}
+
+/// char ranges of the words that differ between `old` and `new`,
+/// `None` if the lines have no word in common (or are too long to
+/// compare) so that highlighting words would not help
+pub fn word_diff(
+	old: &str,
+	new: &str,
+) -> Option<(WordRanges, WordRanges)> {
+	let old = word_tokens(old);
+	let new = word_tokens(new);
+	let (n, m) = (old.len(), new.len());
+	if n > WORD_DIFF_MAX_TOKENS || m > WORD_DIFF_MAX_TOKENS {
+		return None;
+	}
+
+	// length of the longest common subsequence of `old[i..]` and
+	// `new[j..]` at `at(i, j)`
+	let at = |i: usize, j: usize| i * (m + 1) + j;
+	let mut lcs = vec![0_usize; at(n + 1, 0)];
+	for i in (0..n).rev() {
+		for j in (0..m).rev() {
+			lcs[at(i, j)] = if old[i] == new[j] {
+				lcs[at(i + 1, j + 1)] + 1
+			} else {
+				lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+			};
+		}
+	}
+
+	let mut old_changed = vec![true; n];
+	let mut new_changed = vec![true; m];
+	let mut common_word = false;
+	let (mut i, mut j) = (0, 0);
+	while i < n && j < m {
+		if old[i] == new[j] {
+			old_changed[i] = false;
+			new_changed[j] = false;
+			common_word |= !old[i].trim().is_empty();
+			i += 1;
+			j += 1;
+		} else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+			i += 1;
+		} else {
+			j += 1;
+		}
+	}
+
+	common_word.then(|| {
+		(
+			changed_ranges(&old, &old_changed),
+			changed_ranges(&new, &new_changed),
+		)
+	})
+}
+
+/// fills `{remote}` (web url of the remote), `{commit}`, `{path}`
+/// and `{line}` of a permalink `template`
+pub fn permalink(
+	template: &str,
+	remote: &str,
+	commit: &str,
+	path: &str,
+	line: usize,
+) -> String {
+	let path = path
+		.bytes()
+		.map(|b| {
+			if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+				char::from(b).to_string()
+			} else {
+				format!("%{b:02X}")
+			}
+		})
+		.collect::<String>();
+
+	let fields = [
+		("remote", remote.to_string()),
+		("commit", commit.to_string()),
+		("path", path),
+		("line", line.to_string()),
+	];
+
+	expand_placeholders(template, &fields)
+}
+
+/// replaces each `{name}` in `template` by the value of `name` in
+/// `fields` in a single pass, so values are never expanded again.
+/// unknown names are kept as they are
+pub fn expand_placeholders(
+	template: &str,
+	fields: &[(&str, String)],
+) -> String {
+	let mut res = String::with_capacity(template.len());
+	let mut rest = template;
+
+	while let Some(start) = rest.find('{') {
+		res.push_str(&rest[..start]);
+		rest = &rest[start..];
+
+		let field = rest.find('}').and_then(|end| {
+			fields
+				.iter()
+				.find(|(name, _)| *name == &rest[1..end])
+				.map(|(_, value)| (end, value))
+		});
+
+		if let Some((end, value)) = field {
+			res.push_str(value);
+			rest = &rest[end + 1..];
+		} else {
+			res.push('{');
+			rest = &rest[1..];
+		}
+	}
+	res.push_str(rest);
+
+	res
+}
+
 #[cfg(test)]
 mod test {
 	use pretty_assertions::assert_eq;
 
-	use crate::string_utils::trim_length_left;
+	use crate::string_utils::{
+		expand_placeholders, mark_whitespace, permalink,
+		trim_length_left, word_diff, wrap_parts,
+	};
 
 	#[test]
 	fn test_trim() {
 		assert_eq!(trim_length_left("👍foo", 3), "foo");
 		assert_eq!(trim_length_left("👍foo", 4), "foo");
 	}
+
+	#[test]
+	fn test_mark_whitespace() {
+		let marker = |s: &str| (s.to_string(), true);
+		let text = |s: &str| (s.to_string(), false);
+
+		assert_eq!(mark_whitespace("a b"), vec![text("a b")]);
+		assert_eq!(
+			mark_whitespace("\ta b \t "),
+			vec![marker("→ "), text("a b"), marker("·→ ·")]
+		);
+		assert_eq!(mark_whitespace("  "), vec![marker("··")]);
+		assert!(mark_whitespace("").is_empty());
+	}
+
+	#[test]
+	fn test_permalink() {
+		assert_eq!(
+			permalink(
+				"{remote}/blob/{commit}/{path}#L{line}",
+				"https://host/owner/repo",
+				"abc123",
+				"src/a file#1.rs",
+				7,
+			),
+			"https://host/owner/repo/blob/abc123/src/a%20file%231.rs#L7"
+		);
+	}
+
+	#[test]
+	fn test_expand_placeholders() {
+		let fields = [
+			("summary", String::from("use {author} {x")),
+			("author", String::from("jane")),
+		];
+
+		assert_eq!(
+			expand_placeholders(
+				"{summary} ({author}) {other}",
+				&fields
+			),
+			"use {author} {x (jane) {other}"
+		);
+		assert_eq!(
+			expand_placeholders("{{author}} {", &fields),
+			"{jane} {"
+		);
+	}
+
+	#[test]
+	fn test_wrap_parts() {
+		let part = |s: &str, tag: u8| (s.to_string(), tag);
+
+		assert_eq!(
+			wrap_parts(&[part("abcdef", 0)], 4),
+			vec![vec![part("abcd", 0)], vec![part("ef", 0)]]
+		);
+		assert_eq!(
+			wrap_parts(&[part("ab", 0), part("cdef", 1)], 3),
+			vec![
+				vec![part("ab", 0), part("c", 1)],
+				vec![part("def", 1)]
+			]
+		);
+		// wide characters don't get cut in half
+		assert_eq!(
+			wrap_parts(&[part("a漢字", 0)], 4),
+			vec![vec![part("a漢", 0)], vec![part("字", 0)]]
+		);
+		assert_eq!(wrap_parts::<u8>(&[], 4), vec![vec![]]);
+		assert_eq!(
+			wrap_parts(&[part("ab", 0)], 0),
+			vec![vec![part("a", 0)], vec![part("b", 0)]]
+		);
+	}
+
+	#[test]
+	#[allow(clippy::single_range_in_vec_init)]
+	fn test_word_diff() {
+		assert_eq!(
+			word_diff("let a = 1;", "let b = 1;"),
+			Some((vec![4..5], vec![4..5]))
+		);
+		assert_eq!(
+			word_diff("foo(a, b)", "foo(a, b) + c"),
+			Some((vec![], vec![9..13]))
+		);
+		// tokens are counted in chars, not bytes
+		assert_eq!(
+			word_diff("äö x y", "äö z y"),
+			Some((vec![3..4], vec![3..4]))
+		);
+		assert_eq!(word_diff("foo bar", "baz qux"), None);
+	}
 }
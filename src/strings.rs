@@ -1,6 +1,6 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, path::Path};
 
-use asyncgit::sync::CommitId;
+use asyncgit::sync::{CommitId, RepoState};
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
@@ -15,6 +15,7 @@ pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
 pub static PULL_POPUP_MSG: &str = "Pull";
 pub static FETCH_POPUP_MSG: &str = "Fetch";
+pub static PULL_REQUEST_POPUP_MSG: &str = "Fetch Pull Request";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
 pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
 pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
@@ -28,28 +29,98 @@ pub static PUSH_TAGS_STATES_PUSHING: &str = "pushing";
 pub static PUSH_TAGS_STATES_DONE: &str = "done";
 
 pub static POPUP_TITLE_SUBMODULES: &str = "Submodules";
+pub static POPUP_TITLE_REMOTES: &str = "Remotes";
+pub static POPUP_TITLE_WORKTREES: &str = "Worktrees";
+pub static WORKTREE_LOCKED: &str = " [locked]";
+pub static WORKTREE_PRUNABLE: &str = " [prunable]";
+pub static TITLE_LINKED_WORKTREE: &str = " (worktree)";
 pub static POPUP_TITLE_FUZZY_FIND: &str = "Fuzzy Finder";
+pub static POPUP_TITLE_FUZZY_FIND_PREVIEW: &str = "Preview";
+pub static POPUP_TITLE_GREP: &str = "Search Files";
+pub static POPUP_GREP_PLACEHOLDER: &str = "text to search for..";
 
 pub static POPUP_FAIL_COPY: &str = "Failed to copy text";
+pub static POPUP_FAIL_GREP: &str = "Failed to search files";
+pub static SUBMODULE_DETACHED_HEAD: &str =
+	"this submodule is at a detached HEAD, check out a branch before committing or the commits end up on no branch";
+pub static PERMALINK_NO_REMOTE: &str =
+	"no remote with a web page to link to";
+pub static PERMALINK_NOT_COMMITTED: &str =
+	"line is not committed yet, nothing to link to";
 //pub static POPUP_SUCCESS_COPY: &str = "Copied Text";
 
 pub mod symbol {
 	pub const WHITESPACE: &str = "\u{00B7}"; //·
 	pub const CHECKMARK: &str = "\u{2713}"; //✓
+	pub const CROSSMARK: &str = "\u{2717}"; //✗
 	pub const SPACE: &str = "\u{02FD}"; //˽
 	pub const EMPTY_SPACE: &str = " ";
 	pub const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}"; //▸
 	pub const FOLDER_ICON_EXPANDED: &str = "\u{25be}"; //▾
 	pub const EMPTY_STR: &str = "";
 	pub const ELLIPSIS: char = '\u{2026}'; // …
+	pub const NOTE: &str = "\u{270E}"; //✎
 }
 
 pub fn title_branches() -> String {
 	"Branches".to_string()
 }
+pub fn title_branches_filter(filter: &str, typing: bool) -> String {
+	format!("[filter: {filter}{}]", if typing { "_" } else { "" })
+}
 pub fn title_tags() -> String {
 	"Tags".to_string()
 }
+pub fn title_reflog(reference: &str) -> String {
+	format!("Reflog: {reference}")
+}
+pub fn title_rebase_todo() -> String {
+	"Interactive Rebase".to_string()
+}
+pub fn repo_state_conflicts(count: usize) -> String {
+	if count == 1 {
+		"1 conflicted file".to_string()
+	} else {
+		format!("{count} conflicted files")
+	}
+}
+/// next steps out of a pending merge, rebase or revert, with the
+/// keys the status tab (and the log for skipping) react to
+pub fn repo_state_hint(
+	key_config: &SharedKeyConfig,
+	state: &RepoState,
+) -> String {
+	let keys = &key_config.keys;
+	match state {
+		RepoState::Clean => String::new(),
+		RepoState::Merge | RepoState::Revert => format!(
+			"resolve conflicts and stage them, then commit [{}] to conclude or abort [{}]",
+			key_config.get_hint(keys.open_commit),
+			key_config.get_hint(keys.abort_merge),
+		),
+		RepoState::Rebase => format!(
+			"resolve conflicts and stage them, then continue [{}] or abort [{}], skip the current commit from the log [{}]",
+			key_config.get_hint(keys.rebase_branch),
+			key_config.get_hint(keys.abort_merge),
+			key_config.get_hint(keys.rebase_skip),
+		),
+		RepoState::Other => {
+			"gitui cannot resolve this state, use git to continue or abort".to_string()
+		}
+	}
+}
+/// status header branch of a repo without commits
+pub static HEADER_UNBORN_HEAD: &str = "unborn";
+/// short repo state for the status header
+pub fn header_repo_state(state: &RepoState) -> &'static str {
+	match state {
+		RepoState::Clean => "clean",
+		RepoState::Merge => "merging",
+		RepoState::Rebase => "rebasing",
+		RepoState::Revert => "reverting",
+		RepoState::Other => "busy",
+	}
+}
 pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 	"Unstaged Changes".to_string()
 }
@@ -108,6 +179,14 @@ pub fn commit_reword_title() -> String {
 	"Reword Commit".to_string()
 }
 
+pub fn commit_title_marked(files: usize) -> String {
+	if files == 1 {
+		"Commit (1 marked file)".to_string()
+	} else {
+		format!("Commit ({files} marked files)")
+	}
+}
+
 pub fn commit_title_merge() -> String {
 	"Commit (Merge)".to_string()
 }
@@ -177,6 +256,60 @@ pub fn confirm_msg_merge(
 pub fn confirm_title_abortmerge() -> String {
 	"Abort merge?".to_string()
 }
+pub fn confirm_title_checkout_pull_request() -> String {
+	"Checkout".to_string()
+}
+pub fn confirm_msg_checkout_pull_request(branch: &str) -> String {
+	format!("Fetched into branch '{branch}'. Check it out now?")
+}
+pub fn confirm_title_init_submodule() -> String {
+	"Init submodule?".to_string()
+}
+pub fn confirm_msg_init_submodule(path: &str) -> String {
+	format!("Submodule '{path}' is not initialized. Run 'git submodule update --init' to open it?")
+}
+pub fn submodule_not_a_repo(path: &Path) -> String {
+	format!("submodule '{}' is not a repository", path.display())
+}
+pub fn confirm_title_delete_remote() -> String {
+	"Delete remote?".to_string()
+}
+pub fn confirm_msg_delete_remote(name: &str) -> String {
+	format!("Remove remote '{name}' including its remote tracking branches?")
+}
+pub fn auth_retry_cancelled(operation: &str) -> String {
+	format!("{operation} failed:\nauthentication cancelled")
+}
+pub fn confirm_title_quit_pending() -> String {
+	"Quit?".to_string()
+}
+pub fn confirm_msg_quit_pending(
+	key_config: &SharedKeyConfig,
+	operations: &[&str],
+) -> String {
+	format!(
+		"Still running: {}.\nQuitting now can leave the repository in an inconsistent state. Confirm or press [{}] again to quit anyway.",
+		operations.join(", "),
+		key_config.get_hint(key_config.keys.quit),
+	)
+}
+pub fn confirm_title_amend() -> String {
+	"Amend last commit?".to_string()
+}
+pub fn confirm_msg_amend(
+	summary: &str,
+	is_merge: bool,
+	is_pushed: bool,
+) -> String {
+	let mut msg = summary.to_string();
+	if is_merge {
+		msg.push_str("\n\nHEAD is a merge commit, amending it keeps all of its parents.");
+	}
+	if is_pushed {
+		msg.push_str("\n\nHEAD is already pushed, a force-push will be needed afterwards.");
+	}
+	msg
+}
 pub fn confirm_title_abortrevert() -> String {
 	"Abort revert?".to_string()
 }
@@ -194,6 +327,54 @@ pub fn confirm_msg_abortrebase() -> String {
 pub fn confirm_msg_reset() -> String {
 	"confirm file reset?".to_string()
 }
+pub fn confirm_title_restore() -> String {
+	"Restore".to_string()
+}
+pub fn confirm_msg_restore(files: usize, untracked: usize) -> String {
+	format!(
+		"discard all changes (staged and unstaged) of {files} file{} and restore {} to HEAD{}?",
+		if files == 1 { "" } else { "s" },
+		if files == 1 { "it" } else { "them" },
+		untracked_suffix("delete", untracked)
+	)
+}
+/// `, <verb> n untracked files` if there are any
+fn untracked_suffix(verb: &str, untracked: usize) -> String {
+	if untracked == 0 {
+		return String::new();
+	}
+	format!(
+		", {verb} {untracked} untracked file{}",
+		if untracked == 1 { "" } else { "s" }
+	)
+}
+pub fn confirm_title_delete_untracked() -> String {
+	"Delete".to_string()
+}
+pub fn confirm_msg_delete_untracked(files: usize) -> String {
+	format!(
+		"{} untracked and cannot be restored, delete {}?",
+		if files == 1 {
+			"file is".to_string()
+		} else {
+			format!("{files} files are")
+		},
+		if files == 1 { "it" } else { "them" }
+	)
+}
+pub fn restored_files(files: usize, untracked: usize) -> String {
+	format!(
+		"Restored {files} file{} to HEAD{}",
+		if files == 1 { "" } else { "s" },
+		untracked_suffix("deleted", untracked)
+	)
+}
+pub fn deleted_untracked_files(files: usize) -> String {
+	format!(
+		"Deleted {files} untracked file{}",
+		if files == 1 { "" } else { "s" }
+	)
+}
 pub fn confirm_msg_reset_lines(lines: usize) -> String {
 	format!(
 		"are you sure you want to discard {lines} selected lines?"
@@ -221,6 +402,13 @@ pub fn confirm_msg_stashpop(_key_config: &SharedKeyConfig) -> String {
 	"The stash will be applied and removed from the stash list. Confirm stash pop?"
         .to_string()
 }
+pub fn confirm_msg_reset_hard(commit: &str) -> String {
+	format!("discard all local changes and reset hard to {commit}?")
+}
+/// asks to type `expected` before a strict confirmation is accepted
+pub fn confirm_type_to_confirm(expected: &str) -> String {
+	format!("type '{expected}' to confirm:")
+}
 pub fn confirm_msg_resethunk(
 	_key_config: &SharedKeyConfig,
 ) -> String {
@@ -294,6 +482,9 @@ pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
 pub fn tag_popup_name_title() -> String {
 	"Tag".to_string()
 }
+pub fn tag_popup_name_title_at(commit: &CommitId) -> String {
+	format!("Tag {}", commit.get_short_string())
+}
 pub fn tag_popup_name_msg() -> String {
 	"type tag name".to_string()
 }
@@ -303,6 +494,12 @@ pub fn tag_popup_annotation_title(name: &str) -> String {
 pub fn tag_popup_annotation_msg() -> String {
 	"type tag annotation".to_string()
 }
+pub fn note_popup_title(commit: &CommitId) -> String {
+	format!("Note {}", commit.get_short_string())
+}
+pub fn note_popup_msg() -> String {
+	"type note, empty removes it".to_string()
+}
 pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
 	"Stashes".to_string()
 }
@@ -330,6 +527,70 @@ pub fn create_branch_popup_msg(
 ) -> String {
 	"type branch name".to_string()
 }
+pub fn pull_request_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Pull Request".to_string()
+}
+pub fn pull_request_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type pull request number".to_string()
+}
+pub fn stage_pattern_popup_title(stage: bool) -> String {
+	if stage {
+		"Stage matching files".to_string()
+	} else {
+		"Unstage matching files".to_string()
+	}
+}
+pub fn stage_pattern_popup_msg(_key_config: &SharedKeyConfig) -> String {
+	"pattern, e.g. *.rs".to_string()
+}
+pub fn stage_pattern_result(
+	pattern: &str,
+	count: usize,
+	stage: bool,
+) -> String {
+	format!(
+		"{} {} file(s) matching '{}'",
+		if stage { "staged" } else { "unstaged" },
+		count,
+		pattern
+	)
+}
+/// `12345` -> `12,345`
+fn thousands(n: usize) -> String {
+	let digits = n.to_string();
+	let mut out =
+		String::with_capacity(digits.len() + digits.len() / 3);
+	for (idx, c) in digits.chars().enumerate() {
+		if idx > 0 && (digits.len() - idx) % 3 == 0 {
+			out.push(',');
+		}
+		out.push(c);
+	}
+	out
+}
+pub fn changes_untracked_collapsed_title(
+	title: &str,
+	untracked: usize,
+	key_config: &SharedKeyConfig,
+) -> String {
+	format!(
+		"{} -- {} untracked files [{}] to expand",
+		title,
+		thousands(untracked),
+		key_config.get_hint(key_config.keys.status_toggle_untracked),
+	)
+}
+pub fn copy_clipboard_done(field: &str, commits: usize) -> String {
+	if commits == 1 {
+		format!("Copied {field} to clipboard")
+	} else {
+		format!("Copied {field} of {commits} commits to clipboard")
+	}
+}
 pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
 	"Username".to_string()
 }
@@ -353,6 +614,22 @@ pub fn rename_branch_popup_msg(
 ) -> String {
 	"new branch name".to_string()
 }
+pub fn remote_name_popup_title(rename: bool) -> String {
+	if rename {
+		"Rename Remote".to_string()
+	} else {
+		"Add Remote".to_string()
+	}
+}
+pub fn remote_name_popup_msg() -> String {
+	"remote name".to_string()
+}
+pub fn remote_url_popup_title(name: &str) -> String {
+	format!("Url of '{name}'")
+}
+pub fn remote_url_popup_msg() -> String {
+	"https://host/repo.git or git@host:repo.git".to_string()
+}
 
 //pub fn copy_success(s: &str) -> String {
 //	format!("{POPUP_SUCCESS_COPY} \"{s}\"")
@@ -420,6 +697,7 @@ pub mod commit {
 pub mod commands {
 	use crate::components::CommandText;
 	use crate::keys::SharedKeyConfig;
+	use crate::options::BlameAuthor;
 
 	static CMD_GROUP_GENERAL: &str = "-- General --";
 	static CMD_GROUP_DIFF: &str = "-- Diff --";
@@ -464,6 +742,28 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn filter_branches(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Filter [{}]",
+				key_config.get_hint(key_config.keys.filter_branches_init)
+			),
+			"narrow down the list while typing a branch name",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn clear_branch_filter(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clear filter [{}]",
+				key_config.get_hint(key_config.keys.exit_popup)
+			),
+			"show all branches again",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn assign_shortcut(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -555,6 +855,21 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn toggle_status_header(
+		key_config: &SharedKeyConfig,
+		shown: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} header [{}]",
+				if shown { "Hide" } else { "Show" },
+				key_config
+					.get_hint(key_config.keys.toggle_status_header),
+			),
+			"branch, upstream and repo state above the tabs",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -618,6 +933,137 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn commit_list_mark_range(
+		key_config: &SharedKeyConfig,
+		started: bool,
+	) -> CommandText {
+		CommandText::new(
+			if started {
+				format!(
+					"Mark range [{}]",
+					key_config
+						.get_hint(key_config.keys.log_mark_range),
+				)
+			} else {
+				format!(
+					"Range start [{}]",
+					key_config
+						.get_hint(key_config.keys.log_mark_range_start),
+				)
+			},
+			"toggle marks of all commits between range start and selection",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_signature(
+		key_config: &SharedKeyConfig,
+		showing_signature: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} signatures [{}]",
+				if showing_signature { "Hide" } else { "Show" },
+				key_config
+					.get_hint(key_config.keys.log_toggle_signature),
+			),
+			"show whether commits are signed and verified (slow)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_file_count(
+		key_config: &SharedKeyConfig,
+		showing_file_count: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} files count [{}]",
+				if showing_file_count { "Hide" } else { "Show" },
+				key_config
+					.get_hint(key_config.keys.log_toggle_file_count),
+			),
+			"show how many files each commit changed",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_local_branches(
+		key_config: &SharedKeyConfig,
+		showing: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} local branches [{}]",
+				if showing { "Hide" } else { "Show" },
+				key_config.get_hint(
+					key_config.keys.log_toggle_local_branches
+				),
+			),
+			"show local branches ({name}) next to their commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_remote_branches(
+		key_config: &SharedKeyConfig,
+		showing: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} remote branches [{}]",
+				if showing { "Hide" } else { "Show" },
+				key_config.get_hint(
+					key_config.keys.log_toggle_remote_branches
+				),
+			),
+			"show remote branches ([name]) next to their commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_tags(
+		key_config: &SharedKeyConfig,
+		showing: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} tags [{}]",
+				if showing { "Hide" } else { "Show" },
+				key_config.get_hint(key_config.keys.log_toggle_tags),
+			),
+			"show tags (<name>) next to their commits",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_exclude_branch(
+		key_config: &SharedKeyConfig,
+		excluding: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if excluding { "Show all" } else { "Exclude branch" },
+				key_config.get_hint(key_config.keys.log_exclude_branch),
+			),
+			"only list commits not reachable from a picked branch (base..branch)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn commit_list_toggle_committer(
+		key_config: &SharedKeyConfig,
+		showing_committer: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show {} [{}]",
+				if showing_committer {
+					"author"
+				} else {
+					"committer"
+				},
+				key_config
+					.get_hint(key_config.keys.log_toggle_committer),
+			),
+			"switch the author column between author and committer",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn copy_below(key_config: &SharedKeyConfig, l:usize) -> CommandText {
 		CommandText::new(
 			format!(
@@ -716,15 +1162,17 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn search_all(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn search_last_scope(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Auth/Msg/SHA [{}]",
+				"Last scope [{}]",
 				key_config.get_hint(
 					key_config.keys.start_search_forward_init
 				),
 			),
-			"Start searching by Author/Message/SHA",
+			"Start searching with the last used Author/Message/SHA options",
 			CMD_GROUP_GENERAL,
 		)
 	}
@@ -740,6 +1188,20 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn start_search_backward(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search back [{}]",
+				key_config.get_hint(
+					key_config.keys.start_search_backward_init
+				),
+			),
+			"Start searching backward...",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn filter_by_path(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -752,6 +1214,19 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn path_filter_jump(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next/Prev for path [{}{}]",
+				key_config.get_hint(key_config.keys.log_path_next),
+				key_config.get_hint(key_config.keys.log_path_prev),
+			),
+			"jump to the next (older) or previous (newer) commit touching the filtered path",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn search_author(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -837,6 +1312,19 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn copy_clipboard_date(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Date [{}]",
+				key_config
+					.get_hint(key_config.keys.copy_clipboard_date),
+			),
+			"copy date of the selected commit into clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn copy_clipboard_message(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -863,6 +1351,19 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn copy_clipboard_changelog(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Changelog [{}]",
+				key_config
+					.get_hint(key_config.keys.copy_clipboard_changelog),
+			),
+			"copy a changelog line per selected commit into clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn copy_path(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -873,6 +1374,28 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn reveal_file(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reveal [{}]",
+				key_config.get_hint(key_config.keys.reveal_file),
+			),
+			"open the folder of the selected file in the file manager",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy_abs_path(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Abs Path [{}]",
+				key_config.get_hint(key_config.keys.copy_abs_path),
+			),
+			"copy absolute path of the selected file to clipboard",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -910,63 +1433,210 @@ pub mod commands {
 			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn diff_hunk_add(
+	pub fn diff_scroll_reset(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Add hunk [{}]",
-				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+				"Scroll to line start [{}]",
+				key_config.get_hint(key_config.keys.diff_scroll_reset),
 			),
-			"adds selected hunk to stage",
+			"reset horizontal scroll of diff",
 			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn diff_hunk_revert(
+	pub fn diff_conflict_marker(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset hunk [{}]",
-				key_config
-					.get_hint(key_config.keys.status_reset_item),
+				"Conflict next/prev [{}{}]",
+				key_config.get_hint(key_config.keys.diff_conflict_next),
+				key_config.get_hint(key_config.keys.diff_conflict_prev),
 			),
-			"reverts selected hunk",
+			"jump to next/previous conflict marker (wraps around)",
 			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn diff_lines_revert(
+	pub fn diff_toggle_whitespace(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset lines [{}]",
-				key_config.get_hint(key_config.keys.diff_reset_lines),
+				"Whitespace [{}]",
+				key_config
+					.get_hint(key_config.keys.diff_toggle_whitespace),
 			),
-			"resets selected lines",
+			"toggle ignoring whitespace for this file only",
 			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn diff_lines_stage(
+	pub fn diff_toggle_line_numbers(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stage lines [{}]",
-				key_config.get_hint(key_config.keys.diff_stage_lines),
+				"Line numbers [{}]",
+				key_config
+					.get_hint(key_config.keys.diff_toggle_line_numbers),
 			),
-			"stage selected lines",
+			"toggle relative (or absolute) and old/new file line numbers",
 			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn diff_lines_unstage(
+	pub fn diff_toggle_show_whitespace(
 		key_config: &SharedKeyConfig,
+		showing: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Unstage lines [{}]",
-				key_config.get_hint(key_config.keys.diff_stage_lines),
+				"{} whitespace [{}]",
+				if showing { "Hide" } else { "Show" },
+				key_config.get_hint(
+					key_config.keys.diff_toggle_show_whitespace
+				),
+			),
+			"mark tabs and trailing spaces",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_toggle_wrap(
+		key_config: &SharedKeyConfig,
+		wrapping: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} lines [{}]",
+				if wrapping { "Unwrap" } else { "Wrap" },
+				key_config.get_hint(key_config.keys.diff_toggle_wrap),
+			),
+			"wrap long lines instead of scrolling horizontally",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_search_scope(
+		key_config: &SharedKeyConfig,
+		scope: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search {} [{}]",
+				scope,
+				key_config.get_hint(key_config.keys.diff_search_scope),
+			),
+			"search all, only added, only deleted or only changed lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_save_patch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Save patch [{}]",
+				key_config.get_hint(key_config.keys.diff_save_patch),
+			),
+			"write the diff to <file name>.patch in the repository root",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_search_regex(
+		key_config: &SharedKeyConfig,
+		regex: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if regex { "Plain text" } else { "Regex" },
+				key_config.get_hint(key_config.keys.diff_search_regex),
+			),
+			"search for plain text or a regular expression",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_add(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"adds selected hunk to stage",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunks_add_all(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add all hunks [{}]",
+				key_config
+					.get_hint(key_config.keys.diff_stage_all_hunks),
+			),
+			"adds all remaining hunks of the file to stage",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunks_remove_all(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remove all hunks [{}]",
+				key_config
+					.get_hint(key_config.keys.diff_stage_all_hunks),
+			),
+			"removes all hunks of the file from stage",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_revert(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
+			),
+			"reverts selected hunk",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_revert(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset lines [{}]",
+				key_config.get_hint(key_config.keys.diff_reset_lines),
+			),
+			"resets selected lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_stage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage lines [{}]",
+				key_config.get_hint(key_config.keys.diff_stage_lines),
+			),
+			"stage selected lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_unstage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage lines [{}]",
+				key_config.get_hint(key_config.keys.diff_stage_lines),
 			),
 			"unstage selected lines",
 			CMD_GROUP_DIFF,
@@ -998,6 +1668,73 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+
+	pub fn toggle_fuzzy_finder_preview(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Preview [{}]",
+				key_config.get_hint(key_config.keys.file_find_preview),
+			),
+			"show or hide the first lines of the selected file",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn grep_files(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search files [{}]",
+				key_config.get_hint(key_config.keys.grep_files),
+			),
+			"search the contents of the files for a text",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn grep_search(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Search [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"search the files for the text (ignoring case unless it contains uppercase letters)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn grep_open_match(
+		key_config: &SharedKeyConfig,
+		in_commit: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if in_commit { "Blame" } else { "Edit" },
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"open the file of the selected match",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn grep_regex(
+		key_config: &SharedKeyConfig,
+		regex: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if regex { "Plain text" } else { "Regex" },
+				key_config
+					.get_hint(key_config.keys.diff_search_regex),
+			),
+			"search the files for plain text or a regular expression",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1116,6 +1853,84 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn view_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktrees [{}]",
+				key_config.get_hint(key_config.keys.view_worktrees),
+			),
+			"switch to another worktree of the repo",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn open_worktree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"open worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn view_remotes(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remotes [{}]",
+				key_config.get_hint(key_config.keys.view_remotes),
+			),
+			"add, rename or remove remotes",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn remote_add(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.remote_add),
+			),
+			"add a remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn remote_rename(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rename [{}]",
+				key_config.get_hint(key_config.keys.remote_rename),
+			),
+			"rename selected remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn remote_edit_url(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit Url [{}]",
+				key_config.get_hint(key_config.keys.remote_edit_url),
+			),
+			"change url of selected remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn remote_delete(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Delete [{}]",
+				key_config.get_hint(key_config.keys.remote_delete),
+			),
+			"remove selected remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn rebase_interactive(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1127,6 +1942,20 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn rebase_interactive_onto_marked(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rebase onto marked [{}]",
+				key_config.get_hint(
+					key_config.keys.rebase_interactive_onto_marked
+				),
+			),
+			"interactive rebase of everything above the marked commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
 
 	pub fn continue_rebase(
 		key_config: &SharedKeyConfig,
@@ -1167,6 +1996,31 @@ pub mod commands {
 		)
 	}
 
+	pub fn rebase_autosquash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Autosquash selected [{}]",
+				key_config
+					.get_hint(key_config.keys.rebase_autosquash_marked),
+			),
+			"squash selected fixup!/squash! commits into their targets",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn commit_fixup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fixup commit [{}]",
+				key_config.get_hint(key_config.keys.commit_fixup),
+			),
+			"commit staged changes as fixup! of the selected commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
 	pub fn abort_rebase(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1200,6 +2054,26 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn execute_command_marked(
+		key_config: &SharedKeyConfig,
+		running: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if running {
+					"Abort batch"
+				} else {
+					"Exec per marked"
+				},
+				key_config.get_hint(
+					key_config.keys.run_external_command_marked
+				),
+			),
+			"Execute command once per marked commit, replacing {commit}",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn delete_command(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1340,7 +2214,7 @@ pub mod commands {
 				"Amend [{}]",
 				key_config.get_hint(key_config.keys.commit_amend),
 			),
-			"amend last commit (available in commit popup)",
+			"amend last commit with the staged changes",
 			CMD_GROUP_COMMIT_POPUP,
 		)
 	}
@@ -1375,6 +2249,21 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn toggle_untracked(
+		key_config: &SharedKeyConfig,
+		expanded: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} untracked [{}]",
+				if expanded { "Collapse" } else { "Expand" },
+				key_config
+					.get_hint(key_config.keys.status_toggle_untracked),
+			),
+			"show or hide the list of many untracked files",
+			CMD_GROUP_CHANGES,
+		)
+	}
 	pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1399,6 +2288,36 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn stage_pattern(
+		key_config: &SharedKeyConfig,
+		stage: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} by Pattern [{}]",
+				if stage { "Stage" } else { "Unstage" },
+				key_config
+					.get_hint(key_config.keys.status_stage_pattern),
+			),
+			"(un)stage all files matching a pattern like *.rs",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn stage_pattern_confirm_msg(
+		key_config: &SharedKeyConfig,
+		stage: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if stage { "Stage" } else { "Unstage" },
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"(un)stage matching files",
+			CMD_GROUP_CHANGES,
+		)
+		.hide_help()
+	}
 	pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1409,6 +2328,16 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn mark_file(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Mark [{}]",
+				key_config.get_hint(key_config.keys.status_mark_file),
+			),
+			"mark staged file, a commit then only takes the marked files",
+			CMD_GROUP_CHANGES,
+		)
+	}
 	pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1420,6 +2349,17 @@ pub mod commands {
 			CMD_GROUP_CHANGES,
 		)
 	}
+	pub fn restore_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Restore [{}]",
+				key_config
+					.get_hint(key_config.keys.status_restore_item),
+			),
+			"restore selected file or path to HEAD, dropping staged changes too (deletes untracked files)",
+			CMD_GROUP_CHANGES,
+		)
+	}
 	pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1631,6 +2571,20 @@ pub mod commands {
 		)
 	}
 
+	pub fn options_set_number(
+		key_config: &SharedKeyConfig,
+		num: &str,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Set to {} [0-9 {}]",
+				num,
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"type a value and confirm to set it directly",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn jump_to_line(key_config: &SharedKeyConfig, num: &str) -> CommandText {
 		CommandText::new(
 			format!(
@@ -1643,10 +2597,11 @@ pub mod commands {
 		)
 	}
 
-	pub fn cancel_search_init(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn cancel_search_init(key_config: &SharedKeyConfig, backward: bool) -> CommandText {
 		CommandText::new(
 			format!(
-				"Cancel search [{}]",
+				"Cancel {} search [{}]",
+				if backward { "backward" } else { "forward" },
 				key_config.get_hint(key_config.keys.exit_popup),
 			),
 			"Cancel current search initiation",
@@ -1654,11 +2609,12 @@ pub mod commands {
 		)
 	}
 
-	pub fn cancel_search_init_str(key_config: &SharedKeyConfig, s: &str) -> CommandText {
+	pub fn cancel_search_init_str(key_config: &SharedKeyConfig, s: &str, scope: &str) -> CommandText {
 		CommandText::new(
 			format!(
-				"Cancel search '{}' [{}]",
+				"Cancel search '{}'{} [{}]",
 				s,
+				scope,
 				key_config.get_hint(key_config.keys.exit_popup),
 			),
 			"Cancel current search initiation",
@@ -1678,11 +2634,12 @@ pub mod commands {
 		)
 	}
 
-	pub fn search_for_text_next(key_config: &SharedKeyConfig, num: &str) -> CommandText {
+	pub fn search_for_text_next(key_config: &SharedKeyConfig, num: &str, scope: &str) -> CommandText {
 		CommandText::new(
 			format!(
-				"Search next: '{}' [{}]",
+				"Search next: '{}'{} [{}]",
 				num,
+				scope,
 				key_config.get_hint(key_config.keys.search_next),
 			),
 			"Search for the next text occurence",
@@ -1690,11 +2647,12 @@ pub mod commands {
 		)
 	}
 
-	pub fn search_for_text_prev(key_config: &SharedKeyConfig, num: &str) -> CommandText {
+	pub fn search_for_text_prev(key_config: &SharedKeyConfig, num: &str, scope: &str) -> CommandText {
 		CommandText::new(
 			format!(
-				"Search prev: '{}' [{}]",
+				"Search prev: '{}'{} [{}]",
 				num,
+				scope,
 				key_config.get_hint(key_config.keys.search_prev),
 			),
 			"Search for the previous text occurence",
@@ -1736,6 +2694,18 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn log_edit_note(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Note [{}]",
+				key_config.get_hint(key_config.keys.log_edit_note),
+			),
+			"view or edit the note of a commit",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn log_checkout_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1761,6 +2731,55 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn inspect_parent_commit(
+		key_config: &SharedKeyConfig,
+		parent: usize,
+		parents: usize,
+	) -> CommandText {
+		let label = if parents > 1 {
+			format!("Parent {parent}/{parents}")
+		} else {
+			"Parent".to_string()
+		};
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				label,
+				key_config
+					.get_hint(key_config.keys.commit_history_next),
+			),
+			"inspect parent commit (type its number first to pick one of a merge's parents)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn inspect_diff_parent(
+		key_config: &SharedKeyConfig,
+		parent: usize,
+		parents: usize,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Diff to parent {parent}/{parents} [{}]",
+				key_config
+					.get_hint(key_config.keys.inspect_diff_parent),
+			),
+			"diff the merge against its next parent (type a parent's number first to pick it)",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn inspect_child_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Child [{}]",
+				key_config
+					.get_hint(key_config.keys.inspect_child_commit),
+			),
+			"inspect child commit (back towards HEAD)",
+			CMD_GROUP_LOG,
+		)
+	}
 	pub fn revert_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1819,6 +2838,17 @@ pub mod commands {
 			CMD_GROUP_LOG,
 		)
 	}
+	pub fn note_save(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"save note",
+			CMD_GROUP_LOG,
+		)
+	}
+
 	pub fn tag_commit_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1845,6 +2875,31 @@ pub mod commands {
 		)
 	}
 
+	pub fn pull_request_confirm_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fetch [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"fetch pull request",
+			CMD_GROUP_GENERAL,
+		)
+		.hide_help()
+	}
+	pub fn status_fetch_pull_request(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fetch PR [{}]",
+				key_config.get_hint(key_config.keys.fetch_pull_request),
+			),
+			"fetch a pull request into a local branch",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn create_branch_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1870,6 +2925,20 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn open_branch_create_from_selected_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Create From Selected [{}]",
+				key_config.get_hint(
+					key_config.keys.create_branch_from_selected
+				),
+			),
+			"create branch at the tip of the selected branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn rename_branch_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1895,6 +2964,65 @@ pub mod commands {
 			CMD_GROUP_BRANCHES,
 		)
 	}
+	pub fn copy_branch_name(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Name [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy branch name to clipboard",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn copy_branch_short_name(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Name w/o Remote [{}]",
+				key_config
+					.get_hint(key_config.keys.branch_copy_short_name),
+			),
+			"copy remote branch name without the remote prefix",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn blame_toggle_author(
+		key_config: &SharedKeyConfig,
+		shown: BlameAuthor,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Show {} [{}]",
+				match shown.next() {
+					BlameAuthor::Name => "Name",
+					BlameAuthor::Email => "Email",
+					BlameAuthor::Both => "Name+Email",
+				},
+				key_config
+					.get_hint(key_config.keys.blame_toggle_author),
+			),
+			"show author name, email or both in blame",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn toggle_branch_details(
+		key_config: &SharedKeyConfig,
+		shown: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} Author/Age [{}]",
+				if shown { "Hide" } else { "Show" },
+				key_config
+					.get_hint(key_config.keys.branch_toggle_details),
+			),
+			"show author and age of the last commit of each branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn delete_branch_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -2010,6 +3138,98 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn rebase_todo_set_op(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		let keys = &key_config.keys;
+		CommandText::new(
+			format!(
+				"Pick/Reword/Edit/Squash/Fixup/Drop [{}{}{}{}{}{}]",
+				key_config.get_hint(keys.rebase_todo_pick),
+				key_config.get_hint(keys.rebase_todo_reword),
+				key_config.get_hint(keys.rebase_todo_edit),
+				key_config.get_hint(keys.rebase_todo_squash),
+				key_config.get_hint(keys.rebase_todo_fixup),
+				key_config.get_hint(keys.rebase_todo_drop),
+			),
+			"change what the rebase does with the selected commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn rebase_todo_move(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Move [{}{}]",
+				key_config
+					.get_hint(key_config.keys.rebase_todo_move_up),
+				key_config
+					.get_hint(key_config.keys.rebase_todo_move_down),
+			),
+			"move the selected commit up or down in the todo list",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn rebase_todo_start(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Rebase [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"run the rebase with this todo list",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn rebase_todo_edit_external(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit in editor [{}]",
+				key_config
+					.get_hint(key_config.keys.open_commit_editor),
+			),
+			"edit the todo list in the external editor, then rebase",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn rebase_todo_abort(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"abort the rebase, nothing gets rewritten",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy_permalink(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Link [{}]",
+				key_config.get_hint(key_config.keys.copy_permalink),
+			),
+			"copy a web link to the selected line on the remote's forge",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn view_reflog(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reflog [{}]",
+				key_config.get_hint(key_config.keys.view_reflog),
+			),
+			"show where HEAD (or the selected branch) pointed to before",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn delete_tag_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -6,15 +6,19 @@ use crate::{
 		ExternalSearchRequest, FileTreeOpen, InspectCommitOpen,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::{ListState, SharedOptions},
 	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
 	strings, try_or_popup,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	filter_compose_and,
 	sync::{self, BranchInfo, CommitId, RepoPathRef, Tags},
-	AsyncGitNotification, AsyncLog, CommitFilesParams, FetchStatus,
+	AsyncCommitLookupJob, AsyncCommitSearchJob, AsyncGitNotification,
+	AsyncLog, CommitFilesParams, CommitLookup, CommitLookupResult,
+	FetchStatus,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -26,6 +30,8 @@ use ratatui::{
 use sync::CommitTags;
 
 const SLICE_SIZE: usize = 1200;
+/// commits left below the selection before the next batch is loaded
+const LOAD_MORE_MARGIN: usize = 200;
 
 ///
 pub struct Revlog {
@@ -33,10 +39,17 @@ pub struct Revlog {
 	commit_details: CommitDetailsComponent,
 	list: CommitList,
 	git_log: AsyncLog,
+	git_search: AsyncSingleJob<AsyncCommitSearchJob>,
+	git_signatures: AsyncSingleJob<AsyncCommitLookupJob>,
+	git_file_counts: AsyncSingleJob<AsyncCommitLookupJob>,
+	git_notes: AsyncSingleJob<AsyncCommitLookupJob>,
 	queue: Queue,
 	visible: bool,
 	key_config: SharedKeyConfig,
 	target_branch: Option<(String, CommitId)>,
+	range_base: Option<(String, CommitId)>,
+	/// selection of the last session, applied once the log is in
+	restore: Option<ListState>,
 }
 
 impl Revlog {
@@ -47,7 +60,12 @@ impl Revlog {
 		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
+		let mut git_log =
+			AsyncLog::new(repo.borrow().clone(), sender, None);
+		git_log.set_batch_size(options.borrow().log_batch_size());
+
 		Self {
 			repo: repo.clone(),
 			queue: queue.clone(),
@@ -64,18 +82,67 @@ impl Revlog {
 				theme,
 				queue.clone(),
 				key_config.clone(),
+				options,
 			),
-			git_log: AsyncLog::new(
-				repo.borrow().clone(),
-				sender,
-				None,
-			),
+			git_log,
+			git_search: AsyncSingleJob::new(sender.clone()),
+			git_signatures: AsyncSingleJob::new(sender.clone()),
+			git_file_counts: AsyncSingleJob::new(sender.clone()),
+			git_notes: AsyncSingleJob::new(sender.clone()),
 			visible: false,
 			key_config,
 			target_branch: None,
+			range_base: None,
+			restore: None,
 		}
 	}
 
+	/// selects the commit of `state` once the log is loaded
+	pub fn restore_view_state(&mut self, state: ListState) {
+		self.restore = Some(state);
+	}
+
+	/// commit selected in the log, the restored one until the
+	/// walk has reached it
+	pub fn view_state(&self) -> ListState {
+		self.restore
+			.clone()
+			.unwrap_or_else(|| self.list.view_state())
+	}
+
+	/// waits for the walk to reach the restored commit, loading more
+	/// batches until it shows up. a commit gone since keeps the old
+	/// index instead
+	fn apply_restore(&mut self, count: usize) -> bool {
+		let state = match &self.restore {
+			Some(state) => state,
+			None => return false,
+		};
+
+		let id = state.commit.parse::<CommitId>().ok();
+		let position =
+			id.and_then(|id| self.git_log.search_commit_by_id(&id));
+
+		if position.is_none() {
+			if self.git_log.is_pending() {
+				return false;
+			}
+			// new commits may have pushed it into a later batch
+			if self.git_log.has_more()
+				&& (id.is_some() || state.selection >= count)
+			{
+				self.git_log.load_more();
+				return false;
+			}
+		}
+
+		if let Some(state) = self.restore.take() {
+			self.list.restore_view_state(position, &state);
+		}
+
+		true
+	}
+
 	///
 	pub fn set_target_branch(
 		&mut self,
@@ -86,20 +153,45 @@ impl Revlog {
 			self.target_branch.as_ref().map(|i| i.1.clone()),
 		) {
 			self.list.clear_last_selected_commit();
-			self.list.set_title(
-				self.target_branch
-					.as_ref()
-					.map(|i| i.0.clone())
-					.unwrap_or_default()
-					.into(),
-			);
+			self.update_title();
 			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 		}
 	}
 
+	/// only lists commits not reachable from `base`, like
+	/// `git log base..target`
+	pub fn set_range_base(
+		&mut self,
+		base: Option<(String, CommitId)>,
+	) {
+		self.range_base = base;
+		self.git_log
+			.update_hidden(self.range_base.as_ref().map(|i| i.1));
+		self.update_title();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	///
+	pub const fn has_range_base(&self) -> bool {
+		self.range_base.is_some()
+	}
+
+	fn update_title(&mut self) {
+		let title = log_title(
+			self.range_base.as_ref().map(|i| i.0.as_str()),
+			self.target_branch.as_ref().map(|i| i.0.as_str()),
+			&self.key_config,
+		);
+		self.list.set_title(title.into());
+	}
+
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_log.is_pending()
+			|| self.git_search.is_pending()
+			|| self.git_signatures.is_pending()
+			|| self.git_file_counts.is_pending()
+			|| self.git_notes.is_pending()
 			|| self.commit_details.any_work_pending()
 	}
 
@@ -115,85 +207,66 @@ impl Revlog {
 			if filter_updated {
 				let filter = self.list.get_filter();
 				let path_filter = self.list.get_path_filter();
-				match (filter.is_some(), path_filter.is_some()) {
-					(true, true) => self.git_log.update_filter(Some(
-						filter_compose_and!(filter, path_filter),
-					)),
-					(true, false) => {
+				let merge_filter = self.list.get_merge_filter();
+				match (
+					filter.is_some(),
+					path_filter.is_some(),
+					merge_filter.is_some(),
+				) {
+					(false, false, false) => {
+						self.git_log.update_filter(None)
+					}
+					(true, false, false) => {
 						self.git_log.update_filter(filter)
 					}
-					(false, true) => {
+					(false, true, false) => {
 						self.git_log.update_filter(path_filter)
 					}
-					(false, false) => {
-						self.git_log.update_filter(None)
-					}
+					_ => self.git_log.update_filter(Some(
+						filter_compose_and!(
+							filter,
+							path_filter,
+							merge_filter
+						),
+					)),
 				}
 			}
 			let log_changed =
 				self.git_log.fetch()? == FetchStatus::Started;
+			if log_changed {
+				self.update_notes();
+			}
+
+			let count = self.git_log.count()?;
+			self.list.set_count_total(count);
 
-			self.list.set_count_total(self.git_log.count()?);
+			let restored = self.apply_restore(count);
+
+			if self.git_log.has_more()
+				&& self.list.selection() + LOAD_MORE_MARGIN >= count
+			{
+				self.git_log.load_more();
+			}
+
+			if let Some((first, last)) =
+				self.list.take_range_mark_request()
+			{
+				let ids =
+					self.git_log.get_slice(first, last - first + 1)?;
+				self.list.toggle_marked_range(first, &ids);
+			}
 
 			let ex_req = self.list.has_extended_search_request();
 			if ex_req != ExternalSearchRequest::Empty {
-				let needle = self.list.get_search_needle();
-				let hash_only = self.list.is_search_hash_only();
-				let predicate_hash_only = |commit_id: &CommitId| {
-					commit_id.to_string().contains(&needle)
-				};
-				let predicate = |commit_id: &CommitId| {
-					let commit = sync::get_commit_info(
-						&self.repo.borrow(),
-						commit_id,
-					);
-					if let Ok(c) = commit {
-						self.list.search_commit_check(
-							&needle,
-							&c.author,
-							&c.message,
-							&c.id.to_string(),
-						)
-					} else {
-						false
-					}
-				};
-
-				let ext_search =
-					if ex_req == ExternalSearchRequest::Forward {
-						if hash_only {
-							self.git_log.search_commit_forward(
-								self.list.selection() + 1,
-								predicate_hash_only,
-							)
-						} else {
-							self.git_log.search_commit_forward(
-								self.list.selection() + 1,
-								predicate,
-							)
-						}
-					} else {
-						if hash_only {
-							self.git_log.search_commit_backward(
-								self.list.selection(),
-								predicate_hash_only,
-							)
-						} else {
-							self.git_log.search_commit_backward(
-								self.list.selection(),
-								predicate,
-							)
-						}
-					};
-				if let Some(search_result) = ext_search {
-					self.list.select_entry(search_result);
-				}
+				self.start_search(&ex_req)?;
 			}
 
 			let selection = self.list.selection();
 			let selection_max = self.list.selection_max();
 			if self.list.items().needs_data(selection, selection_max)
-				|| log_changed || filter_updated
+				|| log_changed
+				|| filter_updated
+				|| restored
 			{
 				if let Some(last_commit) =
 					self.list.get_last_selected_commit()
@@ -207,6 +280,8 @@ impl Revlog {
 				self.fetch_commits()?;
 			}
 
+			self.look_up_missing();
+
 			if self.commit_details.is_visible() {
 				let commit = self.selected_commit();
 				let tags = self.selected_commit_tags(&commit);
@@ -243,6 +318,12 @@ impl Revlog {
 			match ev {
 				AsyncGitNotification::CommitFiles
 				| AsyncGitNotification::Log => self.update()?,
+				AsyncGitNotification::CommitSearch => {
+					self.update_search()?;
+				}
+				AsyncGitNotification::CommitLookup => {
+					self.update_lookups()?;
+				}
 				_ => (),
 			}
 		}
@@ -250,6 +331,173 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// hands the rest of the log (loaded or not) to a background
+	/// job instead of only the commits in memory
+	fn start_search(
+		&mut self,
+		direction: &ExternalSearchRequest,
+	) -> Result<()> {
+		let selection = self.list.selection();
+		let candidates: Vec<(usize, CommitId)> =
+			if *direction == ExternalSearchRequest::Forward {
+				let start = selection + 1;
+				self.git_log
+					.get_slice(start, self.git_log.count()?)?
+					.into_iter()
+					.enumerate()
+					.map(|(idx, id)| (start + idx, id))
+					.collect()
+			} else {
+				self.git_log
+					.get_slice(0, selection)?
+					.into_iter()
+					.enumerate()
+					.rev()
+					.collect()
+			};
+
+		self.list.set_search_progress(Some(0));
+		self.git_search.spawn(AsyncCommitSearchJob::new(
+			self.repo.borrow().clone(),
+			candidates,
+			self.list.is_search_hash_only(),
+			self.list.search_predicate(),
+		));
+
+		Ok(())
+	}
+
+	fn update_search(&mut self) -> Result<()> {
+		if let Some(job) = self.git_search.take_last() {
+			self.list.set_search_progress(None);
+			match job.result() {
+				Some(Ok(Some(idx))) => {
+					self.list.select_entry(idx);
+					self.update()?;
+				}
+				Some(Err(e)) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("search failed: {e}"),
+					));
+				}
+				Some(Ok(None)) | None => (),
+			}
+		} else {
+			self.list.set_search_progress(
+				self.git_search.progress().map(|p| p.progress),
+			);
+		}
+
+		Ok(())
+	}
+
+	fn lookup_job(
+		&mut self,
+		lookup: CommitLookup,
+	) -> &mut AsyncSingleJob<AsyncCommitLookupJob> {
+		match lookup {
+			CommitLookup::Signatures => &mut self.git_signatures,
+			CommitLookup::FileCounts => &mut self.git_file_counts,
+			CommitLookup::Notes => &mut self.git_notes,
+		}
+	}
+
+	fn look_up(
+		&mut self,
+		lookup: CommitLookup,
+		commits: Vec<CommitId>,
+	) {
+		let repo = self.repo.borrow().clone();
+		self.lookup_job(lookup)
+			.spawn(AsyncCommitLookupJob::new(repo, lookup, commits));
+	}
+
+	/// one job per column at a time, the next one is started once
+	/// it is done
+	fn look_up_missing(&mut self) {
+		for (lookup, missing) in [
+			(
+				CommitLookup::Signatures,
+				self.list.missing_signatures(),
+			),
+			(
+				CommitLookup::FileCounts,
+				self.list.missing_file_counts(),
+			),
+		] {
+			if !missing.is_empty()
+				&& !self.lookup_job(lookup).is_pending()
+			{
+				self.look_up(lookup, missing);
+			}
+		}
+	}
+
+	fn update_lookups(&mut self) -> Result<()> {
+		let mut columns_changed = false;
+
+		for lookup in [
+			CommitLookup::Signatures,
+			CommitLookup::FileCounts,
+			CommitLookup::Notes,
+		] {
+			let result = self
+				.lookup_job(lookup)
+				.take_last()
+				.and_then(|job| job.result());
+			match result {
+				Some(Ok(CommitLookupResult::Signatures(
+					signatures,
+				))) => {
+					self.list.set_signatures(signatures);
+					columns_changed = true;
+				}
+				Some(Ok(CommitLookupResult::FileCounts(counts))) => {
+					self.list.set_file_counts(counts);
+					columns_changed = true;
+				}
+				Some(Ok(CommitLookupResult::Notes(notes))) => {
+					self.list.set_notes(notes);
+				}
+				Some(Err(e)) => self.lookup_failed(lookup, &e),
+				None => (),
+			}
+		}
+
+		if columns_changed {
+			// looks up the rest around the selection
+			self.update()?;
+		}
+
+		Ok(())
+	}
+
+	/// does not retry on every update
+	fn lookup_failed(
+		&mut self,
+		lookup: CommitLookup,
+		e: &asyncgit::Error,
+	) {
+		match lookup {
+			CommitLookup::Signatures => {
+				self.list.hide_signatures();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("signature verification failed: {e}"),
+				));
+			}
+			CommitLookup::FileCounts => {
+				self.list.hide_file_counts();
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("counting changed files failed: {e}"),
+				));
+			}
+			// keep the marks of the last successful load
+			CommitLookup::Notes => {
+				log::error!("loading commit notes failed: {e}");
+			}
+		}
+	}
+
 	fn fetch_commits(&mut self) -> Result<()> {
 		let want_min =
 			self.list.selection().saturating_sub(SLICE_SIZE / 2);
@@ -270,6 +518,11 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// reloads which commits have a note attached
+	pub fn update_notes(&mut self) {
+		self.look_up(CommitLookup::Notes, Vec::new());
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		self.list.selected_entry().map(|e| e.id)
 	}
@@ -285,6 +538,10 @@ impl Revlog {
 		})
 	}
 
+	pub fn marked_commits(&self) -> Vec<CommitId> {
+		self.list.marked_commits()
+	}
+
 	pub fn select_commit(&mut self, id: CommitId) -> Result<()> {
 		let position = self.git_log.position(id)?;
 
@@ -326,6 +583,22 @@ impl Revlog {
 	}
 }
 
+/// `base..target` while a range is shown, else the target branch,
+/// the plain log title when neither is set
+fn log_title(
+	range_base: Option<&str>,
+	target: Option<&str>,
+	key_config: &SharedKeyConfig,
+) -> String {
+	match (range_base, target) {
+		(Some(base), target) => {
+			format!("{base}..{}", target.unwrap_or("HEAD"))
+		}
+		(None, Some(target)) => target.to_string(),
+		(None, None) => strings::log_title(key_config),
+	}
+}
+
 impl DrawableComponent for Revlog {
 	fn draw<B: Backend>(
 		&self,
@@ -397,6 +670,27 @@ impl Component for Revlog {
 				} else if key_match(k, self.key_config.keys.push) {
 					self.queue.push(InternalEvent::PushTags);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_committer,
+				) {
+					self.list.toggle_committer();
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_signature,
+				) {
+					// only this tab verifies signatures
+					self.list.toggle_signatures();
+					self.update()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_toggle_file_count,
+				) {
+					self.list.toggle_file_counts();
+					self.update()?;
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.log_tag_commit,
@@ -409,6 +703,19 @@ impl Component for Revlog {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_edit_note,
+				) {
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::EditCommitNote(id),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
 				} else if key_match(
 					k,
 					self.key_config.keys.move_right,
@@ -453,6 +760,14 @@ impl Component for Revlog {
 				} else if key_match(k, self.key_config.keys.tags) {
 					self.queue.push(InternalEvent::Tags);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.view_reflog,
+				) {
+					self.queue.push(InternalEvent::ViewReflog(
+						String::from("HEAD"),
+					));
+					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
 					self.key_config.keys.log_reset_comit,
@@ -534,6 +849,33 @@ impl Component for Revlog {
 			self.visible,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_committer(
+				&self.key_config,
+				self.list.shows_committer(),
+			),
+			true,
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_signature(
+				&self.key_config,
+				self.list.shows_signatures(),
+			),
+			true,
+			self.visible || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::commit_list_toggle_file_count(
+				&self.key_config,
+				self.list.shows_file_counts(),
+			),
+			true,
+			self.visible || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::commit_details_open(&self.key_config),
 			true,
@@ -575,6 +917,12 @@ impl Component for Revlog {
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::log_edit_note(&self.key_config),
+			self.selected_commit().is_some(),
+			self.visible || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_checkout_commit(&self.key_config),
 			self.selected_commit().is_some(),
@@ -587,6 +935,12 @@ impl Component for Revlog {
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::view_reflog(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::push_tags(&self.key_config),
 			true,
@@ -645,3 +999,27 @@ impl Component for Revlog {
 		self.list.focused()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_log_title() {
+		let key_config = SharedKeyConfig::default();
+
+		assert_eq!(
+			log_title(None, None, &key_config),
+			strings::log_title(&key_config)
+		);
+		assert_eq!(log_title(None, Some("dev"), &key_config), "dev");
+		assert_eq!(
+			log_title(Some("master"), Some("dev"), &key_config),
+			"master..dev"
+		);
+		assert_eq!(
+			log_title(Some("master"), None, &key_config),
+			"master..HEAD"
+		);
+	}
+}
@@ -6,7 +6,9 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::{ListState, SharedOptions},
 	queue::{InternalEvent, LocalEvent, Queue, SharedLocalQueue},
+	strings,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
@@ -50,6 +52,7 @@ pub struct RevlogExtern {
 	key_config: SharedKeyConfig,
 	focused: Focus,
 	local_queue: SharedLocalQueue,
+	range_queue: SharedLocalQueue,
 	git_local_branches: AsyncSingleJob<AsyncBranchesJob>,
 	git_remote_branches: AsyncSingleJob<AsyncBranchesJob>,
 	git_tags: AsyncTags,
@@ -63,31 +66,40 @@ impl RevlogExtern {
 		sender: &Sender<AsyncGitNotification>,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
+		let mut main_log = Revlog::new(
+			repo,
+			queue,
+			sender,
+			theme.clone(),
+			key_config.clone(),
+			options.clone(),
+		);
+		main_log.restore_view_state(
+			options.borrow().view_state().log.clone(),
+		);
+
 		Self {
 			repo: repo.clone(),
 			queue: queue.clone(),
 			git_local_branches: AsyncSingleJob::new(sender.clone()),
 			git_remote_branches: AsyncSingleJob::new(sender.clone()),
 			git_tags: AsyncTags::new(repo.borrow().clone(), sender),
-			main_log: Revlog::new(
-				repo,
-				queue,
-				sender,
-				theme.clone(),
-				key_config.clone(),
-			),
+			main_log,
 			compare_log: Revlog::new(
 				repo,
 				queue,
 				sender,
 				theme.clone(),
 				key_config.clone(),
+				options,
 			),
 			visible: false,
 			key_config,
 			focused: Focus::MainLog,
 			local_queue: crate::queue::create_local_queue(),
+			range_queue: crate::queue::create_local_queue(),
 		}
 	}
 
@@ -96,6 +108,19 @@ impl RevlogExtern {
 		self.main_log.select_commit(id)
 	}
 
+	/// selection of the main log to persist across launches
+	pub fn view_state(&self) -> ListState {
+		self.main_log.view_state()
+	}
+
+	/// marked commits of the focused log
+	pub fn marked_commits(&self) -> Vec<CommitId> {
+		match self.focused {
+			Focus::MainLog => self.main_log.marked_commits(),
+			Focus::CompareLog => self.compare_log.marked_commits(),
+		}
+	}
+
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_local_branches.is_pending()
@@ -117,6 +142,44 @@ impl RevlogExtern {
 				}
 			}
 		}
+		drop(q);
+
+		let range_queue = self.range_queue.clone();
+		let mut q = range_queue.borrow_mut();
+		while let Some(e) = q.pop_front() {
+			// the picker keeps the focus, so it is the log that asked
+			if let LocalEvent::PickBranch(b) = e {
+				self.focused_log_mut()
+					.set_range_base(Some((b.name, b.top_commit)));
+			}
+		}
+	}
+
+	fn focused_log(&self) -> &Revlog {
+		match self.focused {
+			Focus::MainLog => &self.main_log,
+			Focus::CompareLog => &self.compare_log,
+		}
+	}
+
+	fn focused_log_mut(&mut self) -> &mut Revlog {
+		match self.focused {
+			Focus::MainLog => &mut self.main_log,
+			Focus::CompareLog => &mut self.compare_log,
+		}
+	}
+
+	/// refetches tags right away instead of on the next periodic
+	/// update
+	pub fn trigger_tags_update(&mut self) -> Result<()> {
+		self.git_tags.request(Duration::ZERO, true)?;
+		Ok(())
+	}
+
+	/// reloads which commits have a note attached
+	pub fn update_notes(&mut self) {
+		self.main_log.update_notes();
+		self.compare_log.update_notes();
 	}
 
 	///
@@ -243,6 +306,7 @@ impl Component for RevlogExtern {
 				if key_match(k, self.key_config.keys.toggle_split) {
 					if self.compare_log.is_visible() {
 						self.compare_log.clear_marked();
+						self.compare_log.set_range_base(None);
 						self.compare_log.hide();
 						self.set_focus(Focus::MainLog);
 					} else {
@@ -276,6 +340,19 @@ impl Component for RevlogExtern {
 						self.local_queue.clone(),
 					));
 					return Ok(EventState::Consumed);
+				} else if self.focused_log().is_list_focused()
+					&& key_match(
+						k,
+						self.key_config.keys.log_exclude_branch,
+					) {
+					if self.focused_log().has_range_base() {
+						self.focused_log_mut().set_range_base(None);
+					} else {
+						self.queue.push(InternalEvent::PickBranch(
+							self.range_queue.clone(),
+						));
+					}
+					return Ok(EventState::Consumed);
 				}
 			}
 
@@ -294,6 +371,14 @@ impl Component for RevlogExtern {
 		force_all: bool,
 	) -> CommandBlocking {
 		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::commit_list_exclude_branch(
+					&self.key_config,
+					self.focused_log().has_range_base(),
+				),
+				true,
+				self.focused_log().is_list_focused() || force_all,
+			));
 			match self.focused {
 				Focus::MainLog => {
 					self.main_log.commands(out, force_all)
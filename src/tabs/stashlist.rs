@@ -5,6 +5,7 @@ use crate::{
 		InspectCommitOpen,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::{ListState, SharedOptions},
 	queue::{Action, InternalEvent, Queue, StackablePopupOpen},
 	strings,
 	ui::style::SharedTheme,
@@ -19,6 +20,8 @@ pub struct StashList {
 	visible: bool,
 	queue: Queue,
 	key_config: SharedKeyConfig,
+	/// selection of the last session, applied on the first update
+	restore: Option<ListState>,
 }
 
 impl StashList {
@@ -28,7 +31,11 @@ impl StashList {
 		queue: &Queue,
 		theme: SharedTheme,
 		key_config: SharedKeyConfig,
+		options: SharedOptions,
 	) -> Self {
+		let restore =
+			Some(options.borrow().view_state().stashes.clone());
+
 		Self {
 			visible: false,
 			list: CommitList::new(
@@ -37,13 +44,23 @@ impl StashList {
 				theme,
 				queue.clone(),
 				key_config.clone(),
+				options,
 			),
 			queue: queue.clone(),
 			key_config,
 			repo,
+			restore,
 		}
 	}
 
+	/// stash selected in the list, the restored one if the tab
+	/// never updated
+	pub fn view_state(&self) -> ListState {
+		self.restore
+			.clone()
+			.unwrap_or_else(|| self.list.view_state())
+	}
+
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
@@ -54,8 +71,17 @@ impl StashList {
 				100,
 			)?;
 
+			let position = self.restore.as_ref().and_then(|state| {
+				let id = state.commit.parse::<CommitId>().ok()?;
+				commits.iter().position(|c| c.id == id)
+			});
+
 			self.list.set_count_total(commits.len());
 			self.list.items().set_items(0, commits);
+
+			if let Some(state) = self.restore.take() {
+				self.list.restore_view_state(position, &state);
+			}
 		}
 
 		Ok(())
@@ -248,3 +274,50 @@ impl Component for StashList {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		options::Options,
+		test_utils::{git, temp_repo},
+	};
+	use crossterm::event::KeyEvent;
+	use std::cell::RefCell;
+
+	#[test]
+	fn test_apply_stash_key() {
+		let (td, repo_path) = temp_repo();
+		let file = td.path().join("file.txt");
+		std::fs::write(&file, "one\n").unwrap();
+		git(td.path(), &["add", "file.txt"]);
+		git(td.path(), &["commit", "-q", "-m", "one"]);
+		std::fs::write(&file, "two\n").unwrap();
+		git(td.path(), &["stash", "-q"]);
+
+		let queue = Queue::new();
+		let key_config = SharedKeyConfig::default();
+		let mut stashes = StashList::new(
+			RefCell::new(repo_path),
+			&queue,
+			SharedTheme::default(),
+			key_config.clone(),
+			Options::in_memory(),
+		);
+		stashes.show().unwrap();
+
+		let apply = key_config.keys.stash_apply;
+		stashes
+			.event(&Event::Key(KeyEvent::new(
+				apply.code,
+				apply.modifiers,
+			)))
+			.unwrap();
+
+		assert_eq!(std::fs::read_to_string(&file).unwrap(), "two\n");
+		assert!(matches!(
+			queue.pop(),
+			Some(InternalEvent::TabSwitchStatus)
+		));
+	}
+}
@@ -21,10 +21,12 @@ use asyncgit::{
 	cached,
 	sync::{
 		self, status::StatusType, RepoPath, RepoPathRef, RepoState,
+		ShowUntrackedFilesConfig,
 	},
 	sync::{BranchCompare, CommitId},
 	AsyncBranchesJob, AsyncDiff, AsyncGitNotification, AsyncStatus,
-	DiffParams, DiffType, PushType, StatusItem, StatusParams,
+	DiffParams, DiffType, PushType, StatusItem, StatusItemType,
+	StatusParams,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -32,6 +34,7 @@ use itertools::Itertools;
 use ratatui::{
 	layout::{Alignment, Constraint, Direction, Layout},
 	style::{Color, Style},
+	text::{Span, Spans},
 	widgets::{Block, BorderType, Borders, Paragraph},
 };
 use std::convert::Into;
@@ -62,6 +65,16 @@ enum DiffTarget {
 	WorkingDir,
 }
 
+/// untracked config of the workdir status
+#[derive(PartialEq, Eq, Copy, Clone)]
+enum WorkdirUntracked {
+	/// config of the last workdir status request
+	Requested(Option<ShowUntrackedFilesConfig>),
+	/// config changed while a workdir status was still running, it
+	/// has to be requested again
+	Outdated,
+}
+
 pub struct Status {
 	repo: RepoPathRef,
 	visible: bool,
@@ -73,8 +86,11 @@ pub struct Status {
 	git_diff: AsyncDiff,
 	has_remotes: bool,
 	git_state: RepoState,
+	/// conflicted files in the last workdir status
+	conflicts: usize,
 	git_status_workdir: AsyncStatus,
 	git_status_stage: AsyncStatus,
+	workdir_untracked: WorkdirUntracked,
 	git_branch_state: Option<BranchCompare>,
 	git_branch_name: cached::BranchName,
 	git_branches: AsyncSingleJob<AsyncBranchesJob>,
@@ -96,7 +112,7 @@ impl DrawableComponent for Status {
 			Layout::default()
 				.direction(Direction::Vertical)
 				.constraints(
-					[Constraint::Min(1), Constraint::Length(3)]
+					[Constraint::Min(1), Constraint::Length(4)]
 						.as_ref(),
 				)
 				.split(rect)
@@ -166,11 +182,14 @@ impl Status {
 		options: SharedOptions,
 	) -> Self {
 		let repo_clone = repo.borrow().clone();
+		let untracked_config =
+			options.borrow().status_show_untracked();
 		Self {
 			queue: queue.clone(),
 			visible: true,
 			has_remotes: false,
 			git_state: RepoState::Clean,
+			conflicts: 0,
 			focus: Focus::WorkDir,
 			diff_target: DiffTarget::WorkingDir,
 			index_wd: ChangesComponent::new(
@@ -198,6 +217,7 @@ impl Status {
 				queue.clone(),
 				theme,
 				key_config.clone(),
+				options.clone(),
 				false,
 			),
 			git_diff: AsyncDiff::new(repo_clone.clone(), sender),
@@ -209,6 +229,9 @@ impl Status {
 				repo_clone,
 				sender.clone(),
 			),
+			workdir_untracked: WorkdirUntracked::Requested(
+				untracked_config,
+			),
 			git_branches: AsyncSingleJob::new(sender.clone()),
 			git_action_executed: false,
 			git_branch_state: None,
@@ -313,10 +336,27 @@ impl Status {
 		r: ratatui::layout::Rect,
 	) {
 		if self.git_state != RepoState::Clean {
-			let txt = Self::repo_state_text(
+			let mut txt = Self::repo_state_text(
 				&self.repo.borrow(),
 				&self.git_state,
 			);
+			if self.conflicts > 0 {
+				txt = format!(
+					"{txt} ({})",
+					strings::repo_state_conflicts(self.conflicts)
+				);
+			}
+
+			let txt = vec![
+				Spans::from(Span::styled(
+					txt,
+					Style::default().fg(Color::Red),
+				)),
+				Spans::from(Span::raw(strings::repo_state_hint(
+					&self.key_config,
+					&self.git_state,
+				))),
+			];
 
 			let w = Paragraph::new(txt)
 				.block(
@@ -331,7 +371,6 @@ impl Status {
 							self.git_state
 						)),
 				)
-				.style(Style::default().fg(Color::Red))
 				.alignment(Alignment::Left);
 
 			f.render_widget(w, r);
@@ -415,23 +454,8 @@ impl Status {
 				match e {
 					LocalEvent::Confirmed(ref s) if s == "amend" => {
 						if self.can_commit() {
-							if let Err(e) =
-								sync::get_head(&self.repo.borrow())
-									.and_then(|h| {
-										sync::amend(
-											&self.repo.borrow(),
-											h,
-											"",
-										)
-									}) {
-								self.queue.push(
-									InternalEvent::ShowErrorMsg(
-										format!(
-											"Failed to amend:{e}"
-										),
-									),
-								);
-							}
+							self.queue
+								.push(InternalEvent::AmendCommit);
 						}
 					}
 					_ => {
@@ -452,6 +476,17 @@ impl Status {
 			let config =
 				self.options.borrow().status_show_untracked();
 
+			// a running fetch swallows the new request, it has to be
+			// repeated once that one is done
+			if !self.git_status_workdir.is_pending() {
+				self.workdir_untracked =
+					WorkdirUntracked::Requested(config);
+			} else if self.workdir_untracked
+				!= WorkdirUntracked::Requested(config)
+			{
+				self.workdir_untracked = WorkdirUntracked::Outdated;
+			}
+
 			self.git_diff.refresh()?;
 			self.git_status_workdir.fetch(&StatusParams::new(
 				StatusType::WorkingDir,
@@ -520,11 +555,22 @@ impl Status {
 	}
 
 	fn update_status(&mut self) -> Result<()> {
+		if self.workdir_untracked == WorkdirUntracked::Outdated
+			&& !self.git_status_workdir.is_pending()
+		{
+			self.update()?;
+		}
+
 		let stage_status = self.git_status_stage.last()?;
 		self.index.set_items(&stage_status.items)?;
 
 		let workdir_status = self.git_status_workdir.last()?;
 		self.index_wd.set_items(&workdir_status.items)?;
+		self.conflicts = workdir_status
+			.items
+			.iter()
+			.filter(|item| item.status == StatusItemType::Conflicted)
+			.count();
 
 		self.update_diff()?;
 
@@ -558,7 +604,10 @@ impl Status {
 			let diff_params = DiffParams {
 				path: path.clone(),
 				diff_type,
-				options: self.options.borrow().diff_options(),
+				options: self
+					.options
+					.borrow()
+					.diff_options_for(&path),
 			};
 
 			if self.diff.current() == (path.clone(), is_stage) {
@@ -567,6 +616,7 @@ impl Status {
 				if let Some((params, last)) = self.git_diff.last()? {
 					if params == diff_params {
 						// all params match, so we might need to update
+						self.diff.set_params(params);
 						self.diff.update(path, is_stage, last);
 					} else {
 						// params changed, we need to request the right diff
@@ -594,7 +644,10 @@ impl Status {
 		path: String,
 		is_stage: bool,
 	) -> Result<(), anyhow::Error> {
-		if let Some(diff) = self.git_diff.request(diff_params)? {
+		if let Some(diff) =
+			self.git_diff.request(diff_params.clone())?
+		{
+			self.diff.set_params(diff_params);
 			self.diff.update(path, is_stage, diff);
 		} else {
 			self.diff.clear(true);
@@ -657,6 +710,47 @@ impl Status {
 		}
 	}
 
+	/// asks before amending `HEAD`, warning if that rewrites a merge
+	/// or a commit that is already pushed
+	fn confirm_amend(&self) {
+		let repo = self.repo.borrow();
+		let head = sync::get_head(&repo).and_then(|head| {
+			Ok((
+				sync::get_commit_info(&repo, &head)?,
+				sync::parent_ids(&repo, head)?.len() > 1,
+			))
+		});
+
+		match head {
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"Failed to get current head commit details: {e}"
+					),
+				));
+			}
+			Ok((info, is_merge)) => {
+				let is_pushed = self
+					.git_branch_state
+					.as_ref()
+					.map_or(false, |state| state.ahead == 0);
+
+				self.queue.push(InternalEvent::ConfirmCustom(
+					CustomConfirmData {
+						title: strings::confirm_title_amend(),
+						msg: strings::confirm_msg_amend(
+							&info.get_summary(),
+							is_merge,
+							is_pushed,
+						),
+						confirm: "amend".to_string(),
+						q: self.local_queue.clone(),
+					},
+				));
+			}
+		}
+	}
+
 	fn undo_last_commit(&self) {
 		try_or_popup!(
 			self,
@@ -849,6 +943,13 @@ impl Component for Status {
 				self.can_pull(),
 				!focus_on_diff,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::status_fetch_pull_request(
+					&self.key_config,
+				),
+				true,
+				!focus_on_diff,
+			));
 
 			out.push(CommandInfo::new(
 				strings::commands::undo_commit(&self.key_config),
@@ -886,6 +987,18 @@ impl Component for Status {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::view_worktrees(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::grep_files(&self.key_config),
+				true,
+				true,
+			));
 		}
 
 		self.commands_nav(out, force_all);
@@ -912,7 +1025,12 @@ impl Component for Status {
 					self.key_config.keys.open_commit,
 				) && self.can_commit()
 				{
-					self.queue.push(InternalEvent::OpenCommit);
+					let marked = self.index.marked_paths();
+					self.queue.push(if marked.is_empty() {
+						InternalEvent::OpenCommit
+					} else {
+						InternalEvent::OpenCommitMarked(marked)
+					});
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -928,34 +1046,7 @@ impl Component for Status {
 					self.key_config.keys.commit_amend,
 				) && self.can_commit()
 				{
-					//custom confirmation here
-					let id = sync::get_head(&self.repo.borrow())
-						.and_then(|c| {
-							sync::get_commit_info(
-								&self.repo.borrow(),
-								&c,
-							)
-						});
-					match id {
-						Result::Err(e) => {
-							self.queue.push(
-								InternalEvent::ShowErrorMsg(format!("Failed to get current head commit details: {}", e)),
-							);
-						}
-						Result::Ok(id) => {
-							self.queue.push(
-								InternalEvent::ConfirmCustom(
-									CustomConfirmData {
-										title: "Amend last commit?"
-											.to_string(),
-										msg: id.get_summary(),
-										confirm: "amend".to_string(),
-										q: self.local_queue.clone(),
-									},
-								),
-							);
-						}
-					}
+					self.confirm_amend();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -1021,6 +1112,13 @@ impl Component for Status {
 				{
 					self.pull();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.fetch_pull_request,
+				) && !self.is_focus_on_diff()
+				{
+					self.queue.push(InternalEvent::OpenFetchPullRequest);
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.undo_commit,
@@ -1072,6 +1170,18 @@ impl Component for Status {
 				) {
 					self.queue.push(InternalEvent::ViewSubmodules);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.view_worktrees,
+				) {
+					self.queue.push(InternalEvent::ViewWorktrees);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.grep_files,
+				) {
+					self.queue.push(InternalEvent::OpenGrep(None));
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
@@ -1103,3 +1213,71 @@ impl Component for Status {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{options::Options, test_utils::temp_repo};
+	use crossbeam_channel::{unbounded, Receiver};
+	use std::{cell::RefCell, time::Duration};
+
+	fn settle(
+		status: &mut Status,
+		rx: &Receiver<AsyncGitNotification>,
+	) {
+		loop {
+			let ev = rx
+				.recv_timeout(Duration::from_secs(10))
+				.expect("no status notification");
+			status.update_git(ev).unwrap();
+
+			if !status.git_status_workdir.is_pending()
+				&& !status.git_status_stage.is_pending()
+				&& status.workdir_untracked
+					!= WorkdirUntracked::Outdated
+			{
+				break;
+			}
+		}
+		status.update_git(AsyncGitNotification::Status).unwrap();
+	}
+
+	#[test]
+	fn test_untracked_option_switch() {
+		let (td, repo_path) = temp_repo();
+		std::fs::write(td.path().join("untracked.txt"), "new").unwrap();
+
+		let options = Options::in_memory();
+		options.borrow_mut().set_status_show_untracked(Some(
+			ShowUntrackedFilesConfig::No,
+		));
+
+		let (tx, rx) = unbounded();
+		let mut status = Status::new(
+			RefCell::new(repo_path),
+			&Queue::new(),
+			&tx,
+			SharedTheme::default(),
+			SharedKeyConfig::default(),
+			options.clone(),
+		);
+
+		status.update().unwrap();
+		settle(&mut status, &rx);
+		assert!(status.index_wd.is_empty());
+
+		// switch right away, the first fetch may still be running
+		status.update().unwrap();
+		options.borrow_mut().set_status_show_untracked(Some(
+			ShowUntrackedFilesConfig::All,
+		));
+		status.update().unwrap();
+		settle(&mut status, &rx);
+		assert!(!status.index_wd.is_empty());
+
+		// wait for the remaining jobs to let go of their senders
+		drop(status);
+		drop(tx);
+		while rx.recv_timeout(Duration::from_secs(10)).is_ok() {}
+	}
+}
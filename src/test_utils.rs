@@ -0,0 +1,28 @@
+//! fixtures shared by the tests of several modules
+
+use asyncgit::sync::RepoPath;
+use std::{path::Path, process::Command};
+
+/// runs `git` with `args` in `dir`, failing the test if it does
+pub fn git(dir: &Path, args: &[&str]) {
+	assert!(Command::new("git")
+		.args(args)
+		.current_dir(dir)
+		.status()
+		.unwrap()
+		.success());
+}
+
+/// an empty repository in a temp dir, set up to commit without
+/// relying on the user's git config
+pub fn temp_repo() -> (tempfile::TempDir, RepoPath) {
+	let td = tempfile::TempDir::new().unwrap();
+	let dir = td.path();
+	git(dir, &["init", "-q"]);
+	git(dir, &["config", "user.name", "name"]);
+	git(dir, &["config", "user.email", "email"]);
+	git(dir, &["config", "commit.gpgsign", "false"]);
+
+	let repo = dir.to_str().unwrap().into();
+	(td, repo)
+}
@@ -1,5 +1,7 @@
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{
+	sync::SignatureStatus, DiffLineType, StatusItemType,
+};
 use ratatui::style::{Color, Modifier, Style};
 use ron::{
 	de::from_bytes,
@@ -214,6 +216,20 @@ impl Theme {
 		self.apply_select_or_copied(style, selected, copied)
 	}
 
+	/// visible tab/trailing space markers in a diff line
+	pub fn diff_whitespace(
+		&self,
+		selected: bool,
+		copied: bool,
+	) -> Style {
+		self.apply_select_or_copied(
+			Style::default(),
+			selected,
+			copied,
+		)
+		.fg(self.disabled_fg)
+	}
+
 	pub fn text_danger(&self) -> Style {
 		Style::default().fg(self.danger_fg)
 	}
@@ -262,6 +278,22 @@ impl Theme {
 		)
 	}
 
+	pub fn commit_signature(
+		&self,
+		signature: Option<SignatureStatus>,
+		selected: bool,
+	) -> Style {
+		let color = match signature {
+			Some(SignatureStatus::Good) => self.diff_line_add,
+			Some(SignatureStatus::Bad) => self.diff_line_delete,
+			Some(SignatureStatus::Unsigned) | None => {
+				self.disabled_fg
+			}
+		};
+
+		self.apply_select(Style::default().fg(color), selected)
+	}
+
 	pub fn commit_hash_in_blame(
 		&self,
 		is_blamed_commit: bool,
@@ -1,28 +1,39 @@
 use anyhow::Result;
 use crossbeam_channel::{unbounded, Sender};
 use notify::{Error, RecommendedWatcher, RecursiveMode, Watcher};
-use notify_debouncer_mini::{new_debouncer, DebouncedEvent};
+use notify_debouncer_mini::{
+	new_debouncer, DebouncedEvent, Debouncer,
+};
 use scopetime::scope_time;
 use std::{path::Path, thread, time::Duration};
 
 pub struct RepoWatcher {
 	receiver: crossbeam_channel::Receiver<()>,
+	// dropping this stops the watcher thread (and the debouncer it owns)
+	_shutdown: Sender<()>,
 }
 
 impl RepoWatcher {
-	pub fn new(workdir: &str) -> Self {
+	/// events within `debounce` of each other are reported once
+	pub fn new(workdir: &str, debounce: Duration) -> Self {
 		log::trace!(
 			"recommended watcher: {:?}",
 			RecommendedWatcher::kind()
 		);
 
 		let (tx, rx) = std::sync::mpsc::channel();
+		let (shutdown_tx, shutdown_rx) = unbounded::<()>();
 
 		let workdir = workdir.to_string();
 
 		thread::spawn(move || {
-			let timeout = Duration::from_secs(2);
-			create_watcher(timeout, tx, &workdir);
+			let bouncer = create_watcher(debounce, tx, &workdir);
+
+			// blocks until the owning `RepoWatcher` is dropped
+			let _ = shutdown_rx.recv();
+
+			log::trace!("watcher shutdown: {}", workdir);
+			drop(bouncer);
 		});
 
 		let (out_tx, out_rx) = unbounded();
@@ -34,7 +45,10 @@ impl RepoWatcher {
 			}
 		});
 
-		Self { receiver: out_rx }
+		Self {
+			receiver: out_rx,
+			_shutdown: shutdown_tx,
+		}
 	}
 
 	///
@@ -42,27 +56,42 @@ impl RepoWatcher {
 		self.receiver.clone()
 	}
 
+	fn count_events(
+		ev: Result<Vec<DebouncedEvent>, Vec<Error>>,
+	) -> usize {
+		ev.map_or(0, |ev| {
+			log::debug!("notify events: {}", ev.len());
+
+			for (idx, ev) in ev.iter().enumerate() {
+				log::debug!("notify [{}]: {:?}", idx, ev);
+			}
+
+			ev.len()
+		})
+	}
+
 	fn forwarder(
 		receiver: &std::sync::mpsc::Receiver<
 			Result<Vec<DebouncedEvent>, Vec<Error>>,
 		>,
 		sender: &Sender<()>,
 	) -> Result<()> {
-		loop {
-			let ev = receiver.recv()?;
-
-			if let Ok(ev) = ev {
-				log::debug!("notify events: {}", ev.len());
+		// `recv` only fails once the debouncer got dropped
+		while let Ok(ev) = receiver.recv() {
+			let mut changes = Self::count_events(ev);
 
-				for (idx, ev) in ev.iter().enumerate() {
-					log::debug!("notify [{}]: {:?}", idx, ev);
-				}
+			// batches that piled up meanwhile need no notify of their own
+			while let Ok(ev) = receiver.try_recv() {
+				changes += Self::count_events(ev);
+			}
 
-				if !ev.is_empty() {
-					sender.send(())?;
-				}
+			// an unhandled notify still covers these changes
+			if changes > 0 && sender.is_empty() {
+				sender.send(())?;
 			}
 		}
+
+		Ok(())
 	}
 }
 
@@ -72,7 +101,7 @@ fn create_watcher(
 		Result<Vec<DebouncedEvent>, Vec<Error>>,
 	>,
 	workdir: &str,
-) {
+) -> Debouncer<RecommendedWatcher> {
 	scope_time!("create_watcher");
 
 	let mut bouncer =
@@ -82,5 +111,5 @@ fn create_watcher(
 		.watch(Path::new(&workdir), RecursiveMode::Recursive)
 		.expect("Watch error");
 
-	std::mem::forget(bouncer);
+	bouncer
 }